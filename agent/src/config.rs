@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs,
     net::SocketAddr,
     path::{Path, PathBuf},
     sync::OnceLock,
+    time::Duration,
 };
 
 /// Configuracion basica del agente cargada desde variables de entorno.
@@ -14,6 +16,12 @@ pub struct AgentConfig {
     pub api_bind: SocketAddr,
     pub database_path: PathBuf,
     pub security: SecurityConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub tasks: TasksConfig,
+    #[serde(default)]
+    pub mounts: MountsConfig,
 }
 
 static CONFIG_SOURCES: OnceLock<ConfigSources> = OnceLock::new();
@@ -50,6 +58,132 @@ impl Default for SecurityConfig {
     }
 }
 
+/// Medicion del espacio en disco de los sandboxes y cuotas.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageConfig {
+    /// Cada cuanto se recalcula el uso de todos los contenedores; `0` desactiva el escaneo.
+    pub scan_interval_secs: u64,
+    /// Cuota por defecto de cada contenedor; `limits.disk_mb` la sustituye.
+    pub container_quota_mb: Option<u64>,
+    /// Limite de la suma de todos los contenedores.
+    pub global_quota_mb: Option<u64>,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            scan_interval_secs: 300,
+            container_quota_mb: None,
+            global_quota_mb: None,
+        }
+    }
+}
+
+/// Montajes de directorios del host (`mounts[].source`) que puede usar un contenedor.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MountsConfig {
+    /// Directorios del host bajo los que puede estar el origen de un bind; sin ninguno
+    /// no se admiten binds.
+    pub bind_roots: Vec<PathBuf>,
+    /// Permite binds con `read_only = false`; si no, todos son de solo lectura.
+    pub writable_binds: bool,
+}
+
+/// Comportamiento de las tareas: reintentos automaticos y retencion del historial.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TasksConfig {
+    /// Politica por tipo de tarea (`app.install`, `snapshot.create`...); los tipos sin
+    /// entrada no se reintentan solos.
+    #[serde(default)]
+    pub retry: BTreeMap<String, RetryPolicy>,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+impl TasksConfig {
+    pub fn policy(&self, task_type: &str) -> Option<RetryPolicy> {
+        self.retry.get(task_type).copied()
+    }
+}
+
+/// Reintentos con espera exponencial: `backoff_ms`, el doble en cada intento y como
+/// mucho `max_backoff_ms`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Intentos en total, contando el primero.
+    pub max_attempts: u32,
+    #[serde(default = "RetryPolicy::default_backoff_ms")]
+    pub backoff_ms: u64,
+    #[serde(default = "RetryPolicy::default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl RetryPolicy {
+    fn default_backoff_ms() -> u64 {
+        1_000
+    }
+
+    fn default_max_backoff_ms() -> u64 {
+        60_000
+    }
+
+    /// Espera antes del intento que sigue a `attempt`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        Duration::from_millis(
+            self.backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// Limpieza del historial de tareas terminadas. Solo cuenta cada tarea de primer nivel,
+/// que se borra junto con sus subtareas; sin ningun limite no se borra nada.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Dias que se conservan las tareas terminadas desde su ultima actualizacion.
+    pub max_age_days: Option<u32>,
+    /// Tareas terminadas que se conservan como mucho, sin contar las fallidas.
+    pub max_tasks: Option<u64>,
+    /// Dias que se conservan las fallidas, nunca menos que `max_age_days`; sin valor no se
+    /// borran.
+    pub failed_max_age_days: Option<u32>,
+    /// Guarda las tareas en `<data_dir>/archive/tasks` (NDJSON comprimido) antes de borrarlas.
+    pub archive: bool,
+    /// Cada cuanto se aplica; `0` desactiva la limpieza.
+    pub interval_secs: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: None,
+            max_tasks: None,
+            failed_max_age_days: None,
+            archive: false,
+            interval_secs: 3_600,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn enabled(&self) -> bool {
+        self.interval_secs > 0
+            && (self.max_age_days.is_some()
+                || self.max_tasks.is_some()
+                || self.failed_max_age_days.is_some())
+    }
+
+    /// Antiguedad a partir de la cual se borran las tareas fallidas.
+    pub fn failed_max_age_days(&self) -> Option<u32> {
+        self.failed_max_age_days
+            .map(|days| days.max(self.max_age_days.unwrap_or(0)))
+    }
+}
+
 impl AgentConfig {
     pub fn from_env() -> Self {
         let (file_config, mut sources) = FileConfig::load();
@@ -73,6 +207,9 @@ impl AgentConfig {
 
         let security =
             SecurityConfig::from_layers(file_config.security, &mut sources.env_overrides);
+        let storage = StorageConfig::from_layers(file_config.storage, &mut sources.env_overrides);
+        let tasks = file_config.tasks.unwrap_or_default();
+        let mounts = file_config.mounts.unwrap_or_default();
 
         CONFIG_SOURCES.get_or_init(|| sources.clone());
 
@@ -82,9 +219,20 @@ impl AgentConfig {
             api_bind,
             database_path,
             security,
+            storage,
+            tasks,
+            mounts,
         }
     }
 
+    /// Directorio de datos del agente: el que contiene la base de datos.
+    pub fn data_dir(&self) -> PathBuf {
+        self.database_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
     pub fn snapshot(&self) -> ConfigSnapshot {
         ConfigSnapshot {
             containers_root: self.containers_root.display().to_string(),
@@ -96,6 +244,13 @@ impl AgentConfig {
                 admin_token_present: self.security.admin_token.is_some(),
                 static_tokens: self.security.api_tokens.len() as u64,
             },
+            storage: ConfigStorageView {
+                scan_interval_secs: self.storage.scan_interval_secs,
+                container_quota_mb: self.storage.container_quota_mb,
+                global_quota_mb: self.storage.global_quota_mb,
+            },
+            tasks: self.tasks.clone(),
+            mounts: self.mounts.clone(),
         }
     }
 }
@@ -107,6 +262,9 @@ pub struct ConfigSnapshot {
     pub api_bind: String,
     pub database_path: String,
     pub security: ConfigSecurityView,
+    pub storage: ConfigStorageView,
+    pub tasks: TasksConfig,
+    pub mounts: MountsConfig,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -116,6 +274,13 @@ pub struct ConfigSecurityView {
     pub static_tokens: u64,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigStorageView {
+    pub scan_interval_secs: u64,
+    pub container_quota_mb: Option<u64>,
+    pub global_quota_mb: Option<u64>,
+}
+
 impl SecurityConfig {
     pub fn from_env() -> Self {
         Self::from_layers(None, &mut Vec::new())
@@ -142,6 +307,22 @@ impl SecurityConfig {
     }
 }
 
+impl StorageConfig {
+    fn from_layers(file: Option<FileStorageConfig>, env_overrides: &mut Vec<String>) -> Self {
+        let file = file.unwrap_or_default();
+        let defaults = Self::default();
+        StorageConfig {
+            scan_interval_secs: env_u64("ORBIT_STORAGE_SCAN_SECS", env_overrides)
+                .or(file.scan_interval_secs)
+                .unwrap_or(defaults.scan_interval_secs),
+            container_quota_mb: env_u64("ORBIT_CONTAINER_QUOTA_MB", env_overrides)
+                .or(file.container_quota_mb),
+            global_quota_mb: env_u64("ORBIT_GLOBAL_QUOTA_MB", env_overrides)
+                .or(file.global_quota_mb),
+        }
+    }
+}
+
 pub fn config_sources() -> ConfigSources {
     CONFIG_SOURCES.get().cloned().unwrap_or_default()
 }
@@ -162,6 +343,13 @@ struct FileSecurityConfig {
     api_tokens: Option<Vec<String>>,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+struct FileStorageConfig {
+    scan_interval_secs: Option<u64>,
+    container_quota_mb: Option<u64>,
+    global_quota_mb: Option<u64>,
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 struct FileConfig {
     containers_root: Option<PathBuf>,
@@ -169,6 +357,9 @@ struct FileConfig {
     api_bind: Option<String>,
     database_path: Option<PathBuf>,
     security: Option<FileSecurityConfig>,
+    storage: Option<FileStorageConfig>,
+    tasks: Option<TasksConfig>,
+    mounts: Option<MountsConfig>,
 }
 
 impl FileConfig {
@@ -214,6 +405,15 @@ impl FileConfig {
         if other.security.is_some() {
             self.security = other.security;
         }
+        if other.storage.is_some() {
+            self.storage = other.storage;
+        }
+        if other.tasks.is_some() {
+            self.tasks = other.tasks;
+        }
+        if other.mounts.is_some() {
+            self.mounts = other.mounts;
+        }
     }
 }
 
@@ -237,6 +437,15 @@ fn env_bool(var: &'static str, overrides: &mut Vec<String>) -> Option<bool> {
     env_string(var, overrides).map(|value| matches!(value.as_str(), "1" | "true" | "TRUE"))
 }
 
+fn env_u64(var: &'static str, overrides: &mut Vec<String>) -> Option<u64> {
+    env_string(var, overrides).map(|value| {
+        value
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("{var} debe ser un entero sin signo"))
+    })
+}
+
 fn parse_token_list(raw: String) -> Vec<String> {
     raw.split(',')
         .map(|s| s.trim().to_string())
@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+/// Anos que se recorren buscando la siguiente coincidencia antes de rendirse
+/// (p.e. `0 0 30 2 *` nunca coincide).
+const SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Expresion cron de cinco campos (`minuto hora dia mes dia-semana`) evaluada en UTC.
+/// Admite `*`, listas, rangos, pasos (`*/15`, `1-5/2`), nombres de mes y de dia
+/// (`jan`, `mon`) y las macros `@hourly`, `@daily`, `@weekly`, `@monthly` y `@yearly`.
+/// Como en Vixie cron, si se restringen el dia del mes y el de la semana basta con
+/// que coincida uno de los dos.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CronError {
+    #[error("Se esperaban 5 campos y hay {0}")]
+    FieldCount(usize),
+    #[error("Valor invalido en el campo {field}: '{value}'")]
+    InvalidValue { field: &'static str, value: String },
+    #[error("Macro desconocida: {0}")]
+    UnknownMacro(String),
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY: Field = Field {
+    name: "day",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: MONTH_NAMES,
+};
+// 7 tambien es domingo; se pliega a 0 al parsear.
+const WEEKDAY: Field = Field {
+    name: "weekday",
+    min: 0,
+    max: 7,
+    names: WEEKDAY_NAMES,
+};
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, CronError> {
+        let expr = expr.trim();
+        let expanded = match expr.strip_prefix('@') {
+            Some(name) => match name.to_ascii_lowercase().as_str() {
+                "yearly" | "annually" => "0 0 1 1 *",
+                "monthly" => "0 0 1 * *",
+                "weekly" => "0 0 * * 0",
+                "daily" | "midnight" => "0 0 * * *",
+                "hourly" => "0 * * * *",
+                _ => return Err(CronError::UnknownMacro(expr.to_string())),
+            },
+            None => expr,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+        let mut weekdays = parse_field(&WEEKDAY, weekday)?;
+        if has(weekdays, 7) {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(&MINUTE, minute)?,
+            hours: parse_field(&HOUR, hour)?,
+            days: parse_field(&DAY, day)?,
+            months: parse_field(&MONTH, month)?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+
+    /// Primer minuto estrictamente posterior a `after` que cumple la expresion; `None`
+    /// si no hay ninguno en los proximos anos.
+    pub fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let after = after.to_offset(time::UtcOffset::UTC);
+        let mut current =
+            after.replace_second(0).ok()?.replace_nanosecond(0).ok()? + Duration::MINUTE;
+        let limit = after.year() + SEARCH_YEARS;
+        while current.year() <= limit {
+            if !has(self.months, u8::from(current.month()).into()) {
+                current = start_of_next_month(current)?;
+            } else if !self.day_matches(current.date()) {
+                current = midnight(current.date().next_day()?);
+            } else if !has(self.hours, current.hour().into()) {
+                current = current.replace_minute(0).ok()? + Duration::HOUR;
+            } else if !has(self.minutes, current.minute().into()) {
+                current += Duration::MINUTE;
+            } else {
+                return Some(current);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, date: Date) -> bool {
+        let day = has(self.days, date.day().into());
+        let weekday = has(
+            self.weekdays,
+            date.weekday().number_days_from_sunday().into(),
+        );
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = CronError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn midnight(date: Date) -> OffsetDateTime {
+    PrimitiveDateTime::new(date, Time::MIDNIGHT).assume_utc()
+}
+
+fn start_of_next_month(current: OffsetDateTime) -> Option<OffsetDateTime> {
+    let (year, month) = match current.month() {
+        Month::December => (current.year() + 1, Month::January),
+        month => (current.year(), month.next()),
+    };
+    Date::from_calendar_date(year, month, 1).ok().map(midnight)
+}
+
+/// Convierte un campo en un mapa de bits con un bit por valor admitido.
+fn parse_field(field: &Field, raw: &str) -> Result<u64, CronError> {
+    let invalid = || CronError::InvalidValue {
+        field: field.name,
+        value: raw.to_string(),
+    };
+    let mut bits = 0u64;
+    for item in raw.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid())?;
+                if step == 0 {
+                    return Err(invalid());
+                }
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" {
+            (field.min, field.max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                value(field, start).ok_or_else(invalid)?,
+                value(field, end).ok_or_else(invalid)?,
+            )
+        } else {
+            let start = value(field, range).ok_or_else(invalid)?;
+            // `5/15` equivale a `5-max/15`.
+            let end = if item.contains('/') { field.max } else { start };
+            (start, end)
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+fn value(field: &Field, raw: &str) -> Option<u32> {
+    let parsed = match raw.parse::<u32>() {
+        Ok(number) => number,
+        Err(_) => {
+            let lower = raw.to_ascii_lowercase();
+            // Los nombres empiezan en el minimo del campo: enero es 1 y domingo 0.
+            field.names.iter().position(|name| *name == lower)? as u32 + field.min
+        }
+    };
+    (field.min..=field.max).contains(&parsed).then_some(parsed)
+}
@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{ContainerLimits, ContainerSettings},
+    templates::TemplateApp,
+    virtualization::{MountSpec, Platform},
+};
+
+/// Documento de `orbit apply`: los contenedores que deben existir, con sus ajustes, tags
+/// y apps. Los contenedores se identifican por nombre y las apps por `name`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesiredState {
+    #[serde(default)]
+    pub containers: Vec<DesiredContainer>,
+}
+
+impl DesiredState {
+    pub fn container(&self, name: &str) -> Option<&DesiredContainer> {
+        self.containers
+            .iter()
+            .find(|container| container.name.trim().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesiredContainer {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub platform: Platform,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub compat_flags: Vec<String>,
+    #[serde(default)]
+    pub limits: ContainerLimits,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+    #[serde(default)]
+    pub apps: Vec<TemplateApp>,
+}
+
+impl DesiredContainer {
+    pub fn settings(&self) -> ContainerSettings {
+        ContainerSettings {
+            env: self.env.clone(),
+            compat_flags: self.compat_flags.clone(),
+            limits: self.limits.clone(),
+            mounts: self.mounts.clone(),
+        }
+    }
+
+    pub fn app(&self, name: &str) -> Option<&TemplateApp> {
+        self.apps.iter().find(|app| {
+            app.name
+                .as_deref()
+                .is_some_and(|app_name| app_name.trim().eq_ignore_ascii_case(name.trim()))
+        })
+    }
+}
@@ -0,0 +1,416 @@
+use axum::{
+    Json,
+    body::Body,
+    extract::{
+        FromRequest, FromRequestParts,
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+    },
+    http::{HeaderValue, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Codigos estables expuestos en el cuerpo de error; los clientes ramifican sobre ellos.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    Unauthorized,
+    AdminRequired,
+    ScopeMissing,
+    InvalidBody,
+    InvalidPath,
+    InvalidQuery,
+    ValidationFailed,
+    UnknownCompatFlag,
+    InvalidExpiration,
+    ContainerNotFound,
+    ContainerNameConflict,
+    ContainerNotReady,
+    AppNotFound,
+    AppBusy,
+    EntryPointNotFound,
+    EntryPointConflict,
+    EntryPointRequired,
+    FileNotFound,
+    SnapshotNotFound,
+    TaskNotFound,
+    TokenNotFound,
+    OrphanNotFound,
+    TemplateNotFound,
+    ScheduleNotFound,
+    ScheduleNameConflict,
+    TaskNotRetryable,
+    PlanNotConfirmed,
+    IdempotencyKeyInUse,
+    IdempotencyKeyReused,
+    QuotaExceeded,
+    InternalError,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::AdminRequired => "admin_required",
+            ErrorCode::ScopeMissing => "scope_missing",
+            ErrorCode::InvalidBody => "invalid_body",
+            ErrorCode::InvalidPath => "invalid_path",
+            ErrorCode::InvalidQuery => "invalid_query",
+            ErrorCode::ValidationFailed => "validation_failed",
+            ErrorCode::UnknownCompatFlag => "unknown_compat_flag",
+            ErrorCode::InvalidExpiration => "invalid_expiration",
+            ErrorCode::ContainerNotFound => "container_not_found",
+            ErrorCode::ContainerNameConflict => "container_name_conflict",
+            ErrorCode::ContainerNotReady => "container_not_ready",
+            ErrorCode::AppNotFound => "app_not_found",
+            ErrorCode::AppBusy => "app_busy",
+            ErrorCode::EntryPointNotFound => "entry_point_not_found",
+            ErrorCode::EntryPointConflict => "entry_point_conflict",
+            ErrorCode::EntryPointRequired => "entry_point_required",
+            ErrorCode::FileNotFound => "file_not_found",
+            ErrorCode::SnapshotNotFound => "snapshot_not_found",
+            ErrorCode::TaskNotFound => "task_not_found",
+            ErrorCode::TokenNotFound => "token_not_found",
+            ErrorCode::OrphanNotFound => "orphan_not_found",
+            ErrorCode::TemplateNotFound => "template_not_found",
+            ErrorCode::ScheduleNotFound => "schedule_not_found",
+            ErrorCode::ScheduleNameConflict => "schedule_name_conflict",
+            ErrorCode::TaskNotRetryable => "task_not_retryable",
+            ErrorCode::PlanNotConfirmed => "plan_not_confirmed",
+            ErrorCode::IdempotencyKeyInUse => "idempotency_key_in_use",
+            ErrorCode::IdempotencyKeyReused => "idempotency_key_reused",
+            ErrorCode::QuotaExceeded => "quota_exceeded",
+            ErrorCode::InternalError => "internal_error",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::AdminRequired | ErrorCode::ScopeMissing => StatusCode::FORBIDDEN,
+            ErrorCode::InvalidBody | ErrorCode::InvalidPath | ErrorCode::InvalidQuery => {
+                StatusCode::BAD_REQUEST
+            }
+            ErrorCode::ValidationFailed
+            | ErrorCode::UnknownCompatFlag
+            | ErrorCode::InvalidExpiration
+            | ErrorCode::IdempotencyKeyReused => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::ContainerNotFound
+            | ErrorCode::AppNotFound
+            | ErrorCode::EntryPointNotFound
+            | ErrorCode::FileNotFound
+            | ErrorCode::SnapshotNotFound
+            | ErrorCode::TaskNotFound
+            | ErrorCode::TokenNotFound
+            | ErrorCode::OrphanNotFound
+            | ErrorCode::TemplateNotFound
+            | ErrorCode::ScheduleNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::ContainerNameConflict
+            | ErrorCode::ContainerNotReady
+            | ErrorCode::ScheduleNameConflict
+            | ErrorCode::AppBusy
+            | ErrorCode::EntryPointConflict
+            | ErrorCode::EntryPointRequired
+            | ErrorCode::TaskNotRetryable
+            | ErrorCode::PlanNotConfirmed
+            | ErrorCode::IdempotencyKeyInUse => StatusCode::CONFLICT,
+            ErrorCode::QuotaExceeded => StatusCode::INSUFFICIENT_STORAGE,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self, lang: Lang) -> &'static str {
+        match (self, lang) {
+            (ErrorCode::Unauthorized, Lang::Es) => "Token ausente o invalido",
+            (ErrorCode::Unauthorized, Lang::En) => "Missing or invalid token",
+            (ErrorCode::AdminRequired, Lang::Es) => {
+                "La operacion requiere credenciales de administrador"
+            }
+            (ErrorCode::AdminRequired, Lang::En) => "This operation requires admin credentials",
+            (ErrorCode::ScopeMissing, Lang::Es) => {
+                "Permisos insuficientes para la operacion solicitada"
+            }
+            (ErrorCode::ScopeMissing, Lang::En) => {
+                "The token lacks the scope required for this operation"
+            }
+            (ErrorCode::InvalidBody, Lang::Es) => "El cuerpo de la peticion no es valido",
+            (ErrorCode::InvalidBody, Lang::En) => "The request body is not valid",
+            (ErrorCode::InvalidPath, Lang::Es) => "Parametro de ruta invalido",
+            (ErrorCode::InvalidPath, Lang::En) => "Invalid path parameter",
+            (ErrorCode::InvalidQuery, Lang::Es) => "Parametros de consulta invalidos",
+            (ErrorCode::InvalidQuery, Lang::En) => "Invalid query parameters",
+            (ErrorCode::ValidationFailed, Lang::Es) => "La peticion contiene campos invalidos",
+            (ErrorCode::ValidationFailed, Lang::En) => "The request contains invalid fields",
+            (ErrorCode::UnknownCompatFlag, Lang::Es) => "Flags de compatibilidad desconocidos",
+            (ErrorCode::UnknownCompatFlag, Lang::En) => "Unknown compatibility flags",
+            (ErrorCode::InvalidExpiration, Lang::Es) => {
+                "expires_at debe ser una fecha futura en formato RFC3339"
+            }
+            (ErrorCode::InvalidExpiration, Lang::En) => {
+                "expires_at must be a future RFC3339 timestamp"
+            }
+            (ErrorCode::ContainerNotFound, Lang::Es) => "Contenedor no encontrado",
+            (ErrorCode::ContainerNotFound, Lang::En) => "Container not found",
+            (ErrorCode::ContainerNameConflict, Lang::Es) => {
+                "Ya existe un contenedor con ese nombre"
+            }
+            (ErrorCode::ContainerNameConflict, Lang::En) => {
+                "A container with that name already exists"
+            }
+            (ErrorCode::ContainerNotReady, Lang::Es) => {
+                "El contenedor no admite la operacion en su estado actual"
+            }
+            (ErrorCode::ContainerNotReady, Lang::En) => {
+                "The container cannot perform this operation in its current state"
+            }
+            (ErrorCode::AppNotFound, Lang::Es) => "Aplicacion no encontrada",
+            (ErrorCode::AppNotFound, Lang::En) => "Application not found",
+            (ErrorCode::AppBusy, Lang::Es) => {
+                "La aplicacion no admite la operacion en su estado actual"
+            }
+            (ErrorCode::AppBusy, Lang::En) => {
+                "The application cannot perform this operation in its current state"
+            }
+            (ErrorCode::EntryPointNotFound, Lang::Es) => "Entry point no encontrado",
+            (ErrorCode::EntryPointNotFound, Lang::En) => "Entry point not found",
+            (ErrorCode::EntryPointConflict, Lang::Es) => {
+                "La aplicacion ya tiene un entry point con ese id"
+            }
+            (ErrorCode::EntryPointConflict, Lang::En) => {
+                "The application already has an entry point with that id"
+            }
+            (ErrorCode::EntryPointRequired, Lang::Es) => {
+                "Una aplicacion lista debe conservar al menos un entry point"
+            }
+            (ErrorCode::EntryPointRequired, Lang::En) => {
+                "A ready application must keep at least one entry point"
+            }
+            (ErrorCode::FileNotFound, Lang::Es) => "Archivo no encontrado en el contenedor",
+            (ErrorCode::FileNotFound, Lang::En) => "File not found in the container",
+            (ErrorCode::SnapshotNotFound, Lang::Es) => "Snapshot no encontrado",
+            (ErrorCode::SnapshotNotFound, Lang::En) => "Snapshot not found",
+            (ErrorCode::TaskNotFound, Lang::Es) => "Tarea no encontrada",
+            (ErrorCode::TaskNotFound, Lang::En) => "Task not found",
+            (ErrorCode::TokenNotFound, Lang::Es) => "Token no encontrado o ya revocado",
+            (ErrorCode::TokenNotFound, Lang::En) => "Token not found or already revoked",
+            (ErrorCode::OrphanNotFound, Lang::Es) => {
+                "No hay un sandbox huerfano con manifest valido en esa ruta"
+            }
+            (ErrorCode::OrphanNotFound, Lang::En) => {
+                "No orphan sandbox with a valid manifest at that path"
+            }
+            (ErrorCode::TemplateNotFound, Lang::Es) => "Plantilla no encontrada",
+            (ErrorCode::TemplateNotFound, Lang::En) => "Template not found",
+            (ErrorCode::ScheduleNotFound, Lang::Es) => "Programacion no encontrada",
+            (ErrorCode::ScheduleNotFound, Lang::En) => "Schedule not found",
+            (ErrorCode::ScheduleNameConflict, Lang::Es) => {
+                "Ya existe una programacion con ese nombre"
+            }
+            (ErrorCode::ScheduleNameConflict, Lang::En) => {
+                "A schedule with that name already exists"
+            }
+            (ErrorCode::TaskNotRetryable, Lang::Es) => "La tarea no se puede reintentar",
+            (ErrorCode::TaskNotRetryable, Lang::En) => "The task cannot be retried",
+            (ErrorCode::PlanNotConfirmed, Lang::Es) => "El plan a aplicar no es el confirmado",
+            (ErrorCode::PlanNotConfirmed, Lang::En) => "The plan to apply is not the confirmed one",
+            (ErrorCode::IdempotencyKeyInUse, Lang::Es) => {
+                "Hay otra peticion en curso con la misma Idempotency-Key"
+            }
+            (ErrorCode::IdempotencyKeyInUse, Lang::En) => {
+                "Another request with the same Idempotency-Key is in progress"
+            }
+            (ErrorCode::IdempotencyKeyReused, Lang::Es) => {
+                "La Idempotency-Key ya se uso con otra peticion"
+            }
+            (ErrorCode::IdempotencyKeyReused, Lang::En) => {
+                "The Idempotency-Key was already used for a different request"
+            }
+            (ErrorCode::QuotaExceeded, Lang::Es) => "Cuota de disco agotada",
+            (ErrorCode::QuotaExceeded, Lang::En) => "Disk quota exceeded",
+            (ErrorCode::InternalError, Lang::Es) => "Error interno del agente",
+            (ErrorCode::InternalError, Lang::En) => "Internal agent error",
+        }
+    }
+}
+
+/// Idiomas soportados para los mensajes de error; `es` es el predeterminado.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    Es,
+    En,
+}
+
+impl Lang {
+    /// Elige el idioma soportado con mayor `q` de un encabezado `Accept-Language`.
+    pub fn from_accept_language(header: Option<&str>) -> Self {
+        let Some(header) = header else {
+            return Lang::default();
+        };
+
+        let mut best: Option<(Lang, f32)> = None;
+        for entry in header.split(',') {
+            let mut parts = entry.split(';');
+            let tag = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let lang = match tag.split('-').next() {
+                Some("es") => Lang::Es,
+                Some("en") => Lang::En,
+                _ => continue,
+            };
+            if best.is_none_or(|(_, current)| quality > current) {
+                best = Some((lang, quality));
+            }
+        }
+        best.map(|(lang, _)| lang).unwrap_or_default()
+    }
+
+    fn tag(&self) -> &'static str {
+        match self {
+            Lang::Es => "es",
+            Lang::En => "en",
+        }
+    }
+}
+
+/// Error devuelto por todos los handlers HTTP con un cuerpo JSON estable.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    status: StatusCode,
+    code: ErrorCode,
+    details: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: ErrorCode,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a Value>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            status: code.status(),
+            code,
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Registra el error original y devuelve un 500 sin filtrar detalles internos.
+    pub fn internal(context: &str, err: anyhow::Error) -> Self {
+        tracing::error!(?err, "{context}");
+        Self::new(ErrorCode::InternalError)
+    }
+
+    /// Error de validacion de un unico campo, con el mismo formato que `Validator`.
+    pub fn invalid_field(field: &str, code: &str, value: impl Serialize) -> Self {
+        Self::new(ErrorCode::ValidationFailed).with_details(serde_json::json!({
+            "fields": [{
+                "field": field,
+                "code": code,
+                "params": { "value": value },
+            }]
+        }))
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    fn render(&self, lang: Lang) -> Response {
+        let body = ErrorBody {
+            code: self.code,
+            message: self.code.message(lang),
+            details: self.details.as_ref(),
+        };
+        let mut response = (self.status, Json(body)).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_LANGUAGE,
+            HeaderValue::from_static(lang.tag()),
+        );
+        response
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = self.render(Lang::default());
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Vuelve a renderizar los errores segun el `Accept-Language` de la peticion.
+pub async fn localize_errors(req: Request<Body>, next: Next) -> Response {
+    let lang = Lang::from_accept_language(
+        req.headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let response = next.run(req).await;
+    if lang == Lang::default() {
+        return response;
+    }
+    match response.extensions().get::<ApiError>() {
+        Some(error) => error.render(lang),
+        None => response,
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(ErrorCode::InvalidBody)
+            .with_status(rejection.status())
+            .with_details(serde_json::json!({ "reason": rejection.body_text() }))
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::new(ErrorCode::InvalidPath)
+            .with_details(serde_json::json!({ "reason": rejection.body_text() }))
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(ErrorCode::InvalidQuery)
+            .with_details(serde_json::json!({ "reason": rejection.body_text() }))
+    }
+}
+
+/// `Json` que reporta errores de deserializacion como [`ApiError`].
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct ApiJson<T>(pub T);
+
+/// `Path` que reporta parametros invalidos como [`ApiError`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Path), rejection(ApiError))]
+pub struct ApiPath<T>(pub T);
+
+/// `Query` que reporta parametros invalidos como [`ApiError`].
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct ApiQuery<T>(pub T);
@@ -3,6 +3,8 @@ use time::OffsetDateTime;
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
+use crate::virtualization::FsChange;
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum AgentEvent {
@@ -10,6 +12,8 @@ pub enum AgentEvent {
         id: Uuid,
         task_type: String,
         status: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parent_task_id: Option<Uuid>,
     },
     TaskProgress {
         id: Uuid,
@@ -20,6 +24,21 @@ pub enum AgentEvent {
         container_id: Uuid,
         status: String,
     },
+    ContainerUpdated {
+        container_id: Uuid,
+    },
+    AppStatus {
+        app_id: Uuid,
+        container_id: Uuid,
+        status: String,
+    },
+    /// Cambio en `fs/` de un contenedor vigilado.
+    #[serde(rename = "container.fs-changed")]
+    ContainerFsChanged {
+        container_id: Uuid,
+        #[serde(flatten)]
+        change: FsChange,
+    },
 }
 
 #[derive(Debug, Clone, Serialize)]
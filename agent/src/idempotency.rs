@@ -0,0 +1,199 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::State,
+    http::{HeaderValue, Method, Request, StatusCode, Uri, header},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+use crate::{
+    error::{ApiError, ErrorCode},
+    models::IdempotencyRecord,
+    security::{AuthContext, hash_token},
+    store::SqliteStore,
+};
+
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+/// Marca las respuestas repetidas a partir de una clave ya usada.
+pub const IDEMPOTENT_REPLAYED: &str = "idempotent-replayed";
+
+const KEY_MAX_LEN: usize = 255;
+const BODY_MAX_BYTES: usize = 32 * 1024 * 1024;
+/// Tiempo que se recuerda cada clave.
+const RETENTION: time::Duration = time::Duration::hours(24);
+
+/// Respuestas de las peticiones con `Idempotency-Key`, por clave y credencial. Una
+/// peticion repetida con la misma clave y el mismo cuerpo recibe la respuesta original
+/// (p.e. la tarea que creo el contenedor) en lugar de ejecutarse otra vez.
+#[derive(Clone)]
+pub struct Idempotency {
+    store: SqliteStore,
+    in_flight: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Idempotency {
+    pub fn new(store: SqliteStore) -> Self {
+        Self {
+            store,
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// Reserva la clave mientras se atiende la peticion; `None` si ya esta reservada.
+    fn claim(&self, key: &str) -> Option<Claim> {
+        let mut in_flight = self.in_flight.lock().expect("idempotency lock");
+        in_flight.insert(key.to_string()).then(|| Claim {
+            in_flight: self.in_flight.clone(),
+            key: key.to_string(),
+        })
+    }
+}
+
+struct Claim {
+    in_flight: Arc<Mutex<HashSet<String>>>,
+    key: String,
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if let Ok(mut in_flight) = self.in_flight.lock() {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+/// Solo se guardan las respuestas correctas: un error se puede reintentar con la misma clave.
+pub async fn idempotency_middleware(
+    State(idempotency): State<Idempotency>,
+    req: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let mutating = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY).filter(|_| mutating) else {
+        return Ok(next.run(req).await);
+    };
+    let key = parse_key(key)?;
+    let scoped = format!("{}:{key}", principal(req.extensions().get::<AuthContext>()));
+
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, BODY_MAX_BYTES).await.map_err(|_| {
+        ApiError::new(ErrorCode::InvalidBody)
+            .with_status(StatusCode::PAYLOAD_TOO_LARGE)
+            .with_details(serde_json::json!({ "max_bytes": BODY_MAX_BYTES }))
+    })?;
+    let fingerprint = fingerprint(&parts.method, &parts.uri, &body);
+
+    let Some(_claim) = idempotency.claim(&scoped) else {
+        return Err(ApiError::new(ErrorCode::IdempotencyKeyInUse)
+            .with_details(serde_json::json!({ "key": key })));
+    };
+    let stored = idempotency
+        .store
+        .get_idempotency_record(&scoped)
+        .await
+        .map_err(|err| ApiError::internal("Error consultando Idempotency-Key", err))?;
+    if let Some(record) = stored {
+        if record.fingerprint != fingerprint {
+            return Err(ApiError::new(ErrorCode::IdempotencyKeyReused)
+                .with_details(serde_json::json!({ "key": key })));
+        }
+        return Ok(replay(record));
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if !response.status().is_success() {
+        return Ok(response);
+    }
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|err| ApiError::internal("No se pudo leer la respuesta", err.into()))?;
+    let record = IdempotencyRecord::new(
+        scoped,
+        fingerprint,
+        parts.status.as_u16(),
+        parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        body.to_vec(),
+    );
+    if let Err(err) = remember(&idempotency.store, &record).await {
+        tracing::warn!(
+            ?err,
+            "No se pudo guardar la respuesta de la Idempotency-Key"
+        );
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+async fn remember(store: &SqliteStore, record: &IdempotencyRecord) -> anyhow::Result<()> {
+    store
+        .purge_idempotency_records(OffsetDateTime::now_utc() - RETENTION)
+        .await?;
+    store.insert_idempotency_record(record).await
+}
+
+fn parse_key(value: &HeaderValue) -> Result<String, ApiError> {
+    let key = value.to_str().unwrap_or_default().trim();
+    let code = if key.is_empty() {
+        "required"
+    } else if key.len() > KEY_MAX_LEN {
+        "too_long"
+    } else {
+        return Ok(key.to_string());
+    };
+    Err(
+        ApiError::new(ErrorCode::ValidationFailed).with_details(serde_json::json!({
+            "fields": [{
+                "field": "Idempotency-Key",
+                "code": code,
+                "params": { "max": KEY_MAX_LEN },
+            }]
+        })),
+    )
+}
+
+/// Las claves de cada credencial son independientes.
+fn principal(context: Option<&AuthContext>) -> String {
+    match context {
+        Some(AuthContext::Admin) | None => "admin".into(),
+        Some(AuthContext::StaticToken { token }) => format!("static:{}", &hash_token(token)[..16]),
+        Some(AuthContext::ServiceToken { token }) => format!("token:{}", token.id),
+    }
+}
+
+fn fingerprint(method: &Method, uri: &Uri, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(uri.to_string().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+fn replay(record: IdempotencyRecord) -> Response {
+    let mut response = Response::new(Body::from(record.body));
+    *response.status_mut() = StatusCode::from_u16(record.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    if let Some(value) = record
+        .content_type
+        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
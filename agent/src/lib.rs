@@ -1,11 +1,17 @@
 pub mod config;
+pub mod cron;
+pub mod desired;
+pub mod error;
 pub mod events;
+pub mod idempotency;
 pub mod models;
 pub mod security;
 pub mod server;
 pub mod services;
 pub mod store;
 pub mod telemetry;
+pub mod templates;
+pub mod validation;
 pub mod virtualization;
 
 pub use security::AuthManager;
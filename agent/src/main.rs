@@ -22,6 +22,9 @@ impl Agent {
     }
 
     pub async fn bootstrap(&self) -> Result<()> {
+        if self.containers.find_by_name("chrome-poc").await?.is_some() {
+            return Ok(());
+        }
         let _ = self
             .containers
             .create_container(
@@ -52,6 +55,25 @@ async fn main() -> Result<()> {
     let token_service = TokenService::new(store.clone());
     let auth_manager = AuthManager::new(config.security.clone(), store.clone());
 
+    let migrated = container_service.migrate_storage().await?;
+    if migrated > 0 {
+        info!("{migrated} contenedor(es) migrados a rutas basadas en id");
+    }
+    let adopted = container_service.adopt_orphans().await?;
+    if !adopted.is_empty() {
+        info!(
+            "{} sandbox(es) de containers_root registrados desde su manifest",
+            adopted.len()
+        );
+    }
+
+    let watched = container_service.resume_watches().await?;
+    if watched > 0 {
+        info!("{watched} contenedor(es) en ejecucion vigilados de nuevo");
+    }
+
+    tokio::spawn(container_service.clone().run_usage_scanner());
+
     let agent = Agent::new(container_service.clone());
     let app_state = AppState::new(
         config.clone(),
@@ -63,6 +85,9 @@ async fn main() -> Result<()> {
         token_service.clone(),
         auth_manager.clone(),
     );
+    tokio::spawn(app_state.retries.clone().run_auto_retry());
+    tokio::spawn(app_state.schedules.clone().run_scheduler());
+    tokio::spawn(app_state.retention.clone().run_retention());
 
     let (shutdown_tx, shutdown_rx) = oneshot::channel();
     let server_handle = tokio::spawn(async move {
@@ -1,7 +1,10 @@
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::virtualization::Platform;
+use crate::virtualization::{MountSpec, Platform};
 
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -41,13 +44,41 @@ pub struct ContainerModel {
     pub name: String,
     pub description: Option<String>,
     pub status: ContainerStatus,
+    /// Motivo del estado actual cuando es `error` (p.e. archivos criticos ausentes).
+    pub status_reason: Option<String>,
     pub platform: Platform,
     pub tags: Vec<String>,
     pub size_bytes: u64,
+    pub storage_path: String,
+    pub settings: ContainerSettings,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Ajustes editables del contenedor: variables, flags de compatibilidad y limites.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ContainerSettings {
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub compat_flags: Vec<String>,
+    #[serde(default)]
+    pub limits: ContainerLimits,
+    /// Montajes adicionales a los por defecto (`C:\`, `C:\Program Files`, `C:\ProgramData`).
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerLimits {
+    pub cpu_percent: Option<u8>,
+    pub memory_mb: Option<u64>,
+    pub max_processes: Option<u32>,
+    /// Cuota de disco del sandbox; sustituye a `storage.container_quota_mb`.
+    #[serde(default)]
+    pub disk_mb: Option<u64>,
+}
+
 impl ContainerModel {
     pub fn new(id: Uuid, name: String, description: Option<String>, platform: Platform) -> Self {
         let timestamp = current_timestamp();
@@ -56,9 +87,12 @@ impl ContainerModel {
             name,
             description,
             status: ContainerStatus::Ready,
+            status_reason: None,
             platform,
             tags: vec![],
             size_bytes: 0,
+            storage_path: String::new(),
+            settings: ContainerSettings::default(),
             created_at: timestamp.clone(),
             updated_at: timestamp,
         }
@@ -109,10 +143,102 @@ pub struct TaskModel {
     pub status: TaskStatus,
     pub progress: u8,
     pub message: Option<String>,
+    /// Resultado estructurado de la tarea (p.e. el informe de una verificacion).
+    pub result: Option<serde_json::Value>,
+    /// Tarea de la que esta es un paso; `None` en las de primer nivel.
+    pub parent_task_id: Option<Uuid>,
+    /// Orden del paso dentro de la tarea padre.
+    pub step: Option<u32>,
+    /// Si un paso obligatorio falla, la tarea padre falla con el.
+    pub required: bool,
+    /// Pasos previstos de una tarea agrupada; su progreso se calcula con los de las subtareas.
+    pub total_steps: Option<u32>,
+    /// Intento actual, desde 1; cada reintento es una tarea nueva con `retry_of`.
+    pub attempt: u32,
+    /// Intentos que admite la politica de reintentos del tipo de tarea.
+    pub max_attempts: u32,
+    /// Tarea fallida de la que esta es el reintento.
+    pub retry_of: Option<Uuid>,
+    /// Momento en que se lanzara el reintento automatico ya programado.
+    pub next_retry_at: Option<String>,
+    /// Peticion que origino la tarea; permite reintentarla.
+    pub payload: Option<serde_json::Value>,
+    /// Programacion que lanzo la tarea.
+    pub schedule_id: Option<Uuid>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Posicion de una subtarea dentro de su tarea padre.
+#[derive(Clone, Copy, Debug)]
+pub struct TaskStep {
+    pub parent: Uuid,
+    pub step: u32,
+    pub required: bool,
+}
+
+/// Tarea con sus subtareas ordenadas por `step`.
+#[derive(Clone, Serialize)]
+pub struct TaskTree {
+    #[serde(flatten)]
+    pub task: TaskModel,
+    pub children: Vec<TaskTree>,
+}
+
+impl TaskTree {
+    /// Arma el arbol de `root` con sus descendientes, que deben venir ya ordenados.
+    pub fn build(root: TaskModel, descendants: Vec<TaskModel>) -> Self {
+        let mut by_parent: HashMap<Uuid, Vec<TaskModel>> = HashMap::new();
+        for task in descendants {
+            if let Some(parent) = task.parent_task_id {
+                by_parent.entry(parent).or_default().push(task);
+            }
+        }
+        Self::attach(root, &mut by_parent)
+    }
+
+    fn attach(task: TaskModel, by_parent: &mut HashMap<Uuid, Vec<TaskModel>>) -> Self {
+        let children = by_parent
+            .remove(&task.id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| Self::attach(child, by_parent))
+            .collect();
+        Self { task, children }
+    }
+}
+
+/// Respuesta guardada de una peticion con `Idempotency-Key`, que se repite tal cual.
+#[derive(Clone)]
+pub struct IdempotencyRecord {
+    pub key: String,
+    /// Hash del metodo, la ruta y el cuerpo de la peticion original.
+    pub fingerprint: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    pub created_at: String,
+}
+
+impl IdempotencyRecord {
+    pub fn new(
+        key: String,
+        fingerprint: String,
+        status: u16,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    ) -> Self {
+        Self {
+            key,
+            fingerprint,
+            status,
+            content_type,
+            body,
+            created_at: current_timestamp(),
+        }
+    }
+}
+
 impl TaskModel {
     pub fn new(task_type: impl Into<String>) -> Self {
         let timestamp = current_timestamp();
@@ -122,6 +248,17 @@ impl TaskModel {
             status: TaskStatus::Queued,
             progress: 0,
             message: None,
+            result: None,
+            parent_task_id: None,
+            step: None,
+            required: true,
+            total_steps: None,
+            attempt: 1,
+            max_attempts: 1,
+            retry_of: None,
+            next_retry_at: None,
+            payload: None,
+            schedule_id: None,
             created_at: timestamp.clone(),
             updated_at: timestamp,
         }
@@ -132,8 +269,65 @@ impl TaskModel {
         self
     }
 
+    pub fn with_step(mut self, step: TaskStep) -> Self {
+        self.parent_task_id = Some(step.parent);
+        self.step = Some(step.step);
+        self.required = step.required;
+        self
+    }
+
+    pub fn with_schedule(mut self, schedule_id: Uuid) -> Self {
+        self.schedule_id = Some(schedule_id);
+        self
+    }
+
+    pub fn with_total_steps(mut self, total_steps: usize) -> Self {
+        self.total_steps = Some(total_steps as u32);
+        self
+    }
+
+    /// Siguiente intento de esta tarea: mismo tipo y peticion, sin estado ni resultado.
+    pub fn next_attempt(&self) -> Self {
+        let mut task = Self::new(self.task_type.clone());
+        task.attempt = self.attempt + 1;
+        task.max_attempts = self.max_attempts;
+        task.retry_of = Some(self.id);
+        task.payload = self.payload.clone();
+        task.schedule_id = self.schedule_id;
+        task
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            TaskStatus::Succeeded | TaskStatus::Failed | TaskStatus::Cancelled
+        )
+    }
+
+    /// Media del progreso de las subtareas sobre los pasos previstos; las terminadas
+    /// cuentan como completas. Sin subtareas se conserva el progreso propio.
+    pub fn aggregate_progress(&self, children: &[TaskModel]) -> u8 {
+        let steps = children
+            .len()
+            .max(self.total_steps.unwrap_or_default() as usize);
+        if children.is_empty() || steps == 0 {
+            return self.progress;
+        }
+        let done: usize = children
+            .iter()
+            .map(|child| {
+                if child.is_finished() {
+                    100
+                } else {
+                    child.progress as usize
+                }
+            })
+            .sum();
+        (done / steps).min(100) as u8
+    }
+
     pub fn set_progress(&mut self, progress: u8, message: impl Into<Option<String>>) {
-        self.progress = progress;
+        self.progress = progress.min(100);
         self.message = message.into();
         self.touch();
     }
@@ -147,8 +341,22 @@ impl TaskModel {
 pub struct AppEntryPoint {
     pub id: String,
     pub label: String,
+    /// Ruta virtual del ejecutable (`C:\Program Files\...`).
     pub command: String,
+    /// Plantilla de argumentos; admite los marcadores de [`AppEntryPoint::ARGS_PLACEHOLDERS`].
+    #[serde(default)]
+    pub args: Option<String>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
     pub icon: Option<String>,
+    /// Variables que se superponen al entorno del contenedor al lanzar.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+impl AppEntryPoint {
+    pub const ARGS_PLACEHOLDERS: &'static [&'static str] =
+        &["app_id", "container_id", "fs_root", "args"];
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -158,6 +366,7 @@ pub enum AppStatus {
     Ready,
     Failed,
     Disabled,
+    Uninstalling,
 }
 
 impl AppStatus {
@@ -167,6 +376,7 @@ impl AppStatus {
             AppStatus::Ready => "ready",
             AppStatus::Failed => "failed",
             AppStatus::Disabled => "disabled",
+            AppStatus::Uninstalling => "uninstalling",
         }
     }
 
@@ -175,6 +385,7 @@ impl AppStatus {
             "installing" => AppStatus::Installing,
             "failed" => AppStatus::Failed,
             "disabled" => AppStatus::Disabled,
+            "uninstalling" => AppStatus::Uninstalling,
             _ => AppStatus::Ready,
         }
     }
@@ -186,8 +397,27 @@ pub struct AppInstance {
     pub container_id: Uuid,
     pub name: String,
     pub version: Option<String>,
+    pub vendor: Option<String>,
     pub status: AppStatus,
     pub entry_points: Vec<AppEntryPoint>,
+    /// Instalador o archivo original con el que se instalo la app.
+    pub install_source: Option<String>,
+    /// SHA-256 de `install_source`, registrado para auditoria.
+    pub installer_sha256: Option<String>,
+    /// Copia del instalador que se ejecuto, en `runtime/installers/<app_id>/`; vacio
+    /// para las apps portables.
+    pub installer_path: Option<String>,
+    pub silent_args: Option<String>,
+    pub exit_code: Option<i32>,
+    pub log_path: Option<String>,
+    /// Carpeta virtual donde quedo la app; se elimina al desinstalar.
+    pub install_dir: Option<String>,
+    /// Desinstalador detectado durante la instalacion (ruta virtual).
+    pub uninstaller: Option<String>,
+    /// Duracion de la ultima instalacion o actualizacion.
+    pub install_duration_ms: Option<u64>,
+    /// Espacio ocupado por los archivos que escribio la instalacion.
+    pub size_bytes: u64,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -200,8 +430,19 @@ impl AppInstance {
             container_id,
             name,
             version,
+            vendor: None,
             status: AppStatus::Ready,
             entry_points: vec![],
+            install_source: None,
+            installer_sha256: None,
+            installer_path: None,
+            silent_args: None,
+            exit_code: None,
+            log_path: None,
+            install_dir: None,
+            uninstaller: None,
+            install_duration_ms: None,
+            size_bytes: 0,
             created_at: timestamp.clone(),
             updated_at: timestamp,
         }
@@ -212,14 +453,17 @@ impl AppInstance {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum SnapshotType {
+    #[default]
     Full,
     Delta,
 }
 
 impl SnapshotType {
+    pub const VALUES: &'static [&'static str] = &["full", "delta"];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             SnapshotType::Full => "full",
@@ -260,6 +504,139 @@ impl Snapshot {
     }
 }
 
+/// Trabajo que lanza una programacion; cada ejecucion es una tarea normal.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ScheduleJob {
+    /// Snapshot del contenedor (`snapshot.create`).
+    Snapshot {
+        container_id: Uuid,
+        #[serde(default)]
+        label: Option<String>,
+        #[serde(default)]
+        snapshot_type: SnapshotType,
+    },
+    /// Verificacion de integridad (`container.verify`).
+    Verify { container_id: Uuid },
+    /// Borra los snapshots mas antiguos y conserva los `keep` mas recientes (`snapshot.prune`).
+    Prune { container_id: Uuid, keep: u32 },
+    /// Empaqueta el sandbox en un `.tar.gz` (`container.export`) en `<data_dir>/exports`
+    /// o en el subdirectorio relativo `destination`; con `keep` solo se conservan los
+    /// `keep` archivos mas recientes del contenedor en ese directorio.
+    Export {
+        container_id: Uuid,
+        #[serde(default)]
+        destination: Option<String>,
+        #[serde(default)]
+        keep: Option<u32>,
+    },
+    /// Elimina los tokens de servicio caducados (`token.sweep`).
+    TokenSweep,
+}
+
+impl ScheduleJob {
+    pub const VALUES: &'static [&'static str] =
+        &["snapshot", "verify", "prune", "export", "token-sweep"];
+
+    /// Tipo de las tareas que crea cada ejecucion.
+    pub fn task_type(&self) -> &'static str {
+        match self {
+            ScheduleJob::Snapshot { .. } => "snapshot.create",
+            ScheduleJob::Verify { .. } => "container.verify",
+            ScheduleJob::Prune { .. } => "snapshot.prune",
+            ScheduleJob::Export { .. } => "container.export",
+            ScheduleJob::TokenSweep => "token.sweep",
+        }
+    }
+
+    pub fn container_id(&self) -> Option<Uuid> {
+        match self {
+            ScheduleJob::Snapshot { container_id, .. }
+            | ScheduleJob::Verify { container_id }
+            | ScheduleJob::Prune { container_id, .. }
+            | ScheduleJob::Export { container_id, .. } => Some(*container_id),
+            ScheduleJob::TokenSweep => None,
+        }
+    }
+}
+
+/// Que hacer al arrancar con las ejecuciones que cayeron mientras el agente estaba parado.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MissedRunPolicy {
+    /// Se descartan; la programacion sigue en su proxima hora.
+    Skip,
+    /// Se lanza una sola ejecucion, aunque se perdieran varias.
+    #[default]
+    RunOnce,
+}
+
+impl MissedRunPolicy {
+    pub const VALUES: &'static [&'static str] = &["skip", "run-once"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissedRunPolicy::Skip => "skip",
+            MissedRunPolicy::RunOnce => "run-once",
+        }
+    }
+}
+
+impl FromStr for MissedRunPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "skip" => Ok(MissedRunPolicy::Skip),
+            "run-once" => Ok(MissedRunPolicy::RunOnce),
+            other => Err(format!(
+                "Politica de ejecuciones perdidas desconocida: {other}"
+            )),
+        }
+    }
+}
+
+/// Trabajo periodico definido con una expresion cron (en UTC).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: Uuid,
+    pub name: String,
+    pub cron: String,
+    pub job: ScheduleJob,
+    pub enabled: bool,
+    pub missed_runs: MissedRunPolicy,
+    /// Proxima ejecucion; `None` mientras esta desactivada.
+    pub next_run_at: Option<String>,
+    pub last_run_at: Option<String>,
+    /// Tarea de la ultima ejecucion.
+    pub last_task_id: Option<Uuid>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Schedule {
+    pub fn new(name: String, cron: String, job: ScheduleJob) -> Self {
+        let timestamp = current_timestamp();
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            cron,
+            job,
+            enabled: true,
+            missed_runs: MissedRunPolicy::default(),
+            next_run_at: None,
+            last_run_at: None,
+            last_task_id: None,
+            created_at: timestamp.clone(),
+            updated_at: timestamp,
+        }
+    }
+
+    pub fn touch(&mut self) {
+        self.updated_at = current_timestamp();
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiTokenInfo {
     pub id: Uuid,
@@ -1,15 +1,14 @@
-use axum::{
-    body::Body,
-    extract::State,
-    http::{Request, StatusCode},
-    middleware::Next,
-    response::Response,
-};
+use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
 use sha2::{Digest, Sha256};
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 use tokio::sync::RwLock;
 
-use crate::{config::SecurityConfig, models::ApiTokenInfo, store::SqliteStore};
+use crate::{
+    config::SecurityConfig,
+    error::{ApiError, ErrorCode},
+    models::ApiTokenInfo,
+    store::SqliteStore,
+};
 
 #[derive(Clone)]
 pub struct AuthManager {
@@ -127,11 +126,18 @@ pub fn hash_token(token: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// SHA-256 en hexadecimal de un archivo; bloqueante, llamar desde `spawn_blocking`.
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 pub async fn auth_middleware(
     State(manager): State<AuthManager>,
     mut req: Request<Body>,
     next: Next,
-) -> Result<Response, StatusCode> {
+) -> Result<Response, ApiError> {
     let context = if manager.enabled().await {
         let header = req
             .headers()
@@ -139,7 +145,7 @@ pub async fn auth_middleware(
             .and_then(|value| value.to_str().ok());
         match manager.authorize(header).await {
             Some(ctx) => ctx,
-            None => return Err(StatusCode::UNAUTHORIZED),
+            None => return Err(ApiError::new(ErrorCode::Unauthorized)),
         }
     } else {
         AuthContext::Admin
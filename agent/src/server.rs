@@ -1,28 +1,61 @@
-use std::{collections::BTreeSet, convert::Infallible, time::Duration as StdDuration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::Infallible,
+    path::{Path, PathBuf},
+    time::Duration as StdDuration,
+};
 
 use anyhow::Result;
 use axum::{
     Json, Router,
-    extract::{Extension, Path, Query, State},
-    http::StatusCode,
-    middleware::from_fn_with_state,
-    response::sse::{Event, KeepAlive, Sse},
-    routing::{delete, get, post},
+    body::Body,
+    extract::{Extension, State},
+    http::{StatusCode, header},
+    middleware::{from_fn, from_fn_with_state},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{delete, get, patch, post},
 };
 use futures_core::stream::Stream;
 use serde::{Deserialize, Serialize};
 use tokio::{net::TcpListener, sync::oneshot};
 use tokio_stream::{StreamExt, wrappers::BroadcastStream};
+use tokio_util::io::{ReaderStream, StreamReader};
 use uuid::Uuid;
 
 use crate::{
     config::{self, AgentConfig, ConfigSnapshot, ConfigSourcesView, SecurityConfig},
+    cron::CronExpr,
+    desired::DesiredState,
+    error::{ApiError, ApiPath, ApiQuery, ErrorCode, localize_errors},
     events::EventHub,
-    models::{ApiTokenInfo, AppInstance, ContainerModel, Snapshot, SnapshotType, TaskModel},
+    idempotency::{Idempotency, idempotency_middleware},
+    models::{
+        ApiTokenInfo, AppEntryPoint, AppInstance, ContainerLimits, ContainerModel, MissedRunPolicy,
+        Schedule, ScheduleJob, Snapshot, SnapshotType, TaskModel, TaskTree,
+    },
     security::{AuthContext, AuthManager, SecuritySnapshot, auth_middleware},
-    services::{AppService, ContainerService, SnapshotService, TokenService, TokenSpec},
-    store::SqliteStore,
-    virtualization::Platform,
+    services::{
+        AppBusyError, AppInstallSpec, AppService, AppUpdateSpec, ApplyPlan, ApplyService,
+        ChangeLog, ContainerFileError, ContainerNotReadyError, ContainerService, ContainerUpdate,
+        EntryPointError, EntryPointSpec, EntryPointUpdate, InstallSource, MountPolicyError,
+        OrphanSandbox, PathResolution, PlanConfirmationError, PlatformChangeError,
+        QuotaExceededError, ReconcileReport, RetentionService, RetentionStats, RetryQueue,
+        RetryService, ScheduleError, ScheduleService, ScheduleSpec, ScheduleUpdate,
+        SnapshotService, StorageSummary, TaskRetryError, TemplateService, TokenService, TokenSpec,
+    },
+    store::{SqliteStore, StoreError},
+    templates::{ContainerTemplate, TemplateApp},
+    validation::{
+        FieldError, Validate, ValidatedDocument, ValidatedJson, Validator, is_env_key_char,
+        is_scope_char, is_tag_char,
+    },
+    virtualization::{
+        FsEntry, MountSpec, PathMapError, Platform, RegistryValue, app_files::AppFileManifest,
+        archive::ArchiveFormat, compat,
+    },
 };
 use time::{Duration, OffsetDateTime, format_description::well_known::Rfc3339};
 
@@ -30,6 +63,15 @@ const SCOPE_CONTAINERS_READ: &str = "containers:read";
 const SCOPE_CONTAINERS_WRITE: &str = "containers:write";
 const SCOPE_TASKS_READ: &str = "tasks:read";
 
+const NAME_MAX_LEN: usize = 64;
+const DESCRIPTION_MAX_LEN: usize = 1024;
+const TAG_MAX_LEN: usize = 64;
+const LABEL_MAX_LEN: usize = 128;
+const PATH_MAX_LEN: usize = 4096;
+const MOUNTS_MAX: usize = 64;
+/// Snapshots que puede conservar como mucho un trabajo `prune`.
+const SCHEDULE_KEEP_MAX: u32 = 1000;
+
 #[derive(Clone)]
 pub struct AppState {
     pub config: AgentConfig,
@@ -39,6 +81,12 @@ pub struct AppState {
     pub apps: AppService,
     pub snapshots: SnapshotService,
     pub tokens: TokenService,
+    pub templates: TemplateService,
+    pub apply: ApplyService,
+    pub retries: RetryService,
+    pub schedules: ScheduleService,
+    pub retention: RetentionService,
+    pub idempotency: Idempotency,
     pub auth: AuthManager,
     pub started_at: OffsetDateTime,
 }
@@ -54,7 +102,31 @@ impl AppState {
         tokens: TokenService,
         auth: AuthManager,
     ) -> Self {
+        let (retry_queue, failed_tasks) = RetryQueue::channel();
+        let apps = apps.with_retry(&config.tasks, retry_queue.clone());
+        let snapshots = snapshots.with_retry(&config.tasks, retry_queue);
+        let apply = ApplyService::new(store.clone(), containers.clone(), apps.clone());
         Self {
+            templates: TemplateService::new(&config, containers.clone(), apps.clone()),
+            retries: RetryService::new(
+                &config.tasks,
+                events.clone(),
+                store.clone(),
+                apps.clone(),
+                snapshots.clone(),
+                apply.clone(),
+                failed_tasks,
+            ),
+            schedules: ScheduleService::new(
+                events.clone(),
+                store.clone(),
+                containers.clone(),
+                snapshots.clone(),
+                tokens.clone(),
+            ),
+            retention: RetentionService::new(&config, store.clone()),
+            idempotency: Idempotency::new(store.clone()),
+            apply,
             config,
             events,
             store,
@@ -69,19 +141,72 @@ impl AppState {
 }
 
 pub async fn serve(state: AppState, shutdown: oneshot::Receiver<()>) -> Result<()> {
+    // Las subidas se escriben en streaming y sin limite de tamano; quedan fuera de la
+    // capa de Idempotency-Key, que tendria que cargar el cuerpo entero en memoria.
+    let streaming = Router::new()
+        .route(
+            "/containers/:container_id/files/content",
+            get(download_container_file).put(upload_container_file),
+        )
+        .with_state(state.clone());
     let app = Router::new()
         .route("/system/info", get(system_info))
         .route("/system/config", get(system_config))
+        .route("/system/storage", get(system_storage))
+        .route("/system/reconcile", post(system_reconcile))
         .route("/system/security/reload", post(reload_security))
+        .route("/templates", get(list_templates))
+        .route(
+            "/templates/:name",
+            get(get_template).put(put_template).delete(delete_template),
+        )
         .route("/containers", get(list_containers).post(create_container))
+        .route("/containers/orphans", get(list_orphans))
+        .route("/containers/from-template", post(create_from_template))
+        .route("/containers/plan", post(plan_containers))
+        .route("/containers/apply", post(apply_containers))
+        .route("/containers/orphans/adopt", post(adopt_orphan))
         .route(
             "/containers/:container_id",
-            get(get_container).delete(delete_container),
+            get(get_container)
+                .patch(update_container)
+                .delete(delete_container),
         )
         .route(
             "/containers/:container_id/apps",
             get(list_apps).post(install_app),
         )
+        .route("/containers/:container_id/rename", post(rename_container))
+        .route("/containers/:container_id/verify", post(verify_container))
+        .route("/containers/:container_id/start", post(start_container))
+        .route("/containers/:container_id/stop", post(stop_container))
+        .route(
+            "/containers/:container_id/changes",
+            get(list_container_changes),
+        )
+        .route("/containers/:container_id/files", get(list_container_files))
+        .route(
+            "/containers/:container_id/paths/resolve",
+            get(resolve_container_path),
+        )
+        .route("/containers/:container_id/tags", post(add_container_tags))
+        .route(
+            "/containers/:container_id/tags/:tag",
+            delete(remove_container_tag),
+        )
+        .route(
+            "/apps/:app_id/entry-points",
+            get(list_entry_points).post(create_entry_point),
+        )
+        .route(
+            "/apps/:app_id/entry-points/:entry_point_id",
+            patch(update_entry_point).delete(delete_entry_point),
+        )
+        .route("/apps/:app_id", delete(uninstall_app))
+        .route("/apps/:app_id/files", get(list_app_files))
+        .route("/apps/:app_id/disable", post(disable_app))
+        .route("/apps/:app_id/enable", post(enable_app))
+        .route("/apps/:app_id/update", post(update_app))
         .route("/apps/:app_id/launch", post(launch_app))
         .route(
             "/containers/:container_id/snapshots",
@@ -90,6 +215,15 @@ pub async fn serve(state: AppState, shutdown: oneshot::Receiver<()>) -> Result<(
         .route("/snapshots/:snapshot_id/restore", post(restore_snapshot))
         .route("/tasks", get(list_tasks))
         .route("/tasks/:task_id", get(task_detail))
+        .route("/tasks/:task_id/retry", post(retry_task))
+        .route("/schedules", get(list_schedules).post(create_schedule))
+        .route(
+            "/schedules/:schedule_id",
+            get(get_schedule)
+                .patch(update_schedule)
+                .delete(delete_schedule),
+        )
+        .route("/schedules/:schedule_id/run", post(run_schedule))
         .route("/events/stream", get(events_stream))
         .route(
             "/security/tokens",
@@ -97,7 +231,13 @@ pub async fn serve(state: AppState, shutdown: oneshot::Receiver<()>) -> Result<(
         )
         .route("/security/tokens/:token_id", delete(revoke_api_token))
         .with_state(state.clone())
-        .layer(from_fn_with_state(state.auth.clone(), auth_middleware));
+        .layer(from_fn_with_state(
+            state.idempotency.clone(),
+            idempotency_middleware,
+        ))
+        .merge(streaming)
+        .layer(from_fn_with_state(state.auth.clone(), auth_middleware))
+        .layer(from_fn(localize_errors));
 
     let listener = TcpListener::bind(state.config.api_bind).await?;
     tracing::info!("API escuchando en {}", state.config.api_bind);
@@ -123,20 +263,22 @@ async fn system_info(State(state): State<AppState>) -> Json<SystemInfo> {
         build,
         uptime_seconds: uptime.whole_seconds().max(0) as u64,
         driver_status: "not-configured".into(),
+        task_retention: state.retention.stats().await,
     })
 }
 
 async fn system_config(
     Extension(ctx): Extension<AuthContext>,
     State(state): State<AppState>,
-) -> Result<Json<ConfigResponse>, StatusCode> {
+) -> Result<Json<ConfigResponse>, ApiError> {
     ensure_admin(&ctx)?;
     let config_snapshot = state.config.snapshot();
     let security_snapshot = state.auth.snapshot().await;
-    let tokens = state.tokens.list().await.map_err(|err| {
-        tracing::error!(?err, "No se pudieron listar tokens administrados");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let tokens = state
+        .tokens
+        .list()
+        .await
+        .map_err(|err| ApiError::internal("No se pudieron listar tokens administrados", err))?;
     let sources = config::config_sources_view();
     let security_view = build_security_response(&security_snapshot, &tokens);
 
@@ -147,12 +289,39 @@ async fn system_config(
     }))
 }
 
+async fn system_storage(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Json<StorageSummary>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .containers
+        .storage_summary()
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudo calcular el uso de disco", err))
+}
+
+async fn system_reconcile(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Json<ReconcileReport>, ApiError> {
+    ensure_admin(&ctx)?;
+    state
+        .containers
+        .reconcile()
+        .await
+        .map(Json)
+        .map_err(|err| service_error("No se pudo reconciliar la base de datos", err))
+}
+
 #[derive(Serialize)]
 struct SystemInfo {
     version: String,
     build: String,
     uptime_seconds: u64,
     driver_status: String,
+    task_retention: RetentionStats,
 }
 
 #[derive(Serialize)]
@@ -175,15 +344,16 @@ struct SecurityReloadResponse {
 async fn reload_security(
     Extension(ctx): Extension<AuthContext>,
     State(state): State<AppState>,
-) -> Result<Json<SecurityReloadResponse>, StatusCode> {
+) -> Result<Json<SecurityReloadResponse>, ApiError> {
     ensure_admin(&ctx)?;
     let latest = SecurityConfig::from_env();
     state.auth.reload(latest).await;
     let snapshot = state.auth.snapshot().await;
-    let tokens = state.tokens.list().await.map_err(|err| {
-        tracing::error!(?err, "No se pudieron listar tokens administrados");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let tokens = state
+        .tokens
+        .list()
+        .await
+        .map_err(|err| ApiError::internal("No se pudieron listar tokens administrados", err))?;
     let response = build_security_response(&snapshot, &tokens);
 
     Ok(Json(response))
@@ -192,23 +362,21 @@ async fn reload_security(
 #[derive(Deserialize)]
 struct ContainersQuery {
     status: Option<String>,
+    tag: Option<String>,
 }
 
 async fn list_containers(
     Extension(ctx): Extension<AuthContext>,
-    Query(params): Query<ContainersQuery>,
+    ApiQuery(params): ApiQuery<ContainersQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<ContainerModel>>, StatusCode> {
+) -> Result<Json<Vec<ContainerModel>>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
     state
         .store
-        .list_containers(params.status.map(|s| s.to_lowercase()))
+        .list_containers(params.status.map(|s| s.to_lowercase()), params.tag)
         .await
         .map(Json)
-        .map_err(|err| {
-            tracing::error!(?err, "No se pudieron listar los contenedores");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(|err| ApiError::internal("No se pudieron listar los contenedores", err))
 }
 
 #[derive(Deserialize)]
@@ -218,341 +386,1629 @@ struct CreateContainerRequest {
     platform: String,
 }
 
+impl Validate for CreateContainerRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_name(v, "name", &self.name);
+        validate_description(v, self.description.as_deref());
+        v.one_of("platform", &self.platform, Platform::VALUES);
+    }
+}
+
 async fn create_container(
     Extension(ctx): Extension<AuthContext>,
     State(state): State<AppState>,
-    Json(payload): Json<CreateContainerRequest>,
-) -> Result<Json<TaskModel>, (StatusCode, String)> {
-    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE).map_err(forbidden_with_message)?;
+    ValidatedJson(payload): ValidatedJson<CreateContainerRequest>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
     let platform = Platform::from_str(&payload.platform);
     state
         .containers
         .create_container(payload.name, platform, payload.description)
         .await
         .map(Json)
-        .map_err(|err| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("No se pudo crear el contenedor: {err}"),
-            )
-        })
+        .map_err(|err| service_error("No se pudo crear el contenedor", err))
 }
 
 async fn get_container(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<ContainerModel>, StatusCode> {
+) -> Result<Json<ContainerModel>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
     state
         .containers
         .get_container(container_id)
         .await
-        .map_err(|err| {
-            tracing::error!(?err, "Error consultando contenedor");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .map_err(|err| ApiError::internal("Error consultando contenedor", err))?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(|| container_not_found(container_id))
 }
 
-async fn delete_container(
+#[derive(Deserialize)]
+struct UpdateContainerRequest {
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    env: Option<BTreeMap<String, String>>,
+    compat_flags: Option<Vec<String>>,
+    limits: Option<ContainerLimits>,
+    mounts: Option<Vec<MountSpec>>,
+}
+
+impl Validate for UpdateContainerRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_description(v, self.description.as_deref());
+        if let Some(tags) = &self.tags {
+            validate_tags(v, tags);
+        }
+        if let Some(env) = &self.env {
+            validate_env(v, env);
+        }
+        if let Some(compat_flags) = &self.compat_flags {
+            validate_compat_flags(v, compat_flags);
+        }
+        if let Some(limits) = &self.limits {
+            validate_limits(v, limits);
+        }
+        if let Some(mounts) = &self.mounts {
+            validate_mounts(v, mounts);
+        }
+    }
+
+    fn error_code(&self, errors: &[FieldError]) -> ErrorCode {
+        compat_error_code(errors)
+    }
+}
+
+async fn update_container(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<TaskModel>, StatusCode> {
+    ValidatedJson(payload): ValidatedJson<UpdateContainerRequest>,
+) -> Result<Json<ContainerModel>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let update = ContainerUpdate {
+        description: payload.description,
+        tags: payload.tags,
+        env: payload.env,
+        compat_flags: payload.compat_flags,
+        limits: payload.limits,
+        mounts: payload.mounts,
+    };
     state
         .containers
-        .delete_container(container_id)
+        .update_container(container_id, update)
         .await
-        .map_err(|err| {
-            tracing::error!(?err, "Error eliminando contenedor");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .map_err(|err| service_error("No se pudo actualizar el contenedor", err))?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+async fn list_orphans(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<OrphanSandbox>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .containers
+        .list_orphans()
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar los sandboxes huerfanos", err))
 }
 
 #[derive(Deserialize)]
-struct AppInstallRequest {
+struct AdoptOrphanRequest {
+    path: String,
+    #[serde(default)]
     name: Option<String>,
-    version: Option<String>,
-    installer_path: Option<String>,
-    silent_args: Option<String>,
 }
 
-async fn list_apps(
+impl Validate for AdoptOrphanRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("path", &self.path);
+        if let Some(name) = &self.name {
+            validate_name(v, "name", name);
+        }
+    }
+}
+
+async fn adopt_orphan(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<AdoptOrphanRequest>,
+) -> Result<(StatusCode, Json<ContainerModel>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .containers
+        .adopt_orphan(&payload.path, payload.name)
+        .await
+        .map_err(|err| service_error("No se pudo adoptar el sandbox", err))?
+        .map(|container| (StatusCode::CREATED, Json(container)))
+        .ok_or_else(|| {
+            ApiError::new(ErrorCode::OrphanNotFound)
+                .with_details(serde_json::json!({ "path": payload.path }))
+        })
+}
+
+async fn list_templates(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<AppInstance>>, StatusCode> {
+) -> Result<Json<Vec<ContainerTemplate>>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
     state
-        .apps
-        .list(container_id)
+        .templates
+        .list()
         .await
         .map(Json)
-        .map_err(|err| {
-            tracing::error!(?err, "No se pudieron listar las apps");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(|err| ApiError::internal("No se pudieron listar las plantillas", err))
 }
 
-async fn install_app(
+async fn get_template(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
+    ApiPath(name): ApiPath<String>,
     State(state): State<AppState>,
-    Json(payload): Json<AppInstallRequest>,
-) -> Result<Json<TaskModel>, (StatusCode, String)> {
-    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE).map_err(forbidden_with_message)?;
-    let AppInstallRequest {
-        name,
-        version,
-        installer_path,
-        silent_args,
-    } = payload;
-    let resolved_name = name
-        .or(installer_path.clone())
-        .unwrap_or_else(|| "Aplicacion".into());
-    let _ = (installer_path, silent_args);
+) -> Result<Json<ContainerTemplate>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    ensure_template_name(&name)?;
     state
-        .apps
-        .install(container_id, resolved_name, version)
+        .templates
+        .get(&name)
         .await
+        .map_err(|err| ApiError::internal("No se pudo leer la plantilla", err))?
         .map(Json)
-        .map_err(|err| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("No se pudo instalar la app: {err}"),
-            )
-        })
+        .ok_or_else(|| template_not_found(&name))
+}
+
+impl Validate for ContainerTemplate {
+    fn validate(&self, v: &mut Validator) {
+        validate_description(v, self.description.as_deref());
+        validate_tags(v, &self.tags);
+        validate_env(v, &self.env);
+        validate_compat_flags(v, &self.compat_flags);
+        validate_limits(v, &self.limits);
+        validate_mounts(v, &self.mounts);
+        for (index, seed) in self.registry.iter().enumerate() {
+            v.nested(&format!("registry[{index}]"), |v| {
+                if v.required("key", &seed.key) {
+                    v.length("key", &seed.key, 1, PATH_MAX_LEN);
+                    v.printable("key", &seed.key);
+                    if seed.hive().is_none() {
+                        v.push(
+                            "key",
+                            "unknown_hive",
+                            Some(serde_json::json!({
+                                "allowed": ["HKLM\\SOFTWARE", "HKLM\\SYSTEM", "HKCU"],
+                            })),
+                        );
+                    }
+                }
+                if let Some(name) = &seed.name {
+                    validate_label(v, "name", name);
+                }
+                if let RegistryValue::String(value) = &seed.value {
+                    v.length("value", value, 0, PATH_MAX_LEN);
+                    v.printable("value", value);
+                }
+            });
+        }
+        for (index, app) in self.apps.iter().enumerate() {
+            v.nested(&format!("apps[{index}]"), |v| {
+                AppInstallRequest::from(app).validate(v)
+            });
+        }
+    }
+
+    fn error_code(&self, errors: &[FieldError]) -> ErrorCode {
+        compat_error_code(errors)
+    }
+}
+
+async fn put_template(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(name): ApiPath<String>,
+    State(state): State<AppState>,
+    ValidatedDocument(mut template): ValidatedDocument<ContainerTemplate>,
+) -> Result<(StatusCode, Json<ContainerTemplate>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    ensure_template_name(&name)?;
+    template.name = name;
+    let created = state
+        .templates
+        .save(&template)
+        .await
+        .map_err(|err| service_error("No se pudo guardar la plantilla", err))?;
+    let status = if created {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(template)))
+}
+
+async fn delete_template(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(name): ApiPath<String>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    ensure_template_name(&name)?;
+    let deleted = state
+        .templates
+        .delete(&name)
+        .await
+        .map_err(|err| service_error("No se pudo borrar la plantilla", err))?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(template_not_found(&name))
+    }
 }
 
 #[derive(Deserialize)]
-struct LaunchAppRequest {
-    entry_point_id: Option<String>,
-    args: Option<Vec<String>>,
+struct FromTemplateRequest {
+    template: String,
+    name: String,
+    description: Option<String>,
 }
 
-async fn launch_app(
+impl Validate for FromTemplateRequest {
+    fn validate(&self, v: &mut Validator) {
+        if v.required("template", &self.template) && !is_template_name(&self.template) {
+            v.push("template", "invalid_characters", None);
+        }
+        validate_name(v, "name", &self.name);
+        validate_description(v, self.description.as_deref());
+    }
+}
+
+async fn create_from_template(
     Extension(ctx): Extension<AuthContext>,
-    Path(app_id): Path<Uuid>,
     State(state): State<AppState>,
-    Json(payload): Json<LaunchAppRequest>,
-) -> Result<Json<TaskModel>, StatusCode> {
+    ValidatedJson(payload): ValidatedJson<FromTemplateRequest>,
+) -> Result<(StatusCode, Json<TaskModel>), ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
-    let _ = (payload.entry_point_id, payload.args);
     state
-        .apps
-        .launch(app_id)
+        .templates
+        .instantiate(&payload.template, payload.name, payload.description)
         .await
-        .map_err(|err| {
-            tracing::error!(?err, "Error lanzando app");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .map_err(|err| service_error("No se pudo crear el contenedor", err))?
+        .map(|task| (StatusCode::ACCEPTED, Json(task)))
+        .ok_or_else(|| template_not_found(&payload.template))
+}
+
+impl Validate for DesiredState {
+    fn validate(&self, v: &mut Validator) {
+        let mut names = BTreeSet::new();
+        for (index, container) in self.containers.iter().enumerate() {
+            v.nested(&format!("containers[{index}]"), |v| {
+                validate_name(v, "name", &container.name);
+                if !names.insert(container.name.trim().to_lowercase()) {
+                    v.push("name", "duplicate", None);
+                }
+                validate_description(v, container.description.as_deref());
+                validate_tags(v, &container.tags);
+                validate_env(v, &container.env);
+                validate_compat_flags(v, &container.compat_flags);
+                validate_limits(v, &container.limits);
+                validate_mounts(v, &container.mounts);
+                // Las apps se comparan por nombre con las instaladas, asi que es obligatorio.
+                let mut apps = BTreeSet::new();
+                for (index, app) in container.apps.iter().enumerate() {
+                    v.nested(&format!("apps[{index}]"), |v| {
+                        match &app.name {
+                            None => v.push("name", "required", None),
+                            Some(name) if !apps.insert(name.trim().to_lowercase()) => {
+                                v.push("name", "duplicate", None)
+                            }
+                            Some(_) => {}
+                        }
+                        AppInstallRequest::from(app).validate(v);
+                    });
+                }
+            });
+        }
+    }
+
+    fn error_code(&self, errors: &[FieldError]) -> ErrorCode {
+        compat_error_code(errors)
+    }
 }
 
 #[derive(Deserialize)]
-struct SnapshotRequest {
-    label: Option<String>,
-    #[serde(rename = "type")]
-    snapshot_type: Option<String>,
-    base_snapshot_id: Option<Uuid>,
+struct ApplyQuery {
+    #[serde(default)]
+    prune: bool,
+    /// Huella del plan mostrado; obligatoria si el plan elimina algo.
+    fingerprint: Option<String>,
 }
 
-async fn list_snapshots(
+async fn plan_containers(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
+    ApiQuery(params): ApiQuery<ApplyQuery>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<Snapshot>>, StatusCode> {
+    ValidatedDocument(desired): ValidatedDocument<DesiredState>,
+) -> Result<Json<ApplyPlan>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
     state
-        .snapshots
-        .list(container_id)
+        .apply
+        .plan(&desired, params.prune)
         .await
         .map(Json)
-        .map_err(|err| {
-            tracing::error!(?err, "No se pudieron listar snapshots");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(|err| service_error("No se pudo calcular el plan", err))
 }
 
-async fn create_snapshot(
+async fn apply_containers(
     Extension(ctx): Extension<AuthContext>,
-    Path(container_id): Path<Uuid>,
+    ApiQuery(params): ApiQuery<ApplyQuery>,
     State(state): State<AppState>,
-    Json(payload): Json<SnapshotRequest>,
-) -> Result<Json<TaskModel>, (StatusCode, String)> {
-    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE).map_err(forbidden_with_message)?;
-    let SnapshotRequest {
-        label,
-        snapshot_type,
-        base_snapshot_id,
-    } = payload;
-    let _ = base_snapshot_id;
-    let resolved_type = snapshot_type
-        .map(|value| SnapshotType::from_str(&value))
-        .unwrap_or(SnapshotType::Full);
+    ValidatedDocument(desired): ValidatedDocument<DesiredState>,
+) -> Result<(StatusCode, Json<TaskModel>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
     state
-        .snapshots
-        .create(container_id, label, resolved_type)
+        .apply
+        .apply(desired, params.prune, params.fingerprint)
         .await
-        .map(Json)
-        .map_err(|err| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("No se pudo crear el snapshot: {err}"),
-            )
-        })
+        .map(|task| (StatusCode::ACCEPTED, Json(task)))
+        .map_err(|err| service_error("No se pudo aplicar el estado deseado", err))
 }
 
-async fn restore_snapshot(
+#[derive(Deserialize)]
+struct RenameContainerRequest {
+    name: String,
+}
+
+impl Validate for RenameContainerRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_name(v, "name", &self.name);
+    }
+}
+
+async fn rename_container(
     Extension(ctx): Extension<AuthContext>,
-    Path(snapshot_id): Path<Uuid>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<TaskModel>, StatusCode> {
+    ValidatedJson(payload): ValidatedJson<RenameContainerRequest>,
+) -> Result<Json<ContainerModel>, ApiError> {
     ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
     state
-        .snapshots
-        .restore(snapshot_id)
+        .containers
+        .rename_container(container_id, payload.name)
         .await
-        .map_err(|err| {
-            tracing::error!(?err, "Error restaurando snapshot");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .map_err(|err| service_error("No se pudo renombrar el contenedor", err))?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(|| container_not_found(container_id))
 }
 
 #[derive(Deserialize)]
-struct TasksQuery {
-    status: Option<String>,
-    limit: Option<i64>,
+struct ContainerTagsRequest {
+    tags: Vec<String>,
 }
 
-async fn list_tasks(
+impl Validate for ContainerTagsRequest {
+    fn validate(&self, v: &mut Validator) {
+        if self.tags.is_empty() {
+            v.push("tags", "required", None);
+        }
+        validate_tags(v, &self.tags);
+    }
+}
+
+async fn add_container_tags(
     Extension(ctx): Extension<AuthContext>,
-    Query(params): Query<TasksQuery>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<TaskModel>>, StatusCode> {
-    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
+    ValidatedJson(payload): ValidatedJson<ContainerTagsRequest>,
+) -> Result<Json<ContainerModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
     state
-        .store
-        .list_tasks(
-            params.status.map(|s| s.to_lowercase()),
-            params.limit.map(|l| l.clamp(1, 500)),
-        )
+        .containers
+        .add_tags(container_id, payload.tags)
         .await
+        .map_err(|err| service_error("No se pudieron agregar tags", err))?
         .map(Json)
-        .map_err(|err| {
-            tracing::error!(?err, "No se pudieron listar tareas");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .ok_or_else(|| container_not_found(container_id))
 }
 
-async fn task_detail(
+async fn remove_container_tag(
     Extension(ctx): Extension<AuthContext>,
-    Path(task_id): Path<Uuid>,
+    ApiPath((container_id, tag)): ApiPath<(Uuid, String)>,
     State(state): State<AppState>,
-) -> Result<Json<TaskModel>, StatusCode> {
-    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
+) -> Result<Json<ContainerModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
     state
-        .store
-        .get_task(task_id)
+        .containers
+        .remove_tag(container_id, &tag)
         .await
-        .map_err(|err| {
-            tracing::error!(?err, "Error consultando tarea");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .map_err(|err| service_error("Error eliminando tag", err))?
         .map(Json)
-        .ok_or(StatusCode::NOT_FOUND)
+        .ok_or_else(|| container_not_found(container_id))
 }
 
-async fn events_stream(
+async fn delete_container(
     Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
-    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
-    let rx = state.events.subscribe();
-    let stream = BroadcastStream::new(rx).filter_map(|event| match event {
-        Ok(envelope) => match serde_json::to_string(&envelope) {
-            Ok(json) => Some(Ok(Event::default().data(json))),
-            Err(err) => {
-                tracing::error!(?err, "No se pudo serializar evento SSE");
-                None
-            }
-        },
-        Err(err) => {
-            tracing::warn!(?err, "Error recibiendo evento SSE");
-            None
-        }
-    });
-
-    Ok(Sse::new(stream).keep_alive(
-        KeepAlive::new()
-            .interval(StdDuration::from_secs(10))
-            .text("keep-alive"),
-    ))
-}
-
-#[derive(Deserialize)]
-struct CreateTokenRequest {
-    name: String,
-    scopes: Option<Vec<String>>,
-    expires_at: Option<String>,
-}
-
-#[derive(Serialize)]
-struct CreateTokenResponse {
-    token: String,
-    #[serde(flatten)]
-    info: ApiTokenInfo,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .containers
+        .delete_container(container_id)
+        .await
+        .map_err(|err| service_error("Error eliminando contenedor", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
 }
 
-async fn list_api_tokens(
+async fn verify_container(
     Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<ApiTokenInfo>>, StatusCode> {
-    ensure_admin(&ctx)?;
-    state.tokens.list().await.map(Json).map_err(|err| {
-        tracing::error!(?err, "No se pudieron listar tokens");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .containers
+        .verify(container_id)
+        .await
+        .map_err(|err| service_error("No se pudo verificar el contenedor", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+async fn start_container(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ContainerModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .containers
+        .start(container_id)
+        .await
+        .map_err(|err| service_error("No se pudo arrancar el contenedor", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+async fn stop_container(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<ContainerModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .containers
+        .stop(container_id)
+        .await
+        .map_err(|err| service_error("No se pudo detener el contenedor", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+#[derive(Deserialize)]
+struct ChangesQuery {
+    since: Option<u64>,
+    limit: Option<usize>,
+}
+
+async fn list_container_changes(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<ChangesQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<ChangeLog>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .containers
+        .changes(
+            container_id,
+            params.since.unwrap_or(0),
+            params.limit.unwrap_or(200).clamp(1, 1000),
+        )
+        .await
+        .map_err(|err| ApiError::internal("No se pudo leer el diario de cambios", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+#[derive(Deserialize)]
+struct PathResolveQuery {
+    #[serde(rename = "virtual")]
+    virtual_path: Option<String>,
+    physical: Option<String>,
+}
+
+impl Validate for PathResolveQuery {
+    fn validate(&self, v: &mut Validator) {
+        match (&self.virtual_path, &self.physical) {
+            (Some(_), Some(_)) => v.push(
+                "physical",
+                "conflicts_with",
+                Some(serde_json::json!({ "field": "virtual" })),
+            ),
+            (None, None) => v.push("virtual", "required", None),
+            (Some(path), None) => validate_path(v, "virtual", path),
+            (None, Some(path)) => validate_path(v, "physical", path),
+        }
+    }
+}
+
+async fn resolve_container_path(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<PathResolveQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<PathResolution>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    let mut v = Validator::default();
+    params.validate(&mut v);
+    v.finish()?;
+    let (field, path, reverse) = match (params.virtual_path, params.physical) {
+        (Some(path), _) => ("virtual", path, false),
+        (None, Some(path)) => ("physical", path, true),
+        (None, None) => return Err(ApiError::new(ErrorCode::ValidationFailed)),
+    };
+    state
+        .containers
+        .resolve_path(container_id, &path, reverse)
+        .await
+        .map_err(|err| match err.downcast_ref::<PathMapError>() {
+            Some(map_err) => path_map_error(field, map_err),
+            None => ApiError::internal("No se pudo resolver la ruta", err),
+        })?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+fn path_map_error(field: &str, err: &PathMapError) -> ApiError {
+    let (code, params) = match err {
+        PathMapError::UnknownVariable(name) => {
+            ("unknown_variable", serde_json::json!({ "variable": name }))
+        }
+        PathMapError::ExpansionTooDeep(value) => {
+            ("expansion_too_deep", serde_json::json!({ "value": value }))
+        }
+        PathMapError::UnsupportedDrive(drive) => {
+            ("unsupported_drive", serde_json::json!({ "drive": drive }))
+        }
+        PathMapError::OutsideContainer(value) => {
+            ("not_in_container", serde_json::json!({ "value": value }))
+        }
+    };
+    ApiError::new(ErrorCode::ValidationFailed).with_details(serde_json::json!({
+        "fields": [{ "field": field, "code": code, "params": params }]
+    }))
+}
+
+#[derive(Deserialize)]
+struct ContainerFilesQuery {
+    path: Option<String>,
+}
+
+impl ContainerFilesQuery {
+    /// Ruta pedida; sin `path` se usa la raiz (`C:\`) solo si `default_root`.
+    fn path(&self, default_root: bool) -> Result<&str, ApiError> {
+        let mut v = Validator::default();
+        match self.path.as_deref() {
+            Some(path) => {
+                v.length("path", path, 0, PATH_MAX_LEN);
+                v.printable("path", path);
+            }
+            None if !default_root => v.push("path", "required", None),
+            None => {}
+        }
+        v.finish()?;
+        Ok(self.path.as_deref().unwrap_or("C:\\"))
+    }
+}
+
+async fn list_container_files(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<ContainerFilesQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<FsEntry>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .containers
+        .list_files(container_id, params.path(true)?)
+        .await
+        .map_err(|err| service_error("No se pudo listar el directorio", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+async fn download_container_file(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<ContainerFilesQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    let (entry, file) = state
+        .containers
+        .open_file(container_id, params.path(false)?)
+        .await
+        .map_err(|err| service_error("No se pudo abrir el archivo", err))?
+        .ok_or_else(|| container_not_found(container_id))?;
+    let filename = entry
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .filter(|c| *c != '"')
+        .collect::<String>();
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_LENGTH, entry.size.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+async fn upload_container_file(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<ContainerFilesQuery>,
+    State(state): State<AppState>,
+    body: Body,
+) -> Result<Json<FsEntry>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let content = StreamReader::new(
+        body.into_data_stream()
+            .map(|chunk| chunk.map_err(std::io::Error::other)),
+    );
+    state
+        .containers
+        .write_file(container_id, params.path(false)?, content)
+        .await
+        .map_err(|err| service_error("No se pudo subir el archivo", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+#[derive(Deserialize)]
+struct AppInstallRequest {
+    name: Option<String>,
+    version: Option<String>,
+    vendor: Option<String>,
+    installer_path: Option<String>,
+    archive_path: Option<String>,
+    silent_args: Option<String>,
+}
+
+impl Validate for AppInstallRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            validate_label(v, "name", name);
+        }
+        if let Some(version) = &self.version {
+            v.length("version", version, 1, TAG_MAX_LEN);
+            v.printable("version", version);
+        }
+        if let Some(vendor) = &self.vendor {
+            validate_label(v, "vendor", vendor);
+        }
+        if let Some(path) = &self.archive_path
+            && v.required("archive_path", path)
+        {
+            v.length("archive_path", path, 1, PATH_MAX_LEN);
+            v.printable("archive_path", path);
+            if ArchiveFormat::detect(Path::new(path.trim())).is_none() {
+                v.push(
+                    "archive_path",
+                    "unsupported_format",
+                    Some(serde_json::json!({ "allowed": ArchiveFormat::EXTENSIONS })),
+                );
+            }
+            if self.installer_path.is_some() {
+                v.push(
+                    "archive_path",
+                    "conflicts_with",
+                    Some(serde_json::json!({ "field": "installer_path" })),
+                );
+            }
+        }
+        if let Some(path) = &self.installer_path
+            && v.required("installer_path", path)
+        {
+            v.length("installer_path", path, 1, PATH_MAX_LEN);
+            v.printable("installer_path", path);
+        }
+        if let Some(args) = &self.silent_args {
+            v.length("silent_args", args, 0, PATH_MAX_LEN);
+            v.printable("silent_args", args);
+        }
+    }
+}
+
+impl From<&TemplateApp> for AppInstallRequest {
+    fn from(app: &TemplateApp) -> Self {
+        Self {
+            name: app.name.clone(),
+            version: app.version.clone(),
+            vendor: app.vendor.clone(),
+            installer_path: app.installer_path.clone(),
+            archive_path: app.archive_path.clone(),
+            silent_args: app.silent_args.clone(),
+        }
+    }
+}
+
+async fn list_apps(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AppInstance>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .apps
+        .list(container_id)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar las apps", err))
+}
+
+async fn install_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<AppInstallRequest>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let AppInstallRequest {
+        name,
+        version,
+        vendor,
+        installer_path,
+        archive_path,
+        silent_args,
+    } = payload;
+    let source = match (installer_path, archive_path) {
+        (_, Some(archive)) => Some(InstallSource::Archive(PathBuf::from(archive.trim()))),
+        (Some(installer), None) => Some(InstallSource::Installer(PathBuf::from(installer.trim()))),
+        (None, None) => None,
+    };
+    let spec = AppInstallSpec {
+        name: name.map(|name| name.trim().to_string()),
+        version: version.map(|version| version.trim().to_string()),
+        vendor: vendor.map(|vendor| vendor.trim().to_string()),
+        source,
+        silent_args,
+    };
+    state
+        .containers
+        .ensure_quota(container_id)
+        .await
+        .map_err(|err| service_error("No se pudo comprobar la cuota de disco", err))?;
+    state
+        .apps
+        .install(container_id, spec)
+        .await
+        .map_err(|err| service_error("No se pudo instalar la app", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+#[derive(Deserialize)]
+struct AppUpdateRequest {
+    version: Option<String>,
+    installer_path: Option<String>,
+    archive_path: Option<String>,
+    silent_args: Option<String>,
+}
+
+impl Validate for AppUpdateRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(version) = &self.version {
+            v.length("version", version, 1, TAG_MAX_LEN);
+            v.printable("version", version);
+        }
+        match (&self.installer_path, &self.archive_path) {
+            (None, None) => v.push(
+                "installer_path",
+                "required",
+                Some(serde_json::json!({ "alternative": "archive_path" })),
+            ),
+            (Some(_), Some(_)) => v.push(
+                "archive_path",
+                "conflicts_with",
+                Some(serde_json::json!({ "field": "installer_path" })),
+            ),
+            (Some(path), None) => validate_path(v, "installer_path", path),
+            (None, Some(path)) => {
+                validate_path(v, "archive_path", path);
+                if ArchiveFormat::detect(Path::new(path.trim())).is_none() {
+                    v.push(
+                        "archive_path",
+                        "unsupported_format",
+                        Some(serde_json::json!({ "allowed": ArchiveFormat::EXTENSIONS })),
+                    );
+                }
+            }
+        }
+        if let Some(args) = &self.silent_args {
+            v.length("silent_args", args, 0, PATH_MAX_LEN);
+            v.printable("silent_args", args);
+        }
+    }
+}
+
+async fn update_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<AppUpdateRequest>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let source = match (payload.installer_path, payload.archive_path) {
+        (_, Some(archive)) => InstallSource::Archive(PathBuf::from(archive.trim())),
+        (Some(installer), None) => InstallSource::Installer(PathBuf::from(installer.trim())),
+        // `AppUpdateRequest::validate` ya exige una de las dos rutas.
+        (None, None) => return Err(ApiError::new(ErrorCode::ValidationFailed)),
+    };
+    let spec = AppUpdateSpec {
+        version: payload.version.map(|version| version.trim().to_string()),
+        source,
+        silent_args: payload.silent_args,
+    };
+    state
+        .apps
+        .update(app_id, spec)
+        .await
+        .map_err(|err| service_error("No se pudo actualizar la app", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn list_app_files(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<AppFileManifest>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .apps
+        .files(app_id)
+        .await
+        .map_err(|err| ApiError::internal("No se pudieron listar los archivos de la app", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn uninstall_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .apps
+        .uninstall(app_id)
+        .await
+        .map_err(|err| service_error("No se pudo desinstalar la app", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn disable_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<TaskModel>, ApiError> {
+    set_app_enabled(&ctx, &state, app_id, false).await
+}
+
+async fn enable_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<TaskModel>, ApiError> {
+    set_app_enabled(&ctx, &state, app_id, true).await
+}
+
+async fn set_app_enabled(
+    ctx: &AuthContext,
+    state: &AppState,
+    app_id: Uuid,
+    enabled: bool,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .apps
+        .set_enabled(app_id, enabled)
+        .await
+        .map_err(|err| service_error("No se pudo cambiar el estado de la app", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+#[derive(Deserialize)]
+struct LaunchAppRequest {
+    entry_point_id: Option<String>,
+    args: Option<Vec<String>>,
+}
+
+impl Validate for LaunchAppRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(entry_point_id) = &self.entry_point_id {
+            validate_label(v, "entry_point_id", entry_point_id);
+        }
+        for (index, arg) in self.args.iter().flatten().enumerate() {
+            let field = format!("args[{index}]");
+            v.length(&field, arg, 0, PATH_MAX_LEN);
+            v.printable(&field, arg);
+        }
+    }
+}
+
+async fn launch_app(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<LaunchAppRequest>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let _ = (payload.entry_point_id, payload.args);
+    state
+        .apps
+        .launch(app_id)
+        .await
+        .map_err(|err| service_error("Error lanzando app", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+#[derive(Deserialize)]
+struct EntryPointRequest {
+    id: Option<String>,
+    label: String,
+    command: String,
+    args: Option<String>,
+    working_dir: Option<String>,
+    icon: Option<String>,
+    env: Option<BTreeMap<String, String>>,
+}
+
+impl Validate for EntryPointRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(id) = &self.id
+            && v.required("id", id)
+        {
+            v.length("id", id, 1, TAG_MAX_LEN);
+            v.charset("id", id, is_tag_char);
+        }
+        if v.required("label", &self.label) {
+            validate_label(v, "label", &self.label);
+        }
+        validate_path(v, "command", &self.command);
+        if let Some(args) = &self.args {
+            validate_args_template(v, args);
+        }
+        if let Some(dir) = &self.working_dir {
+            validate_path(v, "working_dir", dir);
+        }
+        if let Some(icon) = &self.icon {
+            validate_path(v, "icon", icon);
+        }
+        if let Some(env) = &self.env {
+            validate_env(v, env);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct EntryPointPatchRequest {
+    label: Option<String>,
+    command: Option<String>,
+    args: Option<String>,
+    working_dir: Option<String>,
+    icon: Option<String>,
+    env: Option<BTreeMap<String, String>>,
+}
+
+impl Validate for EntryPointPatchRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(label) = &self.label
+            && v.required("label", label)
+        {
+            validate_label(v, "label", label);
+        }
+        if let Some(command) = &self.command {
+            validate_path(v, "command", command);
+        }
+        if let Some(args) = &self.args {
+            validate_args_template(v, args);
+        }
+        if let Some(dir) = &self.working_dir {
+            validate_path(v, "working_dir", dir);
+        }
+        if let Some(icon) = &self.icon {
+            validate_path(v, "icon", icon);
+        }
+        if let Some(env) = &self.env {
+            validate_env(v, env);
+        }
+    }
+}
+
+async fn list_entry_points(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AppEntryPoint>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .apps
+        .list_entry_points(app_id)
+        .await
+        .map_err(|err| ApiError::internal("No se pudieron listar los entry points", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn create_entry_point(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(app_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<EntryPointRequest>,
+) -> Result<(StatusCode, Json<AppEntryPoint>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let spec = EntryPointSpec {
+        id: payload.id.map(|id| id.trim().to_string()),
+        label: payload.label.trim().to_string(),
+        command: payload.command.trim().to_string(),
+        args: payload.args,
+        working_dir: payload.working_dir.map(|dir| dir.trim().to_string()),
+        icon: payload.icon.map(|icon| icon.trim().to_string()),
+        env: payload.env.unwrap_or_default(),
+    };
+    state
+        .apps
+        .add_entry_point(app_id, spec)
+        .await
+        .map_err(|err| service_error("No se pudo crear el entry point", err))?
+        .map(|entry| (StatusCode::CREATED, Json(entry)))
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn update_entry_point(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath((app_id, entry_point_id)): ApiPath<(Uuid, String)>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<EntryPointPatchRequest>,
+) -> Result<Json<AppEntryPoint>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let update = EntryPointUpdate {
+        label: payload.label.map(|label| label.trim().to_string()),
+        command: payload.command.map(|command| command.trim().to_string()),
+        args: payload.args,
+        working_dir: payload.working_dir.map(|dir| dir.trim().to_string()),
+        icon: payload.icon.map(|icon| icon.trim().to_string()),
+        env: payload.env,
+    };
+    state
+        .apps
+        .update_entry_point(app_id, &entry_point_id, update)
+        .await
+        .map_err(|err| service_error("No se pudo actualizar el entry point", err))?
+        .map(Json)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+async fn delete_entry_point(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath((app_id, entry_point_id)): ApiPath<(Uuid, String)>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .apps
+        .remove_entry_point(app_id, &entry_point_id)
+        .await
+        .map_err(|err| service_error("No se pudo eliminar el entry point", err))?
+        .map(|()| StatusCode::NO_CONTENT)
+        .ok_or_else(|| app_not_found(app_id))
+}
+
+#[derive(Deserialize)]
+struct SnapshotRequest {
+    label: Option<String>,
+    #[serde(rename = "type")]
+    snapshot_type: Option<String>,
+    base_snapshot_id: Option<Uuid>,
+}
+
+impl Validate for SnapshotRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(label) = &self.label {
+            validate_label(v, "label", label);
+        }
+        if let Some(snapshot_type) = &self.snapshot_type {
+            v.one_of("type", snapshot_type, SnapshotType::VALUES);
+        }
+    }
+}
+
+async fn list_snapshots(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Snapshot>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .snapshots
+        .list(container_id)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar snapshots", err))
+}
+
+async fn create_snapshot(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(container_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<SnapshotRequest>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let SnapshotRequest {
+        label,
+        snapshot_type,
+        base_snapshot_id,
+    } = payload;
+    let _ = base_snapshot_id;
+    let resolved_type = snapshot_type
+        .map(|value| SnapshotType::from_str(&value))
+        .unwrap_or(SnapshotType::Full);
+    state
+        .containers
+        .ensure_quota(container_id)
+        .await
+        .map_err(|err| service_error("No se pudo comprobar la cuota de disco", err))?;
+    state
+        .snapshots
+        .create(container_id, label, resolved_type)
+        .await
+        .map_err(|err| service_error("No se pudo crear el snapshot", err))?
+        .map(Json)
+        .ok_or_else(|| container_not_found(container_id))
+}
+
+async fn restore_snapshot(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(snapshot_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<TaskModel>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .snapshots
+        .restore(snapshot_id)
+        .await
+        .map_err(|err| service_error("Error restaurando snapshot", err))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::new(ErrorCode::SnapshotNotFound)
+                .with_details(serde_json::json!({ "snapshot_id": snapshot_id }))
+        })
+}
+
+#[derive(Deserialize)]
+struct TasksQuery {
+    status: Option<String>,
+    schedule_id: Option<Uuid>,
+    limit: Option<i64>,
+}
+
+async fn list_tasks(
+    Extension(ctx): Extension<AuthContext>,
+    ApiQuery(params): ApiQuery<TasksQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TaskModel>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
+    state
+        .store
+        .list_tasks(
+            params.status.map(|s| s.to_lowercase()),
+            params.schedule_id,
+            params.limit.map(|l| l.clamp(1, 500)),
+        )
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar tareas", err))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskInclude {
+    Children,
+}
+
+#[derive(Deserialize)]
+struct TaskDetailQuery {
+    include: Option<TaskInclude>,
+}
+
+async fn task_detail(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(task_id): ApiPath<Uuid>,
+    ApiQuery(params): ApiQuery<TaskDetailQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
+    let task = state
+        .store
+        .get_task(task_id)
+        .await
+        .map_err(|err| ApiError::internal("Error consultando tarea", err))?
+        .ok_or_else(|| {
+            ApiError::new(ErrorCode::TaskNotFound)
+                .with_details(serde_json::json!({ "task_id": task_id }))
+        })?;
+    match params.include {
+        Some(TaskInclude::Children) => {
+            let descendants = state
+                .store
+                .list_task_descendants(task_id)
+                .await
+                .map_err(|err| ApiError::internal("Error consultando subtareas", err))?;
+            Ok(Json(TaskTree::build(task, descendants)).into_response())
+        }
+        None => Ok(Json(task).into_response()),
+    }
+}
+
+async fn retry_task(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(task_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<TaskModel>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .retries
+        .retry(task_id)
+        .await
+        .map_err(|err| service_error("No se pudo reintentar la tarea", err))?
+        .map(|task| (StatusCode::ACCEPTED, Json(task)))
+        .ok_or_else(|| {
+            ApiError::new(ErrorCode::TaskNotFound)
+                .with_details(serde_json::json!({ "task_id": task_id }))
+        })
+}
+
+#[derive(Deserialize)]
+struct CreateScheduleRequest {
+    name: String,
+    cron: String,
+    job: ScheduleJob,
+    enabled: Option<bool>,
+    missed_runs: Option<String>,
+}
+
+impl Validate for CreateScheduleRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_name(v, "name", &self.name);
+        validate_cron(v, &self.cron);
+        validate_schedule_job(v, &self.job);
+        if let Some(missed_runs) = &self.missed_runs {
+            v.one_of("missed_runs", missed_runs, MissedRunPolicy::VALUES);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UpdateScheduleRequest {
+    name: Option<String>,
+    cron: Option<String>,
+    job: Option<ScheduleJob>,
+    enabled: Option<bool>,
+    missed_runs: Option<String>,
+}
+
+impl Validate for UpdateScheduleRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            validate_name(v, "name", name);
+        }
+        if let Some(cron) = &self.cron {
+            validate_cron(v, cron);
+        }
+        if let Some(job) = &self.job {
+            validate_schedule_job(v, job);
+        }
+        if let Some(missed_runs) = &self.missed_runs {
+            v.one_of("missed_runs", missed_runs, MissedRunPolicy::VALUES);
+        }
+    }
+}
+
+fn validate_cron(v: &mut Validator, cron: &str) {
+    if !v.required("cron", cron) {
+        return;
+    }
+    match CronExpr::parse(cron) {
+        Ok(expr) if expr.next_after(OffsetDateTime::now_utc()).is_none() => {
+            v.push("cron", "never_matches", None);
+        }
+        Ok(_) => {}
+        Err(err) => v.push(
+            "cron",
+            "invalid_cron",
+            Some(serde_json::json!({ "reason": err.to_string() })),
+        ),
+    }
+}
+
+fn validate_schedule_job(v: &mut Validator, job: &ScheduleJob) {
+    v.nested("job", |v| match job {
+        ScheduleJob::Snapshot {
+            label: Some(label), ..
+        } => validate_label(v, "label", label),
+        ScheduleJob::Prune { keep, .. } => v.range("keep", *keep, 1, SCHEDULE_KEEP_MAX),
+        ScheduleJob::Export {
+            destination, keep, ..
+        } => {
+            if let Some(destination) = destination {
+                validate_path(v, "destination", destination);
+                if destination.contains(':')
+                    || Path::new(destination)
+                        .components()
+                        .any(|part| !matches!(part, std::path::Component::Normal(_)))
+                {
+                    v.push("destination", "not_allowed", None);
+                }
+            }
+            if let Some(keep) = keep {
+                v.range("keep", *keep, 1, SCHEDULE_KEEP_MAX);
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Solo el administrador programa trabajos que tocan los tokens.
+fn ensure_schedule_job(ctx: &AuthContext, job: Option<&ScheduleJob>) -> Result<(), ApiError> {
+    match job {
+        Some(ScheduleJob::TokenSweep) => ensure_admin(ctx),
+        _ => Ok(()),
+    }
+}
+
+fn schedule_not_found(schedule_id: Uuid) -> ApiError {
+    ApiError::new(ErrorCode::ScheduleNotFound)
+        .with_details(serde_json::json!({ "schedule_id": schedule_id }))
+}
+
+async fn list_schedules(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Schedule>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .schedules
+        .list()
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar las programaciones", err))
+}
+
+async fn get_schedule(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(schedule_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Schedule>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_READ)?;
+    state
+        .schedules
+        .get(schedule_id)
+        .await
+        .map_err(|err| ApiError::internal("No se pudo leer la programacion", err))?
+        .map(Json)
+        .ok_or_else(|| schedule_not_found(schedule_id))
+}
+
+async fn create_schedule(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<CreateScheduleRequest>,
+) -> Result<(StatusCode, Json<Schedule>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    ensure_schedule_job(&ctx, Some(&payload.job))?;
+    let spec = ScheduleSpec {
+        name: payload.name.trim().to_string(),
+        cron: payload.cron.trim().to_string(),
+        job: payload.job,
+        enabled: payload.enabled.unwrap_or(true),
+        missed_runs: payload
+            .missed_runs
+            .as_deref()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default(),
+    };
+    let schedule = state
+        .schedules
+        .create(spec)
+        .await
+        .map_err(|err| service_error("No se pudo crear la programacion", err))?;
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
+
+async fn update_schedule(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(schedule_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<UpdateScheduleRequest>,
+) -> Result<Json<Schedule>, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    ensure_schedule_job(&ctx, payload.job.as_ref())?;
+    let update = ScheduleUpdate {
+        name: payload.name.map(|name| name.trim().to_string()),
+        cron: payload.cron.map(|cron| cron.trim().to_string()),
+        job: payload.job,
+        enabled: payload.enabled,
+        missed_runs: payload
+            .missed_runs
+            .as_deref()
+            .and_then(|value| value.parse().ok()),
+    };
+    state
+        .schedules
+        .update(schedule_id, update)
+        .await
+        .map_err(|err| service_error("No se pudo actualizar la programacion", err))?
+        .map(Json)
+        .ok_or_else(|| schedule_not_found(schedule_id))
+}
+
+async fn delete_schedule(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(schedule_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    let deleted = state
+        .schedules
+        .delete(schedule_id)
+        .await
+        .map_err(|err| service_error("No se pudo borrar la programacion", err))?;
+    if deleted {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(schedule_not_found(schedule_id))
+    }
+}
+
+async fn run_schedule(
+    Extension(ctx): Extension<AuthContext>,
+    ApiPath(schedule_id): ApiPath<Uuid>,
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<TaskModel>), ApiError> {
+    ensure_scope(&ctx, SCOPE_CONTAINERS_WRITE)?;
+    state
+        .schedules
+        .run_now(schedule_id)
+        .await
+        .map_err(|err| service_error("No se pudo lanzar la programacion", err))?
+        .map(|task| (StatusCode::ACCEPTED, Json(task)))
+        .ok_or_else(|| schedule_not_found(schedule_id))
+}
+
+async fn events_stream(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    ensure_scope(&ctx, SCOPE_TASKS_READ)?;
+    let rx = state.events.subscribe();
+    let stream = BroadcastStream::new(rx).filter_map(|event| match event {
+        Ok(envelope) => match serde_json::to_string(&envelope) {
+            Ok(json) => Some(Ok(Event::default().data(json))),
+            Err(err) => {
+                tracing::error!(?err, "No se pudo serializar evento SSE");
+                None
+            }
+        },
+        Err(err) => {
+            tracing::warn!(?err, "Error recibiendo evento SSE");
+            None
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(StdDuration::from_secs(10))
+            .text("keep-alive"),
+    ))
+}
+
+#[derive(Deserialize)]
+struct CreateTokenRequest {
+    name: String,
+    scopes: Option<Vec<String>>,
+    expires_at: Option<String>,
+}
+
+impl Validate for CreateTokenRequest {
+    fn validate(&self, v: &mut Validator) {
+        if v.required("name", &self.name) {
+            validate_label(v, "name", &self.name);
+        }
+        for (index, scope) in self.scopes.iter().flatten().enumerate() {
+            let field = format!("scopes[{index}]");
+            v.length(&field, scope, 1, TAG_MAX_LEN);
+            v.charset(&field, scope, is_scope_char);
+        }
+        if let Some(raw) = &self.expires_at
+            && let Some(expires_at) = v.rfc3339("expires_at", raw)
+            && expires_at <= OffsetDateTime::now_utc()
+        {
+            v.push("expires_at", "must_be_future", None);
+        }
+    }
+
+    /// Una caducidad invalida conserva su codigo publicado si es el unico error.
+    fn error_code(&self, errors: &[FieldError]) -> ErrorCode {
+        if errors.iter().all(|error| error.field == "expires_at") {
+            ErrorCode::InvalidExpiration
+        } else {
+            ErrorCode::ValidationFailed
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CreateTokenResponse {
+    token: String,
+    #[serde(flatten)]
+    info: ApiTokenInfo,
+}
+
+async fn list_api_tokens(
+    Extension(ctx): Extension<AuthContext>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ApiTokenInfo>>, ApiError> {
+    ensure_admin(&ctx)?;
+    state
+        .tokens
+        .list()
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::internal("No se pudieron listar tokens", err))
 }
 
 async fn create_api_token(
     Extension(ctx): Extension<AuthContext>,
     State(state): State<AppState>,
-    Json(payload): Json<CreateTokenRequest>,
-) -> Result<(StatusCode, Json<CreateTokenResponse>), (StatusCode, String)> {
-    ensure_admin(&ctx).map_err(forbidden_with_message)?;
+    ValidatedJson(payload): ValidatedJson<CreateTokenRequest>,
+) -> Result<(StatusCode, Json<CreateTokenResponse>), ApiError> {
+    ensure_admin(&ctx)?;
     let CreateTokenRequest {
         name,
         scopes,
         expires_at,
     } = payload;
 
-    if name.trim().is_empty() {
-        return Err((StatusCode::BAD_REQUEST, "El nombre es obligatorio".into()));
-    }
-
     let scopes = scopes.unwrap_or_else(|| vec!["containers:read".into(), "tasks:read".into()]);
-    let expires_at = parse_expiration(expires_at)?;
+    let expires_at = expires_at.as_deref().and_then(parse_rfc3339_timestamp);
     let spec = TokenSpec {
         name: name.trim().to_string(),
         scopes,
         expires_at,
     };
 
-    let issued = state.tokens.issue(spec).await.map_err(|err| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("No se pudo emitir token: {err}"),
-        )
-    })?;
+    let issued = state
+        .tokens
+        .issue(spec)
+        .await
+        .map_err(|err| service_error("No se pudo emitir token", err))?;
 
     let response = CreateTokenResponse {
         token: issued.secret,
@@ -564,38 +2020,275 @@ async fn create_api_token(
 
 async fn revoke_api_token(
     Extension(ctx): Extension<AuthContext>,
-    Path(token_id): Path<Uuid>,
+    ApiPath(token_id): ApiPath<Uuid>,
     State(state): State<AppState>,
-) -> StatusCode {
-    if let Err(status) = ensure_admin(&ctx) {
-        return status;
-    }
+) -> Result<StatusCode, ApiError> {
+    ensure_admin(&ctx)?;
     match state.tokens.revoke(token_id).await {
-        Ok(true) => StatusCode::NO_CONTENT,
-        Ok(false) => StatusCode::NOT_FOUND,
-        Err(err) => {
-            tracing::error!(?err, token_id = %token_id, "No se pudo revocar token");
-            StatusCode::INTERNAL_SERVER_ERROR
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::new(ErrorCode::TokenNotFound)
+            .with_details(serde_json::json!({ "token_id": token_id }))),
+        Err(err) => Err(service_error("No se pudo revocar token", err)),
+    }
+}
+
+fn container_not_found(container_id: Uuid) -> ApiError {
+    ApiError::new(ErrorCode::ContainerNotFound)
+        .with_details(serde_json::json!({ "container_id": container_id }))
+}
+
+/// Los nombres de plantilla son nombres de archivo: sin separadores ni `..`.
+fn is_template_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= NAME_MAX_LEN
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn ensure_template_name(name: &str) -> Result<(), ApiError> {
+    if is_template_name(name) {
+        Ok(())
+    } else {
+        Err(ApiError::new(ErrorCode::InvalidPath)
+            .with_details(serde_json::json!({ "reason": "Nombre de plantilla invalido" })))
+    }
+}
+
+fn template_not_found(name: &str) -> ApiError {
+    ApiError::new(ErrorCode::TemplateNotFound).with_details(serde_json::json!({ "name": name }))
+}
+
+fn app_not_found(app_id: Uuid) -> ApiError {
+    ApiError::new(ErrorCode::AppNotFound).with_details(serde_json::json!({ "app_id": app_id }))
+}
+
+fn validate_name(v: &mut Validator, field: &str, value: &str) {
+    if v.required(field, value) {
+        v.length(field, value, 1, NAME_MAX_LEN);
+        v.printable(field, value);
+    }
+}
+
+fn validate_label(v: &mut Validator, field: &str, value: &str) {
+    v.length(field, value, 1, LABEL_MAX_LEN);
+    v.printable(field, value);
+}
+
+fn validate_description(v: &mut Validator, value: Option<&str>) {
+    if let Some(description) = value {
+        v.length("description", description, 0, DESCRIPTION_MAX_LEN);
+        v.printable("description", description);
+    }
+}
+
+fn validate_env(v: &mut Validator, env: &BTreeMap<String, String>) {
+    for (key, value) in env {
+        let field = format!("env.{key}");
+        if v.required(&field, key) {
+            v.length(&field, key, 1, 128);
+            v.charset(&field, key, is_env_key_char);
+        }
+        if value.len() > PATH_MAX_LEN || value.contains('\0') {
+            v.push(field, "invalid_value", None);
+        }
+    }
+}
+
+/// Montajes literales (sin `%VARIABLES%`) bajo `C:\` hacia directorios relativos a `fs/`.
+fn validate_limits(v: &mut Validator, limits: &ContainerLimits) {
+    if let Some(cpu) = limits.cpu_percent {
+        v.range("limits.cpu_percent", cpu, 1, 100);
+    }
+    if let Some(memory) = limits.memory_mb {
+        v.range("limits.memory_mb", memory, 16, 1_048_576);
+    }
+    if let Some(processes) = limits.max_processes {
+        v.range("limits.max_processes", processes, 1, 65_535);
+    }
+    if let Some(disk) = limits.disk_mb {
+        v.range("limits.disk_mb", disk, 1, 16_777_216);
+    }
+}
+
+fn validate_compat_flags(v: &mut Validator, flags: &[String]) {
+    let known: Vec<&str> = compat::COMPAT_FLAGS.iter().map(|flag| flag.name).collect();
+    for (index, flag) in flags.iter().enumerate() {
+        v.one_of(&format!("compat_flags[{index}]"), flag, &known);
+    }
+}
+
+/// Si solo fallan flags desconocidos se conserva su codigo publicado `unknown_compat_flag`.
+fn compat_error_code(errors: &[FieldError]) -> ErrorCode {
+    let only_flags = errors.iter().all(|error| {
+        error.code == "unknown_value"
+            && error
+                .field
+                .rsplit('.')
+                .next()
+                .is_some_and(|field| field.starts_with("compat_flags["))
+    });
+    if only_flags {
+        ErrorCode::UnknownCompatFlag
+    } else {
+        ErrorCode::ValidationFailed
+    }
+}
+
+fn validate_mounts(v: &mut Validator, mounts: &[MountSpec]) {
+    if mounts.len() > MOUNTS_MAX {
+        v.push(
+            "mounts",
+            "too_many",
+            Some(serde_json::json!({ "max": MOUNTS_MAX })),
+        );
+    }
+    let mut seen = BTreeSet::new();
+    for (index, mount) in mounts.iter().enumerate() {
+        let field = format!("mounts[{index}].virtual_path");
+        validate_path(v, &field, &mount.virtual_path);
+        let normalized = mount.virtual_path.trim().replace('/', "\\").to_lowercase();
+        let Some(rest) = normalized.strip_prefix("c:\\") else {
+            v.push(&field, "invalid_value", None);
+            continue;
+        };
+        let parts = rest
+            .split('\\')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+        if parts.is_empty() {
+            v.push(&field, "reserved", None);
+        } else if parts
+            .iter()
+            .any(|part| matches!(*part, "." | "..") || part.contains('%'))
+        {
+            v.push(&field, "invalid_value", None);
+        } else if !seen.insert(parts.join("\\")) {
+            v.push(&field, "duplicate", None);
+        }
+
+        let field = format!("mounts[{index}].target");
+        v.length(&field, &mount.target, 0, PATH_MAX_LEN);
+        v.printable(&field, &mount.target);
+        let target = Path::new(&mount.target);
+        if target.is_absolute()
+            || mount.target.contains(':')
+            || target
+                .components()
+                .any(|part| !matches!(part, std::path::Component::Normal(_)))
+        {
+            v.push(&field, "invalid_value", None);
+        }
+
+        let Some(source) = &mount.source else {
+            continue;
+        };
+        if !mount.target.is_empty() {
+            v.push(
+                &field,
+                "conflicts_with",
+                Some(serde_json::json!({ "field": format!("mounts[{index}].source") })),
+            );
+        }
+        let field = format!("mounts[{index}].source");
+        validate_path(v, &field, source);
+        let source = Path::new(source);
+        if !source.is_absolute() {
+            v.push(&field, "invalid_value", None);
+        } else if !source.is_dir() {
+            v.push(&field, "not_a_directory", None);
+        }
+    }
+}
+
+fn validate_path(v: &mut Validator, field: &str, value: &str) {
+    if v.required(field, value) {
+        v.length(field, value, 1, PATH_MAX_LEN);
+        v.printable(field, value);
+    }
+}
+
+/// Solo se aceptan marcadores `{nombre}` conocidos en la plantilla de argumentos.
+fn validate_args_template(v: &mut Validator, template: &str) {
+    v.length("args", template, 0, PATH_MAX_LEN);
+    v.printable("args", template);
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            v.push("args", "unclosed_placeholder", None);
+            return;
+        };
+        let name = &rest[start + 1..start + end];
+        if !AppEntryPoint::ARGS_PLACEHOLDERS.contains(&name) {
+            v.push(
+                "args",
+                "unknown_placeholder",
+                Some(serde_json::json!({
+                    "placeholder": name,
+                    "allowed": AppEntryPoint::ARGS_PLACEHOLDERS,
+                })),
+            );
+        }
+        rest = &rest[start + end + 1..];
+    }
+}
+
+fn validate_tags(v: &mut Validator, tags: &[String]) {
+    for (index, tag) in tags.iter().enumerate() {
+        let field = format!("tags[{index}]");
+        if v.required(&field, tag) {
+            v.length(&field, tag, 1, TAG_MAX_LEN);
+            v.charset(&field, tag.trim(), is_tag_char);
         }
     }
 }
 
-fn ensure_admin(ctx: &AuthContext) -> Result<(), StatusCode> {
+/// Traduce los errores de dominio que devuelven los servicios (cada tipo define su
+/// respuesta con `From<&E> for ApiError`); cualquier otro es un 500.
+fn service_error(context: &str, err: anyhow::Error) -> ApiError {
+    fn mapped<E>(err: &anyhow::Error) -> Option<ApiError>
+    where
+        E: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+        for<'a> ApiError: From<&'a E>,
+    {
+        err.downcast_ref::<E>().map(ApiError::from)
+    }
+
+    let mapped = mapped::<StoreError>(&err)
+        .or_else(|| mapped::<QuotaExceededError>(&err))
+        .or_else(|| mapped::<ContainerFileError>(&err))
+        .or_else(|| mapped::<ContainerNotReadyError>(&err))
+        .or_else(|| mapped::<AppBusyError>(&err))
+        .or_else(|| mapped::<EntryPointError>(&err))
+        .or_else(|| mapped::<PlatformChangeError>(&err))
+        .or_else(|| mapped::<PlanConfirmationError>(&err))
+        .or_else(|| mapped::<TaskRetryError>(&err))
+        .or_else(|| mapped::<ScheduleError>(&err))
+        .or_else(|| mapped::<MountPolicyError>(&err));
+    match mapped {
+        Some(api_err) => api_err,
+        None => ApiError::internal(context, err),
+    }
+}
+
+fn ensure_admin(ctx: &AuthContext) -> Result<(), ApiError> {
     match ctx {
         AuthContext::Admin => Ok(()),
         other => {
             tracing::warn!(?other, "Intento de acceso admin sin privilegios");
-            Err(StatusCode::FORBIDDEN)
+            Err(ApiError::new(ErrorCode::AdminRequired))
         }
     }
 }
 
-fn ensure_scope(ctx: &AuthContext, scope: &str) -> Result<(), StatusCode> {
+fn ensure_scope(ctx: &AuthContext, scope: &str) -> Result<(), ApiError> {
     if has_scope(ctx, scope) {
         Ok(())
     } else {
         tracing::warn!(?ctx, scope, "Token sin scope requerido");
-        Err(StatusCode::FORBIDDEN)
+        Err(ApiError::new(ErrorCode::ScopeMissing)
+            .with_details(serde_json::json!({ "required_scope": scope })))
     }
 }
 
@@ -606,34 +2299,6 @@ fn has_scope(ctx: &AuthContext, scope: &str) -> bool {
     }
 }
 
-fn forbidden_with_message(status: StatusCode) -> (StatusCode, String) {
-    (
-        status,
-        "Permisos insuficientes para la operacion solicitada".into(),
-    )
-}
-
-fn parse_expiration(raw: Option<String>) -> Result<Option<OffsetDateTime>, (StatusCode, String)> {
-    match raw {
-        Some(value) => {
-            let parsed = OffsetDateTime::parse(&value, &Rfc3339).map_err(|_| {
-                (
-                    StatusCode::BAD_REQUEST,
-                    "expires_at debe seguir el formato RFC3339".into(),
-                )
-            })?;
-            if parsed <= OffsetDateTime::now_utc() {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    "expires_at debe ser una fecha futura".into(),
-                ));
-            }
-            Ok(Some(parsed))
-        }
-        None => Ok(None),
-    }
-}
-
 fn expires_within(token: &ApiTokenInfo, window: Duration) -> bool {
     token
         .expires_at
@@ -1,22 +1,60 @@
-use std::{collections::BTreeSet, path::Path, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::{Context, Result};
 use rand::{Rng, distr::Alphanumeric, rng};
-use time::{OffsetDateTime, format_description::well_known::Rfc3339};
-use tokio::{fs, sync::Mutex};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339, macros::format_description};
+use tokio::{
+    fs,
+    io::{AsyncRead, AsyncWriteExt},
+    sync::{Mutex, Notify, mpsc},
+};
 use uuid::Uuid;
 
 use crate::{
-    config::AgentConfig,
+    config::{AgentConfig, MountsConfig, RetentionConfig, TasksConfig},
+    cron::CronExpr,
+    desired::{DesiredContainer, DesiredState},
+    error::{ApiError, ErrorCode},
     events::{AgentEvent, EventHub},
     models::{
-        ApiTokenInfo, AppInstance, ContainerModel, Snapshot, SnapshotType, TaskModel, TaskStatus,
+        ApiTokenInfo, AppEntryPoint, AppInstance, AppStatus, ContainerLimits, ContainerModel,
+        ContainerSettings, ContainerStatus, MissedRunPolicy, Schedule, ScheduleJob, Snapshot,
+        SnapshotType, TaskModel, TaskStatus, TaskStep,
+    },
+    security::{hash_token, sha256_file},
+    store::{SqliteStore, StoreError},
+    templates::{ContainerTemplate, TemplateApp, TemplateStore},
+    virtualization::{
+        ChangeJournal, DiskUsage, FsChange, FsEntry, FsLayer, FsWatcher, LoadedManifest,
+        MappedPath, MountSpec, PathMapError, Platform, ProcessLauncher, ProcessOutcome,
+        RegistryLayer, SandboxDescriptor, SandboxManifest, SandboxRuntime, SnapshotLayer,
+        WatchSignal,
+        app_files::{self, AppFileManifest},
+        archive,
+        integrity::{self, AppExpectation, IntegrityReport},
+        manifest, measure_usage, resolve_virtual_path, split_args, virtual_path,
     },
-    security::hash_token,
-    store::SqliteStore,
-    virtualization::{Platform, SandboxDescriptor, SandboxRuntime},
 };
 
+/// Tiempo maximo que se espera a un instalador antes de abortarlo.
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Cambios que conserva el diario de cada contenedor vigilado.
+const JOURNAL_CAPACITY: usize = 10_000;
+
+/// Prefijo de `status_reason` de los contenedores cuyo sandbox no esta en disco.
+const MISSING_SANDBOX_REASON: &str = "Sandbox ausente";
+
+/// Prefijo de `status_reason` que pone la verificacion al faltar archivos criticos.
+const VERIFY_MISSING_REASON: &str = "Faltan archivos criticos";
+
 #[derive(Clone)]
 pub struct ContainerService {
     inner: Arc<ContainerServiceInner>,
@@ -27,6 +65,21 @@ struct ContainerServiceInner {
     events: EventHub,
     store: SqliteStore,
     mutex: Mutex<()>,
+    /// Ultima medicion de disco de cada contenedor.
+    usage: std::sync::Mutex<HashMap<Uuid, ContainerUsage>>,
+    /// Watchers de `fs/` y sus diarios de cambios.
+    watches: std::sync::Mutex<HashMap<Uuid, ContainerWatch>>,
+}
+
+/// Cambios parciales sobre un contenedor; `None` conserva el valor actual.
+#[derive(Clone, Default)]
+pub struct ContainerUpdate {
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub env: Option<BTreeMap<String, String>>,
+    pub compat_flags: Option<Vec<String>>,
+    pub limits: Option<ContainerLimits>,
+    pub mounts: Option<Vec<MountSpec>>,
 }
 
 impl ContainerService {
@@ -37,6 +90,8 @@ impl ContainerService {
                 events,
                 store,
                 mutex: Mutex::new(()),
+                usage: std::sync::Mutex::new(HashMap::new()),
+                watches: std::sync::Mutex::new(HashMap::new()),
             }),
         }
     }
@@ -48,6 +103,16 @@ impl ContainerService {
         description: Option<String>,
     ) -> Result<TaskModel> {
         let _guard = self.inner.mutex.lock().await;
+        let name = name.trim().to_string();
+        if self
+            .inner
+            .store
+            .find_container_by_name(&name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::DuplicateContainerName(name).into());
+        }
 
         let mut task = TaskModel::new("container.create").with_status(TaskStatus::Running);
         task.set_progress(5, Some("Inicializando creacion".into()));
@@ -56,11 +121,14 @@ impl ContainerService {
             id: task.id,
             task_type: task.task_type.clone(),
             status: "running".into(),
+            parent_task_id: task.parent_task_id,
         });
 
-        let sandbox_root = container_root(&self.inner.config.containers_root, &name);
-        let descriptor = SandboxDescriptor::new(name.clone(), platform.clone(), sandbox_root);
-        let sandbox = SandboxRuntime::new(descriptor);
+        let mut container = ContainerModel::new(Uuid::new_v4(), name, description, platform);
+        let sandbox_root = container_root(&self.inner.config.containers_root, container.id);
+        container.storage_path = sandbox_root.display().to_string();
+        let sandbox =
+            SandboxRuntime::new(SandboxDescriptor::for_container(&container, &sandbox_root));
 
         sandbox
             .prepare()
@@ -86,9 +154,6 @@ impl ContainerService {
             message: "Manifest creado".into(),
         });
 
-        let descriptor = sandbox.descriptor().clone();
-        let mut container =
-            ContainerModel::new(descriptor.container_id, name, description, platform);
         container.touch();
         self.inner.store.upsert_container(&container).await?;
 
@@ -101,202 +166,4880 @@ impl ContainerService {
             message: "Contenedor listo".into(),
         });
         self.inner.events.emit(AgentEvent::ContainerStatus {
-            container_id: descriptor.container_id,
+            container_id: container.id,
             status: "ready".into(),
         });
 
         Ok(task)
     }
 
+    /// Crea un contenedor con sus ajustes iniciales sin registrar una tarea propia; lo
+    /// usan las plantillas, que informan el progreso en la suya.
+    pub async fn provision_container(
+        &self,
+        name: String,
+        platform: Platform,
+        description: Option<String>,
+        tags: Vec<String>,
+        settings: ContainerSettings,
+    ) -> Result<ContainerModel> {
+        check_bind_mounts(&self.inner.config.mounts, &settings.mounts).await?;
+        let _guard = self.inner.mutex.lock().await;
+        let name = name.trim().to_string();
+        if self
+            .inner
+            .store
+            .find_container_by_name(&name)
+            .await?
+            .is_some()
+        {
+            return Err(StoreError::DuplicateContainerName(name).into());
+        }
+
+        let mut container = ContainerModel::new(Uuid::new_v4(), name, description, platform);
+        let sandbox_root = container_root(&self.inner.config.containers_root, container.id);
+        container.storage_path = sandbox_root.display().to_string();
+        container.tags = sanitize_list(tags);
+        container.settings = settings;
+        let sandbox =
+            SandboxRuntime::new(SandboxDescriptor::for_container(&container, &sandbox_root));
+        sandbox
+            .prepare()
+            .await
+            .context("No se pudo preparar el filesystem del contenedor")?;
+        sandbox
+            .persist_manifest()
+            .await
+            .context("No se pudo persistir el manifest del contenedor")?;
+        self.inner.store.upsert_container(&container).await?;
+        self.inner.events.emit(AgentEvent::ContainerStatus {
+            container_id: container.id,
+            status: container.status.as_str().into(),
+        });
+        Ok(container)
+    }
+
     pub async fn get_container(&self, id: Uuid) -> Result<Option<ContainerModel>> {
         self.inner.store.get_container(id).await
     }
 
-    pub async fn delete_container(&self, id: Uuid) -> Result<Option<TaskModel>> {
-        let Some(container) = self.inner.store.get_container(id).await? else {
+    pub async fn find_by_name(&self, name: &str) -> Result<Option<ContainerModel>> {
+        self.inner.store.find_container_by_name(name).await
+    }
+
+    pub async fn update_container(
+        &self,
+        id: Uuid,
+        update: ContainerUpdate,
+    ) -> Result<Option<ContainerModel>> {
+        let ContainerUpdate {
+            description,
+            tags,
+            env,
+            compat_flags,
+            limits,
+            mounts,
+        } = update;
+        if let Some(mounts) = &mounts {
+            check_bind_mounts(&self.inner.config.mounts, mounts).await?;
+        }
+        self.modify_container(id, move |container| {
+            if let Some(description) = description {
+                let trimmed = description.trim();
+                container.description = (!trimmed.is_empty()).then(|| trimmed.to_string());
+            }
+            if let Some(tags) = tags {
+                container.tags = sanitize_list(tags);
+            }
+            if let Some(env) = env {
+                container.settings.env = env;
+            }
+            if let Some(flags) = compat_flags {
+                container.settings.compat_flags = sanitize_list(flags);
+            }
+            if let Some(limits) = limits {
+                container.settings.limits = limits;
+            }
+            if let Some(mounts) = mounts {
+                container.settings.mounts = mounts;
+            }
+        })
+        .await
+    }
+
+    pub async fn rename_container(&self, id: Uuid, name: String) -> Result<Option<ContainerModel>> {
+        self.modify_container(id, move |container| {
+            container.name = name.trim().to_string();
+        })
+        .await
+    }
+
+    pub async fn add_tags(&self, id: Uuid, tags: Vec<String>) -> Result<Option<ContainerModel>> {
+        self.modify_container(id, move |container| {
+            let mut merged = container.tags.clone();
+            merged.extend(tags);
+            container.tags = sanitize_list(merged);
+        })
+        .await
+    }
+
+    pub async fn remove_tag(&self, id: Uuid, tag: &str) -> Result<Option<ContainerModel>> {
+        let tag = tag.trim().to_string();
+        self.modify_container(id, move |container| {
+            container.tags.retain(|existing| *existing != tag);
+        })
+        .await
+    }
+
+    /// Aplica un cambio sobre el contenedor, lo persiste y regenera su manifest.
+    async fn modify_container(
+        &self,
+        id: Uuid,
+        change: impl FnOnce(&mut ContainerModel),
+    ) -> Result<Option<ContainerModel>> {
+        let _guard = self.inner.mutex.lock().await;
+        let Some(mut container) = self.inner.store.get_container(id).await? else {
             return Ok(None);
         };
 
-        let mut task = TaskModel::new("container.delete").with_status(TaskStatus::Running);
-        task.set_progress(5, Some("Eliminando contenedor".into()));
-        self.inner.store.upsert_task(&task).await?;
+        change(&mut container);
+        container.touch();
+        self.inner.store.upsert_container(&container).await?;
 
-        let sandbox_root = container_root(&self.inner.config.containers_root, &container.name);
-        if fs::metadata(&sandbox_root).await.is_ok() {
-            if let Err(err) = fs::remove_dir_all(&sandbox_root).await {
-                tracing::warn!(
-                    ?err,
-                    ?sandbox_root,
-                    "No se pudo eliminar el directorio del contenedor"
-                );
+        let sandbox = SandboxRuntime::new(SandboxDescriptor::for_container(
+            &container,
+            self.sandbox_root(&container),
+        ));
+        sandbox
+            .prepare_mounts()
+            .await
+            .context("No se pudieron crear los directorios de los montajes")?;
+        sandbox
+            .persist_manifest()
+            .await
+            .context("No se pudo regenerar el manifest del contenedor")?;
+
+        self.inner
+            .events
+            .emit(AgentEvent::ContainerUpdated { container_id: id });
+        Ok(Some(container))
+    }
+
+    /// Mueve los sandboxes creados con la ruta basada en nombre a `<containers_root>/<id>`
+    /// y persiste `storage_path` para los contenedores que aun no lo tienen.
+    pub async fn migrate_storage(&self) -> Result<usize> {
+        let _guard = self.inner.mutex.lock().await;
+        let root = &self.inner.config.containers_root;
+        let mut migrated = 0;
+        for mut container in self.inner.store.list_containers(None, None).await? {
+            if !container.storage_path.is_empty() {
+                continue;
             }
-        }
 
-        self.inner.store.delete_container(id).await?;
-        task.status = TaskStatus::Succeeded;
-        task.set_progress(100, Some("Contenedor eliminado".into()));
-        self.inner.store.upsert_task(&task).await?;
-        self.inner.events.emit(AgentEvent::TaskProgress {
-            id: task.id,
-            progress: 100,
-            message: "Contenedor eliminado".into(),
-        });
-        self.inner.events.emit(AgentEvent::ContainerStatus {
-            container_id: id,
-            status: "archived".into(),
-        });
+            let target = container_root(root, container.id);
+            let legacy = legacy_container_root(root, &container.name);
+            let sandbox =
+                SandboxRuntime::new(SandboxDescriptor::for_container(&container, &target));
+            if fs::metadata(&target).await.is_err() {
+                if fs::metadata(&legacy).await.is_ok() {
+                    fs::rename(&legacy, &target).await.with_context(|| {
+                        format!(
+                            "No se pudo mover {} a {}",
+                            legacy.display(),
+                            target.display()
+                        )
+                    })?;
+                } else {
+                    tracing::warn!(
+                        container_id = %container.id,
+                        ?legacy,
+                        "Sandbox heredado inexistente; se prepara uno vacio"
+                    );
+                }
+            }
+            sandbox
+                .prepare()
+                .await
+                .context("No se pudo preparar el filesystem del contenedor")?;
+            sandbox
+                .persist_manifest()
+                .await
+                .context("No se pudo regenerar el manifest del contenedor")?;
 
-        Ok(Some(task))
+            container.storage_path = target.display().to_string();
+            self.inner.store.upsert_container(&container).await?;
+            tracing::info!(
+                container_id = %container.id,
+                storage_path = %container.storage_path,
+                "Sandbox migrado a ruta basada en id"
+            );
+            migrated += 1;
+        }
+        Ok(migrated)
     }
-}
 
-#[derive(Clone)]
-pub struct AppService {
-    events: EventHub,
-    store: SqliteStore,
-}
+    /// Compara la base de datos con los sandboxes de `containers_root`: reconstruye las
+    /// filas que faltan a partir de `runtime/manifest.json`, reescribe los manifests
+    /// ausentes, ilegibles o de un esquema anterior y marca en `error` los contenedores
+    /// cuyo sandbox ya no existe.
+    pub async fn reconcile(&self) -> Result<ReconcileReport> {
+        let _guard = self.inner.mutex.lock().await;
+        let mut report = ReconcileReport::default();
+        let mut containers = self.inner.store.list_containers(None, None).await?;
 
-impl AppService {
-    pub fn new(events: EventHub, store: SqliteStore) -> Self {
-        Self { events, store }
-    }
+        for container in containers.iter_mut() {
+            let root = self.sandbox_root(container);
+            if !fs::metadata(&root).await.is_ok_and(|meta| meta.is_dir()) {
+                report.missing_sandbox.push(container.id);
+                let reason = format!("{MISSING_SANDBOX_REASON}: {}", root.display());
+                if container.status_reason.as_deref() != Some(reason.as_str()) {
+                    tracing::warn!(container_id = %container.id, ?root, "Sandbox ausente");
+                    container.status = ContainerStatus::Error;
+                    container.status_reason = Some(reason);
+                    container.touch();
+                    self.inner.store.upsert_container(container).await?;
+                    self.unwatch(container.id).await?;
+                    self.inner.events.emit(AgentEvent::ContainerStatus {
+                        container_id: container.id,
+                        status: container.status.as_str().into(),
+                    });
+                }
+                continue;
+            }
 
-    pub async fn list(&self, container_id: Uuid) -> Result<Vec<AppInstance>> {
-        self.store.list_apps(container_id).await
-    }
+            if container
+                .status_reason
+                .as_deref()
+                .is_some_and(|reason| reason.starts_with(MISSING_SANDBOX_REASON))
+            {
+                container.status = ContainerStatus::Ready;
+                container.status_reason = None;
+                container.touch();
+                self.inner.store.upsert_container(container).await?;
+                self.inner.events.emit(AgentEvent::ContainerStatus {
+                    container_id: container.id,
+                    status: container.status.as_str().into(),
+                });
+            }
 
-    pub async fn install(
-        &self,
-        container_id: Uuid,
-        name: String,
-        version: Option<String>,
-    ) -> Result<TaskModel> {
-        let mut task = TaskModel::new("app.install").with_status(TaskStatus::Running);
-        task.set_progress(20, Some("Iniciando instalacion".into()));
-        self.store.upsert_task(&task).await?;
-        self.events.emit(AgentEvent::TaskCreated {
-            id: task.id,
-            task_type: task.task_type.clone(),
-            status: "running".into(),
-        });
+            let current = match manifest::load(&root).await {
+                Ok(Some(loaded)) => {
+                    loaded.upgraded_from.is_none() && loaded.manifest.container_id == container.id
+                }
+                Ok(None) => false,
+                Err(err) => {
+                    tracing::warn!(container_id = %container.id, error = %err, "Manifest ilegible");
+                    false
+                }
+            };
+            if !current {
+                SandboxRuntime::new(SandboxDescriptor::for_container(container, &root))
+                    .persist_manifest()
+                    .await
+                    .context("No se pudo regenerar el manifest del contenedor")?;
+                report.repaired.push(container.id);
+            }
+        }
 
-        let mut app = AppInstance::new(container_id, name, version);
-        app.touch();
-        self.store.insert_app(&app).await?;
+        let (restored, skipped) = self.register_orphans(&mut containers).await?;
+        report.restored = restored;
+        report.skipped = skipped;
+        Ok(report)
+    }
 
-        task.status = TaskStatus::Succeeded;
-        task.set_progress(100, Some("Aplicacion instalada".into()));
-        self.store.upsert_task(&task).await?;
-        self.events.emit(AgentEvent::TaskProgress {
-            id: task.id,
-            progress: 100,
-            message: "Aplicacion instalada".into(),
-        });
+    /// Registra los sandboxes huerfanos de `containers_root` cuyo id y nombre estan
+    /// libres. Se llama al arrancar; los demas quedan en `GET /containers/orphans`.
+    pub async fn adopt_orphans(&self) -> Result<Vec<Uuid>> {
+        let _guard = self.inner.mutex.lock().await;
+        let mut containers = self.inner.store.list_containers(None, None).await?;
+        let (adopted, skipped) = self.register_orphans(&mut containers).await?;
+        for skipped in skipped {
+            tracing::info!(path = %skipped.path, reason = %skipped.reason, "Sandbox no adoptado");
+        }
+        Ok(adopted)
+    }
 
-        Ok(task)
+    /// Sandboxes de `containers_root` con un manifest valido que no estan en el store.
+    pub async fn list_orphans(&self) -> Result<Vec<OrphanSandbox>> {
+        let containers = self.inner.store.list_containers(None, None).await?;
+        Ok(self
+            .unregistered_sandboxes(&containers)
+            .await?
+            .iter()
+            .filter_map(|(path, loaded)| {
+                let loaded = loaded.as_ref().ok()?;
+                Some(OrphanSandbox::new(path, loaded, &containers))
+            })
+            .collect())
     }
 
-    pub async fn launch(&self, app_id: Uuid) -> Result<Option<TaskModel>> {
-        let Some(app) = self.store.get_app(app_id).await? else {
+    /// Registra el sandbox huerfano en `path`. Si su id ya esta en uso recibe uno nuevo;
+    /// `name` sustituye al del manifest. `None` si `path` no es un huerfano.
+    pub async fn adopt_orphan(
+        &self,
+        path: &str,
+        name: Option<String>,
+    ) -> Result<Option<ContainerModel>> {
+        let _guard = self.inner.mutex.lock().await;
+        let containers = self.inner.store.list_containers(None, None).await?;
+        let requested = fs::canonicalize(path).await.ok();
+        let mut candidates = self.unregistered_sandboxes(&containers).await?;
+        let Some(index) = candidates.iter().position(|(candidate, loaded)| {
+            loaded.is_ok()
+                && (candidate.as_os_str() == path
+                    || requested.as_deref() == std::fs::canonicalize(candidate).ok().as_deref())
+        }) else {
             return Ok(None);
         };
-        let mut task = TaskModel::new("app.launch").with_status(TaskStatus::Running);
-        task.set_progress(10, Some(format!("Lanzando {}", app.name)));
-        self.store.upsert_task(&task).await?;
-        task.status = TaskStatus::Succeeded;
-        task.set_progress(100, Some("Aplicacion lanzada".into()));
-        self.store.upsert_task(&task).await?;
-        Ok(Some(task))
+        let (path, loaded) = candidates.swap_remove(index);
+        let Ok(loaded) = loaded else {
+            return Ok(None);
+        };
+
+        let manifest = &loaded.manifest;
+        let id = if containers
+            .iter()
+            .any(|container| container.id == manifest.container_id)
+        {
+            Uuid::new_v4()
+        } else {
+            manifest.container_id
+        };
+        let name = name
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|| manifest.name.clone());
+        if containers
+            .iter()
+            .any(|container| container.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(StoreError::DuplicateContainerName(name).into());
+        }
+        check_bind_mounts(&self.inner.config.mounts, &manifest.mounts).await?;
+        self.register_sandbox(&path, manifest, id, name, true)
+            .await
+            .map(Some)
     }
-}
 
-#[derive(Clone)]
-pub struct SnapshotService {
-    store: SqliteStore,
-    events: EventHub,
-}
+    /// Directorios de `containers_root` que no usa ningun contenedor, con su manifest o
+    /// el motivo por el que no se pudo leer.
+    async fn unregistered_sandboxes(
+        &self,
+        containers: &[ContainerModel],
+    ) -> Result<Vec<(PathBuf, Result<LoadedManifest, String>)>> {
+        let mut known_roots = BTreeSet::new();
+        for container in containers {
+            let root = self.sandbox_root(container);
+            known_roots.insert(fs::canonicalize(&root).await.unwrap_or(root));
+        }
 
-impl SnapshotService {
-    pub fn new(events: EventHub, store: SqliteStore) -> Self {
-        Self { store, events }
+        let containers_root = std::path::absolute(&self.inner.config.containers_root)?;
+        let mut entries = match fs::read_dir(&containers_root).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut sandboxes = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !entry.file_type().await?.is_dir()
+                || known_roots.contains(&fs::canonicalize(&path).await.unwrap_or(path.clone()))
+            {
+                continue;
+            }
+            let loaded = match manifest::load(&path).await {
+                Ok(Some(loaded)) => Ok(loaded),
+                Ok(None) => Err("Sin runtime/manifest.json".to_string()),
+                Err(err) => Err(format!("{err:#}")),
+            };
+            sandboxes.push((path, loaded));
+        }
+        sandboxes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(sandboxes)
     }
 
-    pub async fn list(&self, container_id: Uuid) -> Result<Vec<Snapshot>> {
-        self.store.list_snapshots(container_id).await
+    /// Registra los huerfanos sin conflictos y devuelve sus ids junto con los
+    /// directorios que se dejaron como estaban. `containers` incluye los nuevos.
+    async fn register_orphans(
+        &self,
+        containers: &mut Vec<ContainerModel>,
+    ) -> Result<(Vec<Uuid>, Vec<SkippedSandbox>)> {
+        let mut registered = Vec::new();
+        let mut skipped = Vec::new();
+        for (path, loaded) in self.unregistered_sandboxes(containers).await? {
+            let skip = |reason: String| SkippedSandbox {
+                path: path.display().to_string(),
+                reason,
+            };
+            let loaded = match loaded {
+                Ok(loaded) => loaded,
+                Err(reason) => {
+                    skipped.push(skip(reason));
+                    continue;
+                }
+            };
+            let manifest = &loaded.manifest;
+            let reason = match OrphanSandbox::new(&path, &loaded, containers).conflict {
+                Some(OrphanConflict::IdInUse) => Some(format!(
+                    "El contenedor {} ya esta registrado",
+                    manifest.container_id
+                )),
+                Some(OrphanConflict::NameInUse) => {
+                    Some(format!("Ya existe un contenedor llamado {}", manifest.name))
+                }
+                None => None,
+            };
+            if let Some(reason) = reason {
+                skipped.push(skip(reason));
+                continue;
+            }
+
+            let container = self
+                .register_sandbox(
+                    &path,
+                    manifest,
+                    manifest.container_id,
+                    manifest.name.clone(),
+                    false,
+                )
+                .await?;
+            registered.push(container.id);
+            containers.push(container);
+        }
+        Ok((registered, skipped))
     }
 
-    pub async fn create(
+    /// Crea la fila del contenedor a partir del manifest y lo reescribe con el id, el
+    /// nombre y las rutas actuales. Sin `keep_binds` se quitan los montajes de directorios
+    /// del host: un sandbox copiado a `containers_root` no debe dar acceso al host sin que
+    /// un administrador lo adopte a mano.
+    async fn register_sandbox(
         &self,
-        container_id: Uuid,
-        label: Option<String>,
-        snapshot_type: SnapshotType,
-    ) -> Result<TaskModel> {
-        let mut task = TaskModel::new("snapshot.create").with_status(TaskStatus::Running);
-        task.set_progress(25, Some("Capturando snapshot".into()));
-        self.store.upsert_task(&task).await?;
-        self.events.emit(AgentEvent::TaskCreated {
-            id: task.id,
-            task_type: task.task_type.clone(),
-            status: "running".into(),
+        path: &Path,
+        manifest: &SandboxManifest,
+        id: Uuid,
+        name: String,
+        keep_binds: bool,
+    ) -> Result<ContainerModel> {
+        let mut container = ContainerModel::new(
+            id,
+            name,
+            manifest.description.clone(),
+            manifest.platform.clone(),
+        );
+        container.tags = manifest.tags.clone();
+        container.settings = manifest.settings();
+        if !keep_binds {
+            let (binds, mounts) = std::mem::take(&mut container.settings.mounts)
+                .into_iter()
+                .partition::<Vec<_>, _>(|mount| mount.source.is_some());
+            for bind in &binds {
+                tracing::warn!(
+                    container_id = %id,
+                    virtual_path = %bind.virtual_path,
+                    source = bind.source.as_deref().unwrap_or_default(),
+                    "Bind del host descartado al registrar el sandbox"
+                );
+            }
+            container.settings.mounts = mounts;
+        }
+        container.storage_path = path.display().to_string();
+        container.created_at = manifest.created_at.clone();
+        self.inner.store.upsert_container(&container).await?;
+        SandboxRuntime::new(SandboxDescriptor::for_container(&container, path))
+            .persist_manifest()
+            .await
+            .context("No se pudo regenerar el manifest del contenedor")?;
+        tracing::info!(
+            container_id = %container.id,
+            storage_path = %container.storage_path,
+            "Contenedor registrado desde su manifest"
+        );
+        self.inner.events.emit(AgentEvent::ContainerStatus {
+            container_id: container.id,
+            status: container.status.as_str().into(),
         });
+        Ok(container)
+    }
 
-        let mut snapshot = Snapshot::new(container_id, label, snapshot_type);
-        snapshot.size_bytes = 0;
-        self.store.insert_snapshot(&snapshot).await?;
+    /// Capa de archivos para la API: los binds que ya no permite `[mounts]` se ignoran y
+    /// los demas quedan de solo lectura salvo `writable_binds`.
+    fn file_layer(&self, container: &ContainerModel) -> FsLayer {
+        let mut settings = container.settings.clone();
+        settings.mounts = effective_mounts(&self.inner.config.mounts, &settings.mounts);
+        FsLayer::new(self.sandbox_root(container)).with_settings(&container.platform, &settings)
+    }
 
-        task.status = TaskStatus::Succeeded;
-        task.set_progress(100, Some("Snapshot creado".into()));
-        self.store.upsert_task(&task).await?;
-        self.events.emit(AgentEvent::TaskProgress {
-            id: task.id,
-            progress: 100,
-            message: "Snapshot creado".into(),
-        });
-        Ok(task)
+    fn sandbox_root(&self, container: &ContainerModel) -> PathBuf {
+        if container.storage_path.is_empty() {
+            container_root(&self.inner.config.containers_root, container.id)
+        } else {
+            PathBuf::from(&container.storage_path)
+        }
     }
 
-    pub async fn restore(&self, snapshot_id: Uuid) -> Result<Option<TaskModel>> {
-        let Some(_snapshot) = self.store.get_snapshot(snapshot_id).await? else {
+    pub async fn delete_container(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
             return Ok(None);
         };
-        let mut task = TaskModel::new("snapshot.restore").with_status(TaskStatus::Running);
-        task.set_progress(30, Some("Preparando restauracion".into()));
-        self.store.upsert_task(&task).await?;
 
+        let mut task = TaskModel::new("container.delete").with_status(TaskStatus::Running);
+        task.set_progress(5, Some("Eliminando contenedor".into()));
+        self.inner.store.upsert_task(&task).await?;
+
+        self.remove_container(&container).await?;
         task.status = TaskStatus::Succeeded;
-        task.set_progress(100, Some("Snapshot restaurado".into()));
-        self.store.upsert_task(&task).await?;
-        self.events.emit(AgentEvent::TaskProgress {
+        task.set_progress(100, Some("Contenedor eliminado".into()));
+        self.inner.store.upsert_task(&task).await?;
+        self.inner.events.emit(AgentEvent::TaskProgress {
             id: task.id,
             progress: 100,
-            message: "Snapshot restaurado".into(),
+            message: "Contenedor eliminado".into(),
         });
+
         Ok(Some(task))
     }
-}
 
-fn container_root(root: &Path, name: &str) -> std::path::PathBuf {
-    let sanitized = name
-        .chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '"' | '<' | '>' | '|' => '_',
-            _ => c,
-        })
-        .collect::<String>();
-    root.join(sanitized)
-}
+    /// Elimina el contenedor sin registrar una tarea propia; lo usa `apply`, que lo
+    /// informa como paso de la suya. Devuelve `false` si no existe.
+    pub async fn discard_container(&self, id: Uuid) -> Result<bool> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(false);
+        };
+        self.remove_container(&container).await?;
+        Ok(true)
+    }
 
-#[derive(Clone)]
-pub struct TokenService {
-    store: SqliteStore,
-}
+    async fn remove_container(&self, container: &ContainerModel) -> Result<()> {
+        let watch = self
+            .inner
+            .watches
+            .lock()
+            .expect("watches lock")
+            .remove(&container.id);
+        if let Some(watch) = watch {
+            tokio::task::spawn_blocking(move || drop(watch)).await?;
+        }
+        let sandbox_root = self.sandbox_root(container);
+        if fs::metadata(&sandbox_root).await.is_ok() {
+            if let Err(err) = fs::remove_dir_all(&sandbox_root).await {
+                tracing::warn!(
+                    ?err,
+                    ?sandbox_root,
+                    "No se pudo eliminar el directorio del contenedor"
+                );
+            }
+        }
 
-pub struct IssuedToken {
-    pub secret: String,
-    pub info: ApiTokenInfo,
-}
+        self.inner.store.delete_container(container.id).await?;
+        self.inner.events.emit(AgentEvent::ContainerStatus {
+            container_id: container.id,
+            status: "archived".into(),
+        });
+        Ok(())
+    }
 
-#[derive(Clone)]
+    /// Arranca el contenedor (`ready` -> `running`) y empieza a vigilar su `fs/`: cada
+    /// cambio se anota en su diario y se publica como `container.fs-changed`.
+    pub async fn start(&self, id: Uuid) -> Result<Option<ContainerModel>> {
+        let _guard = self.inner.mutex.lock().await;
+        let Some(mut container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        match container.status {
+            ContainerStatus::Running => return Ok(Some(container)),
+            ContainerStatus::Ready => {}
+            ref other => {
+                return Err(ContainerNotReadyError {
+                    status: other.as_str(),
+                }
+                .into());
+            }
+        }
+        self.watch(&container).await?;
+        self.set_status(&mut container, ContainerStatus::Running)
+            .await?;
+        Ok(Some(container))
+    }
+
+    /// Detiene el contenedor (`running` -> `ready`) y su vigilancia; el diario se
+    /// conserva hasta que se borre. En cualquier otro estado no hace nada.
+    pub async fn stop(&self, id: Uuid) -> Result<Option<ContainerModel>> {
+        let _guard = self.inner.mutex.lock().await;
+        let Some(mut container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        if matches!(container.status, ContainerStatus::Running) {
+            self.set_status(&mut container, ContainerStatus::Ready)
+                .await?;
+            self.unwatch(id).await?;
+        }
+        Ok(Some(container))
+    }
+
+    /// Vuelve a vigilar, al arrancar el agente, los contenedores que quedaron `running`.
+    pub async fn resume_watches(&self) -> Result<usize> {
+        let containers = self
+            .inner
+            .store
+            .list_containers(Some(ContainerStatus::Running.as_str().into()), None)
+            .await?;
+        let mut resumed = 0;
+        for container in &containers {
+            match self.watch(container).await {
+                Ok(()) => resumed += 1,
+                Err(err) => {
+                    tracing::warn!(?err, container_id = %container.id, "No se pudo reanudar la vigilancia");
+                }
+            }
+        }
+        Ok(resumed)
+    }
+
+    async fn set_status(
+        &self,
+        container: &mut ContainerModel,
+        status: ContainerStatus,
+    ) -> Result<()> {
+        container.status = status;
+        container.touch();
+        self.inner.store.upsert_container(container).await?;
+        self.inner.events.emit(AgentEvent::ContainerStatus {
+            container_id: container.id,
+            status: container.status.as_str().into(),
+        });
+        Ok(())
+    }
+
+    /// Abre el watcher de `fs/` si aun no hay uno. El recorrido inicial del arbol se hace
+    /// en un hilo bloqueante, sin retener el mapa de watches.
+    async fn watch(&self, container: &ContainerModel) -> Result<()> {
+        let id = container.id;
+        let journal = {
+            let mut watches = self.inner.watches.lock().expect("watches lock");
+            let watch = watches.entry(id).or_insert_with(ContainerWatch::new);
+            if watch.watcher.is_some() {
+                return Ok(());
+            }
+            watch.journal.clone()
+        };
+        let layer = FsLayer::new(self.sandbox_root(container))
+            .with_settings(&container.platform, &container.settings);
+        let events = self.inner.events.clone();
+        let fs_root = layer.fs_root();
+        let watcher = tokio::task::spawn_blocking(move || {
+            FsWatcher::spawn(&fs_root, move |signal| {
+                let mut journal = journal.lock().expect("journal lock");
+                match signal {
+                    WatchSignal::Change(change) => {
+                        let path = layer.virtual_path(&change.relative);
+                        let change = journal.record(change, path);
+                        events.emit(AgentEvent::ContainerFsChanged {
+                            container_id: id,
+                            change,
+                        });
+                    }
+                    WatchSignal::Overflow => journal.mark_overflow(),
+                }
+            })
+            .with_context(|| format!("No se pudo vigilar {}", fs_root.display()))
+        })
+        .await??;
+        tracing::info!(container_id = %id, backend = watcher.backend(), "Vigilando cambios del contenedor");
+        let previous = self
+            .inner
+            .watches
+            .lock()
+            .expect("watches lock")
+            .entry(id)
+            .or_insert_with(ContainerWatch::new)
+            .watcher
+            .replace(watcher);
+        if let Some(previous) = previous {
+            tokio::task::spawn_blocking(move || drop(previous)).await?;
+        }
+        Ok(())
+    }
+
+    /// Cierra el watcher del contenedor si lo hay; soltarlo espera a que termine su hilo.
+    async fn unwatch(&self, id: Uuid) -> Result<()> {
+        let watcher = self
+            .inner
+            .watches
+            .lock()
+            .expect("watches lock")
+            .get_mut(&id)
+            .and_then(|watch| watch.watcher.take());
+        if let Some(watcher) = watcher {
+            tokio::task::spawn_blocking(move || drop(watcher)).await?;
+            tracing::info!(container_id = %id, "Vigilancia del contenedor detenida");
+        }
+        Ok(())
+    }
+
+    /// Cambios del diario posteriores a `since`, como mucho `limit`.
+    pub async fn changes(&self, id: Uuid, since: u64, limit: usize) -> Result<Option<ChangeLog>> {
+        if self.inner.store.get_container(id).await?.is_none() {
+            return Ok(None);
+        }
+        let watches = self.inner.watches.lock().expect("watches lock");
+        let Some(watch) = watches.get(&id) else {
+            return Ok(Some(ChangeLog {
+                status: WatchStatus::default(),
+                truncated: false,
+                changes: vec![],
+            }));
+        };
+        let (changes, truncated) = watch
+            .journal
+            .lock()
+            .expect("journal lock")
+            .since(since, limit);
+        Ok(Some(ChangeLog {
+            status: watch.status(),
+            truncated,
+            changes,
+        }))
+    }
+
+    /// Lanza en segundo plano la verificacion de integridad del contenedor; el
+    /// informe queda en `result` de la tarea. Devuelve `None` si no existe.
+    pub async fn verify(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        self.start_verify(TaskModel::new("container.verify"), id)
+            .await
+    }
+
+    async fn start_verify(&self, task: TaskModel, id: Uuid) -> Result<Option<TaskModel>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+
+        let mut task = task.with_status(TaskStatus::Running);
+        task.set_progress(5, Some("Verificando integridad".into()));
+        self.inner.store.upsert_task(&task).await?;
+        self.inner.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service.run_verify(job, container).await;
+        });
+        Ok(Some(task))
+    }
+
+    async fn run_verify(&self, mut task: TaskModel, container: ContainerModel) {
+        let outcome = self.execute_verify(&mut task, &container).await;
+        self.finish_job(task, outcome, "No se pudo verificar el contenedor")
+            .await;
+    }
+
+    /// Cierra una tarea en segundo plano con el resultado de `outcome`.
+    async fn finish_job(&self, mut task: TaskModel, outcome: Result<String>, failure: &str) {
+        let message = match outcome {
+            Ok(message) => {
+                task.status = TaskStatus::Succeeded;
+                message
+            }
+            Err(err) => {
+                tracing::error!(?err, task_id = %task.id, "{failure}");
+                task.status = TaskStatus::Failed;
+                format!("{failure}: {err:#}")
+            }
+        };
+        task.set_progress(100, Some(message.clone()));
+        if let Err(err) = self.inner.store.upsert_task(&task).await {
+            tracing::error!(?err, task_id = %task.id, "No se pudo persistir la tarea");
+        }
+        self.inner.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message,
+        });
+    }
+
+    async fn execute_verify(
+        &self,
+        task: &mut TaskModel,
+        container: &ContainerModel,
+    ) -> Result<String> {
+        let root = self.sandbox_root(container);
+        // Las rutas se resuelven con la plataforma y los montajes del contenedor, como en
+        // el explorador de archivos.
+        let layer = self.file_layer(container);
+        let fs_root = layer.fs_root();
+        let resolve = |path: &str| {
+            layer
+                .mapper()
+                .to_physical(path)
+                .ok()
+                .map(|mapped| mapped.physical)
+                .filter(|physical| *physical != fs_root)
+        };
+        let mut apps = vec![];
+        for app in self.inner.store.list_apps(container.id).await? {
+            let manifest = app_files::load(&root, app.id)
+                .await?
+                .unwrap_or_else(|| AppFileManifest::empty(app.id));
+            let install_dir = app.install_dir.as_deref().and_then(resolve);
+            // Solo una app `ready` necesita que sus entry points sigan ahi.
+            let critical = if matches!(app.status, AppStatus::Ready) {
+                app.entry_points
+                    .iter()
+                    .filter_map(|entry| resolve(&entry.command))
+                    .collect()
+            } else {
+                BTreeSet::new()
+            };
+            apps.push(AppExpectation {
+                manifest,
+                install_dir,
+                critical,
+            });
+        }
+        let snapshots = self
+            .inner
+            .store
+            .list_snapshots(container.id)
+            .await?
+            .into_iter()
+            .map(|snapshot| snapshot.id)
+            .collect();
+
+        task.set_progress(30, Some("Recalculando hashes".into()));
+        self.inner.store.upsert_task(task).await?;
+        let report = integrity::verify(&root, layer.mapper().clone(), apps, snapshots).await?;
+        self.apply_verify_status(container.id, &report).await?;
+
+        task.result = Some(serde_json::to_value(&report)?);
+        Ok(format!(
+            "Verificacion completada: {} archivos revisados, {} ausentes, {} modificados, {} inesperados",
+            report.checked_files,
+            report.missing.len(),
+            report.modified.len(),
+            report.unexpected.len()
+        ))
+    }
+
+    /// Empaqueta en segundo plano el sandbox (sin `snapshots/`) en un `.tar.gz` dentro de
+    /// `<data_dir>/exports` o de su subdirectorio `destination`; la ruta queda en `result`
+    /// de la tarea. Con `keep` borra despues los archivos mas antiguos del contenedor.
+    async fn start_export(
+        &self,
+        task: TaskModel,
+        id: Uuid,
+        destination: Option<&str>,
+        keep: Option<u32>,
+    ) -> Result<Option<TaskModel>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        let dir = self.export_dir(destination).await?;
+        let stamp = OffsetDateTime::now_utc()
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second]Z"
+            ))
+            .unwrap_or_default();
+        // El id distingue los archivos de cada contenedor; el nombre solo ayuda a leerlos.
+        let prefix = format!("{}-", container.id);
+        let target = dir.join(format!(
+            "{prefix}{stamp}-{}.tar.gz",
+            sanitize_file_name(&container.name)
+        ));
+
+        let mut task = task.with_status(TaskStatus::Running);
+        task.set_progress(5, Some("Exportando contenedor".into()));
+        self.inner.store.upsert_task(&task).await?;
+        self.inner.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+
+        let service = self.clone();
+        let mut job = task.clone();
+        tokio::spawn(async move {
+            let source = service.sandbox_root(&container);
+            let archive_path = target.clone();
+            let outcome = tokio::task::spawn_blocking(move || {
+                let size_bytes = archive::pack_tar_gz(&source, &archive_path, &["snapshots"])?;
+                let deleted = match keep {
+                    Some(keep) => prune_exports(&dir, &prefix, keep as usize)?,
+                    None => Vec::new(),
+                };
+                anyhow::Ok((size_bytes, deleted))
+            })
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|packed| packed)
+            .map(|(size_bytes, deleted)| {
+                job.result = Some(serde_json::json!({
+                    "path": target.display().to_string(),
+                    "size_bytes": size_bytes,
+                    "deleted": deleted,
+                }));
+                format!("Contenedor exportado en {}", target.display())
+            });
+            service
+                .finish_job(job, outcome, "No se pudo exportar el contenedor")
+                .await;
+        });
+        Ok(Some(task))
+    }
+
+    /// Directorio de exportacion: `<data_dir>/exports` o un subdirectorio relativo suyo.
+    /// Se comprueba ya creado para que un enlace simbolico no lo saque de `data_dir`.
+    async fn export_dir(&self, destination: Option<&str>) -> Result<PathBuf> {
+        let data_dir = self.inner.config.data_dir();
+        let mut dir = data_dir.join("exports");
+        if let Some(destination) = destination {
+            let relative = Path::new(destination);
+            if destination.contains(':')
+                || relative
+                    .components()
+                    .any(|part| !matches!(part, std::path::Component::Normal(_)))
+            {
+                anyhow::bail!(
+                    "Destino de exportacion fuera de {}: {destination}",
+                    dir.display()
+                );
+            }
+            dir.push(relative);
+        }
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("No se pudo crear {}", dir.display()))?;
+        let dir = tokio::fs::canonicalize(&dir).await?;
+        if !dir.starts_with(tokio::fs::canonicalize(&data_dir).await?) {
+            anyhow::bail!("Destino de exportacion fuera de {}", data_dir.display());
+        }
+        Ok(dir)
+    }
+
+    /// Traduce una ruta del contenedor a disco con su tabla de montajes, o al reves si
+    /// `reverse` (ruta fisica absoluta o relativa a `fs/`). `None` si no existe.
+    pub async fn resolve_path(
+        &self,
+        id: Uuid,
+        path: &str,
+        reverse: bool,
+    ) -> Result<Option<PathResolution>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        let layer = self.file_layer(&container);
+        let mapper = layer.mapper();
+        let virtual_path = if reverse {
+            mapper
+                .to_virtual(Path::new(path))
+                .ok_or_else(|| PathMapError::OutsideContainer(path.to_string()))?
+        } else {
+            path.to_string()
+        };
+        let mapped = mapper.to_physical(&virtual_path)?;
+        let exists = fs::symlink_metadata(&mapped.physical).await.is_ok();
+        Ok(Some(PathResolution { mapped, exists }))
+    }
+
+    /// Lista un directorio del contenedor; `None` si el contenedor no existe.
+    pub async fn list_files(&self, id: Uuid, path: &str) -> Result<Option<Vec<FsEntry>>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        let layer = self.file_layer(&container);
+        let dir = resolve_existing(&layer, path).await?;
+        if !fs::metadata(&dir.physical).await?.is_dir() {
+            return Err(ContainerFileError::NotADirectory(path.to_string()).into());
+        }
+        Ok(Some(layer.list_dir(&dir).await?))
+    }
+
+    /// Abre un archivo del contenedor para descargarlo.
+    pub async fn open_file(&self, id: Uuid, path: &str) -> Result<Option<(FsEntry, fs::File)>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        let layer = self.file_layer(&container);
+        let mapped = resolve_existing(&layer, path).await?;
+        if !fs::metadata(&mapped.physical).await?.is_file() {
+            return Err(ContainerFileError::NotAFile(path.to_string()).into());
+        }
+        let file = fs::File::open(&mapped.physical)
+            .await
+            .with_context(|| format!("No se pudo abrir {}", mapped.physical.display()))?;
+        Ok(Some((layer.describe(&mapped).await?, file)))
+    }
+
+    /// Escribe `content` en `path`, creando los directorios que falten. Se escribe
+    /// primero en un temporal del mismo directorio y se renombra al terminar, asi una
+    /// subida cortada no deja el archivo a medias.
+    pub async fn write_file(
+        &self,
+        id: Uuid,
+        path: &str,
+        mut content: impl AsyncRead + Unpin,
+    ) -> Result<Option<FsEntry>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        let layer = self.file_layer(&container);
+        let outside = || ContainerFileError::OutsideContainer(path.to_string());
+        let mapped = layer.resolve(path).await.ok_or_else(outside)?;
+        if mapped.read_only {
+            return Err(ContainerFileError::ReadOnly(path.to_string()).into());
+        }
+        let target = &mapped.physical;
+        let (Some(parent), Some(name)) = (target.parent(), target.file_name()) else {
+            return Err(outside().into());
+        };
+        if fs::metadata(target).await.is_ok_and(|meta| !meta.is_file()) {
+            return Err(ContainerFileError::NotAFile(path.to_string()).into());
+        }
+        fs::create_dir_all(parent).await?;
+
+        let staged = parent.join(format!(
+            ".{}.{}.upload",
+            name.to_string_lossy(),
+            Uuid::new_v4()
+        ));
+        let written = async {
+            let mut file = fs::File::create(&staged).await?;
+            tokio::io::copy(&mut content, &mut file).await?;
+            file.flush().await?;
+            fs::rename(&staged, target).await
+        }
+        .await;
+        if let Err(err) = written {
+            let _ = fs::remove_file(&staged).await;
+            return Err(anyhow::Error::new(err)
+                .context(format!("No se pudo escribir {}", target.display())));
+        }
+        tracing::info!(container_id = %id, path, "Archivo subido al contenedor");
+        Ok(Some(layer.describe(&mapped).await?))
+    }
+
+    /// Mide el sandbox del contenedor, guarda el desglose y actualiza `size_bytes` si
+    /// cambio. `None` si el contenedor no existe.
+    pub async fn refresh_usage(&self, id: Uuid) -> Result<Option<DiskUsage>> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(None);
+        };
+        Ok(Some(self.measure_container(&container).await?.usage))
+    }
+
+    async fn measure_container(&self, container: &ContainerModel) -> Result<ContainerUsage> {
+        let measured = ContainerUsage {
+            usage: measure_usage(&self.sandbox_root(container)).await?,
+            scanned_at: OffsetDateTime::now_utc().format(&Rfc3339)?,
+        };
+        self.inner
+            .usage
+            .lock()
+            .expect("usage lock")
+            .insert(container.id, measured.clone());
+        if container.size_bytes != measured.usage.total {
+            let _guard = self.inner.mutex.lock().await;
+            if let Some(mut current) = self.inner.store.get_container(container.id).await? {
+                current.size_bytes = measured.usage.total;
+                self.inner.store.upsert_container(&current).await?;
+            }
+        }
+        Ok(measured)
+    }
+
+    /// Mide todos los contenedores; devuelve cuantos se midieron.
+    pub async fn scan_usage(&self) -> Result<usize> {
+        let containers = self.inner.store.list_containers(None, None).await?;
+        let ids = containers
+            .iter()
+            .map(|container| container.id)
+            .collect::<BTreeSet<_>>();
+        self.inner
+            .usage
+            .lock()
+            .expect("usage lock")
+            .retain(|id, _| ids.contains(id));
+        let mut scanned = 0;
+        for id in ids {
+            match self.refresh_usage(id).await {
+                Ok(Some(_)) => scanned += 1,
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(container_id = %id, ?err, "No se pudo medir el contenedor")
+                }
+            }
+        }
+        Ok(scanned)
+    }
+
+    /// Repite [`ContainerService::scan_usage`] cada `storage.scan_interval_secs`.
+    pub async fn run_usage_scanner(self) {
+        let seconds = self.inner.config.storage.scan_interval_secs;
+        if seconds == 0 {
+            tracing::info!("Escaneo de uso de disco desactivado");
+            return;
+        }
+        let mut interval = tokio::time::interval(Duration::from_secs(seconds));
+        loop {
+            interval.tick().await;
+            match self.scan_usage().await {
+                Ok(scanned) => tracing::debug!(scanned, "Uso de disco actualizado"),
+                Err(err) => tracing::warn!(?err, "Fallo el escaneo de uso de disco"),
+            }
+        }
+    }
+
+    /// Uso de disco de todos los contenedores con sus cuotas. Los que aun no se han
+    /// medido se miden ahora.
+    pub async fn storage_summary(&self) -> Result<StorageSummary> {
+        let containers = self.inner.store.list_containers(None, None).await?;
+        let mut summary = StorageSummary {
+            usage: DiskUsage::default(),
+            global_quota_bytes: self.inner.config.storage.global_quota_mb.map(mb_to_bytes),
+            containers: Vec::with_capacity(containers.len()),
+        };
+        for container in containers {
+            let cached = self
+                .inner
+                .usage
+                .lock()
+                .expect("usage lock")
+                .get(&container.id)
+                .cloned();
+            let measured = match cached {
+                Some(measured) => measured,
+                None => self.measure_container(&container).await?,
+            };
+            let quota_bytes = self.container_quota(&container);
+            summary.usage += measured.usage;
+            summary.containers.push(ContainerStorage {
+                container_id: container.id,
+                name: container.name,
+                over_quota: quota_bytes.is_some_and(|quota| measured.usage.total > quota),
+                quota_bytes,
+                usage: measured.usage,
+                scanned_at: measured.scanned_at,
+            });
+        }
+        Ok(summary)
+    }
+
+    /// Falla con [`QuotaExceededError`] si el contenedor o el total de contenedores ya
+    /// alcanzaron su cuota. Se llama antes de instalar apps o crear snapshots.
+    pub async fn ensure_quota(&self, id: Uuid) -> Result<()> {
+        let Some(container) = self.inner.store.get_container(id).await? else {
+            return Ok(());
+        };
+        let usage = self.measure_container(&container).await?.usage;
+        if let Some(quota_bytes) = self.container_quota(&container)
+            && usage.total >= quota_bytes
+        {
+            return Err(QuotaExceededError {
+                scope: QuotaScope::Container,
+                used_bytes: usage.total,
+                quota_bytes,
+            }
+            .into());
+        }
+        if let Some(quota_bytes) = self.inner.config.storage.global_quota_mb.map(mb_to_bytes) {
+            let used_bytes = self.storage_summary().await?.usage.total;
+            if used_bytes >= quota_bytes {
+                return Err(QuotaExceededError {
+                    scope: QuotaScope::Global,
+                    used_bytes,
+                    quota_bytes,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    fn container_quota(&self, container: &ContainerModel) -> Option<u64> {
+        container
+            .settings
+            .limits
+            .disk_mb
+            .or(self.inner.config.storage.container_quota_mb)
+            .map(mb_to_bytes)
+    }
+
+    /// Marca el contenedor como `error` si faltan archivos criticos y lo devuelve a
+    /// `ready` cuando una verificacion posterior ya no los echa en falta.
+    async fn apply_verify_status(&self, id: Uuid, report: &IntegrityReport) -> Result<()> {
+        let _guard = self.inner.mutex.lock().await;
+        let Some(mut container) = self.inner.store.get_container(id).await? else {
+            return Ok(());
+        };
+        let missing = report
+            .critical_missing()
+            .map(|finding| finding.path.as_str())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            container.status = ContainerStatus::Error;
+            container.status_reason =
+                Some(format!("{VERIFY_MISSING_REASON}: {}", missing.join(", ")));
+        } else if matches!(container.status, ContainerStatus::Error)
+            && container
+                .status_reason
+                .as_deref()
+                .is_some_and(|reason| reason.starts_with(VERIFY_MISSING_REASON))
+        {
+            // Solo se limpia el error que puso la propia verificacion.
+            container.status = ContainerStatus::Ready;
+            container.status_reason = None;
+        } else {
+            return Ok(());
+        }
+
+        container.touch();
+        self.inner.store.upsert_container(&container).await?;
+        if matches!(container.status, ContainerStatus::Error) {
+            self.unwatch(id).await?;
+        }
+        self.inner.events.emit(AgentEvent::ContainerStatus {
+            container_id: id,
+            status: container.status.as_str().into(),
+        });
+        Ok(())
+    }
+}
+
+/// Resultado de [`ContainerService::reconcile`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconcileReport {
+    /// Contenedores reconstruidos a partir de su manifest.
+    pub restored: Vec<Uuid>,
+    /// Contenedores cuyo manifest se reescribio desde la base de datos.
+    pub repaired: Vec<Uuid>,
+    /// Contenedores sin directorio de sandbox; quedan en `error`.
+    pub missing_sandbox: Vec<Uuid>,
+    /// Directorios de `containers_root` que no se pudieron registrar.
+    pub skipped: Vec<SkippedSandbox>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedSandbox {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Directorio de `containers_root` con un manifest valido que no esta en el store.
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanSandbox {
+    pub path: String,
+    pub container_id: Uuid,
+    pub name: String,
+    pub platform: Platform,
+    pub created_at: String,
+    /// Version del manifest en disco; se actualiza al adoptarlo.
+    pub schema_version: u32,
+    /// Por que no se adopta al arrancar; `adopt` lo resuelve con otro id o nombre.
+    pub conflict: Option<OrphanConflict>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanConflict {
+    IdInUse,
+    NameInUse,
+}
+
+impl OrphanSandbox {
+    fn new(path: &Path, loaded: &LoadedManifest, containers: &[ContainerModel]) -> Self {
+        let manifest = &loaded.manifest;
+        let conflict = if containers
+            .iter()
+            .any(|container| container.id == manifest.container_id)
+        {
+            Some(OrphanConflict::IdInUse)
+        } else if containers
+            .iter()
+            .any(|container| container.name.eq_ignore_ascii_case(&manifest.name))
+        {
+            Some(OrphanConflict::NameInUse)
+        } else {
+            None
+        };
+        Self {
+            path: path.display().to_string(),
+            container_id: manifest.container_id,
+            name: manifest.name.clone(),
+            platform: manifest.platform.clone(),
+            created_at: manifest.created_at.clone(),
+            schema_version: loaded.upgraded_from.unwrap_or(manifest.schema_version),
+            conflict,
+        }
+    }
+}
+
+/// Ruta traducida por [`ContainerService::resolve_path`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PathResolution {
+    #[serde(flatten)]
+    pub mapped: MappedPath,
+    pub exists: bool,
+}
+
+/// Vigilancia de `fs/` de un contenedor; el diario sobrevive a detenerla.
+struct ContainerWatch {
+    watcher: Option<FsWatcher>,
+    journal: Arc<std::sync::Mutex<ChangeJournal>>,
+}
+
+impl ContainerWatch {
+    fn new() -> Self {
+        Self {
+            watcher: None,
+            journal: Arc::new(std::sync::Mutex::new(ChangeJournal::new(JOURNAL_CAPACITY))),
+        }
+    }
+
+    fn status(&self) -> WatchStatus {
+        WatchStatus {
+            watching: self.watcher.is_some(),
+            backend: self.watcher.as_ref().map(FsWatcher::backend),
+            last_seq: self.journal.lock().expect("journal lock").last_seq(),
+        }
+    }
+}
+
+/// Estado de la vigilancia de un contenedor.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WatchStatus {
+    pub watching: bool,
+    /// `inotify`, `fsevent`, `kqueue`, `windows` o `poll`.
+    pub backend: Option<&'static str>,
+    /// Ultima secuencia anotada; sirve como `since` de la siguiente consulta.
+    pub last_seq: u64,
+}
+
+/// Respuesta de [`ContainerService::changes`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeLog {
+    #[serde(flatten)]
+    pub status: WatchStatus,
+    /// Se descartaron cambios posteriores a `since` porque el diario estaba lleno.
+    pub truncated: bool,
+    pub changes: Vec<FsChange>,
+}
+
+#[derive(Debug, Clone)]
+struct ContainerUsage {
+    usage: DiskUsage,
+    scanned_at: String,
+}
+
+/// Uso de disco devuelto por [`ContainerService::storage_summary`].
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageSummary {
+    /// Suma de todos los contenedores.
+    pub usage: DiskUsage,
+    pub global_quota_bytes: Option<u64>,
+    pub containers: Vec<ContainerStorage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContainerStorage {
+    pub container_id: Uuid,
+    pub name: String,
+    pub usage: DiskUsage,
+    pub quota_bytes: Option<u64>,
+    pub over_quota: bool,
+    pub scanned_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaScope {
+    Container,
+    Global,
+}
+
+/// Cuota de disco agotada; bloquea instalaciones y snapshots.
+#[derive(Debug, thiserror::Error)]
+#[error("Cuota de disco agotada ({scope:?}): {used_bytes} de {quota_bytes} bytes")]
+pub struct QuotaExceededError {
+    pub scope: QuotaScope,
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+}
+
+impl From<&QuotaExceededError> for ApiError {
+    fn from(err: &QuotaExceededError) -> Self {
+        ApiError::new(ErrorCode::QuotaExceeded).with_details(serde_json::json!({
+            "scope": err.scope,
+            "used_bytes": err.used_bytes,
+            "quota_bytes": err.quota_bytes,
+        }))
+    }
+}
+
+/// Bind que no permite la configuracion `[mounts]` del agente.
+#[derive(Debug, thiserror::Error)]
+pub enum MountPolicyError {
+    #[error("mounts[{index}].source: {path} no esta dentro de mounts.bind_roots")]
+    SourceNotAllowed { index: usize, path: String },
+    #[error("mounts[{index}].read_only: los binds son de solo lectura")]
+    WritableBind { index: usize },
+}
+
+impl From<&MountPolicyError> for ApiError {
+    fn from(err: &MountPolicyError) -> Self {
+        match err {
+            MountPolicyError::SourceNotAllowed { index, path } => {
+                ApiError::invalid_field(&format!("mounts[{index}].source"), "not_allowed", path)
+            }
+            MountPolicyError::WritableBind { index } => {
+                ApiError::invalid_field(&format!("mounts[{index}].read_only"), "not_allowed", false)
+            }
+        }
+    }
+}
+
+/// Exige que cada bind salga de un directorio de `bind_roots` y que sea de solo lectura
+/// salvo que la configuracion permita escribir en ellos.
+async fn check_bind_mounts(config: &MountsConfig, mounts: &[MountSpec]) -> Result<()> {
+    let mut roots = Vec::with_capacity(config.bind_roots.len());
+    for root in &config.bind_roots {
+        if let Ok(root) = fs::canonicalize(root).await {
+            roots.push(root);
+        }
+    }
+    for (index, mount) in mounts.iter().enumerate() {
+        let Some(source) = &mount.source else {
+            continue;
+        };
+        let allowed = fs::canonicalize(source)
+            .await
+            .is_ok_and(|source| roots.iter().any(|root| source.starts_with(root)));
+        if !allowed {
+            return Err(MountPolicyError::SourceNotAllowed {
+                index,
+                path: source.clone(),
+            }
+            .into());
+        }
+        if !mount.read_only && !config.writable_binds {
+            return Err(MountPolicyError::WritableBind { index }.into());
+        }
+    }
+    Ok(())
+}
+
+/// Aplica `[mounts]` a los montajes guardados, que pueden ser anteriores a la
+/// configuracion actual: descarta los binds fuera de `bind_roots` y fuerza solo lectura.
+fn effective_mounts(config: &MountsConfig, mounts: &[MountSpec]) -> Vec<MountSpec> {
+    let roots = config
+        .bind_roots
+        .iter()
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .collect::<Vec<_>>();
+    mounts
+        .iter()
+        .filter(|mount| {
+            mount.source.as_ref().is_none_or(|source| {
+                std::fs::canonicalize(source)
+                    .is_ok_and(|source| roots.iter().any(|root| source.starts_with(root)))
+            })
+        })
+        .map(|mount| {
+            let mut mount = mount.clone();
+            mount.read_only |= mount.source.is_some() && !config.writable_binds;
+            mount
+        })
+        .collect()
+}
+
+fn mb_to_bytes(mb: u64) -> u64 {
+    mb.saturating_mul(1024 * 1024)
+}
+
+/// Resuelve `path` dentro del contenedor y exige que exista.
+async fn resolve_existing(layer: &FsLayer, path: &str) -> Result<MappedPath> {
+    let mapped = layer
+        .resolve(path)
+        .await
+        .ok_or_else(|| ContainerFileError::OutsideContainer(path.to_string()))?;
+    if fs::symlink_metadata(&mapped.physical).await.is_err() {
+        return Err(ContainerFileError::NotFound(path.to_string()).into());
+    }
+    Ok(mapped)
+}
+
+/// Errores al acceder a archivos del contenedor que los handlers traducen a respuestas concretas.
+#[derive(Debug, thiserror::Error)]
+pub enum ContainerFileError {
+    #[error("'{0}' no existe en el contenedor")]
+    NotFound(String),
+    #[error("'{0}' sale del contenedor")]
+    OutsideContainer(String),
+    #[error("'{0}' no es un directorio")]
+    NotADirectory(String),
+    #[error("'{0}' no es un archivo")]
+    NotAFile(String),
+    #[error("'{0}' esta en un montaje de solo lectura")]
+    ReadOnly(String),
+}
+
+impl From<&ContainerFileError> for ApiError {
+    fn from(err: &ContainerFileError) -> Self {
+        let (code, path) = match err {
+            ContainerFileError::NotFound(path) => {
+                return ApiError::new(ErrorCode::FileNotFound)
+                    .with_details(serde_json::json!({ "path": path }));
+            }
+            ContainerFileError::OutsideContainer(path) => ("not_in_container", path),
+            ContainerFileError::NotADirectory(path) => ("not_a_directory", path),
+            ContainerFileError::NotAFile(path) => ("not_a_file", path),
+            ContainerFileError::ReadOnly(path) => ("read_only", path),
+        };
+        ApiError::invalid_field("path", code, path)
+    }
+}
+
+/// Origen de los binarios de una app.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
+pub enum InstallSource {
+    /// Instalador que se ejecuta dentro del contenedor con `silent_args`.
+    Installer(PathBuf),
+    /// Archivo zip/tar de una app portable que se extrae en `fs/ProgramFiles/<app>`.
+    Archive(PathBuf),
+}
+
+impl InstallSource {
+    pub fn path(&self) -> &Path {
+        match self {
+            InstallSource::Installer(path) | InstallSource::Archive(path) => path,
+        }
+    }
+}
+
+/// Parametros de instalacion de una app dentro de un contenedor. Los campos
+/// explicitos tienen prioridad sobre los metadatos que declare la propia app.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AppInstallSpec {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub vendor: Option<String>,
+    pub source: Option<InstallSource>,
+    pub silent_args: Option<String>,
+}
+
+impl From<&TemplateApp> for AppInstallSpec {
+    fn from(app: &TemplateApp) -> Self {
+        let trimmed = |value: &Option<String>| value.as_ref().map(|value| value.trim().to_string());
+        let source = match (&app.installer_path, &app.archive_path) {
+            (_, Some(archive)) => Some(InstallSource::Archive(PathBuf::from(archive.trim()))),
+            (Some(installer), None) => {
+                Some(InstallSource::Installer(PathBuf::from(installer.trim())))
+            }
+            (None, None) => None,
+        };
+        Self {
+            name: trimmed(&app.name),
+            version: trimmed(&app.version),
+            vendor: trimmed(&app.vendor),
+            source,
+            silent_args: app.silent_args.clone(),
+        }
+    }
+}
+
+/// Nueva version de una app ya instalada; `version` y `silent_args` sustituyen a los actuales.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AppUpdateSpec {
+    pub version: Option<String>,
+    pub source: InstallSource,
+    pub silent_args: Option<String>,
+}
+
+/// El contenedor esta en un estado desde el que no puede arrancarse.
+#[derive(Debug, thiserror::Error)]
+#[error("El contenedor esta en estado '{status}' y no admite la operacion")]
+pub struct ContainerNotReadyError {
+    pub status: &'static str,
+}
+
+impl From<&ContainerNotReadyError> for ApiError {
+    fn from(err: &ContainerNotReadyError) -> Self {
+        ApiError::new(ErrorCode::ContainerNotReady)
+            .with_details(serde_json::json!({ "status": err.status }))
+    }
+}
+
+/// La app esta en un estado que no admite la operacion pedida.
+#[derive(Debug, thiserror::Error)]
+#[error("La app esta en estado '{status}' y no admite la operacion")]
+pub struct AppBusyError {
+    pub status: &'static str,
+}
+
+impl From<&AppBusyError> for ApiError {
+    fn from(err: &AppBusyError) -> Self {
+        ApiError::new(ErrorCode::AppBusy).with_details(serde_json::json!({ "status": err.status }))
+    }
+}
+
+/// Datos de un entry point nuevo; `id` se deriva de `label` si no se indica.
+#[derive(Clone, Default)]
+pub struct EntryPointSpec {
+    pub id: Option<String>,
+    pub label: String,
+    pub command: String,
+    pub args: Option<String>,
+    pub working_dir: Option<String>,
+    pub icon: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+/// Cambios parciales sobre un entry point; `None` conserva el valor actual.
+#[derive(Clone, Default)]
+pub struct EntryPointUpdate {
+    pub label: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<String>,
+    pub working_dir: Option<String>,
+    pub icon: Option<String>,
+    pub env: Option<BTreeMap<String, String>>,
+}
+
+/// Errores de entry points que los handlers traducen a respuestas concretas.
+#[derive(Debug, thiserror::Error)]
+pub enum EntryPointError {
+    #[error("Entry point '{0}' no encontrado")]
+    NotFound(String),
+    #[error("Ya existe un entry point '{0}'")]
+    Duplicate(String),
+    #[error("Una app lista debe conservar al menos un entry point")]
+    LastOfReadyApp,
+    #[error("'{value}' no existe dentro del contenedor")]
+    Unresolved { field: &'static str, value: String },
+}
+
+impl From<&EntryPointError> for ApiError {
+    fn from(err: &EntryPointError) -> Self {
+        match err {
+            EntryPointError::NotFound(id) => ApiError::new(ErrorCode::EntryPointNotFound)
+                .with_details(serde_json::json!({ "entry_point_id": id })),
+            EntryPointError::Duplicate(id) => ApiError::new(ErrorCode::EntryPointConflict)
+                .with_details(serde_json::json!({ "entry_point_id": id })),
+            EntryPointError::LastOfReadyApp => ApiError::new(ErrorCode::EntryPointRequired),
+            EntryPointError::Unresolved { field, value } => {
+                ApiError::invalid_field(field, "not_in_container", value)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AppService {
+    events: EventHub,
+    store: SqliteStore,
+    snapshots: SnapshotService,
+    tasks: Arc<TasksConfig>,
+    retries: RetryQueue,
+    mutex: Arc<Mutex<()>>,
+}
+
+impl AppService {
+    pub fn new(events: EventHub, store: SqliteStore) -> Self {
+        Self {
+            snapshots: SnapshotService::new(events.clone(), store.clone()),
+            events,
+            store,
+            tasks: Arc::default(),
+            retries: RetryQueue::default(),
+            mutex: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Aplica las politicas de reintento de `tasks` a las tareas que se creen desde ahora
+    /// y avisa a `retries` de las que fallen.
+    pub fn with_retry(mut self, tasks: &TasksConfig, retries: RetryQueue) -> Self {
+        self.snapshots = self.snapshots.with_retry(tasks, retries.clone());
+        self.tasks = Arc::new(tasks.clone());
+        self.retries = retries;
+        self
+    }
+
+    pub async fn list(&self, container_id: Uuid) -> Result<Vec<AppInstance>> {
+        self.store.list_apps(container_id).await
+    }
+
+    /// Registra la app y, si hay instalador, lanza el pipeline en segundo plano.
+    /// Devuelve `None` si el contenedor no existe.
+    pub async fn install(
+        &self,
+        container_id: Uuid,
+        spec: AppInstallSpec,
+    ) -> Result<Option<TaskModel>> {
+        self.start_install(container_id, spec, TaskModel::new("app.install"))
+            .await
+    }
+
+    /// Como [`AppService::install`], pero como paso `step` de otra tarea: espera a que
+    /// termine el pipeline y devuelve la subtarea ya cerrada.
+    pub async fn install_and_wait(
+        &self,
+        container_id: Uuid,
+        spec: AppInstallSpec,
+        step: TaskStep,
+    ) -> Result<Option<TaskModel>> {
+        let task = TaskModel::new("app.install").with_step(step);
+        self.start_install(container_id, spec, task).await
+    }
+
+    /// Las tareas de primer nivel corren el pipeline en segundo plano; las subtareas lo
+    /// esperan.
+    async fn start_install(
+        &self,
+        container_id: Uuid,
+        spec: AppInstallSpec,
+        task: TaskModel,
+    ) -> Result<Option<TaskModel>> {
+        let Some(container) = self.store.get_container(container_id).await? else {
+            return Ok(None);
+        };
+
+        let inline = task.parent_task_id.is_some();
+        let mut task = self.open_task(task, "Iniciando instalacion").await?;
+        let requested = spec.clone();
+
+        let AppInstallSpec {
+            name,
+            version,
+            vendor,
+            source,
+            silent_args,
+        } = spec;
+        let name_given = name.is_some();
+        let name = name
+            .or_else(|| {
+                source
+                    .as_ref()
+                    .and_then(|source| source_stem(source.path()))
+            })
+            .unwrap_or_else(|| "Aplicacion".into());
+        let mut app = AppInstance::new(container_id, name, version);
+        app.vendor = vendor;
+        app.silent_args = silent_args;
+        task.payload = TaskRequest::AppInstall {
+            container_id,
+            app_id: app.id,
+            spec: requested,
+        }
+        .to_payload();
+
+        let Some(source) = source else {
+            // Sin instalador solo se registra la app (p.e. binarios ya copiados al contenedor);
+            // queda deshabilitada hasta que se le agregue un entry point.
+            app.status = AppStatus::Disabled;
+            app.touch();
+            self.store.insert_app(&app).await?;
+            self.succeed_task(
+                &mut task,
+                "Aplicacion registrada; agregue un entry point para habilitarla",
+            )
+            .await?;
+            return Ok(Some(task));
+        };
+
+        app.install_source = Some(source.path().display().to_string());
+        app.status = AppStatus::Installing;
+        app.touch();
+        self.store.insert_app(&app).await?;
+        self.emit_app_status(&app);
+
+        if inline {
+            return Ok(Some(
+                self.run_install(task, app, container, source, name_given)
+                    .await,
+            ));
+        }
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service
+                .run_install(job, app, container, source, name_given)
+                .await;
+        });
+
+        Ok(Some(task))
+    }
+
+    async fn run_install(
+        &self,
+        mut task: TaskModel,
+        mut app: AppInstance,
+        container: ContainerModel,
+        source: InstallSource,
+        name_given: bool,
+    ) -> TaskModel {
+        if let Err(err) = self
+            .execute_install(&mut task, &mut app, &container, &source, name_given)
+            .await
+        {
+            tracing::error!(?err, app_id = %app.id, "Fallo la instalacion de la aplicacion");
+            app.status = AppStatus::Failed;
+            app.touch();
+            if let Err(err) = self.store.insert_app(&app).await {
+                tracing::error!(?err, app_id = %app.id, "No se pudo persistir el estado de la app");
+            }
+            self.emit_app_status(&app);
+            self.fail_task(&mut task, format!("Instalacion fallida: {err:#}"))
+                .await;
+        }
+        task
+    }
+
+    async fn execute_install(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        container: &ContainerModel,
+        source: &InstallSource,
+        name_given: bool,
+    ) -> Result<()> {
+        anyhow::ensure!(
+            !container.storage_path.is_empty(),
+            "El contenedor {} no tiene storage_path",
+            container.id
+        );
+        let started = Instant::now();
+        let fs_layer = FsLayer::new(&container.storage_path);
+        self.record_source_hash(task, app, source).await?;
+        let before = fs_layer.inventory().await?;
+
+        match source {
+            InstallSource::Installer(installer) => {
+                self.run_installer(task, app, container, &fs_layer, installer)
+                    .await?
+            }
+            InstallSource::Archive(archive) => {
+                let target = portable_target(&fs_layer, app);
+                self.extract_portable(task, app, &fs_layer, archive, &target, name_given)
+                    .await?
+            }
+        }
+
+        self.report(task, 80, "Detectando entry points").await?;
+        let after = fs_layer.inventory().await?;
+        let detected = discover_installation(&before.executables(), &after.executables());
+        let mut files = AppFileManifest::empty(app.id);
+        files
+            .apply(&fs_layer.fs_root(), &after.changes_since(&before))
+            .await?;
+        app_files::save(Path::new(&container.storage_path), &files).await?;
+        app.size_bytes = files.total_bytes;
+        app.install_duration_ms = Some(started.elapsed().as_millis() as u64);
+        app.entry_points = detected.entry_points;
+        app.uninstaller = detected.uninstaller;
+        app.install_dir = app.install_dir.take().or(detected.install_dir);
+        // `ready` exige al menos un entry point; sin ejecutables nuevos la app queda deshabilitada.
+        let message = if app.entry_points.is_empty() {
+            app.status = AppStatus::Disabled;
+            "Aplicacion instalada sin entry points; queda deshabilitada"
+        } else {
+            app.status = AppStatus::Ready;
+            "Aplicacion instalada"
+        };
+        app.touch();
+        self.store.insert_app(app).await?;
+        self.emit_app_status(app);
+        self.succeed_task(task, message).await
+    }
+
+    /// Instala una version nueva sobre la actual. Antes se captura un snapshot del
+    /// contenedor y, si algo falla, se restaura junto con el registro previo de la app.
+    pub async fn update(&self, app_id: Uuid, spec: AppUpdateSpec) -> Result<Option<TaskModel>> {
+        self.start_update(app_id, spec, TaskModel::new("app.update"))
+            .await
+    }
+
+    /// Como [`AppService::update`], pero como paso `step` de otra tarea; devuelve la
+    /// subtarea ya cerrada.
+    pub async fn update_and_wait(
+        &self,
+        app_id: Uuid,
+        spec: AppUpdateSpec,
+        step: TaskStep,
+    ) -> Result<Option<TaskModel>> {
+        let task = TaskModel::new("app.update").with_step(step);
+        self.start_update(app_id, spec, task).await
+    }
+
+    async fn start_update(
+        &self,
+        app_id: Uuid,
+        spec: AppUpdateSpec,
+        mut task: TaskModel,
+    ) -> Result<Option<TaskModel>> {
+        let guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        ensure_app_state(&app, &[AppStatus::Ready, AppStatus::Disabled])?;
+        let container = self.app_container(&app).await?;
+        let previous = app.clone();
+
+        let inline = task.parent_task_id.is_some();
+        task.payload = TaskRequest::AppUpdate {
+            app_id,
+            spec: spec.clone(),
+        }
+        .to_payload();
+        let task = self.open_task(task, "Iniciando actualizacion").await?;
+        if spec.version.is_some() {
+            app.version = spec.version;
+        }
+        if spec.silent_args.is_some() {
+            app.silent_args = spec.silent_args;
+        }
+        app.install_source = Some(spec.source.path().display().to_string());
+        // Lo fija `run_installer` con la copia nueva; una actualizacion portable lo limpia.
+        app.installer_path = None;
+        app.status = AppStatus::Installing;
+        app.touch();
+        self.store.insert_app(&app).await?;
+        self.emit_app_status(&app);
+        drop(guard);
+
+        if inline {
+            return Ok(Some(
+                self.run_update(task, app, previous, container, spec.source)
+                    .await,
+            ));
+        }
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service
+                .run_update(job, app, previous, container, spec.source)
+                .await;
+        });
+        Ok(Some(task))
+    }
+
+    async fn run_update(
+        &self,
+        mut task: TaskModel,
+        mut app: AppInstance,
+        mut previous: AppInstance,
+        container: ContainerModel,
+        source: InstallSource,
+    ) -> TaskModel {
+        let label = format!("pre-update {}", app.name);
+        let snapshot = match self.report(&mut task, 10, "Creando snapshot previo").await {
+            Ok(()) => {
+                self.snapshots
+                    .capture(&container, Some(label), SnapshotType::Full)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        let snapshot = match snapshot {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                tracing::error!(?err, app_id = %app.id, "No se pudo crear el snapshot previo");
+                self.restore_app(&previous).await;
+                self.fail_task(
+                    &mut task,
+                    format!("Actualizacion cancelada; no se pudo crear el snapshot: {err:#}"),
+                )
+                .await;
+                return task;
+            }
+        };
+
+        let Err(err) = self
+            .execute_update(&mut task, &mut app, &previous, &container, &source)
+            .await
+        else {
+            return task;
+        };
+        tracing::error!(?err, app_id = %app.id, "Fallo la actualizacion; restaurando snapshot");
+        let message = match self.snapshots.restore_files(&snapshot).await {
+            Ok(()) => format!(
+                "Actualizacion fallida; se restauro el snapshot {}: {err:#}",
+                snapshot.id
+            ),
+            Err(restore_err) => {
+                tracing::error!(?restore_err, snapshot_id = %snapshot.id, "No se pudo restaurar");
+                format!(
+                    "Actualizacion fallida y no se pudo restaurar el snapshot {}: {err:#}; {restore_err:#}",
+                    snapshot.id
+                )
+            }
+        };
+        // Se conserva el diagnostico del intento fallido sobre el registro restaurado.
+        previous.exit_code = app.exit_code;
+        previous.log_path = app.log_path.clone();
+        self.restore_app(&previous).await;
+        self.fail_task(&mut task, message).await;
+        task
+    }
+
+    async fn execute_update(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        previous: &AppInstance,
+        container: &ContainerModel,
+        source: &InstallSource,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let fs_layer = FsLayer::new(&container.storage_path);
+        self.record_source_hash(task, app, source).await?;
+        let before = fs_layer.inventory().await?;
+        match source {
+            InstallSource::Installer(installer) => {
+                self.run_installer(task, app, container, &fs_layer, installer)
+                    .await?
+            }
+            InstallSource::Archive(archive) => {
+                self.replace_portable(task, app, container, &fs_layer, archive)
+                    .await?
+            }
+        }
+
+        self.report(task, 80, "Detectando entry points").await?;
+        let after = fs_layer.inventory().await?;
+        let detected = discover_installation(&before.executables(), &after.executables());
+        let root = Path::new(&container.storage_path);
+        let mut files = app_files::load(root, app.id)
+            .await?
+            .unwrap_or_else(|| AppFileManifest::empty(app.id));
+        files
+            .apply(&fs_layer.fs_root(), &after.changes_since(&before))
+            .await?;
+        app_files::save(root, &files).await?;
+        app.size_bytes = files.total_bytes;
+        app.install_duration_ms = Some(started.elapsed().as_millis() as u64);
+        for mut entry in detected.entry_points {
+            if app
+                .entry_points
+                .iter()
+                .any(|existing| existing.command == entry.command)
+            {
+                continue;
+            }
+            while app
+                .entry_points
+                .iter()
+                .any(|existing| existing.id == entry.id)
+            {
+                entry.id.push('_');
+            }
+            app.entry_points.push(entry);
+        }
+        app.uninstaller = detected.uninstaller.or(app.uninstaller.take());
+        app.install_dir = app.install_dir.take().or(detected.install_dir);
+
+        // Una app deshabilitada a proposito sigue asi tras actualizarse.
+        let keep_disabled =
+            matches!(previous.status, AppStatus::Disabled) && !previous.entry_points.is_empty();
+        app.status = if keep_disabled || app.entry_points.is_empty() {
+            AppStatus::Disabled
+        } else {
+            AppStatus::Ready
+        };
+        app.touch();
+        self.store.insert_app(app).await?;
+        self.emit_app_status(app);
+        self.succeed_task(task, "Aplicacion actualizada").await
+    }
+
+    /// Ejecuta el desinstalador registrado (si lo hay), elimina la carpeta de
+    /// instalacion y borra la app.
+    pub async fn uninstall(&self, app_id: Uuid) -> Result<Option<TaskModel>> {
+        self.start_uninstall(app_id, TaskModel::new("app.uninstall"))
+            .await
+    }
+
+    /// Como [`AppService::uninstall`], pero como paso `step` de otra tarea; devuelve la
+    /// subtarea ya cerrada.
+    pub async fn uninstall_and_wait(
+        &self,
+        app_id: Uuid,
+        step: TaskStep,
+    ) -> Result<Option<TaskModel>> {
+        let task = TaskModel::new("app.uninstall").with_step(step);
+        self.start_uninstall(app_id, task).await
+    }
+
+    async fn start_uninstall(
+        &self,
+        app_id: Uuid,
+        mut task: TaskModel,
+    ) -> Result<Option<TaskModel>> {
+        let guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        ensure_app_state(
+            &app,
+            &[AppStatus::Ready, AppStatus::Disabled, AppStatus::Failed],
+        )?;
+        let container = self.app_container(&app).await?;
+        let previous = app.status.clone();
+
+        let inline = task.parent_task_id.is_some();
+        task.payload = TaskRequest::AppUninstall { app_id }.to_payload();
+        let task = self.open_task(task, "Iniciando desinstalacion").await?;
+        app.status = AppStatus::Uninstalling;
+        app.touch();
+        self.store.insert_app(&app).await?;
+        self.emit_app_status(&app);
+        drop(guard);
+
+        if inline {
+            return Ok(Some(
+                self.run_uninstall(task, app, previous, container).await,
+            ));
+        }
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service.run_uninstall(job, app, previous, container).await;
+        });
+        Ok(Some(task))
+    }
+
+    async fn run_uninstall(
+        &self,
+        mut task: TaskModel,
+        mut app: AppInstance,
+        previous: AppStatus,
+        container: ContainerModel,
+    ) -> TaskModel {
+        if let Err(err) = self
+            .execute_uninstall(&mut task, &mut app, &container)
+            .await
+        {
+            tracing::error!(?err, app_id = %app.id, "Fallo la desinstalacion de la aplicacion");
+            app.status = previous;
+            self.restore_app(&app).await;
+            self.fail_task(&mut task, format!("Desinstalacion fallida: {err:#}"))
+                .await;
+        }
+        task
+    }
+
+    async fn execute_uninstall(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        container: &ContainerModel,
+    ) -> Result<()> {
+        let root = PathBuf::from(&container.storage_path);
+        let fs_layer = FsLayer::new(&root);
+        let fs_root = fs_layer.fs_root();
+
+        if let Some(uninstaller) = app.uninstaller.as_deref()
+            && let Some(program) = resolve_virtual_path(&fs_root, uninstaller)
+            && fs::metadata(&program).await.is_ok()
+        {
+            self.report(task, 20, "Ejecutando desinstalador").await?;
+            let log_path = root.join("logs").join(format!("uninstall-{}.log", app.id));
+            app.log_path = Some(log_path.display().to_string());
+            let outcome = self
+                .run_in_container(
+                    container,
+                    &fs_layer,
+                    &program,
+                    &uninstall_args(uninstaller),
+                    &log_path,
+                )
+                .await?;
+            app.exit_code = outcome.exit_code;
+            ensure_outcome("el desinstalador", &outcome, &log_path)?;
+        }
+
+        self.report(task, 60, "Eliminando archivos de la aplicacion")
+            .await?;
+        // Solo se borra lo que la app creo segun su manifest; las carpetas que quedan
+        // vacias se podan y lo demas (datos del usuario, archivos compartidos) se conserva.
+        match app_files::load(&root, app.id).await? {
+            Some(files) => {
+                for file in files.owned() {
+                    remove_owned_file(&fs_root, &file.relative_path).await?;
+                }
+            }
+            None => tracing::warn!(
+                app_id = %app.id,
+                "La app no tiene manifest de archivos; no se elimina ningun archivo"
+            ),
+        }
+
+        app_files::remove(&root, app.id).await?;
+        self.store.delete_app(app.id).await?;
+        self.events.emit(AgentEvent::AppStatus {
+            app_id: app.id,
+            container_id: app.container_id,
+            status: "uninstalled".into(),
+        });
+        self.succeed_task(task, "Aplicacion desinstalada").await
+    }
+
+    /// Habilita o deshabilita la app; habilitarla exige al menos un entry point.
+    pub async fn set_enabled(&self, app_id: Uuid, enabled: bool) -> Result<Option<TaskModel>> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        ensure_app_state(&app, &[AppStatus::Ready, AppStatus::Disabled])?;
+        if enabled && app.entry_points.is_empty() {
+            return Err(EntryPointError::LastOfReadyApp.into());
+        }
+
+        let (task_type, message) = if enabled {
+            ("app.enable", "Aplicacion habilitada")
+        } else {
+            ("app.disable", "Aplicacion deshabilitada")
+        };
+        let mut task = self.start_task(task_type, "Actualizando estado").await?;
+        app.status = if enabled {
+            AppStatus::Ready
+        } else {
+            AppStatus::Disabled
+        };
+        app.touch();
+        self.store.insert_app(&app).await?;
+        self.emit_app_status(&app);
+        self.succeed_task(&mut task, message).await?;
+        Ok(Some(task))
+    }
+
+    async fn run_installer(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        container: &ContainerModel,
+        fs_layer: &FsLayer,
+        installer: &Path,
+    ) -> Result<()> {
+        let root = PathBuf::from(&container.storage_path);
+        self.report(task, 15, "Copiando instalador al contenedor")
+            .await?;
+        let staged = stage_installer(&root, app.id, installer).await?;
+        app.installer_path = Some(staged.display().to_string());
+
+        self.report(task, 30, "Ejecutando instalador").await?;
+        let log_path = root.join("logs").join(format!("install-{}.log", app.id));
+        app.log_path = Some(log_path.display().to_string());
+        let args = app
+            .silent_args
+            .as_deref()
+            .map(split_args)
+            .unwrap_or_default();
+        let outcome = self
+            .run_in_container(container, fs_layer, &staged, &args, &log_path)
+            .await?;
+        app.exit_code = outcome.exit_code;
+        ensure_outcome("el instalador", &outcome, &log_path)
+    }
+
+    /// Ejecuta `program` con el entorno del contenedor y `ORBIT_FS_ROOT`.
+    async fn run_in_container(
+        &self,
+        container: &ContainerModel,
+        fs_layer: &FsLayer,
+        program: &Path,
+        args: &[String],
+        log_path: &Path,
+    ) -> Result<ProcessOutcome> {
+        let launcher = ProcessLauncher::new(Path::new(&container.storage_path).join("runtime"));
+        let mut env = launcher.runtime_env(container.id).env;
+        env.extend(container.settings.env.clone());
+        env.insert(
+            "ORBIT_FS_ROOT".into(),
+            fs_layer.fs_root().display().to_string(),
+        );
+        launcher
+            .run(program, args, &env, log_path, INSTALL_TIMEOUT)
+            .await
+    }
+
+    /// Sustituye la carpeta de una app portable por el contenido del archivo nuevo.
+    async fn replace_portable(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        container: &ContainerModel,
+        fs_layer: &FsLayer,
+        archive: &Path,
+    ) -> Result<()> {
+        // Se reemplaza la carpeta registrada al instalar, resuelta con el mapper del
+        // contenedor; nunca se deriva del nombre, que pudo cambiar desde entonces.
+        let fs_root = fs_layer.fs_root();
+        let layer = fs_layer
+            .clone()
+            .with_settings(&container.platform, &container.settings);
+        let target = app
+            .install_dir
+            .as_deref()
+            .and_then(|dir| layer.mapper().to_physical(dir).ok())
+            .map(|mapped| mapped.physical)
+            .filter(|dir| {
+                dir.strip_prefix(&fs_root)
+                    .is_ok_and(|relative| relative.components().count() >= 2)
+            })
+            .with_context(|| {
+                format!(
+                    "La app portable no tiene una install_dir valida: {}",
+                    app.install_dir.as_deref().unwrap_or("-")
+                )
+            })?;
+        if fs::metadata(&target).await.is_ok() {
+            fs::remove_dir_all(&target)
+                .await
+                .with_context(|| format!("No se pudo eliminar {}", target.display()))?;
+        }
+        self.extract_portable(task, app, fs_layer, archive, &target, true)
+            .await
+    }
+
+    async fn extract_portable(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        fs_layer: &FsLayer,
+        archive: &Path,
+        target: &Path,
+        name_given: bool,
+    ) -> Result<()> {
+        let target = target.to_path_buf();
+        if dir_has_entries(&target).await {
+            anyhow::bail!("{} ya existe en el contenedor", target.display());
+        }
+
+        self.report(task, 20, "Extrayendo archivo").await?;
+        let source = archive.to_path_buf();
+        let dest = target.clone();
+        let extracted =
+            tokio::task::spawn_blocking(move || archive::extract(&source, &dest)).await?;
+        let summary = match extracted {
+            Ok(summary) => summary,
+            Err(err) => {
+                if fs::metadata(&target).await.is_ok()
+                    && let Err(cleanup) = fs::remove_dir_all(&target).await
+                {
+                    tracing::warn!(
+                        ?cleanup,
+                        ?target,
+                        "No se pudo limpiar la extraccion parcial"
+                    );
+                }
+                return Err(err.context(format!("No se pudo extraer {}", archive.display())));
+            }
+        };
+        tracing::info!(
+            app_id = %app.id,
+            files = summary.files,
+            bytes = summary.bytes,
+            "Archivo portable extraido"
+        );
+
+        if let Ok(relative) = target.strip_prefix(fs_layer.fs_root()) {
+            app.install_dir = Some(virtual_path(relative));
+        }
+        let metadata = archive::read_app_metadata(&target);
+        if !name_given && let Some(name) = metadata.name {
+            app.name = name;
+        }
+        app.version = app.version.take().or(metadata.version);
+        app.vendor = app.vendor.take().or(metadata.vendor);
+        Ok(())
+    }
+
+    /// Informa el avance de la tarea. En las agrupadas `progress` se ignora y se usa el
+    /// calculado con sus subtareas.
+    async fn report(&self, task: &mut TaskModel, progress: u8, message: &str) -> Result<()> {
+        let progress = if task.total_steps.is_some() {
+            task.aggregate_progress(&self.store.list_task_children(task.id).await?)
+        } else {
+            progress
+        };
+        task.set_progress(progress, Some(message.to_string()));
+        self.store.upsert_task(task).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: task.progress,
+            message: message.to_string(),
+        });
+        self.sync_parent(task).await;
+        Ok(())
+    }
+
+    /// Registra el SHA-256 del instalador o archivo antes de usarlo.
+    async fn record_source_hash(
+        &self,
+        task: &mut TaskModel,
+        app: &mut AppInstance,
+        source: &InstallSource,
+    ) -> Result<()> {
+        self.report(task, 10, "Calculando SHA-256 del instalador")
+            .await?;
+        let path = source.path().to_path_buf();
+        let hash = tokio::task::spawn_blocking(move || sha256_file(&path))
+            .await?
+            .with_context(|| format!("No se pudo leer {}", source.path().display()))?;
+        tracing::info!(app_id = %app.id, sha256 = %hash, source = %source.path().display(), "Instalador verificado");
+        app.installer_sha256 = Some(hash);
+        Ok(())
+    }
+
+    async fn start_task(&self, task_type: &str, message: &str) -> Result<TaskModel> {
+        self.open_task(TaskModel::new(task_type), message).await
+    }
+
+    /// Registra `task` como en curso; admite subtareas, tareas agrupadas y reintentos.
+    async fn open_task(&self, task: TaskModel, message: &str) -> Result<TaskModel> {
+        let mut task = task.with_status(TaskStatus::Running);
+        apply_retry_policy(&self.tasks, &mut task);
+        task.set_progress(5, Some(message.into()));
+        self.store.upsert_task(&task).await?;
+        self.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+        self.sync_parent(&task).await;
+        Ok(task)
+    }
+
+    async fn succeed_task(&self, task: &mut TaskModel, message: &str) -> Result<()> {
+        task.status = TaskStatus::Succeeded;
+        task.set_progress(100, Some(message.into()));
+        self.store.upsert_task(task).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message: message.into(),
+        });
+        self.sync_parent(task).await;
+        Ok(())
+    }
+
+    /// Cierra la tarea como fallida; los errores de persistencia solo se registran.
+    async fn fail_task(&self, task: &mut TaskModel, message: String) {
+        task.status = TaskStatus::Failed;
+        task.set_progress(100, Some(message.clone()));
+        if let Err(err) = self.store.upsert_task(task).await {
+            tracing::error!(?err, task_id = %task.id, "No se pudo persistir la tarea");
+        }
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message,
+        });
+        self.retries.failed(task);
+        self.sync_parent(task).await;
+    }
+
+    /// Ejecuta `work` como la subtarea `task_type` del paso `step` y la cierra segun su
+    /// resultado. Devuelve la subtarea junto con el resultado de `work`.
+    async fn run_step<T>(
+        &self,
+        task_type: &str,
+        message: &str,
+        step: TaskStep,
+        work: impl Future<Output = Result<T>>,
+    ) -> Result<(TaskModel, Result<T>)> {
+        let mut task = self
+            .open_task(TaskModel::new(task_type).with_step(step), message)
+            .await?;
+        let outcome = work.await;
+        match &outcome {
+            Ok(_) => self.succeed_task(&mut task, "Paso completado").await?,
+            Err(err) => self.fail_task(&mut task, format!("{err:#}")).await,
+        }
+        Ok((task, outcome))
+    }
+
+    /// Propaga el cambio de una subtarea hacia arriba: recalcula el progreso de cada
+    /// tarea padre en curso y la marca como fallida si fallo un paso obligatorio. Los
+    /// errores solo se registran; la subtarea ya quedo persistida.
+    async fn sync_parent(&self, task: &TaskModel) {
+        let mut child = task.clone();
+        while let Some(parent_id) = child.parent_task_id {
+            match self.update_parent(parent_id, &child).await {
+                Ok(Some(parent)) => child = parent,
+                Ok(None) => break,
+                Err(err) => {
+                    tracing::warn!(?err, task_id = %parent_id, "No se pudo actualizar la tarea padre");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn update_parent(&self, parent_id: Uuid, child: &TaskModel) -> Result<Option<TaskModel>> {
+        let Some(mut parent) = self.store.get_task(parent_id).await? else {
+            return Ok(None);
+        };
+        if parent.is_finished() {
+            return Ok(None);
+        }
+        let children = self.store.list_task_children(parent_id).await?;
+        if child.required && matches!(child.status, TaskStatus::Failed | TaskStatus::Cancelled) {
+            parent.status = TaskStatus::Failed;
+            parent.set_progress(
+                100,
+                Some(format!(
+                    "Fallo el paso {} ({}): {}",
+                    child.step.unwrap_or_default(),
+                    child.task_type,
+                    child.message.as_deref().unwrap_or_default()
+                )),
+            );
+        } else {
+            let progress = parent.aggregate_progress(&children);
+            parent.set_progress(progress, parent.message.clone());
+        }
+        self.store.upsert_task(&parent).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: parent.id,
+            progress: parent.progress,
+            message: parent.message.clone().unwrap_or_default(),
+        });
+        self.retries.failed(&parent);
+        Ok(Some(parent))
+    }
+
+    async fn restore_app(&self, app: &AppInstance) {
+        let mut app = app.clone();
+        app.touch();
+        if let Err(err) = self.store.insert_app(&app).await {
+            tracing::error!(?err, app_id = %app.id, "No se pudo persistir el estado de la app");
+        }
+        self.emit_app_status(&app);
+    }
+
+    async fn app_container(&self, app: &AppInstance) -> Result<ContainerModel> {
+        let container = self
+            .store
+            .get_container(app.container_id)
+            .await?
+            .with_context(|| format!("El contenedor {} ya no existe", app.container_id))?;
+        anyhow::ensure!(
+            !container.storage_path.is_empty(),
+            "El contenedor {} no tiene storage_path",
+            container.id
+        );
+        Ok(container)
+    }
+
+    fn emit_app_status(&self, app: &AppInstance) {
+        self.events.emit(AgentEvent::AppStatus {
+            app_id: app.id,
+            container_id: app.container_id,
+            status: app.status.as_str().into(),
+        });
+    }
+
+    /// Manifest de archivos capturados al instalar; vacio si la app solo se registro.
+    pub async fn files(&self, app_id: Uuid) -> Result<Option<AppFileManifest>> {
+        let Some(app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        let container = self.app_container(&app).await?;
+        Ok(Some(
+            app_files::load(Path::new(&container.storage_path), app.id)
+                .await?
+                .unwrap_or_else(|| AppFileManifest::empty(app.id)),
+        ))
+    }
+
+    pub async fn list_entry_points(&self, app_id: Uuid) -> Result<Option<Vec<AppEntryPoint>>> {
+        Ok(self
+            .store
+            .get_app(app_id)
+            .await?
+            .map(|app| app.entry_points))
+    }
+
+    /// Agrega un entry point; el primero de una app deshabilitada la deja lista.
+    pub async fn add_entry_point(
+        &self,
+        app_id: Uuid,
+        spec: EntryPointSpec,
+    ) -> Result<Option<AppEntryPoint>> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        let fs_root = self.container_fs_root(&app).await?;
+
+        let id = spec.id.unwrap_or_else(|| entry_point_slug(&spec.label));
+        if app.entry_points.iter().any(|entry| entry.id == id) {
+            return Err(EntryPointError::Duplicate(id).into());
+        }
+        let entry = AppEntryPoint {
+            id,
+            label: spec.label,
+            command: resolve_in_container(&fs_root, "command", &spec.command, false).await?,
+            args: spec.args,
+            working_dir: match spec.working_dir {
+                Some(dir) => Some(resolve_in_container(&fs_root, "working_dir", &dir, true).await?),
+                None => None,
+            },
+            icon: spec.icon,
+            env: spec.env,
+        };
+
+        app.entry_points.push(entry.clone());
+        if app.entry_points.len() == 1 && matches!(app.status, AppStatus::Disabled) {
+            app.status = AppStatus::Ready;
+            self.emit_app_status(&app);
+        }
+        app.touch();
+        self.store.insert_app(&app).await?;
+        Ok(Some(entry))
+    }
+
+    pub async fn update_entry_point(
+        &self,
+        app_id: Uuid,
+        entry_point_id: &str,
+        update: EntryPointUpdate,
+    ) -> Result<Option<AppEntryPoint>> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        let fs_root = self.container_fs_root(&app).await?;
+        let Some(entry) = app
+            .entry_points
+            .iter_mut()
+            .find(|entry| entry.id == entry_point_id)
+        else {
+            return Err(EntryPointError::NotFound(entry_point_id.to_string()).into());
+        };
+
+        if let Some(label) = update.label {
+            entry.label = label;
+        }
+        if let Some(command) = update.command {
+            entry.command = resolve_in_container(&fs_root, "command", &command, false).await?;
+        }
+        if let Some(args) = update.args {
+            entry.args = Some(args);
+        }
+        if let Some(dir) = update.working_dir {
+            entry.working_dir =
+                Some(resolve_in_container(&fs_root, "working_dir", &dir, true).await?);
+        }
+        if let Some(icon) = update.icon {
+            entry.icon = Some(icon);
+        }
+        if let Some(env) = update.env {
+            entry.env = env;
+        }
+        let entry = entry.clone();
+        app.touch();
+        self.store.insert_app(&app).await?;
+        Ok(Some(entry))
+    }
+
+    /// Elimina un entry point; no permite dejar sin entry points a una app `ready`.
+    pub async fn remove_entry_point(
+        &self,
+        app_id: Uuid,
+        entry_point_id: &str,
+    ) -> Result<Option<()>> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        let Some(index) = app
+            .entry_points
+            .iter()
+            .position(|entry| entry.id == entry_point_id)
+        else {
+            return Err(EntryPointError::NotFound(entry_point_id.to_string()).into());
+        };
+        if app.entry_points.len() == 1 && matches!(app.status, AppStatus::Ready) {
+            return Err(EntryPointError::LastOfReadyApp.into());
+        }
+        app.entry_points.remove(index);
+        app.touch();
+        self.store.insert_app(&app).await?;
+        Ok(Some(()))
+    }
+
+    async fn container_fs_root(&self, app: &AppInstance) -> Result<PathBuf> {
+        let container = self.app_container(app).await?;
+        Ok(FsLayer::new(&container.storage_path).fs_root())
+    }
+
+    pub async fn launch(&self, app_id: Uuid) -> Result<Option<TaskModel>> {
+        let Some(app) = self.store.get_app(app_id).await? else {
+            return Ok(None);
+        };
+        let mut task = TaskModel::new("app.launch").with_status(TaskStatus::Running);
+        task.set_progress(10, Some(format!("Lanzando {}", app.name)));
+        self.store.upsert_task(&task).await?;
+        task.status = TaskStatus::Succeeded;
+        task.set_progress(100, Some("Aplicacion lanzada".into()));
+        self.store.upsert_task(&task).await?;
+        Ok(Some(task))
+    }
+}
+
+/// Borra un archivo de la app y los directorios que queden vacios, sin tocar las
+/// raices del overlay (`ProgramFiles`, `ProgramData`, ...).
+async fn remove_owned_file(fs_root: &Path, relative: &Path) -> Result<()> {
+    let path = fs_root.join(relative);
+    match fs::remove_file(&path).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(
+                anyhow::Error::from(err).context(format!("No se pudo eliminar {}", path.display()))
+            );
+        }
+        _ => {}
+    }
+    let mut dir = relative.parent();
+    while let Some(current) = dir.filter(|dir| dir.components().count() >= 2) {
+        if fs::remove_dir(fs_root.join(current)).await.is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+    Ok(())
+}
+
+/// Fija `max_attempts` en los primeros intentos de las tareas de primer nivel; los
+/// reintentos conservan el de la tarea original.
+fn apply_retry_policy(tasks: &TasksConfig, task: &mut TaskModel) {
+    if task.parent_task_id.is_some() || task.retry_of.is_some() {
+        return;
+    }
+    if let Some(policy) = tasks.policy(&task.task_type) {
+        task.max_attempts = policy.max_attempts.max(1);
+    }
+}
+
+fn ensure_app_state(app: &AppInstance, allowed: &[AppStatus]) -> Result<()> {
+    if allowed
+        .iter()
+        .any(|status| status.as_str() == app.status.as_str())
+    {
+        return Ok(());
+    }
+    Err(AppBusyError {
+        status: app.status.as_str(),
+    }
+    .into())
+}
+
+fn ensure_outcome(what: &str, outcome: &ProcessOutcome, log_path: &Path) -> Result<()> {
+    if outcome.timed_out {
+        anyhow::bail!(
+            "{what} supero {} s; ver {}",
+            INSTALL_TIMEOUT.as_secs(),
+            log_path.display()
+        );
+    }
+    if !outcome.succeeded() {
+        anyhow::bail!(
+            "{what} termino con codigo {}; ver {}",
+            outcome
+                .exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "desconocido".into()),
+            log_path.display()
+        );
+    }
+    Ok(())
+}
+
+fn portable_target(fs_layer: &FsLayer, app: &AppInstance) -> PathBuf {
+    fs_layer
+        .fs_root()
+        .join("ProgramFiles")
+        .join(portable_dir_name(&app.name))
+}
+
+fn source_stem(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_string_lossy();
+    let lowered = name.to_lowercase();
+    let stem = [".tar.gz", ".tgz", ".tar", ".zip", ".exe", ".msi"]
+        .iter()
+        .find_map(|ext| {
+            lowered
+                .ends_with(ext)
+                .then(|| &name[..name.len() - ext.len()])
+        })
+        .unwrap_or(&name);
+    (!stem.is_empty()).then(|| stem.to_string())
+}
+
+async fn dir_has_entries(path: &Path) -> bool {
+    match fs::read_dir(path).await {
+        Ok(mut entries) => matches!(entries.next_entry().await, Ok(Some(_))),
+        Err(_) => false,
+    }
+}
+
+/// Nombre de carpeta valido en Windows para `ProgramFiles/<app>`.
+fn portable_dir_name(name: &str) -> String {
+    let sanitized = name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>();
+    let sanitized = sanitized.trim_matches(|c| c == '.' || c == ' ');
+    if sanitized.is_empty() {
+        "App".into()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+/// Copia el instalador a `runtime/installers/<app_id>/` para ejecutarlo desde el contenedor.
+async fn stage_installer(root: &Path, app_id: Uuid, installer: &Path) -> Result<PathBuf> {
+    let file_name = installer
+        .file_name()
+        .with_context(|| format!("Ruta de instalador invalida: {}", installer.display()))?;
+    let staging = root
+        .join("runtime")
+        .join("installers")
+        .join(app_id.to_string());
+    fs::create_dir_all(&staging).await?;
+    let staged = staging.join(file_name);
+    fs::copy(installer, &staged)
+        .await
+        .with_context(|| format!("No se pudo copiar el instalador {}", installer.display()))?;
+    Ok(staged)
+}
+
+/// Traduce `value` a una ruta dentro de `fs/` y la devuelve en forma virtual canonica.
+/// Rechaza rutas inexistentes, del tipo equivocado o que escapen via enlaces.
+async fn resolve_in_container(
+    fs_root: &Path,
+    field: &'static str,
+    value: &str,
+    directory: bool,
+) -> Result<String> {
+    let unresolved = || EntryPointError::Unresolved {
+        field,
+        value: value.to_string(),
+    };
+    let physical = resolve_virtual_path(fs_root, value).ok_or_else(unresolved)?;
+    let (Ok(canonical), Ok(root)) = (
+        fs::canonicalize(&physical).await,
+        fs::canonicalize(fs_root).await,
+    ) else {
+        return Err(unresolved().into());
+    };
+    let kind_matches = fs::metadata(&canonical)
+        .await
+        .map(|meta| {
+            if directory {
+                meta.is_dir()
+            } else {
+                meta.is_file()
+            }
+        })
+        .unwrap_or(false);
+    match canonical.strip_prefix(&root) {
+        Ok(relative) if kind_matches => Ok(virtual_path(relative)),
+        _ => Err(unresolved().into()),
+    }
+}
+
+fn entry_point_slug(label: &str) -> String {
+    let slug = label
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "main".into()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Lo que deja una instalacion en `fs/`, deducido de los ejecutables nuevos.
+#[derive(Default)]
+struct DetectedInstall {
+    entry_points: Vec<AppEntryPoint>,
+    uninstaller: Option<String>,
+    install_dir: Option<String>,
+}
+
+/// Cada ejecutable nuevo es un entry point salvo los desinstaladores, que se guardan
+/// aparte; la carpeta comun de todos ellos es la carpeta de instalacion.
+fn discover_installation(before: &BTreeSet<PathBuf>, after: &BTreeSet<PathBuf>) -> DetectedInstall {
+    let added = after.difference(before).collect::<Vec<_>>();
+    let mut detected = DetectedInstall {
+        install_dir: common_install_dir(&added).map(|dir| virtual_path(&dir)),
+        ..Default::default()
+    };
+    let mut seen = BTreeSet::new();
+    for relative in added {
+        let Some(stem) = relative
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+        else {
+            continue;
+        };
+        if is_uninstaller(&stem) {
+            detected
+                .uninstaller
+                .get_or_insert_with(|| virtual_path(relative));
+            continue;
+        }
+        let mut id = entry_point_slug(&stem);
+        while !seen.insert(id.clone()) {
+            id.push('_');
+        }
+        detected.entry_points.push(AppEntryPoint {
+            id,
+            label: stem,
+            command: virtual_path(relative),
+            ..Default::default()
+        });
+    }
+    detected
+}
+
+fn is_uninstaller(stem: &str) -> bool {
+    let lowered = stem.to_lowercase();
+    lowered.starts_with("unins") || lowered.contains("uninstall")
+}
+
+/// Directorio comun de `files`; nunca una raiz del overlay como `ProgramFiles`.
+fn common_install_dir(files: &[&PathBuf]) -> Option<PathBuf> {
+    let mut common: Option<PathBuf> = None;
+    for file in files {
+        let parent = file.parent()?;
+        common = Some(match common {
+            None => parent.to_path_buf(),
+            Some(current) => current
+                .components()
+                .zip(parent.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+    common.filter(|dir| dir.components().count() >= 2)
+}
+
+/// Argumentos silenciosos segun el tipo de desinstalador (Inno Setup o NSIS).
+fn uninstall_args(uninstaller: &str) -> Vec<String> {
+    let name = uninstaller
+        .rsplit(['\\', '/'])
+        .next()
+        .unwrap_or(uninstaller)
+        .to_lowercase();
+    let args: &[&str] = if name.starts_with("unins0") {
+        &["/VERYSILENT", "/SUPPRESSMSGBOXES", "/NORESTART"]
+    } else {
+        &["/S"]
+    };
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Plantillas de contenedor y su ejecucion como tarea `container.template`.
+#[derive(Clone)]
+pub struct TemplateService {
+    templates: TemplateStore,
+    containers: ContainerService,
+    apps: AppService,
+}
+
+/// Resultado de la tarea `container.template`; se rellena tambien si falla a medias.
+#[derive(Debug, Clone, Serialize)]
+struct TemplateRun {
+    template: String,
+    container_id: Option<Uuid>,
+    /// Tareas `app.install` lanzadas, en el orden de la plantilla.
+    app_tasks: Vec<Uuid>,
+}
+
+impl TemplateService {
+    pub fn new(config: &AgentConfig, containers: ContainerService, apps: AppService) -> Self {
+        Self {
+            templates: TemplateStore::new(config.data_dir().join("templates")),
+            containers,
+            apps,
+        }
+    }
+
+    pub async fn list(&self) -> Result<Vec<ContainerTemplate>> {
+        self.templates.list().await
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Option<ContainerTemplate>> {
+        self.templates.get(name).await
+    }
+
+    /// Crea o reemplaza la plantilla; devuelve `true` si es nueva.
+    pub async fn save(&self, template: &ContainerTemplate) -> Result<bool> {
+        self.templates.save(template).await
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        self.templates.delete(name).await
+    }
+
+    /// Crea un contenedor a partir de la plantilla: lo prepara con sus ajustes, escribe
+    /// los valores de registro e instala las apps una tras otra. Devuelve `None` si la
+    /// plantilla no existe.
+    pub async fn instantiate(
+        &self,
+        template: &str,
+        name: String,
+        description: Option<String>,
+    ) -> Result<Option<TaskModel>> {
+        let Some(template) = self.templates.get(template).await? else {
+            return Ok(None);
+        };
+        let name = name.trim().to_string();
+        if self.containers.find_by_name(&name).await?.is_some() {
+            return Err(StoreError::DuplicateContainerName(name).into());
+        }
+
+        let steps = 1 + usize::from(!template.registry.is_empty()) + template.apps.len();
+        let task = self
+            .apps
+            .open_task(
+                TaskModel::new("container.template").with_total_steps(steps),
+                "Iniciando plantilla",
+            )
+            .await?;
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service.run_template(job, template, name, description).await;
+        });
+        Ok(Some(task))
+    }
+
+    async fn run_template(
+        &self,
+        mut task: TaskModel,
+        template: ContainerTemplate,
+        name: String,
+        description: Option<String>,
+    ) {
+        let mut run = TemplateRun {
+            template: template.name.clone(),
+            container_id: None,
+            app_tasks: vec![],
+        };
+        if let Err(err) = self
+            .execute_template(&mut task, &template, name, description, &mut run)
+            .await
+        {
+            tracing::error!(?err, template = %template.name, "Fallo la plantilla");
+            task.result = serde_json::to_value(&run).ok();
+            self.apps
+                .fail_task(&mut task, format!("Plantilla fallida: {err:#}"))
+                .await;
+        }
+    }
+
+    async fn execute_template(
+        &self,
+        task: &mut TaskModel,
+        template: &ContainerTemplate,
+        name: String,
+        description: Option<String>,
+        run: &mut TemplateRun,
+    ) -> Result<()> {
+        // Cada paso es una subtarea obligatoria: si falla, la plantilla falla con el.
+        let mut step = TaskStep {
+            parent: task.id,
+            step: 1,
+            required: true,
+        };
+        let provision = self.containers.provision_container(
+            name,
+            template.platform.clone(),
+            description.or_else(|| template.description.clone()),
+            template.tags.clone(),
+            template.settings(),
+        );
+        let (_, container) = self
+            .apps
+            .run_step("container.create", "Creando contenedor", step, provision)
+            .await?;
+        let container = container?;
+        run.container_id = Some(container.id);
+
+        if !template.registry.is_empty() {
+            step.step += 1;
+            let seed = async {
+                RegistryLayer::new(&container.storage_path)
+                    .seed(&template.registry)
+                    .await
+                    .context("No se pudieron escribir los valores de registro")
+            };
+            let (_, seeded) = self
+                .apps
+                .run_step(
+                    "container.registry",
+                    "Escribiendo valores de registro",
+                    step,
+                    seed,
+                )
+                .await?;
+            seeded?;
+        }
+
+        let total = template.apps.len();
+        for (index, app) in template.apps.iter().enumerate() {
+            step.step += 1;
+            let label = app.label();
+            let message = format!("Instalando {label} ({}/{total})", index + 1);
+            let progress = task.progress;
+            self.apps.report(task, progress, &message).await?;
+            self.containers.ensure_quota(container.id).await?;
+            let install = self
+                .apps
+                .install_and_wait(container.id, AppInstallSpec::from(app), step)
+                .await?
+                .context("El contenedor se elimino durante la plantilla")?;
+            run.app_tasks.push(install.id);
+            anyhow::ensure!(
+                matches!(install.status, TaskStatus::Succeeded),
+                "No se pudo instalar {label}: {}",
+                install.message.unwrap_or_default()
+            );
+        }
+
+        task.result = serde_json::to_value(&*run).ok();
+        self.apps
+            .succeed_task(task, "Contenedor creado desde la plantilla")
+            .await
+    }
+}
+
+/// Planifica y aplica un [`DesiredState`] como tarea `container.apply`.
+#[derive(Clone)]
+pub struct ApplyService {
+    store: SqliteStore,
+    containers: ContainerService,
+    apps: AppService,
+}
+
+/// Cambios que llevan el store al estado declarado, en el orden en que se aplican.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplyPlan {
+    pub prune: bool,
+    pub items: Vec<PlanItem>,
+    /// Contenedores que no estan en el documento y se conservan porque no se pidio `prune`.
+    pub unmanaged: Vec<String>,
+    /// Huella de `prune` e `items`. Si el plan elimina algo, `POST /containers/apply`
+    /// solo lo ejecuta cuando recibe la misma huella.
+    pub fingerprint: String,
+}
+
+impl ApplyPlan {
+    fn new(prune: bool, items: Vec<PlanItem>, unmanaged: Vec<String>) -> Result<Self> {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&(prune, &items))?);
+        Ok(Self {
+            prune,
+            items,
+            unmanaged,
+            fingerprint: format!("{:x}", hasher.finalize()),
+        })
+    }
+
+    pub fn deletes(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.action == PlanAction::Delete)
+            .count()
+    }
+
+    /// Exige que `confirmed` sea la huella de este plan si elimina algo; sin borrados
+    /// basta con que no contradiga el plan.
+    fn confirm(&self, confirmed: Option<&str>) -> Result<(), PlanConfirmationError> {
+        let deletes = self.deletes();
+        match confirmed {
+            Some(confirmed) if confirmed != self.fingerprint => {
+                Err(PlanConfirmationError::Changed {
+                    fingerprint: self.fingerprint.clone(),
+                    deletes,
+                })
+            }
+            None if deletes > 0 => Err(PlanConfirmationError::Missing {
+                fingerprint: self.fingerprint.clone(),
+                deletes,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// El plan que se iba a aplicar no es el que se confirmo.
+#[derive(Debug, thiserror::Error)]
+pub enum PlanConfirmationError {
+    #[error("El plan elimina {deletes} elemento(s); hay que confirmarlo con su fingerprint")]
+    Missing { fingerprint: String, deletes: usize },
+    #[error("El plan ha cambiado desde que se confirmo")]
+    Changed { fingerprint: String, deletes: usize },
+}
+
+impl From<&PlanConfirmationError> for ApiError {
+    fn from(err: &PlanConfirmationError) -> Self {
+        let (reason, fingerprint, deletes) = match err {
+            PlanConfirmationError::Missing {
+                fingerprint,
+                deletes,
+            } => ("missing", fingerprint, deletes),
+            PlanConfirmationError::Changed {
+                fingerprint,
+                deletes,
+            } => ("changed", fingerprint, deletes),
+        };
+        ApiError::new(ErrorCode::PlanNotConfirmed).with_details(serde_json::json!({
+            "reason": reason,
+            "fingerprint": fingerprint,
+            "deletes": deletes,
+        }))
+    }
+}
+
+/// Cambio sobre un contenedor o, si lleva `app`, sobre una de sus apps.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanItem {
+    pub action: PlanAction,
+    pub container: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    /// Campos que difieren en un `update`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl PlanItem {
+    fn container(action: PlanAction, container: &str, changes: Vec<&'static str>) -> Self {
+        Self {
+            action,
+            container: container.to_string(),
+            app: None,
+            changes,
+        }
+    }
+
+    fn app(action: PlanAction, container: &str, app: &str, changes: Vec<&'static str>) -> Self {
+        Self {
+            action,
+            container: container.to_string(),
+            app: Some(app.to_string()),
+            changes,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match (&self.app, self.action) {
+            (None, PlanAction::Create) => format!("Creando contenedor {}", self.container),
+            (None, PlanAction::Update) => format!("Actualizando contenedor {}", self.container),
+            (None, PlanAction::Delete) => format!("Eliminando contenedor {}", self.container),
+            (Some(app), PlanAction::Create) => format!("Instalando {app} en {}", self.container),
+            (Some(app), PlanAction::Update) => format!("Actualizando {app} en {}", self.container),
+            (Some(app), PlanAction::Delete) => format!("Desinstalando {app} de {}", self.container),
+        }
+    }
+}
+
+/// El documento pide otra plataforma para un contenedor existente.
+#[derive(Debug, thiserror::Error)]
+#[error("La plataforma de un contenedor existente no se puede cambiar ({current})")]
+pub struct PlatformChangeError {
+    pub field: String,
+    pub current: &'static str,
+}
+
+impl From<&PlatformChangeError> for ApiError {
+    fn from(err: &PlatformChangeError) -> Self {
+        ApiError::invalid_field(&err.field, "immutable", err.current)
+    }
+}
+
+/// Resultado de la tarea `container.apply`: el plan con el desenlace de cada cambio.
+#[derive(Debug, Clone, Serialize)]
+struct ApplyRun {
+    prune: bool,
+    items: Vec<ApplyItemResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ApplyItemResult {
+    #[serde(flatten)]
+    item: PlanItem,
+    status: ApplyItemStatus,
+    /// Tarea propia del cambio (instalaciones, desinstalaciones, borrados).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ApplyItemStatus {
+    Succeeded,
+    Failed,
+    /// No se intento porque fallo el cambio de su contenedor.
+    Skipped,
+}
+
+impl ApplyService {
+    pub fn new(store: SqliteStore, containers: ContainerService, apps: AppService) -> Self {
+        Self {
+            store,
+            containers,
+            apps,
+        }
+    }
+
+    /// Compara el documento con el store. Los contenedores se crean o actualizan en el
+    /// orden del documento, cada uno seguido de sus apps; con `prune` tambien se
+    /// desinstalan las apps no declaradas y se eliminan los contenedores no declarados.
+    pub async fn plan(&self, desired: &DesiredState, prune: bool) -> Result<ApplyPlan> {
+        let containers = self.store.list_containers(None, None).await?;
+        let mut items = Vec::new();
+        for (index, wanted) in desired.containers.iter().enumerate() {
+            let name = wanted.name.trim();
+            let Some(current) = containers
+                .iter()
+                .find(|container| container.name.eq_ignore_ascii_case(name))
+            else {
+                items.push(PlanItem::container(PlanAction::Create, name, vec![]));
+                for app in &wanted.apps {
+                    let app = app.name.as_deref().unwrap_or_default().trim();
+                    items.push(PlanItem::app(PlanAction::Create, name, app, vec![]));
+                }
+                continue;
+            };
+            if current.platform.as_str() != wanted.platform.as_str() {
+                return Err(PlatformChangeError {
+                    field: format!("containers[{index}].platform"),
+                    current: current.platform.as_str(),
+                }
+                .into());
+            }
+
+            let changes = container_changes(current, wanted);
+            if !changes.is_empty() {
+                items.push(PlanItem::container(PlanAction::Update, name, changes));
+            }
+            let apps = self.store.list_apps(current.id).await?;
+            if prune {
+                for app in apps.iter().filter(|app| wanted.app(&app.name).is_none()) {
+                    items.push(PlanItem::app(PlanAction::Delete, name, &app.name, vec![]));
+                }
+            }
+            for app in &wanted.apps {
+                let app_name = app.name.as_deref().unwrap_or_default().trim();
+                let installed = apps
+                    .iter()
+                    .find(|installed| installed.name.eq_ignore_ascii_case(app_name));
+                match installed {
+                    None => items.push(PlanItem::app(PlanAction::Create, name, app_name, vec![])),
+                    // Cambiar de version exige un instalador o archivo con el que actualizar.
+                    Some(installed)
+                        if app_version_changed(installed, app)
+                            && (app.installer_path.is_some() || app.archive_path.is_some()) =>
+                    {
+                        items.push(PlanItem::app(
+                            PlanAction::Update,
+                            name,
+                            app_name,
+                            vec!["version"],
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let mut unmanaged = Vec::new();
+        for container in &containers {
+            if desired.container(&container.name).is_some() {
+                continue;
+            }
+            if prune {
+                items.push(PlanItem::container(
+                    PlanAction::Delete,
+                    &container.name,
+                    vec![],
+                ));
+            } else {
+                unmanaged.push(container.name.clone());
+            }
+        }
+        ApplyPlan::new(prune, items, unmanaged)
+    }
+
+    /// Calcula el plan y lo ejecuta en segundo plano. Un cambio fallido no detiene los
+    /// demas, salvo las apps de un contenedor que no se pudo crear o actualizar. Un plan
+    /// con borrados solo se ejecuta si `fingerprint` es el suyo (ver [`ApplyPlan`]).
+    pub async fn apply(
+        &self,
+        desired: DesiredState,
+        prune: bool,
+        fingerprint: Option<String>,
+    ) -> Result<TaskModel> {
+        self.start_apply(
+            TaskModel::new("container.apply"),
+            desired,
+            prune,
+            fingerprint,
+        )
+        .await
+    }
+
+    async fn start_apply(
+        &self,
+        mut task: TaskModel,
+        desired: DesiredState,
+        prune: bool,
+        fingerprint: Option<String>,
+    ) -> Result<TaskModel> {
+        let plan = self.plan(&desired, prune).await?;
+        plan.confirm(fingerprint.as_deref())?;
+        // Un reintento recalcula el plan y vuelve a exigir la huella confirmada.
+        task.payload = TaskRequest::ContainerApply {
+            desired: desired.clone(),
+            prune,
+            fingerprint: Some(plan.fingerprint.clone()),
+        }
+        .to_payload();
+        let task = self
+            .apps
+            .open_task(
+                task.with_total_steps(plan.items.len()),
+                "Aplicando estado deseado",
+            )
+            .await?;
+        let service = self.clone();
+        let job = task.clone();
+        tokio::spawn(async move {
+            service.run_apply(job, desired, plan).await;
+        });
+        Ok(task)
+    }
+
+    async fn run_apply(&self, mut task: TaskModel, desired: DesiredState, plan: ApplyPlan) {
+        let total = plan.items.len();
+        let mut run = ApplyRun {
+            prune: plan.prune,
+            items: Vec::with_capacity(total),
+        };
+        let mut failed_containers = BTreeSet::new();
+        for (index, item) in plan.items.into_iter().enumerate() {
+            let message = format!("{} ({}/{total})", item.describe(), index + 1);
+            let progress = task.progress;
+            if let Err(err) = self.apps.report(&mut task, progress, &message).await {
+                tracing::warn!(?err, task_id = %task.id, "No se pudo informar el progreso");
+            }
+
+            let key = item.container.to_lowercase();
+            if item.app.is_some() && failed_containers.contains(&key) {
+                run.items.push(ApplyItemResult {
+                    item,
+                    status: ApplyItemStatus::Skipped,
+                    task_id: None,
+                    error: None,
+                });
+                continue;
+            }
+            // Los pasos son opcionales: un cambio fallido no hace fallar a los demas.
+            let step = TaskStep {
+                parent: task.id,
+                step: index as u32 + 1,
+                required: false,
+            };
+            let (status, task_id, error) = match self.apply_item(&desired, &item, step).await {
+                Ok(child) if matches!(child.status, TaskStatus::Succeeded) => {
+                    (ApplyItemStatus::Succeeded, Some(child.id), None)
+                }
+                Ok(child) => (ApplyItemStatus::Failed, Some(child.id), child.message),
+                Err(err) => {
+                    tracing::warn!(?err, change = %item.describe(), "Fallo un cambio del plan");
+                    (ApplyItemStatus::Failed, None, Some(format!("{err:#}")))
+                }
+            };
+            if status == ApplyItemStatus::Failed && item.app.is_none() {
+                failed_containers.insert(key);
+            }
+            run.items.push(ApplyItemResult {
+                item,
+                status,
+                task_id,
+                error,
+            });
+        }
+
+        task.result = serde_json::to_value(&run).ok();
+        let failed = run
+            .items
+            .iter()
+            .filter(|item| item.status != ApplyItemStatus::Succeeded)
+            .count();
+        if failed > 0 {
+            self.apps
+                .fail_task(
+                    &mut task,
+                    format!("No se aplicaron {failed} de {total} cambios"),
+                )
+                .await;
+        } else if let Err(err) = self
+            .apps
+            .succeed_task(&mut task, &format!("Estado aplicado: {total} cambios"))
+            .await
+        {
+            tracing::error!(?err, task_id = %task.id, "No se pudo persistir la tarea");
+        }
+    }
+
+    /// Ejecuta un cambio como subtarea `step` y la devuelve cerrada, haya fallado o no.
+    async fn apply_item(
+        &self,
+        desired: &DesiredState,
+        item: &PlanItem,
+        step: TaskStep,
+    ) -> Result<TaskModel> {
+        let wanted = desired.container(&item.container);
+        let message = item.describe();
+        let child = match (&item.app, item.action) {
+            (None, PlanAction::Create) => {
+                let wanted = wanted.context("El contenedor no esta en el documento")?;
+                let provision = self.containers.provision_container(
+                    wanted.name.clone(),
+                    wanted.platform.clone(),
+                    wanted.description.clone(),
+                    wanted.tags.clone(),
+                    wanted.settings(),
+                );
+                let (child, _) = self
+                    .apps
+                    .run_step("container.create", &message, step, provision)
+                    .await?;
+                Some(child)
+            }
+            (None, PlanAction::Update) => {
+                let wanted = wanted.context("El contenedor no esta en el documento")?;
+                let update = async {
+                    let container = self.existing(&item.container).await?;
+                    let update = ContainerUpdate {
+                        description: Some(wanted.description.clone().unwrap_or_default()),
+                        tags: Some(wanted.tags.clone()),
+                        env: Some(wanted.env.clone()),
+                        compat_flags: Some(wanted.compat_flags.clone()),
+                        limits: Some(wanted.limits.clone()),
+                        mounts: Some(wanted.mounts.clone()),
+                    };
+                    self.containers
+                        .update_container(container.id, update)
+                        .await?
+                        .context("El contenedor ya no existe")
+                };
+                let (child, _) = self
+                    .apps
+                    .run_step("container.update", &message, step, update)
+                    .await?;
+                Some(child)
+            }
+            (None, PlanAction::Delete) => {
+                let discard = async {
+                    let container = self.existing(&item.container).await?;
+                    self.containers.discard_container(container.id).await
+                };
+                let (child, _) = self
+                    .apps
+                    .run_step("container.delete", &message, step, discard)
+                    .await?;
+                Some(child)
+            }
+            (Some(app), PlanAction::Create) => {
+                let spec = wanted
+                    .and_then(|wanted| wanted.app(app))
+                    .context("La app no esta en el documento")?;
+                let container = self.existing(&item.container).await?;
+                self.containers.ensure_quota(container.id).await?;
+                self.apps
+                    .install_and_wait(container.id, AppInstallSpec::from(spec), step)
+                    .await?
+            }
+            (Some(app), PlanAction::Update) => {
+                let spec = AppInstallSpec::from(
+                    wanted
+                        .and_then(|wanted| wanted.app(app))
+                        .context("La app no esta en el documento")?,
+                );
+                let installed = self.installed_app(&item.container, app).await?;
+                let update = AppUpdateSpec {
+                    version: spec.version,
+                    source: spec.source.context("La app no declara instalador")?,
+                    silent_args: spec.silent_args,
+                };
+                self.containers.ensure_quota(installed.container_id).await?;
+                self.apps
+                    .update_and_wait(installed.id, update, step)
+                    .await?
+            }
+            (Some(app), PlanAction::Delete) => {
+                let installed = self.installed_app(&item.container, app).await?;
+                self.apps.uninstall_and_wait(installed.id, step).await?
+            }
+        };
+        child.with_context(|| format!("{} ya no existe", item.container))
+    }
+
+    async fn existing(&self, name: &str) -> Result<ContainerModel> {
+        self.containers
+            .find_by_name(name)
+            .await?
+            .with_context(|| format!("El contenedor {name} ya no existe"))
+    }
+
+    async fn installed_app(&self, container: &str, app: &str) -> Result<AppInstance> {
+        let container = self.existing(container).await?;
+        self.store
+            .list_apps(container.id)
+            .await?
+            .into_iter()
+            .find(|installed| installed.name.eq_ignore_ascii_case(app))
+            .with_context(|| format!("La app {app} ya no esta instalada"))
+    }
+}
+
+/// Campos del contenedor que difieren del documento, con la misma normalizacion que
+/// aplica [`ContainerService::update_container`].
+fn container_changes(current: &ContainerModel, wanted: &DesiredContainer) -> Vec<&'static str> {
+    let description = wanted
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty());
+    let settings = &current.settings;
+    [
+        ("description", current.description.as_deref() != description),
+        ("tags", current.tags != sanitize_list(wanted.tags.clone())),
+        ("env", settings.env != wanted.env),
+        (
+            "compat_flags",
+            settings.compat_flags != sanitize_list(wanted.compat_flags.clone()),
+        ),
+        ("limits", settings.limits != wanted.limits),
+        ("mounts", settings.mounts != wanted.mounts),
+    ]
+    .into_iter()
+    .filter_map(|(field, changed)| changed.then_some(field))
+    .collect()
+}
+
+/// Solo cuenta como cambio una version declarada distinta de la instalada.
+fn app_version_changed(installed: &AppInstance, wanted: &TemplateApp) -> bool {
+    wanted
+        .version
+        .as_deref()
+        .map(str::trim)
+        .is_some_and(|version| installed.version.as_deref() != Some(version))
+}
+
+#[derive(Clone)]
+pub struct SnapshotService {
+    store: SqliteStore,
+    events: EventHub,
+    tasks: Arc<TasksConfig>,
+    retries: RetryQueue,
+}
+
+impl SnapshotService {
+    pub fn new(events: EventHub, store: SqliteStore) -> Self {
+        Self {
+            store,
+            events,
+            tasks: Arc::default(),
+            retries: RetryQueue::default(),
+        }
+    }
+
+    /// Aplica las politicas de reintento de `tasks` a las tareas que se creen desde ahora
+    /// y avisa a `retries` de las que fallen.
+    pub fn with_retry(mut self, tasks: &TasksConfig, retries: RetryQueue) -> Self {
+        self.tasks = Arc::new(tasks.clone());
+        self.retries = retries;
+        self
+    }
+
+    pub async fn list(&self, container_id: Uuid) -> Result<Vec<Snapshot>> {
+        self.store.list_snapshots(container_id).await
+    }
+
+    /// Captura el contenedor de forma sincrona; devuelve `None` si no existe.
+    pub async fn create(
+        &self,
+        container_id: Uuid,
+        label: Option<String>,
+        snapshot_type: SnapshotType,
+    ) -> Result<Option<TaskModel>> {
+        let task = TaskModel::new("snapshot.create");
+        self.start_create(task, container_id, label, snapshot_type)
+            .await
+    }
+
+    async fn start_create(
+        &self,
+        task: TaskModel,
+        container_id: Uuid,
+        label: Option<String>,
+        snapshot_type: SnapshotType,
+    ) -> Result<Option<TaskModel>> {
+        let Some(container) = self.store.get_container(container_id).await? else {
+            return Ok(None);
+        };
+        let mut task = task.with_status(TaskStatus::Running);
+        apply_retry_policy(&self.tasks, &mut task);
+        task.payload = TaskRequest::SnapshotCreate {
+            container_id,
+            label: label.clone(),
+            snapshot_type: snapshot_type.clone(),
+        }
+        .to_payload();
+        task.set_progress(25, Some("Capturando snapshot".into()));
+        self.store.upsert_task(&task).await?;
+        self.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+
+        let outcome = self
+            .capture(&container, label, snapshot_type)
+            .await
+            .map(|_| "Snapshot creado".to_string());
+        self.finish(&mut task, outcome, "No se pudo crear el snapshot")
+            .await?;
+        Ok(Some(task))
+    }
+
+    /// Copia `fs/` y `registry/` del contenedor y registra el snapshot.
+    pub async fn capture(
+        &self,
+        container: &ContainerModel,
+        label: Option<String>,
+        snapshot_type: SnapshotType,
+    ) -> Result<Snapshot> {
+        anyhow::ensure!(
+            !container.storage_path.is_empty(),
+            "El contenedor {} no tiene storage_path",
+            container.id
+        );
+        let mut snapshot = Snapshot::new(container.id, label, snapshot_type);
+        snapshot.size_bytes = SnapshotLayer::new(&container.storage_path)
+            .capture(snapshot.id)
+            .await?;
+        self.store.insert_snapshot(&snapshot).await?;
+        Ok(snapshot)
+    }
+
+    pub async fn restore(&self, snapshot_id: Uuid) -> Result<Option<TaskModel>> {
+        self.start_restore(TaskModel::new("snapshot.restore"), snapshot_id)
+            .await
+    }
+
+    async fn start_restore(&self, task: TaskModel, snapshot_id: Uuid) -> Result<Option<TaskModel>> {
+        let Some(snapshot) = self.store.get_snapshot(snapshot_id).await? else {
+            return Ok(None);
+        };
+        let mut task = task.with_status(TaskStatus::Running);
+        apply_retry_policy(&self.tasks, &mut task);
+        task.payload = TaskRequest::SnapshotRestore { snapshot_id }.to_payload();
+        task.set_progress(30, Some("Preparando restauracion".into()));
+        self.store.upsert_task(&task).await?;
+
+        let outcome = self
+            .restore_files(&snapshot)
+            .await
+            .map(|_| "Snapshot restaurado".to_string());
+        self.finish(&mut task, outcome, "No se pudo restaurar el snapshot")
+            .await?;
+        Ok(Some(task))
+    }
+
+    /// Borra los snapshots del contenedor salvo los `keep` mas recientes; el detalle queda
+    /// en `result` de la tarea. Devuelve `None` si el contenedor no existe.
+    async fn start_prune(
+        &self,
+        task: TaskModel,
+        container_id: Uuid,
+        keep: u32,
+    ) -> Result<Option<TaskModel>> {
+        let Some(container) = self.store.get_container(container_id).await? else {
+            return Ok(None);
+        };
+        let mut task = task.with_status(TaskStatus::Running);
+        task.set_progress(10, Some("Buscando snapshots antiguos".into()));
+        self.store.upsert_task(&task).await?;
+        self.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+
+        let outcome = self.prune(&mut task, &container, keep as usize).await;
+        self.finish(&mut task, outcome, "No se pudieron borrar los snapshots")
+            .await?;
+        Ok(Some(task))
+    }
+
+    async fn prune(
+        &self,
+        task: &mut TaskModel,
+        container: &ContainerModel,
+        keep: usize,
+    ) -> Result<String> {
+        let snapshots = self.store.list_snapshots(container.id).await?;
+        let expired = expired_snapshots(&snapshots, keep);
+        let layer = SnapshotLayer::new(&container.storage_path);
+        let mut deleted = Vec::with_capacity(expired.len());
+        let mut freed_bytes = 0;
+        for snapshot in expired {
+            layer.remove(snapshot.id).await?;
+            self.store.delete_snapshot(snapshot.id).await?;
+            deleted.push(snapshot.id);
+            freed_bytes += snapshot.size_bytes;
+        }
+        let kept = snapshots.len() - deleted.len();
+        task.result = Some(serde_json::json!({
+            "deleted": deleted,
+            "kept": kept,
+            "freed_bytes": freed_bytes,
+        }));
+        Ok(format!(
+            "{} snapshot(s) borrados, {kept} conservados",
+            deleted.len()
+        ))
+    }
+
+    /// Devuelve `fs/` y `registry/` del contenedor al estado del snapshot.
+    pub async fn restore_files(&self, snapshot: &Snapshot) -> Result<()> {
+        let container = self
+            .store
+            .get_container(snapshot.container_id)
+            .await?
+            .with_context(|| format!("El contenedor {} ya no existe", snapshot.container_id))?;
+        SnapshotLayer::new(&container.storage_path)
+            .restore(snapshot.id)
+            .await
+    }
+
+    async fn finish(
+        &self,
+        task: &mut TaskModel,
+        outcome: Result<String>,
+        failure: &str,
+    ) -> Result<()> {
+        let message = match outcome {
+            Ok(message) => {
+                task.status = TaskStatus::Succeeded;
+                message
+            }
+            Err(err) => {
+                tracing::error!(?err, task_id = %task.id, "{failure}");
+                task.status = TaskStatus::Failed;
+                format!("{failure}: {err:#}")
+            }
+        };
+        task.set_progress(100, Some(message.clone()));
+        self.store.upsert_task(task).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message,
+        });
+        self.retries.failed(task);
+        Ok(())
+    }
+}
+
+/// Snapshots que sobran al conservar los `keep` mas recientes de `snapshots` (ordenados
+/// del mas nuevo al mas antiguo). La base de un delta conservado tambien se conserva.
+fn expired_snapshots(snapshots: &[Snapshot], keep: usize) -> Vec<&Snapshot> {
+    let (kept, older) = snapshots.split_at(keep.min(snapshots.len()));
+    let mut bases: BTreeSet<Uuid> = kept
+        .iter()
+        .filter_map(|snapshot| snapshot.base_snapshot_id)
+        .collect();
+    let mut expired = vec![];
+    for snapshot in older {
+        if bases.contains(&snapshot.id) {
+            bases.extend(snapshot.base_snapshot_id);
+        } else {
+            expired.push(snapshot);
+        }
+    }
+    expired
+}
+
+/// Peticion que origino una tarea; se guarda en su `payload` para poder reintentarla.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TaskRequest {
+    AppInstall {
+        container_id: Uuid,
+        app_id: Uuid,
+        spec: AppInstallSpec,
+    },
+    AppUpdate {
+        app_id: Uuid,
+        spec: AppUpdateSpec,
+    },
+    AppUninstall {
+        app_id: Uuid,
+    },
+    SnapshotCreate {
+        container_id: Uuid,
+        label: Option<String>,
+        snapshot_type: SnapshotType,
+    },
+    SnapshotRestore {
+        snapshot_id: Uuid,
+    },
+    ContainerApply {
+        desired: DesiredState,
+        prune: bool,
+        /// Huella del plan confirmado; las tareas anteriores no la tienen.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fingerprint: Option<String>,
+    },
+}
+
+impl TaskRequest {
+    fn to_payload(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(self).ok()
+    }
+
+    fn from_task(task: &TaskModel) -> Option<Self> {
+        task.payload
+            .clone()
+            .and_then(|payload| serde_json::from_value(payload).ok())
+    }
+}
+
+/// La tarea no admite otro intento.
+#[derive(Debug, thiserror::Error)]
+pub enum TaskRetryError {
+    #[error("La tarea esta en estado '{0}'; solo se reintentan las fallidas o canceladas")]
+    NotFailed(&'static str),
+    #[error("Las subtareas se reintentan desde su tarea padre")]
+    Subtask,
+    #[error("Las tareas de tipo '{0}' no se pueden reintentar")]
+    Unsupported(String),
+    #[error("La tarea ya se reintento como {0}")]
+    AlreadyRetried(Uuid),
+}
+
+impl TaskRetryError {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            TaskRetryError::NotFailed(_) => "not_failed",
+            TaskRetryError::Subtask => "subtask",
+            TaskRetryError::Unsupported(_) => "unsupported",
+            TaskRetryError::AlreadyRetried(_) => "already_retried",
+        }
+    }
+}
+
+impl From<&TaskRetryError> for ApiError {
+    fn from(err: &TaskRetryError) -> Self {
+        let mut details = serde_json::json!({ "reason": err.reason() });
+        if let TaskRetryError::AlreadyRetried(retry_task_id) = err {
+            details["retry_task_id"] = serde_json::json!(retry_task_id);
+        }
+        ApiError::new(ErrorCode::TaskNotRetryable).with_details(details)
+    }
+}
+
+/// Nuevos intentos de tareas fallidas: a peticion (`POST /tasks/{id}/retry`) o
+/// automaticos segun la politica de su tipo. Cada intento es una tarea nueva que
+/// apunta a la anterior con `retry_of`.
+#[derive(Clone)]
+pub struct RetryService {
+    store: SqliteStore,
+    events: EventHub,
+    apps: AppService,
+    snapshots: SnapshotService,
+    apply: ApplyService,
+    tasks: Arc<TasksConfig>,
+    failed: Arc<std::sync::Mutex<Option<mpsc::UnboundedReceiver<Uuid>>>>,
+    mutex: Arc<Mutex<()>>,
+}
+
+/// Aviso directo a [`RetryService`] de las tareas que fallan. A diferencia del
+/// `EventHub`, no pierde avisos si el consumidor se retrasa. Sin canal no hace nada.
+#[derive(Clone, Default)]
+pub struct RetryQueue {
+    sender: Option<mpsc::UnboundedSender<Uuid>>,
+}
+
+impl RetryQueue {
+    /// Cola y receptor que consume `RetryService::run_auto_retry`.
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<Uuid>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (
+            Self {
+                sender: Some(sender),
+            },
+            receiver,
+        )
+    }
+
+    fn failed(&self, task: &TaskModel) {
+        if let Some(sender) = &self.sender
+            && matches!(task.status, TaskStatus::Failed)
+            && task.parent_task_id.is_none()
+        {
+            let _ = sender.send(task.id);
+        }
+    }
+}
+
+impl RetryService {
+    /// `failed` recibe los avisos de la [`RetryQueue`] que comparten los servicios.
+    pub fn new(
+        tasks: &TasksConfig,
+        events: EventHub,
+        store: SqliteStore,
+        apps: AppService,
+        snapshots: SnapshotService,
+        apply: ApplyService,
+        failed: mpsc::UnboundedReceiver<Uuid>,
+    ) -> Self {
+        Self {
+            store,
+            events,
+            apps,
+            snapshots,
+            apply,
+            tasks: Arc::new(tasks.clone()),
+            failed: Arc::new(std::sync::Mutex::new(Some(failed))),
+            mutex: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Lanza el siguiente intento de la tarea `id`; devuelve `None` si no existe.
+    pub async fn retry(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        let guard = self.mutex.lock().await;
+        let Some(previous) = self.store.get_task(id).await? else {
+            return Ok(None);
+        };
+        let request = self.retryable(&previous).await?;
+        // Se registra antes de soltar el lock para que nadie mas reintente la misma tarea.
+        let mut next = previous.next_attempt();
+        next.set_progress(0, Some("Reintento en cola".into()));
+        self.store.upsert_task(&next).await?;
+        drop(guard);
+
+        tracing::info!(task_id = %id, retry_id = %next.id, attempt = next.attempt, "Reintentando tarea");
+        self.dispatch(next, request).await.map(Some)
+    }
+
+    async fn retryable(&self, task: &TaskModel) -> Result<TaskRequest> {
+        if task.parent_task_id.is_some() {
+            return Err(TaskRetryError::Subtask.into());
+        }
+        if !matches!(task.status, TaskStatus::Failed | TaskStatus::Cancelled) {
+            return Err(TaskRetryError::NotFailed(task.status.as_str()).into());
+        }
+        let Some(request) = TaskRequest::from_task(task) else {
+            return Err(TaskRetryError::Unsupported(task.task_type.clone()).into());
+        };
+        if let Some(retry) = self.store.find_task_retry(task.id).await? {
+            return Err(TaskRetryError::AlreadyRetried(retry.id).into());
+        }
+        Ok(request)
+    }
+
+    /// Ejecuta `request` con `task` como tarea. Si no llega a arrancar, `task` queda
+    /// fallida con el motivo.
+    async fn dispatch(&self, task: TaskModel, request: TaskRequest) -> Result<TaskModel> {
+        let pending = task.clone();
+        let started = match request {
+            TaskRequest::AppInstall {
+                container_id,
+                app_id,
+                spec,
+            } => {
+                // La app de la instalacion fallida se sustituye por la del nuevo intento.
+                if let Some(app) = self.store.get_app(app_id).await? {
+                    ensure_app_state(&app, &[AppStatus::Failed])?;
+                    self.store.delete_app(app_id).await?;
+                }
+                self.apps.start_install(container_id, spec, task).await
+            }
+            TaskRequest::AppUpdate { app_id, spec } => {
+                self.apps.start_update(app_id, spec, task).await
+            }
+            TaskRequest::AppUninstall { app_id } => self.apps.start_uninstall(app_id, task).await,
+            TaskRequest::SnapshotCreate {
+                container_id,
+                label,
+                snapshot_type,
+            } => {
+                self.snapshots
+                    .start_create(task, container_id, label, snapshot_type)
+                    .await
+            }
+            TaskRequest::SnapshotRestore { snapshot_id } => {
+                self.snapshots.start_restore(task, snapshot_id).await
+            }
+            TaskRequest::ContainerApply {
+                desired,
+                prune,
+                fingerprint,
+            } => self
+                .apply
+                .start_apply(task, desired, prune, fingerprint)
+                .await
+                .map(Some),
+        };
+        match started {
+            Ok(Some(task)) => Ok(task),
+            Ok(None) => {
+                self.abandon(pending, "El recurso de la tarea ya no existe".into())
+                    .await
+            }
+            Err(err) => {
+                self.abandon(pending, format!("{err:#}")).await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn abandon(&self, mut task: TaskModel, message: String) -> Result<TaskModel> {
+        task.status = TaskStatus::Failed;
+        task.set_progress(100, Some(message.clone()));
+        self.store.upsert_task(&task).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message,
+        });
+        if let Err(err) = self.schedule(task.id).await {
+            tracing::warn!(?err, task_id = %task.id, "No se pudo programar el reintento");
+        }
+        Ok(task)
+    }
+
+    /// Vuelve a armar los reintentos que quedaron programados antes de reiniciar el
+    /// agente y despues programa el siguiente intento de cada tarea que falla mientras
+    /// su politica lo permita.
+    pub async fn run_auto_retry(self) {
+        let Some(mut failed) = self.failed.lock().expect("retry queue lock").take() else {
+            tracing::warn!("Los reintentos automaticos ya estan en marcha");
+            return;
+        };
+        if let Err(err) = self.rearm().await {
+            tracing::warn!(?err, "No se pudieron recuperar los reintentos programados");
+        }
+        while let Some(id) = failed.recv().await {
+            if let Err(err) = self.schedule(id).await {
+                tracing::warn!(?err, task_id = %id, "No se pudo programar el reintento");
+            }
+        }
+    }
+
+    async fn rearm(&self) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+        for task in self.store.list_pending_retries().await? {
+            let due = task
+                .next_retry_at
+                .as_deref()
+                .and_then(parse_timestamp)
+                .unwrap_or(now);
+            let delay = Duration::try_from(due - now).unwrap_or_default();
+            tracing::info!(task_id = %task.id, ?delay, "Reintento pendiente recuperado");
+            self.spawn_retry(task.id, delay);
+        }
+        Ok(())
+    }
+
+    async fn schedule(&self, id: Uuid) -> Result<()> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut task) = self.store.get_task(id).await? else {
+            return Ok(());
+        };
+        if task.next_retry_at.is_some() || task.attempt >= task.max_attempts {
+            return Ok(());
+        }
+        let Some(policy) = self.tasks.policy(&task.task_type) else {
+            return Ok(());
+        };
+        if self.retryable(&task).await.is_err() {
+            return Ok(());
+        }
+
+        let delay = policy.delay(task.attempt);
+        task.next_retry_at = Some(format_timestamp(OffsetDateTime::now_utc() + delay));
+        self.store.upsert_task(&task).await?;
+        tracing::info!(task_id = %id, ?delay, attempt = task.attempt + 1, "Reintento programado");
+        self.spawn_retry(id, delay);
+        Ok(())
+    }
+
+    fn spawn_retry(&self, id: Uuid, delay: Duration) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if let Err(err) = service.retry(id).await {
+                // Un reintento manual en la espera lo deja sin efecto.
+                tracing::warn!(error = %format!("{err:#}"), task_id = %id, "Reintento automatico descartado");
+            }
+        });
+    }
+}
+
+/// Espera maxima del planificador entre dos revisiones de las programaciones.
+const SCHEDULER_IDLE: Duration = Duration::from_secs(60);
+
+/// Datos de una programacion nueva.
+#[derive(Clone)]
+pub struct ScheduleSpec {
+    pub name: String,
+    pub cron: String,
+    pub job: ScheduleJob,
+    pub enabled: bool,
+    pub missed_runs: MissedRunPolicy,
+}
+
+/// Cambios parciales sobre una programacion; `None` conserva el valor actual.
+#[derive(Clone, Default)]
+pub struct ScheduleUpdate {
+    pub name: Option<String>,
+    pub cron: Option<String>,
+    pub job: Option<ScheduleJob>,
+    pub enabled: Option<bool>,
+    pub missed_runs: Option<MissedRunPolicy>,
+}
+
+/// El trabajo de la programacion apunta a un recurso que no existe.
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("El contenedor {0} no existe")]
+    ContainerNotFound(Uuid),
+}
+
+impl From<&ScheduleError> for ApiError {
+    fn from(err: &ScheduleError) -> Self {
+        match err {
+            ScheduleError::ContainerNotFound(container_id) => {
+                ApiError::new(ErrorCode::ContainerNotFound)
+                    .with_details(serde_json::json!({ "container_id": container_id }))
+            }
+        }
+    }
+}
+
+/// Trabajos periodicos definidos con expresiones cron. Cada ejecucion es una tarea
+/// normal con `schedule_id`; al arrancar, las ejecuciones que cayeron con el agente
+/// parado se lanzan o se descartan segun `missed_runs`.
+#[derive(Clone)]
+pub struct ScheduleService {
+    store: SqliteStore,
+    events: EventHub,
+    containers: ContainerService,
+    snapshots: SnapshotService,
+    tokens: TokenService,
+    mutex: Arc<Mutex<()>>,
+    /// Despierta al planificador cuando cambian las programaciones.
+    wake: Arc<Notify>,
+}
+
+impl ScheduleService {
+    pub fn new(
+        events: EventHub,
+        store: SqliteStore,
+        containers: ContainerService,
+        snapshots: SnapshotService,
+        tokens: TokenService,
+    ) -> Self {
+        Self {
+            store,
+            events,
+            containers,
+            snapshots,
+            tokens,
+            mutex: Arc::new(Mutex::new(())),
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    pub async fn list(&self) -> Result<Vec<Schedule>> {
+        self.store.list_schedules().await
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Option<Schedule>> {
+        self.store.get_schedule(id).await
+    }
+
+    pub async fn create(&self, spec: ScheduleSpec) -> Result<Schedule> {
+        self.ensure_target(&spec.job).await?;
+        let mut schedule = Schedule::new(spec.name, spec.cron, spec.job);
+        schedule.enabled = spec.enabled;
+        schedule.missed_runs = spec.missed_runs;
+        schedule.next_run_at = next_run(&schedule, OffsetDateTime::now_utc())?;
+
+        let _guard = self.mutex.lock().await;
+        self.store.upsert_schedule(&schedule).await?;
+        self.wake.notify_one();
+        Ok(schedule)
+    }
+
+    /// Aplica `update` y recalcula la proxima ejecucion desde ahora; `None` si no existe.
+    pub async fn update(&self, id: Uuid, update: ScheduleUpdate) -> Result<Option<Schedule>> {
+        if let Some(job) = &update.job {
+            self.ensure_target(job).await?;
+        }
+        let _guard = self.mutex.lock().await;
+        let Some(mut schedule) = self.store.get_schedule(id).await? else {
+            return Ok(None);
+        };
+        let ScheduleUpdate {
+            name,
+            cron,
+            job,
+            enabled,
+            missed_runs,
+        } = update;
+        if let Some(name) = name {
+            schedule.name = name;
+        }
+        if let Some(cron) = cron {
+            schedule.cron = cron;
+        }
+        if let Some(job) = job {
+            schedule.job = job;
+        }
+        if let Some(enabled) = enabled {
+            schedule.enabled = enabled;
+        }
+        if let Some(missed_runs) = missed_runs {
+            schedule.missed_runs = missed_runs;
+        }
+        schedule.next_run_at = next_run(&schedule, OffsetDateTime::now_utc())?;
+        schedule.touch();
+        self.store.upsert_schedule(&schedule).await?;
+        self.wake.notify_one();
+        Ok(Some(schedule))
+    }
+
+    /// Borra la programacion; sus tareas se conservan.
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let _guard = self.mutex.lock().await;
+        let deleted = self.store.delete_schedule(id).await?;
+        self.wake.notify_one();
+        Ok(deleted)
+    }
+
+    /// Lanza una ejecucion ahora sin mover la proxima programada; `None` si no existe.
+    pub async fn run_now(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        let _guard = self.mutex.lock().await;
+        let Some(mut schedule) = self.store.get_schedule(id).await? else {
+            return Ok(None);
+        };
+        let task = self.launch(&mut schedule).await?;
+        self.store.upsert_schedule(&schedule).await?;
+        Ok(Some(task))
+    }
+
+    /// Lanza las programaciones vencidas y duerme hasta la siguiente. La primera pasada
+    /// trata los vencimientos perdidos mientras el agente estaba parado.
+    pub async fn run_scheduler(self) {
+        let mut startup = true;
+        loop {
+            let now = OffsetDateTime::now_utc();
+            let wait = match self.run_due(now, startup).await {
+                Ok(Some(next)) => Duration::try_from(next - now)
+                    .unwrap_or_default()
+                    .min(SCHEDULER_IDLE),
+                Ok(None) => SCHEDULER_IDLE,
+                Err(err) => {
+                    tracing::warn!(?err, "No se pudieron revisar las programaciones");
+                    SCHEDULER_IDLE
+                }
+            };
+            startup = false;
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = self.wake.notified() => {}
+            }
+        }
+    }
+
+    /// Lanza las programaciones con `next_run_at <= now` y devuelve la proxima hora
+    /// pendiente. Tras un vencimiento la siguiente se calcula desde `now`, de modo que
+    /// varias ejecuciones perdidas se juntan en una.
+    async fn run_due(&self, now: OffsetDateTime, startup: bool) -> Result<Option<OffsetDateTime>> {
+        let _guard = self.mutex.lock().await;
+        let mut earliest: Option<OffsetDateTime> = None;
+        for mut schedule in self.store.list_schedules().await? {
+            let Some(due) = schedule.next_run_at.as_deref().and_then(parse_timestamp) else {
+                continue;
+            };
+            if due > now {
+                earliest = Some(earliest.map_or(due, |current| current.min(due)));
+                continue;
+            }
+
+            if startup && matches!(schedule.missed_runs, MissedRunPolicy::Skip) {
+                tracing::info!(schedule = %schedule.name, due = %format_timestamp(due), "Ejecucion perdida descartada");
+            } else if let Err(err) = self.fire(&mut schedule).await {
+                tracing::warn!(?err, schedule = %schedule.name, "No se pudo lanzar la ejecucion programada");
+            }
+            schedule.next_run_at = next_run(&schedule, now).unwrap_or_else(|err| {
+                tracing::warn!(?err, schedule = %schedule.name, "Programacion sin proxima ejecucion");
+                None
+            });
+            schedule.touch();
+            self.store.upsert_schedule(&schedule).await?;
+            if let Some(next) = schedule.next_run_at.as_deref().and_then(parse_timestamp) {
+                earliest = Some(earliest.map_or(next, |current| current.min(next)));
+            }
+        }
+        Ok(earliest)
+    }
+
+    /// Ejecucion automatica: se omite si la anterior sigue en curso.
+    async fn fire(&self, schedule: &mut Schedule) -> Result<()> {
+        if let Some(previous) = schedule.last_task_id {
+            let running = self.store.get_task(previous).await?;
+            if running.is_some_and(|task| !task.is_finished()) {
+                tracing::info!(schedule = %schedule.name, task_id = %previous, "La ejecucion anterior sigue en curso; se omite");
+                return Ok(());
+            }
+        }
+        let task = self.launch(schedule).await?;
+        tracing::info!(schedule = %schedule.name, task_id = %task.id, "Ejecucion programada lanzada");
+        Ok(())
+    }
+
+    /// Registra la tarea de la ejecucion y la arranca en segundo plano.
+    async fn launch(&self, schedule: &mut Schedule) -> Result<TaskModel> {
+        let mut task = TaskModel::new(schedule.job.task_type())
+            .with_schedule(schedule.id)
+            .with_status(TaskStatus::Running);
+        task.set_progress(0, Some(format!("Ejecucion de '{}'", schedule.name)));
+        self.store.upsert_task(&task).await?;
+        schedule.last_run_at = Some(task.created_at.clone());
+        schedule.last_task_id = Some(task.id);
+        schedule.touch();
+
+        let service = self.clone();
+        let job = schedule.job.clone();
+        let pending = task.clone();
+        tokio::spawn(async move {
+            service.dispatch(pending, job).await;
+        });
+        Ok(task)
+    }
+
+    /// Ejecuta el trabajo con `task` como tarea; si no llega a arrancar, la tarea queda
+    /// fallida con el motivo.
+    async fn dispatch(&self, task: TaskModel, job: ScheduleJob) {
+        let mut pending = task.clone();
+        let started = match job {
+            ScheduleJob::Snapshot {
+                container_id,
+                label,
+                snapshot_type,
+            } => match self.containers.ensure_quota(container_id).await {
+                Ok(()) => {
+                    self.snapshots
+                        .start_create(task, container_id, label, snapshot_type)
+                        .await
+                }
+                Err(err) => Err(err),
+            },
+            ScheduleJob::Verify { container_id } => {
+                self.containers.start_verify(task, container_id).await
+            }
+            ScheduleJob::Prune { container_id, keep } => {
+                self.snapshots.start_prune(task, container_id, keep).await
+            }
+            ScheduleJob::Export {
+                container_id,
+                destination,
+                keep,
+            } => {
+                self.containers
+                    .start_export(task, container_id, destination.as_deref(), keep)
+                    .await
+            }
+            ScheduleJob::TokenSweep => self.sweep_tokens(task).await.map(Some),
+        };
+        let outcome = match started {
+            Ok(Some(_)) => return,
+            Ok(None) => Err(anyhow::anyhow!(
+                "El recurso de la programacion ya no existe"
+            )),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = self
+            .finish(
+                &mut pending,
+                outcome,
+                "No se pudo lanzar la ejecucion programada",
+            )
+            .await
+        {
+            tracing::error!(?err, task_id = %pending.id, "No se pudo persistir la tarea");
+        }
+    }
+
+    async fn sweep_tokens(&self, mut task: TaskModel) -> Result<TaskModel> {
+        self.events.emit(AgentEvent::TaskCreated {
+            id: task.id,
+            task_type: task.task_type.clone(),
+            status: "running".into(),
+            parent_task_id: task.parent_task_id,
+        });
+        let outcome = match self.tokens.sweep_expired().await {
+            Ok(removed) => {
+                task.result = Some(serde_json::json!({ "removed": removed }));
+                Ok(format!("{removed} token(s) caducados eliminados"))
+            }
+            Err(err) => Err(err),
+        };
+        self.finish(
+            &mut task,
+            outcome,
+            "No se pudieron eliminar los tokens caducados",
+        )
+        .await?;
+        Ok(task)
+    }
+
+    async fn finish(
+        &self,
+        task: &mut TaskModel,
+        outcome: Result<String>,
+        failure: &str,
+    ) -> Result<()> {
+        let message = match outcome {
+            Ok(message) => {
+                task.status = TaskStatus::Succeeded;
+                message
+            }
+            Err(err) => {
+                tracing::error!(?err, task_id = %task.id, "{failure}");
+                task.status = TaskStatus::Failed;
+                format!("{failure}: {err:#}")
+            }
+        };
+        task.set_progress(100, Some(message.clone()));
+        self.store.upsert_task(task).await?;
+        self.events.emit(AgentEvent::TaskProgress {
+            id: task.id,
+            progress: 100,
+            message,
+        });
+        Ok(())
+    }
+
+    async fn ensure_target(&self, job: &ScheduleJob) -> Result<()> {
+        if let Some(container_id) = job.container_id()
+            && self.store.get_container(container_id).await?.is_none()
+        {
+            return Err(ScheduleError::ContainerNotFound(container_id).into());
+        }
+        Ok(())
+    }
+}
+
+/// Proxima ejecucion de `schedule` posterior a `after`; `None` si esta desactivada o la
+/// expresion no vuelve a coincidir.
+fn next_run(schedule: &Schedule, after: OffsetDateTime) -> Result<Option<String>> {
+    if !schedule.enabled {
+        return Ok(None);
+    }
+    let cron = CronExpr::parse(&schedule.cron)?;
+    Ok(cron.next_after(after).map(format_timestamp))
+}
+
+fn parse_timestamp(value: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(value, &Rfc3339).ok()
+}
+
+/// Tareas de primer nivel que se archivan y borran de una vez en cada lote.
+const RETENTION_BATCH: i64 = 500;
+
+/// Resultado de una pasada de [`RetentionService::sweep`].
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionSweep {
+    pub finished_at: String,
+    /// Tareas borradas, subtareas incluidas.
+    pub deleted: u64,
+    /// Tareas guardadas en el archivo antes de borrarlas.
+    pub archived: u64,
+    /// Archivos creados en `archive/tasks`, uno por lote.
+    pub archive_files: Vec<String>,
+}
+
+/// Estado de la retencion que muestra `/system/info`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionStats {
+    pub enabled: bool,
+    pub archive: bool,
+    /// Archivos `.ndjson.gz` en `archive/tasks` y lo que ocupan.
+    pub archive_files: u64,
+    pub archive_bytes: u64,
+    /// Tareas borradas y archivadas desde que arranco el agente.
+    pub deleted_total: u64,
+    pub archived_total: u64,
+    pub last_sweep: Option<RetentionSweep>,
+}
+
+#[derive(Default)]
+struct RetentionTotals {
+    deleted: u64,
+    archived: u64,
+    last_sweep: Option<RetentionSweep>,
+}
+
+/// Limpieza periodica del historial de tareas segun `[tasks.retention]`. Con `archive`
+/// las tareas se guardan en `<data_dir>/archive/tasks/*.ndjson.gz`, una por linea,
+/// antes de borrarlas.
+#[derive(Clone)]
+pub struct RetentionService {
+    config: RetentionConfig,
+    archive_dir: PathBuf,
+    store: SqliteStore,
+    mutex: Arc<Mutex<()>>,
+    totals: Arc<std::sync::Mutex<RetentionTotals>>,
+}
+
+impl RetentionService {
+    pub fn new(config: &AgentConfig, store: SqliteStore) -> Self {
+        Self {
+            config: config.tasks.retention.clone(),
+            archive_dir: config.data_dir().join("archive").join("tasks"),
+            store,
+            mutex: Arc::new(Mutex::new(())),
+            totals: Arc::new(std::sync::Mutex::new(RetentionTotals::default())),
+        }
+    }
+
+    /// Archiva (si procede) y borra las tareas terminadas que exceden la retencion. Cada
+    /// tarea de primer nivel se trata junto con sus subtareas. Un reintento solo se borra
+    /// en el lote siguiente al de la tarea que reintenta, para que
+    /// [`RetryService`] no vuelva a lanzar un original cuyo reintento ya no existe.
+    pub async fn sweep(&self) -> Result<RetentionSweep> {
+        let _guard = self.mutex.lock().await;
+        let now = OffsetDateTime::now_utc();
+        let days_ago = |days: u32| now - time::Duration::days(days.into());
+        let cutoff = self.config.max_age_days.map(days_ago);
+        let failed_cutoff = self.config.failed_max_age_days().map(days_ago);
+        let stamp = now
+            .format(format_description!(
+                "[year][month][day]T[hour][minute][second]Z"
+            ))
+            .unwrap_or_default();
+
+        let mut sweep = RetentionSweep {
+            finished_at: String::new(),
+            deleted: 0,
+            archived: 0,
+            archive_files: Vec::new(),
+        };
+        loop {
+            let roots = self
+                .store
+                .list_expired_task_roots(
+                    cutoff,
+                    self.config.max_tasks,
+                    failed_cutoff,
+                    RETENTION_BATCH,
+                )
+                .await?;
+            if roots.is_empty() {
+                break;
+            }
+            let tasks = self.store.list_task_trees(&roots).await?;
+            let ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+            if self.config.archive {
+                let name = format!("tasks-{stamp}-{}.ndjson.gz", sweep.archive_files.len() + 1);
+                let target = self.archive_dir.join(&name);
+                tokio::task::spawn_blocking(move || archive::pack_ndjson_gz(&tasks, &target))
+                    .await?
+                    .context("No se pudo archivar el historial de tareas")?;
+                sweep.archived += ids.len() as u64;
+                sweep.archive_files.push(name);
+            }
+            sweep.deleted += self.store.delete_tasks(&ids).await?;
+        }
+        sweep.finished_at = format_timestamp(OffsetDateTime::now_utc());
+
+        let mut totals = self.totals.lock().expect("retention lock");
+        totals.deleted += sweep.deleted;
+        totals.archived += sweep.archived;
+        totals.last_sweep = Some(sweep.clone());
+        Ok(sweep)
+    }
+
+    /// Repite [`RetentionService::sweep`] cada `tasks.retention.interval_secs`, empezando
+    /// al arrancar.
+    pub async fn run_retention(self) {
+        if !self.config.enabled() {
+            tracing::info!("Retencion del historial de tareas desactivada");
+            return;
+        }
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            match self.sweep().await {
+                Ok(sweep) if sweep.deleted > 0 => tracing::info!(
+                    deleted = sweep.deleted,
+                    archived = sweep.archived,
+                    "Historial de tareas recortado"
+                ),
+                Ok(_) => {}
+                Err(err) => tracing::warn!(?err, "Fallo la limpieza del historial de tareas"),
+            }
+        }
+    }
+
+    pub async fn stats(&self) -> RetentionStats {
+        let (archive_files, archive_bytes) = archive_usage(&self.archive_dir).await;
+        let totals = self.totals.lock().expect("retention lock");
+        RetentionStats {
+            enabled: self.config.enabled(),
+            archive: self.config.archive,
+            archive_files,
+            archive_bytes,
+            deleted_total: totals.deleted,
+            archived_total: totals.archived,
+            last_sweep: totals.last_sweep.clone(),
+        }
+    }
+}
+
+/// Archivos `.ndjson.gz` de `dir` y su tamano total; un directorio que no existe cuenta
+/// como vacio.
+async fn archive_usage(dir: &Path) -> (u64, u64) {
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return (0, 0);
+    };
+    let (mut files, mut bytes) = (0, 0);
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if !entry.file_name().to_string_lossy().ends_with(".ndjson.gz") {
+            continue;
+        }
+        if let Ok(metadata) = entry.metadata().await {
+            files += 1;
+            bytes += metadata.len();
+        }
+    }
+    (files, bytes)
+}
+
+fn container_root(root: &Path, id: Uuid) -> PathBuf {
+    let root = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+    root.join(id.to_string())
+}
+
+/// Ruta usada antes de que el sandbox se identificara por id.
+fn legacy_container_root(root: &Path, name: &str) -> PathBuf {
+    root.join(sanitize_file_name(name))
+}
+
+/// Borra las exportaciones `<prefix><marca>.tar.gz` de `dir` salvo las `keep` mas recientes
+/// (la marca de tiempo ordena por nombre) y devuelve los nombres borrados.
+fn prune_exports(dir: &Path, prefix: &str, keep: usize) -> Result<Vec<String>> {
+    let mut archives = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+            continue;
+        };
+        // `<id>-<stamp>-<nombre>.tar.gz`; se ordena por la fecha porque el nombre cambia
+        // si se renombra el contenedor.
+        let stamp = name
+            .strip_prefix(prefix)
+            .filter(|rest| rest.ends_with(".tar.gz"))
+            .and_then(|rest| rest.get(..16).zip(rest.get(16..)))
+            .filter(|(stamp, rest)| {
+                rest.starts_with('-')
+                    && stamp.bytes().enumerate().all(|(index, byte)| match index {
+                        8 => byte == b'T',
+                        15 => byte == b'Z',
+                        _ => byte.is_ascii_digit(),
+                    })
+            })
+            .map(|(stamp, _)| stamp.to_string());
+        if let Some(stamp) = stamp
+            && entry.file_type()?.is_file()
+        {
+            archives.push((stamp, name));
+        }
+    }
+    archives.sort_unstable_by(|a, b| b.cmp(a));
+    let expired = archives.split_off(keep.min(archives.len()));
+    let mut deleted = Vec::with_capacity(expired.len());
+    for (_, name) in expired {
+        std::fs::remove_file(dir.join(&name))
+            .with_context(|| format!("No se pudo borrar la exportacion {name}"))?;
+        deleted.push(name);
+    }
+    Ok(deleted)
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '"' | '<' | '>' | '|' => '_',
+            _ => c,
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct TokenService {
+    store: SqliteStore,
+}
+
+pub struct IssuedToken {
+    pub secret: String,
+    pub info: ApiTokenInfo,
+}
+
+#[derive(Clone)]
 pub struct TokenSpec {
     pub name: String,
     pub scopes: Vec<String>,
@@ -317,7 +5060,7 @@ impl TokenService {
             scopes,
             expires_at,
         } = spec;
-        let sanitized_scopes = sanitize_scopes(scopes);
+        let sanitized_scopes = sanitize_list(scopes);
         let expires_at = expires_at.map(format_timestamp);
         let info = self
             .store
@@ -334,12 +5077,19 @@ impl TokenService {
     pub async fn revoke(&self, id: Uuid) -> Result<bool> {
         self.store.revoke_api_token(id).await
     }
+
+    /// Elimina los tokens caducados; devuelve cuantos habia.
+    pub async fn sweep_expired(&self) -> Result<u64> {
+        self.store
+            .delete_expired_api_tokens(OffsetDateTime::now_utc())
+            .await
+    }
 }
 
-fn sanitize_scopes(scopes: Vec<String>) -> Vec<String> {
+fn sanitize_list(values: Vec<String>) -> Vec<String> {
     let mut unique = BTreeSet::new();
-    for scope in scopes {
-        let trimmed = scope.trim();
+    for value in values {
+        let trimmed = value.trim();
         if !trimmed.is_empty() {
             unique.insert(trimmed.to_string());
         }
@@ -9,17 +9,47 @@ use time::OffsetDateTime;
 use tokio::fs;
 use uuid::Uuid;
 
+use crate::error::{ApiError, ErrorCode};
 use crate::models::{
-    ApiTokenInfo, AppInstance, AppStatus, ContainerModel, ContainerStatus, Snapshot, SnapshotType,
-    TaskModel, TaskStatus,
+    ApiTokenInfo, AppInstance, AppStatus, ContainerModel, ContainerStatus, IdempotencyRecord,
+    MissedRunPolicy, Schedule, Snapshot, SnapshotType, TaskModel, TaskStatus,
 };
 use crate::virtualization::Platform;
 
+/// Columnas que lee [`map_task_row`].
+const TASK_COLUMNS: &str = "id, type, status, progress, message, result, parent_task_id, step, \
+    required, total_steps, attempt, max_attempts, retry_of, next_retry_at, payload, schedule_id, \
+    created_at, updated_at";
+
 #[derive(Clone)]
 pub struct SqliteStore {
     pool: SqlitePool,
 }
 
+/// Errores del store que los handlers traducen a respuestas concretas.
+#[derive(Debug, thiserror::Error)]
+pub enum StoreError {
+    #[error("Ya existe un contenedor llamado '{0}'")]
+    DuplicateContainerName(String),
+    #[error("Ya existe una programacion llamada '{0}'")]
+    DuplicateScheduleName(String),
+}
+
+impl From<&StoreError> for ApiError {
+    fn from(err: &StoreError) -> Self {
+        match err {
+            StoreError::DuplicateContainerName(name) => {
+                ApiError::new(ErrorCode::ContainerNameConflict)
+                    .with_details(serde_json::json!({ "name": name }))
+            }
+            StoreError::DuplicateScheduleName(name) => {
+                ApiError::new(ErrorCode::ScheduleNameConflict)
+                    .with_details(serde_json::json!({ "name": name }))
+            }
+        }
+    }
+}
+
 impl SqliteStore {
     pub async fn new(db_path: impl AsRef<Path>) -> Result<Self> {
         let db_path = db_path.as_ref();
@@ -43,6 +73,9 @@ impl SqliteStore {
                 platform TEXT NOT NULL,
                 tags TEXT NOT NULL,
                 size_bytes INTEGER NOT NULL,
+                storage_path TEXT NOT NULL DEFAULT '',
+                settings TEXT NOT NULL DEFAULT '{}',
+                status_reason TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             );
@@ -59,6 +92,7 @@ impl SqliteStore {
                 status TEXT NOT NULL,
                 progress INTEGER NOT NULL,
                 message TEXT,
+                result TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             );
@@ -74,8 +108,19 @@ impl SqliteStore {
                 container_id TEXT NOT NULL,
                 name TEXT NOT NULL,
                 version TEXT,
+                vendor TEXT,
                 status TEXT NOT NULL,
                 entry_points TEXT NOT NULL,
+                installer_path TEXT,
+                silent_args TEXT,
+                exit_code INTEGER,
+                log_path TEXT,
+                install_dir TEXT,
+                uninstaller TEXT,
+                install_source TEXT,
+                installer_sha256 TEXT,
+                install_duration_ms INTEGER,
+                size_bytes INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             );
@@ -118,20 +163,150 @@ impl SqliteStore {
         .execute(&pool)
         .await?;
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS idempotency_keys (
+                key TEXT PRIMARY KEY,
+                fingerprint TEXT NOT NULL,
+                status INTEGER NOT NULL,
+                content_type TEXT,
+                body BLOB NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS schedules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                cron TEXT NOT NULL,
+                job TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                missed_runs TEXT NOT NULL,
+                next_run_at TEXT,
+                last_run_at TEXT,
+                last_task_id TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_schedules_name ON schedules(name COLLATE NOCASE);",
+        )
+        .execute(&pool)
+        .await?;
+
         Self::ensure_token_columns(&pool).await?;
+        Self::ensure_container_columns(&pool).await?;
+        Self::ensure_task_columns(&pool).await?;
+        Self::ensure_app_columns(&pool).await?;
+        Self::ensure_unique_container_names(&pool).await?;
 
         Ok(Self { pool })
     }
 
     async fn ensure_token_columns(pool: &SqlitePool) -> Result<()> {
-        Self::add_column_if_missing(pool, "scopes TEXT NOT NULL DEFAULT '[]'").await?;
-        Self::add_column_if_missing(pool, "expires_at TEXT").await?;
-        Self::add_column_if_missing(pool, "last_used_at TEXT").await?;
+        Self::add_column_if_missing(pool, "api_tokens", "scopes TEXT NOT NULL DEFAULT '[]'")
+            .await?;
+        Self::add_column_if_missing(pool, "api_tokens", "expires_at TEXT").await?;
+        Self::add_column_if_missing(pool, "api_tokens", "last_used_at TEXT").await?;
+        Ok(())
+    }
+
+    async fn ensure_container_columns(pool: &SqlitePool) -> Result<()> {
+        Self::add_column_if_missing(pool, "containers", "settings TEXT NOT NULL DEFAULT '{}'")
+            .await?;
+        Self::add_column_if_missing(pool, "containers", "storage_path TEXT NOT NULL DEFAULT ''")
+            .await?;
+        Self::add_column_if_missing(pool, "containers", "status_reason TEXT").await?;
+        Ok(())
+    }
+
+    async fn ensure_task_columns(pool: &SqlitePool) -> Result<()> {
+        Self::add_column_if_missing(pool, "tasks", "result TEXT").await?;
+        Self::add_column_if_missing(pool, "tasks", "parent_task_id TEXT").await?;
+        Self::add_column_if_missing(pool, "tasks", "step INTEGER").await?;
+        Self::add_column_if_missing(pool, "tasks", "required INTEGER NOT NULL DEFAULT 1").await?;
+        Self::add_column_if_missing(pool, "tasks", "total_steps INTEGER").await?;
+        Self::add_column_if_missing(pool, "tasks", "attempt INTEGER NOT NULL DEFAULT 1").await?;
+        Self::add_column_if_missing(pool, "tasks", "max_attempts INTEGER NOT NULL DEFAULT 1")
+            .await?;
+        Self::add_column_if_missing(pool, "tasks", "retry_of TEXT").await?;
+        Self::add_column_if_missing(pool, "tasks", "next_retry_at TEXT").await?;
+        Self::add_column_if_missing(pool, "tasks", "payload TEXT").await?;
+        Self::add_column_if_missing(pool, "tasks", "schedule_id TEXT").await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_parent ON tasks(parent_task_id);")
+            .execute(pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_retry_of ON tasks(retry_of);")
+            .execute(pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_schedule ON tasks(schedule_id);")
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn ensure_app_columns(pool: &SqlitePool) -> Result<()> {
+        Self::add_column_if_missing(pool, "apps", "vendor TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "installer_path TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "silent_args TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "exit_code INTEGER").await?;
+        Self::add_column_if_missing(pool, "apps", "log_path TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "install_dir TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "uninstaller TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "install_source TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "installer_sha256 TEXT").await?;
+        Self::add_column_if_missing(pool, "apps", "install_duration_ms INTEGER").await?;
+        Self::add_column_if_missing(pool, "apps", "size_bytes INTEGER NOT NULL DEFAULT 0").await?;
+        Ok(())
+    }
+
+    /// Renombra duplicados heredados (se conserva el mas antiguo) y crea el indice unico.
+    async fn ensure_unique_container_names(pool: &SqlitePool) -> Result<()> {
+        let duplicates = sqlx::query(
+            r#"
+            SELECT c.id, c.name FROM containers c
+            WHERE EXISTS (
+                SELECT 1 FROM containers o
+                WHERE o.name = c.name COLLATE NOCASE
+                  AND (datetime(o.created_at) < datetime(c.created_at)
+                       OR (o.created_at = c.created_at AND o.id < c.id))
+            );
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for row in duplicates {
+            let id: String = row.get("id");
+            let name: String = row.get("name");
+            let renamed = format!("{name}-{}", &id[..8.min(id.len())]);
+            tracing::warn!(%id, %name, %renamed, "Nombre de contenedor duplicado renombrado");
+            sqlx::query("UPDATE containers SET name = ?2 WHERE id = ?1;")
+                .bind(&id)
+                .bind(&renamed)
+                .execute(pool)
+                .await?;
+        }
+
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_containers_name ON containers(name COLLATE NOCASE);",
+        )
+        .execute(pool)
+        .await?;
         Ok(())
     }
 
-    async fn add_column_if_missing(pool: &SqlitePool, definition: &str) -> Result<()> {
-        let statement = format!("ALTER TABLE api_tokens ADD COLUMN {definition};");
+    async fn add_column_if_missing(pool: &SqlitePool, table: &str, definition: &str) -> Result<()> {
+        let statement = format!("ALTER TABLE {table} ADD COLUMN {definition};");
         match sqlx::query(&statement).execute(pool).await {
             Ok(_) => Ok(()),
             Err(err) if is_duplicate_column_error(&err) => Ok(()),
@@ -142,8 +317,8 @@ impl SqliteStore {
     pub async fn upsert_container(&self, container: &ContainerModel) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO containers (id, name, description, status, platform, tags, size_bytes, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            INSERT INTO containers (id, name, description, status, platform, tags, size_bytes, storage_path, settings, status_reason, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
             ON CONFLICT(id) DO UPDATE SET
                 name=excluded.name,
                 description=excluded.description,
@@ -151,6 +326,9 @@ impl SqliteStore {
                 platform=excluded.platform,
                 tags=excluded.tags,
                 size_bytes=excluded.size_bytes,
+                storage_path=excluded.storage_path,
+                settings=excluded.settings,
+                status_reason=excluded.status_reason,
                 created_at=excluded.created_at,
                 updated_at=excluded.updated_at;
             "#,
@@ -162,17 +340,40 @@ impl SqliteStore {
         .bind(container.platform.as_str())
         .bind(serde_json::to_string(&container.tags)?)
         .bind(container.size_bytes as i64)
+        .bind(&container.storage_path)
+        .bind(serde_json::to_string(&container.settings)?)
+        .bind(&container.status_reason)
         .bind(&container.created_at)
         .bind(&container.updated_at)
         .execute(&self.pool)
-        .await?;
+        .await
+        .map_err(|err| match err {
+            SqlxError::Database(db_err) if db_err.is_unique_violation() => {
+                StoreError::DuplicateContainerName(container.name.clone()).into()
+            }
+            other => anyhow::Error::from(other),
+        })?;
         Ok(())
     }
 
+    pub async fn find_container_by_name(&self, name: &str) -> Result<Option<ContainerModel>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, description, status, platform, tags, size_bytes, storage_path, settings, status_reason, created_at, updated_at
+            FROM containers WHERE name = ?1 COLLATE NOCASE;
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.and_then(map_container_row))
+    }
+
     pub async fn get_container(&self, id: Uuid) -> Result<Option<ContainerModel>> {
         let row = sqlx::query(
             r#"
-            SELECT id, name, description, status, platform, tags, size_bytes, created_at, updated_at
+            SELECT id, name, description, status, platform, tags, size_bytes, storage_path, settings, status_reason, created_at, updated_at
             FROM containers WHERE id = ?1;
             "#,
         )
@@ -191,34 +392,56 @@ impl SqliteStore {
         Ok(result.rows_affected() > 0)
     }
 
-    pub async fn list_containers(&self, status: Option<String>) -> Result<Vec<ContainerModel>> {
-        let mut query = String::from(
-            "SELECT id, name, description, status, platform, tags, size_bytes, created_at, updated_at FROM containers",
+    pub async fn list_containers(
+        &self,
+        status: Option<String>,
+        tag: Option<String>,
+    ) -> Result<Vec<ContainerModel>> {
+        let mut builder = QueryBuilder::new(
+            "SELECT id, name, description, status, platform, tags, size_bytes, storage_path, settings, status_reason, created_at, updated_at FROM containers WHERE 1 = 1",
         );
-        if status.is_some() {
-            query.push_str(" WHERE status = ?1");
+        if let Some(status) = &status {
+            builder.push(" AND status = ").push_bind(status);
         }
-        query.push_str(" ORDER BY datetime(created_at) DESC;");
-
-        let mut statement = sqlx::query(&query);
-        if let Some(status) = status {
-            statement = statement.bind(status);
+        if let Some(tag) = &tag {
+            builder
+                .push(" AND EXISTS (SELECT 1 FROM json_each(containers.tags) WHERE value = ")
+                .push_bind(tag)
+                .push(")");
         }
+        builder.push(" ORDER BY datetime(created_at) DESC;");
 
-        let rows = statement.fetch_all(&self.pool).await?;
+        let rows = builder.build().fetch_all(&self.pool).await?;
         Ok(rows.into_iter().filter_map(map_container_row).collect())
     }
 
     pub async fn upsert_task(&self, task: &TaskModel) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO tasks (id, type, status, progress, message, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            INSERT INTO tasks (
+                id, type, status, progress, message, result, parent_task_id, step, required,
+                total_steps, attempt, max_attempts, retry_of, next_retry_at, payload, schedule_id,
+                created_at, updated_at
+            )
+            VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18
+            )
             ON CONFLICT(id) DO UPDATE SET
                 type=excluded.type,
                 status=excluded.status,
                 progress=excluded.progress,
                 message=excluded.message,
+                result=excluded.result,
+                parent_task_id=excluded.parent_task_id,
+                step=excluded.step,
+                required=excluded.required,
+                total_steps=excluded.total_steps,
+                attempt=excluded.attempt,
+                max_attempts=excluded.max_attempts,
+                retry_of=excluded.retry_of,
+                next_retry_at=excluded.next_retry_at,
+                payload=excluded.payload,
+                schedule_id=excluded.schedule_id,
                 created_at=excluded.created_at,
                 updated_at=excluded.updated_at;
             "#,
@@ -228,6 +451,17 @@ impl SqliteStore {
         .bind(task.status.as_str())
         .bind(task.progress as i64)
         .bind(&task.message)
+        .bind(task.result.as_ref().map(|result| result.to_string()))
+        .bind(task.parent_task_id.map(|id| id.to_string()))
+        .bind(task.step.map(i64::from))
+        .bind(task.required)
+        .bind(task.total_steps.map(i64::from))
+        .bind(i64::from(task.attempt))
+        .bind(i64::from(task.max_attempts))
+        .bind(task.retry_of.map(|id| id.to_string()))
+        .bind(&task.next_retry_at)
+        .bind(task.payload.as_ref().map(|payload| payload.to_string()))
+        .bind(task.schedule_id.map(|id| id.to_string()))
         .bind(&task.created_at)
         .bind(&task.updated_at)
         .execute(&self.pool)
@@ -238,13 +472,18 @@ impl SqliteStore {
     pub async fn list_tasks(
         &self,
         status: Option<String>,
+        schedule_id: Option<Uuid>,
         limit: Option<i64>,
     ) -> Result<Vec<TaskModel>> {
-        let mut builder = QueryBuilder::new(
-            "SELECT id, type, status, progress, message, created_at, updated_at FROM tasks",
-        );
+        let mut builder =
+            QueryBuilder::new(format!("SELECT {TASK_COLUMNS} FROM tasks WHERE 1 = 1"));
         if let Some(status) = &status {
-            builder.push(" WHERE status = ").push_bind(status);
+            builder.push(" AND status = ").push_bind(status);
+        }
+        if let Some(schedule_id) = schedule_id {
+            builder
+                .push(" AND schedule_id = ")
+                .push_bind(schedule_id.to_string());
         }
         builder.push(" ORDER BY datetime(created_at) DESC");
         if let Some(limit) = limit {
@@ -256,29 +495,255 @@ impl SqliteStore {
     }
 
     pub async fn get_task(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        let row = sqlx::query(&format!("SELECT {TASK_COLUMNS} FROM tasks WHERE id = ?1;"))
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.and_then(map_task_row))
+    }
+
+    /// Reintento ya lanzado de la tarea `id`, si lo hay.
+    pub async fn find_task_retry(&self, id: Uuid) -> Result<Option<TaskModel>> {
+        let row = sqlx::query(&format!(
+            "SELECT {TASK_COLUMNS} FROM tasks WHERE retry_of = ?1 LIMIT 1;"
+        ))
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.and_then(map_task_row))
+    }
+
+    /// Tareas fallidas con un reintento automatico programado que todavia no se lanzo.
+    pub async fn list_pending_retries(&self) -> Result<Vec<TaskModel>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {TASK_COLUMNS}
+            FROM tasks AS task
+            WHERE status = 'failed' AND next_retry_at IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM tasks AS retry WHERE retry.retry_of = task.id)
+            ORDER BY datetime(next_retry_at);
+            "#
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(map_task_row).collect())
+    }
+
+    /// Subtareas directas de `parent`, en el orden de sus pasos.
+    pub async fn list_task_children(&self, parent: Uuid) -> Result<Vec<TaskModel>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {TASK_COLUMNS}
+            FROM tasks WHERE parent_task_id = ?1
+            ORDER BY step, datetime(created_at);
+            "#
+        ))
+        .bind(parent.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(map_task_row).collect())
+    }
+
+    /// Todos los descendientes de `root` a cualquier profundidad, ordenados por paso.
+    pub async fn list_task_descendants(&self, root: Uuid) -> Result<Vec<TaskModel>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH RECURSIVE tree(id) AS (
+                SELECT id FROM tasks WHERE parent_task_id = ?1
+                UNION
+                SELECT t.id FROM tasks t JOIN tree ON t.parent_task_id = tree.id
+            )
+            SELECT {TASK_COLUMNS}
+            FROM tasks WHERE id IN tree
+            ORDER BY step, datetime(created_at);
+            "#
+        ))
+        .bind(root.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(map_task_row).collect())
+    }
+
+    /// Tareas de primer nivel terminadas que la retencion puede borrar, de la mas antigua a
+    /// la mas reciente: las correctas o canceladas anteriores a `cutoff` o que quedan fuera
+    /// de las `keep` mas recientes, y las fallidas anteriores a `failed_cutoff`. Un
+    /// reintento no se borra mientras exista la tarea que reintenta: las cadenas de
+    /// `retry_of` se recortan desde el primer intento.
+    pub async fn list_expired_task_roots(
+        &self,
+        cutoff: Option<OffsetDateTime>,
+        keep: Option<u64>,
+        failed_cutoff: Option<OffsetDateTime>,
+        limit: i64,
+    ) -> Result<Vec<Uuid>> {
+        let format = |value: OffsetDateTime| {
+            value
+                .format(&time::format_description::well_known::Rfc3339)
+                .unwrap_or_default()
+        };
+        let mut builder = QueryBuilder::new(
+            "SELECT id FROM tasks WHERE parent_task_id IS NULL AND \
+             ((status IN ('succeeded', 'cancelled') AND (0",
+        );
+        if let Some(cutoff) = cutoff {
+            builder
+                .push(" OR datetime(updated_at) < datetime(")
+                .push_bind(format(cutoff))
+                .push(")");
+        }
+        if let Some(keep) = keep {
+            builder
+                .push(
+                    " OR id NOT IN (SELECT id FROM tasks WHERE parent_task_id IS NULL \
+                     AND status IN ('succeeded', 'cancelled') \
+                     ORDER BY datetime(updated_at) DESC, rowid DESC LIMIT ",
+                )
+                .push_bind(i64::try_from(keep).unwrap_or(i64::MAX))
+                .push(")");
+        }
+        builder.push("))");
+        if let Some(failed_cutoff) = failed_cutoff {
+            builder
+                .push(" OR (status = 'failed' AND datetime(updated_at) < datetime(")
+                .push_bind(format(failed_cutoff))
+                .push("))");
+        }
+        builder.push(
+            ") AND (retry_of IS NULL OR NOT EXISTS \
+             (SELECT 1 FROM tasks AS original WHERE original.id = tasks.retry_of))",
+        );
+        builder
+            .push(" ORDER BY datetime(updated_at), rowid LIMIT ")
+            .push_bind(limit);
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Uuid::parse_str(&row.get::<String, _>("id")).ok())
+            .collect())
+    }
+
+    /// Las tareas `roots` con todos sus descendientes, cada padre antes que sus subtareas.
+    pub async fn list_task_trees(&self, roots: &[Uuid]) -> Result<Vec<TaskModel>> {
+        if roots.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut builder = QueryBuilder::new(
+            "WITH RECURSIVE tree(id, depth) AS (SELECT id, 0 FROM tasks WHERE id IN (",
+        );
+        let mut ids = builder.separated(", ");
+        for root in roots {
+            ids.push_bind(root.to_string());
+        }
+        builder.push(format!(
+            ") UNION SELECT t.id, tree.depth + 1 FROM tasks t JOIN tree ON t.parent_task_id = tree.id) \
+             SELECT {TASK_COLUMNS} FROM tasks JOIN tree USING (id) \
+             ORDER BY tree.depth, step, datetime(created_at)"
+        ));
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(rows.into_iter().filter_map(map_task_row).collect())
+    }
+
+    pub async fn delete_tasks(&self, ids: &[Uuid]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let mut builder = QueryBuilder::new("DELETE FROM tasks WHERE id IN (");
+        let mut separated = builder.separated(", ");
+        for id in ids {
+            separated.push_bind(id.to_string());
+        }
+        builder.push(")");
+        let result = builder.build().execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_idempotency_record(&self, key: &str) -> Result<Option<IdempotencyRecord>> {
         let row = sqlx::query(
             r#"
-            SELECT id, type, status, progress, message, created_at, updated_at
-            FROM tasks WHERE id = ?1;
+            SELECT key, fingerprint, status, content_type, body, created_at
+            FROM idempotency_keys WHERE key = ?1;
             "#,
         )
-        .bind(id.to_string())
+        .bind(key)
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.and_then(map_task_row))
+        Ok(row.map(|row| IdempotencyRecord {
+            key: row.get("key"),
+            fingerprint: row.get("fingerprint"),
+            status: u16::try_from(row.get::<i64, _>("status")).unwrap_or(200),
+            content_type: row.get("content_type"),
+            body: row.get("body"),
+            created_at: row.get("created_at"),
+        }))
+    }
+
+    /// Guarda la respuesta de `record.key` si no habia otra guardada.
+    pub async fn insert_idempotency_record(&self, record: &IdempotencyRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, status, content_type, body, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(key) DO NOTHING;
+            "#,
+        )
+        .bind(&record.key)
+        .bind(&record.fingerprint)
+        .bind(i64::from(record.status))
+        .bind(&record.content_type)
+        .bind(&record.body)
+        .bind(&record.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Borra las claves guardadas antes de `before`; devuelve cuantas se borraron.
+    pub async fn purge_idempotency_records(&self, before: OffsetDateTime) -> Result<u64> {
+        let before = before
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default();
+        let result =
+            sqlx::query("DELETE FROM idempotency_keys WHERE datetime(created_at) < datetime(?1);")
+                .bind(before)
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
     }
 
     pub async fn insert_app(&self, app: &AppInstance) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO apps (id, container_id, name, version, status, entry_points, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            INSERT INTO apps (
+                id, container_id, name, version, vendor, status, entry_points,
+                installer_path, silent_args, exit_code, log_path, install_dir, uninstaller,
+                install_source, installer_sha256, install_duration_ms, size_bytes,
+                created_at, updated_at
+            )
+            VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19
+            )
             ON CONFLICT(id) DO UPDATE SET
                 name=excluded.name,
                 version=excluded.version,
+                vendor=excluded.vendor,
                 status=excluded.status,
                 entry_points=excluded.entry_points,
+                installer_path=excluded.installer_path,
+                silent_args=excluded.silent_args,
+                exit_code=excluded.exit_code,
+                log_path=excluded.log_path,
+                install_dir=excluded.install_dir,
+                uninstaller=excluded.uninstaller,
+                install_source=excluded.install_source,
+                installer_sha256=excluded.installer_sha256,
+                install_duration_ms=excluded.install_duration_ms,
+                size_bytes=excluded.size_bytes,
                 updated_at=excluded.updated_at;
             "#,
         )
@@ -286,8 +751,19 @@ impl SqliteStore {
         .bind(app.container_id.to_string())
         .bind(&app.name)
         .bind(&app.version)
+        .bind(&app.vendor)
         .bind(app.status.as_str())
         .bind(serde_json::to_string(&app.entry_points)?)
+        .bind(&app.installer_path)
+        .bind(&app.silent_args)
+        .bind(app.exit_code)
+        .bind(&app.log_path)
+        .bind(&app.install_dir)
+        .bind(&app.uninstaller)
+        .bind(&app.install_source)
+        .bind(&app.installer_sha256)
+        .bind(app.install_duration_ms.map(|ms| ms as i64))
+        .bind(app.size_bytes as i64)
         .bind(&app.created_at)
         .bind(&app.updated_at)
         .execute(&self.pool)
@@ -298,7 +774,9 @@ impl SqliteStore {
     pub async fn list_apps(&self, container_id: Uuid) -> Result<Vec<AppInstance>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, container_id, name, version, status, entry_points, created_at, updated_at
+            SELECT id, container_id, name, version, vendor, status, entry_points, installer_path,
+                   silent_args, exit_code, log_path, install_dir, uninstaller, install_source,
+                   installer_sha256, install_duration_ms, size_bytes, created_at, updated_at
             FROM apps WHERE container_id = ?1
             ORDER BY datetime(created_at) DESC;
             "#,
@@ -313,7 +791,9 @@ impl SqliteStore {
     pub async fn get_app(&self, app_id: Uuid) -> Result<Option<AppInstance>> {
         let row = sqlx::query(
             r#"
-            SELECT id, container_id, name, version, status, entry_points, created_at, updated_at
+            SELECT id, container_id, name, version, vendor, status, entry_points, installer_path,
+                   silent_args, exit_code, log_path, install_dir, uninstaller, install_source,
+                   installer_sha256, install_duration_ms, size_bytes, created_at, updated_at
             FROM apps WHERE id = ?1;
             "#,
         )
@@ -324,6 +804,14 @@ impl SqliteStore {
         Ok(row.and_then(map_app_row))
     }
 
+    pub async fn delete_app(&self, app_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM apps WHERE id = ?1;")
+            .bind(app_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn insert_snapshot(&self, snapshot: &Snapshot) -> Result<()> {
         sqlx::query(
             r#"
@@ -377,6 +865,91 @@ impl SqliteStore {
         Ok(row.and_then(map_snapshot_row))
     }
 
+    pub async fn delete_snapshot(&self, snapshot_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM snapshots WHERE id = ?1;")
+            .bind(snapshot_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn upsert_schedule(&self, schedule: &Schedule) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO schedules (
+                id, name, cron, job, enabled, missed_runs, next_run_at, last_run_at,
+                last_task_id, created_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT(id) DO UPDATE SET
+                name=excluded.name,
+                cron=excluded.cron,
+                job=excluded.job,
+                enabled=excluded.enabled,
+                missed_runs=excluded.missed_runs,
+                next_run_at=excluded.next_run_at,
+                last_run_at=excluded.last_run_at,
+                last_task_id=excluded.last_task_id,
+                updated_at=excluded.updated_at;
+            "#,
+        )
+        .bind(schedule.id.to_string())
+        .bind(&schedule.name)
+        .bind(&schedule.cron)
+        .bind(serde_json::to_string(&schedule.job)?)
+        .bind(schedule.enabled)
+        .bind(schedule.missed_runs.as_str())
+        .bind(&schedule.next_run_at)
+        .bind(&schedule.last_run_at)
+        .bind(schedule.last_task_id.map(|id| id.to_string()))
+        .bind(&schedule.created_at)
+        .bind(&schedule.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| match err {
+            SqlxError::Database(db_err) if db_err.is_unique_violation() => {
+                StoreError::DuplicateScheduleName(schedule.name.clone()).into()
+            }
+            other => anyhow::Error::from(other),
+        })?;
+        Ok(())
+    }
+
+    pub async fn list_schedules(&self) -> Result<Vec<Schedule>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, cron, job, enabled, missed_runs, next_run_at, last_run_at,
+                   last_task_id, created_at, updated_at
+            FROM schedules ORDER BY name COLLATE NOCASE;
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().filter_map(map_schedule_row).collect())
+    }
+
+    pub async fn get_schedule(&self, id: Uuid) -> Result<Option<Schedule>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, cron, job, enabled, missed_runs, next_run_at, last_run_at,
+                   last_task_id, created_at, updated_at
+            FROM schedules WHERE id = ?1;
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.and_then(map_schedule_row))
+    }
+
+    pub async fn delete_schedule(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM schedules WHERE id = ?1;")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn create_api_token(
         &self,
         name: String,
@@ -518,6 +1091,23 @@ impl SqliteStore {
         Ok(Some(info))
     }
 
+    /// Borra los tokens de servicio caducados antes de `now`; devuelve cuantos se borraron.
+    pub async fn delete_expired_api_tokens(&self, now: OffsetDateTime) -> Result<u64> {
+        let now = now
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM api_tokens
+            WHERE expires_at IS NOT NULL AND datetime(expires_at) <= datetime(?1);
+            "#,
+        )
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn count_active_tokens(&self) -> Result<i64> {
         let now = now_timestamp();
         let count = sqlx::query_scalar::<_, i64>(
@@ -544,6 +1134,9 @@ fn map_container_row(row: sqlx::sqlite::SqliteRow) -> Option<ContainerModel> {
         platform: Platform::from_str(&row.get::<String, _>("platform")),
         tags: serde_json::from_str(&row.get::<String, _>("tags")).ok()?,
         size_bytes: row.get::<i64, _>("size_bytes") as u64,
+        storage_path: row.get("storage_path"),
+        settings: serde_json::from_str(&row.get::<String, _>("settings")).unwrap_or_default(),
+        status_reason: row.get("status_reason"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     })
@@ -554,8 +1147,33 @@ fn map_task_row(row: sqlx::sqlite::SqliteRow) -> Option<TaskModel> {
         id: Uuid::parse_str(row.get::<String, _>("id").as_str()).ok()?,
         task_type: row.get("type"),
         status: TaskStatus::from_str(&row.get::<String, _>("status")),
-        progress: row.get::<i64, _>("progress") as u8,
+        progress: row.get::<i64, _>("progress").clamp(0, 100) as u8,
         message: row.get("message"),
+        result: row
+            .get::<Option<String>, _>("result")
+            .and_then(|raw| serde_json::from_str(&raw).ok()),
+        parent_task_id: row
+            .get::<Option<String>, _>("parent_task_id")
+            .and_then(|raw| Uuid::parse_str(&raw).ok()),
+        step: row
+            .get::<Option<i64>, _>("step")
+            .and_then(|step| u32::try_from(step).ok()),
+        required: row.get::<i64, _>("required") != 0,
+        total_steps: row
+            .get::<Option<i64>, _>("total_steps")
+            .and_then(|steps| u32::try_from(steps).ok()),
+        attempt: u32::try_from(row.get::<i64, _>("attempt")).unwrap_or(1),
+        max_attempts: u32::try_from(row.get::<i64, _>("max_attempts")).unwrap_or(1),
+        retry_of: row
+            .get::<Option<String>, _>("retry_of")
+            .and_then(|raw| Uuid::parse_str(&raw).ok()),
+        next_retry_at: row.get("next_retry_at"),
+        payload: row
+            .get::<Option<String>, _>("payload")
+            .and_then(|raw| serde_json::from_str(&raw).ok()),
+        schedule_id: row
+            .get::<Option<String>, _>("schedule_id")
+            .and_then(|raw| Uuid::parse_str(&raw).ok()),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     })
@@ -567,8 +1185,21 @@ fn map_app_row(row: sqlx::sqlite::SqliteRow) -> Option<AppInstance> {
         container_id: Uuid::parse_str(row.get::<String, _>("container_id").as_str()).ok()?,
         name: row.get("name"),
         version: row.get("version"),
+        vendor: row.get("vendor"),
         status: AppStatus::from_str(&row.get::<String, _>("status")),
         entry_points: serde_json::from_str(&row.get::<String, _>("entry_points")).ok()?,
+        installer_path: row.get("installer_path"),
+        silent_args: row.get("silent_args"),
+        exit_code: row.get("exit_code"),
+        log_path: row.get("log_path"),
+        install_dir: row.get("install_dir"),
+        uninstaller: row.get("uninstaller"),
+        install_source: row.get("install_source"),
+        installer_sha256: row.get("installer_sha256"),
+        install_duration_ms: row
+            .get::<Option<i64>, _>("install_duration_ms")
+            .map(|ms| ms as u64),
+        size_bytes: row.get::<i64, _>("size_bytes") as u64,
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
     })
@@ -588,6 +1219,27 @@ fn map_snapshot_row(row: sqlx::sqlite::SqliteRow) -> Option<Snapshot> {
     })
 }
 
+fn map_schedule_row(row: sqlx::sqlite::SqliteRow) -> Option<Schedule> {
+    Some(Schedule {
+        id: Uuid::parse_str(row.get::<String, _>("id").as_str()).ok()?,
+        name: row.get("name"),
+        cron: row.get("cron"),
+        job: serde_json::from_str(&row.get::<String, _>("job")).ok()?,
+        enabled: row.get::<i64, _>("enabled") != 0,
+        missed_runs: row
+            .get::<String, _>("missed_runs")
+            .parse::<MissedRunPolicy>()
+            .unwrap_or_default(),
+        next_run_at: row.get("next_run_at"),
+        last_run_at: row.get("last_run_at"),
+        last_task_id: row
+            .get::<Option<String>, _>("last_task_id")
+            .and_then(|raw| Uuid::parse_str(&raw).ok()),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
 fn now_timestamp() -> String {
     time::OffsetDateTime::now_utc()
         .format(&time::format_description::well_known::Rfc3339)
@@ -0,0 +1,165 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::{
+    models::{ContainerLimits, ContainerSettings},
+    virtualization::{MountSpec, Platform, RegistrySeed},
+};
+
+const EXTENSION: &str = "toml";
+
+/// Receta declarativa de un contenedor: plataforma, ajustes, valores de registro y apps
+/// que se instalan en orden. Se guarda como `<data_dir>/templates/<name>.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerTemplate {
+    /// Nombre de la plantilla; sale del nombre del archivo.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub platform: Platform,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub compat_flags: Vec<String>,
+    #[serde(default)]
+    pub limits: ContainerLimits,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+    #[serde(default)]
+    pub registry: Vec<RegistrySeed>,
+    #[serde(default)]
+    pub apps: Vec<TemplateApp>,
+}
+
+impl ContainerTemplate {
+    pub fn settings(&self) -> ContainerSettings {
+        ContainerSettings {
+            env: self.env.clone(),
+            compat_flags: self.compat_flags.clone(),
+            limits: self.limits.clone(),
+            mounts: self.mounts.clone(),
+        }
+    }
+}
+
+/// App que se instala al crear el contenedor; mismos campos que `POST .../apps`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TemplateApp {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub vendor: Option<String>,
+    #[serde(default)]
+    pub installer_path: Option<String>,
+    #[serde(default)]
+    pub archive_path: Option<String>,
+    #[serde(default)]
+    pub silent_args: Option<String>,
+}
+
+impl TemplateApp {
+    /// Nombre con el que se informa el progreso de la instalacion.
+    pub fn label(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| {
+                self.installer_path
+                    .as_deref()
+                    .or(self.archive_path.as_deref())
+                    .and_then(|path| Path::new(path.trim()).file_name())
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "Aplicacion".into())
+    }
+}
+
+/// Plantillas guardadas como archivos TOML en un directorio.
+#[derive(Clone)]
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.{EXTENSION}"))
+    }
+
+    /// Plantillas ordenadas por nombre; los archivos que no se pueden leer se omiten.
+    pub async fn list(&self) -> Result<Vec<ContainerTemplate>> {
+        let mut entries = match fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut templates = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            match self.get(name).await {
+                Ok(Some(template)) => templates.push(template),
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(?path, error = %format!("{err:#}"), "Plantilla ilegible")
+                }
+            }
+        }
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    pub async fn get(&self, name: &str) -> Result<Option<ContainerTemplate>> {
+        let path = self.path(name);
+        let raw = match fs::read_to_string(&path).await {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let mut template: ContainerTemplate = toml::from_str(&raw)
+            .with_context(|| format!("Plantilla invalida: {}", path.display()))?;
+        template.name = name.to_string();
+        Ok(Some(template))
+    }
+
+    /// Crea o reemplaza la plantilla; devuelve `true` si no existia.
+    pub async fn save(&self, template: &ContainerTemplate) -> Result<bool> {
+        let path = self.path(&template.name);
+        let created = fs::metadata(&path).await.is_err();
+        let stored = ContainerTemplate {
+            name: String::new(),
+            ..template.clone()
+        };
+        fs::create_dir_all(&self.dir).await?;
+        fs::write(&path, toml::to_string_pretty(&stored)?).await?;
+        Ok(created)
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        match fs::remove_file(self.path(name)).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
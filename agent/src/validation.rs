@@ -0,0 +1,213 @@
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
+    http::header::CONTENT_TYPE,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+
+use crate::error::{ApiError, ApiJson, ErrorCode};
+
+/// Error asociado a un campo concreto del cuerpo de la peticion.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// Acumula errores por campo; se convierte en un `422 validation_failed`.
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn push(&mut self, field: impl Into<String>, code: &'static str, params: Option<Value>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            code,
+            params,
+        });
+    }
+
+    /// Exige un texto no vacio tras recortar espacios.
+    pub fn required(&mut self, field: &str, value: &str) -> bool {
+        if value.trim().is_empty() {
+            self.push(field, "required", None);
+            return false;
+        }
+        true
+    }
+
+    pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) {
+        let len = value.trim().chars().count();
+        if len < min || len > max {
+            self.push(
+                field,
+                "invalid_length",
+                Some(serde_json::json!({ "min": min, "max": max })),
+            );
+        }
+    }
+
+    /// Rechaza caracteres de control (saltos de linea, NUL, escapes).
+    pub fn printable(&mut self, field: &str, value: &str) {
+        if value.chars().any(char::is_control) {
+            self.push(field, "invalid_characters", None);
+        }
+    }
+
+    /// Valida que todos los caracteres cumplan `allowed`.
+    pub fn charset(&mut self, field: &str, value: &str, allowed: fn(char) -> bool) {
+        if !value.chars().all(allowed) {
+            self.push(field, "invalid_characters", None);
+        }
+    }
+
+    pub fn one_of(&mut self, field: &str, value: &str, allowed: &[&str]) {
+        if !allowed.contains(&value) {
+            self.push(
+                field,
+                "unknown_value",
+                Some(serde_json::json!({ "allowed": allowed })),
+            );
+        }
+    }
+
+    pub fn range<T>(&mut self, field: &str, value: T, min: T, max: T)
+    where
+        T: PartialOrd + Serialize,
+    {
+        if value < min || value > max {
+            self.push(
+                field,
+                "out_of_range",
+                Some(serde_json::json!({ "min": min, "max": max })),
+            );
+        }
+    }
+
+    pub fn rfc3339(&mut self, field: &str, value: &str) -> Option<OffsetDateTime> {
+        match OffsetDateTime::parse(value, &Rfc3339) {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                self.push(field, "invalid_datetime", None);
+                None
+            }
+        }
+    }
+
+    /// Valida un elemento anidado; sus errores llevan `prefix` delante del campo.
+    pub fn nested(&mut self, prefix: &str, validate: impl FnOnce(&mut Validator)) {
+        let mut inner = Validator::default();
+        validate(&mut inner);
+        self.errors
+            .extend(inner.errors.into_iter().map(|error| FieldError {
+                field: format!("{prefix}.{}", error.field),
+                ..error
+            }));
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    pub fn finish(self) -> Result<(), ApiError> {
+        self.finish_as(ErrorCode::ValidationFailed)
+    }
+
+    /// Como [`Validator::finish`], pero con otro codigo en el cuerpo del error.
+    pub fn finish_as(self, code: ErrorCode) -> Result<(), ApiError> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        Err(ApiError::new(code).with_details(serde_json::json!({ "fields": self.errors })))
+    }
+}
+
+/// Reglas declarativas de un cuerpo de peticion.
+pub trait Validate {
+    fn validate(&self, v: &mut Validator);
+
+    /// Codigo con el que se rechaza el cuerpo; algunos errores conservan un codigo propio.
+    fn error_code(&self, _errors: &[FieldError]) -> ErrorCode {
+        ErrorCode::ValidationFailed
+    }
+}
+
+/// `Json` que ademas ejecuta [`Validate`] antes de llegar al handler.
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let ApiJson(value) = ApiJson::<T>::from_request(req, state).await?;
+        let mut validator = Validator::default();
+        value.validate(&mut validator);
+        let code = value.error_code(validator.errors());
+        validator.finish_as(code)?;
+        Ok(Self(value))
+    }
+}
+
+pub fn is_tag_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')
+}
+
+pub fn is_env_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+pub fn is_scope_char(c: char) -> bool {
+    c.is_ascii_lowercase() || matches!(c, ':' | '_' | '-' | '*')
+}
+
+/// Como [`ValidatedJson`], pero con `Content-Type: application/toml` el cuerpo se lee
+/// como TOML. Lo usan los recursos que tambien se guardan como archivos TOML.
+pub struct ValidatedDocument<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedDocument<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_toml = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.trim().starts_with("application/toml"));
+        if !is_toml {
+            let ValidatedJson(value) = ValidatedJson::<T>::from_request(req, state).await?;
+            return Ok(Self(value));
+        }
+
+        let invalid = |reason: String| {
+            ApiError::new(ErrorCode::InvalidBody)
+                .with_details(serde_json::json!({ "reason": reason }))
+        };
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|rejection| invalid(rejection.body_text()))?;
+        let text = std::str::from_utf8(&bytes).map_err(|err| invalid(err.to_string()))?;
+        let value: T = toml::from_str(text).map_err(|err| invalid(err.message().to_string()))?;
+        let mut validator = Validator::default();
+        value.validate(&mut validator);
+        let code = value.error_code(validator.errors());
+        validator.finish_as(code)?;
+        Ok(Self(value))
+    }
+}
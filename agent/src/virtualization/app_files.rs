@@ -0,0 +1,197 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tokio::fs;
+use uuid::Uuid;
+
+use super::filesystem::{FsChanges, virtual_path};
+use super::manifest;
+use crate::security::sha256_file;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppFileChange {
+    /// La app creo el archivo; le pertenece.
+    Created,
+    /// El archivo ya existia y la app lo reescribio.
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppFile {
+    /// Ruta vista desde el contenedor (`C:\Program Files\...`).
+    pub path: String,
+    /// Ruta relativa a `fs/`.
+    pub relative_path: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+    pub change: AppFileChange,
+}
+
+/// Archivos que escribio una app al instalarse, guardados en `app_files` de
+/// `runtime/manifest.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppFileManifest {
+    pub app_id: Uuid,
+    pub generated_at: String,
+    pub total_bytes: u64,
+    pub files: Vec<AppFile>,
+}
+
+impl AppFileManifest {
+    pub fn empty(app_id: Uuid) -> Self {
+        Self {
+            app_id,
+            generated_at: now(),
+            total_bytes: 0,
+            files: vec![],
+        }
+    }
+
+    /// Incorpora los cambios de una instalacion o actualizacion: los archivos nuevos
+    /// pasan a ser de la app, los reescritos se actualizan y los borrados se olvidan.
+    pub async fn apply(&mut self, fs_root: &Path, changes: &FsChanges) -> Result<()> {
+        let mut files = self
+            .files
+            .drain(..)
+            .map(|file| (file.relative_path.clone(), file))
+            .collect::<BTreeMap<_, _>>();
+        for relative in changes.removed.keys() {
+            files.remove(relative);
+        }
+
+        let touched = changes
+            .added
+            .keys()
+            .map(|path| (path, AppFileChange::Created))
+            .chain(
+                changes
+                    .modified
+                    .keys()
+                    .map(|path| (path, AppFileChange::Modified)),
+            )
+            .map(|(path, change)| (path.clone(), change))
+            .collect::<Vec<_>>();
+        let root = fs_root.to_path_buf();
+        let hashed = tokio::task::spawn_blocking(move || {
+            touched
+                .into_iter()
+                .map(|(relative, change)| {
+                    let physical = root.join(&relative);
+                    let size = std::fs::metadata(&physical)?.len();
+                    let sha256 = sha256_file(&physical)?;
+                    Ok((relative, change, size, sha256))
+                })
+                .collect::<std::io::Result<Vec<_>>>()
+        })
+        .await?
+        .context("No se pudieron leer los archivos instalados")?;
+
+        for (relative, change, size, sha256) in hashed {
+            // Un archivo que la app creo sigue siendo suyo aunque una actualizacion lo reescriba.
+            let change = match files.get(&relative) {
+                Some(existing) => existing.change,
+                None => change,
+            };
+            files.insert(
+                relative.clone(),
+                AppFile {
+                    path: virtual_path(&relative),
+                    relative_path: relative,
+                    size,
+                    sha256,
+                    change,
+                },
+            );
+        }
+
+        self.files = files.into_values().collect();
+        self.total_bytes = self.files.iter().map(|file| file.size).sum();
+        self.generated_at = now();
+        Ok(())
+    }
+
+    /// Archivos creados por la app, que pueden eliminarse al desinstalarla.
+    pub fn owned(&self) -> impl Iterator<Item = &AppFile> {
+        self.files
+            .iter()
+            .filter(|file| file.change == AppFileChange::Created)
+    }
+}
+
+/// Directorio donde los agentes anteriores guardaban un JSON por app.
+fn legacy_dir(root: &Path) -> PathBuf {
+    root.join("runtime").join("apps")
+}
+
+/// Lee el manifest de archivos de la app desde `runtime/manifest.json`; `None` si
+/// nunca se capturo.
+pub async fn load(root: &Path, app_id: Uuid) -> Result<Option<AppFileManifest>> {
+    if let Some(loaded) = manifest::load(root).await?
+        && let Some(files) = loaded.manifest.app_files.get(&app_id)
+    {
+        return Ok(Some(files.clone()));
+    }
+    Ok(load_legacy(root).await?.remove(&app_id))
+}
+
+pub async fn save(root: &Path, files: &AppFileManifest) -> Result<()> {
+    let files = files.clone();
+    manifest::update_app_files(root, move |apps| {
+        apps.insert(files.app_id, files);
+    })
+    .await
+}
+
+pub async fn remove(root: &Path, app_id: Uuid) -> Result<()> {
+    manifest::update_app_files(root, |apps| {
+        apps.remove(&app_id);
+    })
+    .await
+}
+
+/// Manifests de `runtime/apps/<app_id>.json` que dejaron agentes anteriores.
+pub(super) async fn load_legacy(root: &Path) -> Result<BTreeMap<Uuid, AppFileManifest>> {
+    let dir = legacy_dir(root);
+    let mut entries = match fs::read_dir(&dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut legacy = BTreeMap::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let raw = fs::read(&path).await?;
+        match serde_json::from_slice::<AppFileManifest>(&raw) {
+            Ok(files) => {
+                legacy.insert(files.app_id, files);
+            }
+            Err(err) => tracing::warn!(?path, %err, "Manifest de archivos invalido"),
+        }
+    }
+    Ok(legacy)
+}
+
+/// Borra los JSON de `ids` ya incorporados al manifest y el directorio si queda vacio.
+pub(super) async fn remove_legacy(root: &Path, ids: impl Iterator<Item = &Uuid>) -> Result<()> {
+    let dir = legacy_dir(root);
+    for app_id in ids {
+        match fs::remove_file(dir.join(format!("{app_id}.json"))).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+    let _ = fs::remove_dir(&dir).await;
+    Ok(())
+}
+
+fn now() -> String {
+    OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".into())
+}
@@ -0,0 +1,308 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Manifest opcional dentro del archivo con metadatos de la app.
+const APP_MANIFEST: &str = "orbit-app.json";
+/// Convencion de PortableApps.com para apps portables.
+const PORTABLEAPPS_INFO: &str = "App/AppInfo/appinfo.ini";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    pub const EXTENSIONS: &'static [&'static str] = &[".zip", ".tar", ".tar.gz", ".tgz"];
+
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExtractedArchive {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Extrae `archive` en `dest`. Rechaza entradas absolutas, con `..` o enlaces, y
+/// elimina el directorio raiz comun cuando todo el contenido cuelga de uno solo.
+/// Es bloqueante: llamar desde `spawn_blocking`.
+pub fn extract(archive: &Path, dest: &Path) -> Result<ExtractedArchive> {
+    let format = ArchiveFormat::detect(archive)
+        .with_context(|| format!("Formato de archivo no soportado: {}", archive.display()))?;
+    let names = match format {
+        ArchiveFormat::Zip => zip_entries(archive)?,
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => tar_entries(archive, format)?,
+    };
+    let strip = common_root(&names);
+
+    fs::create_dir_all(dest)?;
+    match format {
+        ArchiveFormat::Zip => extract_zip(archive, dest, strip.as_deref()),
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            extract_tar(archive, format, dest, strip.as_deref())
+        }
+    }
+}
+
+/// Empaqueta `source` en el `.tar.gz` `target`, sin las entradas de primer nivel de
+/// `skip`, y devuelve su tamano. Se escribe en un `.partial` que se renombra al
+/// terminar. Es bloqueante: llamar desde `spawn_blocking`.
+pub fn pack_tar_gz(source: &Path, target: &Path, skip: &[&str]) -> Result<u64> {
+    write_via_partial(target, |partial| write_tar_gz(source, partial, skip))
+}
+
+/// Escribe `items` en `target` como NDJSON comprimido con gzip, un objeto por linea, y
+/// devuelve su tamano. Igual que [`pack_tar_gz`], pasa por un `.partial` y es bloqueante.
+pub fn pack_ndjson_gz<T: Serialize>(items: &[T], target: &Path) -> Result<u64> {
+    write_via_partial(target, |partial| {
+        let mut encoder = flate2::write::GzEncoder::new(
+            io::BufWriter::new(File::create(partial)?),
+            flate2::Compression::default(),
+        );
+        for item in items {
+            serde_json::to_writer(&mut encoder, item)?;
+            encoder.write_all(b"\n")?;
+        }
+        encoder.finish()?.flush()?;
+        Ok(())
+    })
+}
+
+fn write_via_partial(target: &Path, write: impl FnOnce(&Path) -> Result<()>) -> Result<u64> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut partial = target.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let written = write(&partial).and_then(|_| {
+        fs::rename(&partial, target)?;
+        Ok(fs::metadata(target)?.len())
+    });
+    if written.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    written
+}
+
+fn write_tar_gz(source: &Path, target: &Path, skip: &[&str]) -> Result<()> {
+    let encoder =
+        flate2::write::GzEncoder::new(File::create(target)?, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+    let entries = WalkDir::new(source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() > 1 || !skip.iter().any(|name| entry.file_name() == *name)
+        });
+    for entry in entries {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        builder
+            .append_path_with_name(entry.path(), relative)
+            .with_context(|| format!("No se pudo empaquetar {}", entry.path().display()))?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn zip_entries(archive: &Path) -> Result<Vec<PathBuf>> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+    let mut names = Vec::with_capacity(zip.len());
+    for index in 0..zip.len() {
+        let entry = zip.by_index(index)?;
+        if entry.is_symlink() {
+            bail!("El archivo contiene un enlace simbolico: {}", entry.name());
+        }
+        names.push(safe_relative(entry.name())?);
+    }
+    Ok(names)
+}
+
+fn extract_zip(archive: &Path, dest: &Path, strip: Option<&Path>) -> Result<ExtractedArchive> {
+    let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+    let mut summary = ExtractedArchive::default();
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+        let Some(target) = target_path(dest, &safe_relative(entry.name())?, strip) else {
+            continue;
+        };
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        summary.bytes += write_file(&mut entry, &target)?;
+        summary.files += 1;
+    }
+    Ok(summary)
+}
+
+fn open_tar(archive: &Path, format: ArchiveFormat) -> Result<tar::Archive<Box<dyn Read>>> {
+    let file = File::open(archive)?;
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarGz => Box::new(flate2::read::GzDecoder::new(file)),
+        _ => Box::new(file),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+fn tar_entries(archive: &Path, format: ArchiveFormat) -> Result<Vec<PathBuf>> {
+    let mut tar = open_tar(archive, format)?;
+    let mut names = Vec::new();
+    for entry in tar.entries()? {
+        let entry = entry?;
+        let kind = entry.header().entry_type();
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if kind.is_symlink() || kind.is_hard_link() {
+            bail!("El archivo contiene un enlace: {name}");
+        }
+        if kind.is_file() || kind.is_dir() {
+            names.push(safe_relative(&name)?);
+        }
+    }
+    Ok(names)
+}
+
+fn extract_tar(
+    archive: &Path,
+    format: ArchiveFormat,
+    dest: &Path,
+    strip: Option<&Path>,
+) -> Result<ExtractedArchive> {
+    let mut tar = open_tar(archive, format)?;
+    let mut summary = ExtractedArchive::default();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if !kind.is_file() && !kind.is_dir() {
+            continue;
+        }
+        let relative = safe_relative(&entry.path()?.to_string_lossy())?;
+        let Some(target) = target_path(dest, &relative, strip) else {
+            continue;
+        };
+        if kind.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        summary.bytes += write_file(&mut entry, &target)?;
+        summary.files += 1;
+    }
+    Ok(summary)
+}
+
+fn write_file(reader: &mut impl Read, target: &Path) -> Result<u64> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file =
+        File::create(target).with_context(|| format!("No se pudo crear {}", target.display()))?;
+    Ok(io::copy(reader, &mut file)?)
+}
+
+/// Normaliza el nombre de una entrada y rechaza rutas que escapen del destino.
+fn safe_relative(name: &str) -> Result<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    if normalized.starts_with('/') || normalized.contains(':') {
+        bail!("Entrada con ruta absoluta en el archivo: {name}");
+    }
+    let mut clean = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            _ => bail!("Entrada fuera del directorio de destino: {name}"),
+        }
+    }
+    Ok(clean)
+}
+
+fn target_path(dest: &Path, relative: &Path, strip: Option<&Path>) -> Option<PathBuf> {
+    let relative = match strip {
+        Some(prefix) => relative.strip_prefix(prefix).ok()?,
+        None => relative,
+    };
+    (!relative.as_os_str().is_empty()).then(|| dest.join(relative))
+}
+
+/// Directorio de primer nivel compartido por todas las entradas, si lo hay.
+fn common_root(names: &[PathBuf]) -> Option<PathBuf> {
+    let mut root: Option<&std::ffi::OsStr> = None;
+    let mut nested = false;
+    for name in names.iter().filter(|name| !name.as_os_str().is_empty()) {
+        let mut components = name.components();
+        let first = components.next()?.as_os_str();
+        if root.is_some_and(|current| current != first) {
+            return None;
+        }
+        root = Some(first);
+        nested |= components.next().is_some();
+    }
+    // Un unico archivo suelto en la raiz no es un directorio contenedor.
+    root.filter(|_| nested).map(PathBuf::from)
+}
+
+/// Metadatos declarados por la propia app portable.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AppMetadata {
+    pub name: Option<String>,
+    pub vendor: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Lee `orbit-app.json` o, en su defecto, `App/AppInfo/appinfo.ini` de PortableApps.
+pub fn read_app_metadata(dir: &Path) -> AppMetadata {
+    if let Ok(raw) = fs::read_to_string(dir.join(APP_MANIFEST)) {
+        match serde_json::from_str(&raw) {
+            Ok(metadata) => return metadata,
+            Err(err) => tracing::warn!(?err, "Manifest {APP_MANIFEST} invalido; se ignora"),
+        }
+    }
+    fs::read_to_string(dir.join(PORTABLEAPPS_INFO))
+        .map(|raw| parse_appinfo(&raw))
+        .unwrap_or_default()
+}
+
+fn parse_appinfo(raw: &str) -> AppMetadata {
+    let mut metadata = AppMetadata::default();
+    let mut section = String::new();
+    for line in raw.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.to_ascii_lowercase();
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match (section.as_str(), key.trim().to_ascii_lowercase().as_str()) {
+            ("details", "name") => metadata.name = Some(value),
+            ("details", "publisher") => metadata.vendor = Some(value),
+            ("version", "displayversion") => metadata.version = Some(value),
+            ("version", "packageversion") if metadata.version.is_none() => {
+                metadata.version = Some(value)
+            }
+            _ => {}
+        }
+    }
+    metadata
+}
@@ -0,0 +1,35 @@
+use serde::Serialize;
+
+/// Flag de compatibilidad reconocido por el runtime.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CompatFlag {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const COMPAT_FLAGS: &[CompatFlag] = &[
+    CompatFlag {
+        name: "force_32bit_path",
+        description: "Redirige Program Files a Program Files (x86) para instaladores de 32 bits",
+    },
+    CompatFlag {
+        name: "disable_hw_accel",
+        description: "Desactiva la aceleracion por hardware en procesos del contenedor",
+    },
+    CompatFlag {
+        name: "high_dpi_unaware",
+        description: "Fuerza el escalado de sistema para apps sin soporte DPI",
+    },
+    CompatFlag {
+        name: "run_as_invoker",
+        description: "Evita solicitudes de elevacion UAC al lanzar la app",
+    },
+    CompatFlag {
+        name: "windows7_mode",
+        description: "Reporta Windows 7 como version del sistema operativo",
+    },
+];
+
+pub fn is_known_flag(name: &str) -> bool {
+    COMPAT_FLAGS.iter().any(|flag| flag.name == name)
+}
@@ -1,49 +1,176 @@
 use anyhow::Result;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 use tokio::fs;
+use walkdir::WalkDir;
 
-const REQUIRED_DIRS: &[&str] = &["fs", "registry", "runtime", "logs"];
+use super::Platform;
+use super::paths::{MappedPath, PathMapper, join_child};
+use crate::models::ContainerSettings;
+
+/// Traductor con los montajes por defecto, para rutas relativas a `fs/`.
+static DEFAULT_MAPPER: LazyLock<PathMapper> =
+    LazyLock::new(|| PathMapper::new("", &Platform::WindowsX64, &[], &BTreeMap::new()));
+
+pub(super) const REQUIRED_DIRS: &[&str] = &["fs", "registry", "runtime", "logs"];
 
 #[derive(Clone, Debug)]
 pub struct FsLayer {
     root: PathBuf,
+    mapper: PathMapper,
 }
 
 impl FsLayer {
     pub fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref().to_path_buf();
         Self {
-            root: root.as_ref().to_path_buf(),
+            mapper: PathMapper::new(
+                root.join("fs"),
+                &Platform::WindowsX64,
+                &[],
+                &BTreeMap::new(),
+            ),
+            root,
         }
     }
 
+    /// Aplica los montajes de la plataforma y los montajes y variables de entorno
+    /// configurados en el contenedor.
+    pub fn with_settings(mut self, platform: &Platform, settings: &ContainerSettings) -> Self {
+        self.mapper = PathMapper::new(self.fs_root(), platform, &settings.mounts, &settings.env);
+        self
+    }
+
+    pub fn mapper(&self) -> &PathMapper {
+        &self.mapper
+    }
+
+    /// Ruta vista desde el contenedor para `relative` (relativa a `fs/`).
+    pub fn virtual_path(&self, relative: &Path) -> String {
+        self.mapper
+            .to_virtual(relative)
+            .unwrap_or_else(|| virtual_path(relative))
+    }
+
     pub async fn prepare(&self) -> Result<()> {
         for dir in REQUIRED_DIRS {
             fs::create_dir_all(self.root.join(dir)).await?;
         }
+        self.prepare_mounts().await
+    }
 
-        // Pre-crear directorios típicos para overlays.
-        for rel in [
-            "ProgramFiles",
-            "ProgramData",
-            "Users\\Default\\AppData\\Local",
-        ] {
-            fs::create_dir_all(self.root.join("fs").join(rel)).await?;
+    /// Crea los directorios de los overlays de la tabla de montajes. Los binds no se
+    /// tocan: su origen es del host.
+    pub async fn prepare_mounts(&self) -> Result<()> {
+        let fs_root = self.fs_root();
+        for dir in self.mapper.overlay_dirs() {
+            fs::create_dir_all(fs_root.join(dir)).await?;
         }
-
         Ok(())
     }
 
+    pub fn fs_root(&self) -> PathBuf {
+        self.root.join("fs")
+    }
+
+    /// Resuelve una ruta del contenedor a su ruta fisica canonica, siguiendo enlaces.
+    /// Devuelve `None` si la ruta o alguno de sus enlaces sale de `fs/` (o del origen
+    /// del bind que la resuelve). La ruta no tiene por que existir: se comprueba el
+    /// ancestro existente mas cercano.
+    pub async fn resolve(&self, path: &str) -> Option<MappedPath> {
+        let mut mapped = self.mapper.to_physical(path).ok()?;
+        let base = fs::canonicalize(&mapped.base).await.ok()?;
+
+        let mut pending = vec![];
+        let mut existing = mapped.physical.as_path();
+        while fs::symlink_metadata(existing).await.is_err() {
+            pending.push(existing.file_name()?.to_os_string());
+            existing = existing.parent()?;
+        }
+        let mut resolved = fs::canonicalize(existing).await.ok()?;
+        if !resolved.starts_with(&base) {
+            return None;
+        }
+        resolved.extend(pending.iter().rev());
+        mapped.physical = resolved;
+        mapped.base = base;
+        Some(mapped)
+    }
+
+    /// Describe una ruta ya resuelta con [`FsLayer::resolve`].
+    pub async fn describe(&self, path: &MappedPath) -> Result<FsEntry> {
+        let metadata = fs::symlink_metadata(&path.physical).await?;
+        let virtual_path = match &path.relative {
+            Some(relative) => self.virtual_path(relative),
+            None => path.virtual_path.clone(),
+        };
+        Ok(FsEntry::new(virtual_path, &metadata))
+    }
+
+    /// Contenido de un directorio ya resuelto con [`FsLayer::resolve`]. Los enlaces se
+    /// listan como tales, sin seguirlos.
+    pub async fn list_dir(&self, dir: &MappedPath) -> Result<Vec<FsEntry>> {
+        let mut entries = vec![];
+        let mut reader = fs::read_dir(&dir.physical).await?;
+        while let Some(entry) = reader.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            // Los hijos de un overlay pueden ser a su vez destino de otro montaje
+            // (`fs/ProgramFiles` es `C:\Program Files`).
+            let virtual_path = match &dir.relative {
+                Some(relative) => self.virtual_path(&relative.join(entry.file_name())),
+                None => join_child(&dir.virtual_path, &entry.file_name().to_string_lossy()),
+            };
+            entries.push(FsEntry::new(virtual_path, &metadata));
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Inventario de archivos bajo `fs/`, relativo a la raiz del overlay.
+    pub async fn inventory(&self) -> Result<FsInventory> {
+        let fs_root = self.fs_root();
+        let files = tokio::task::spawn_blocking(move || {
+            WalkDir::new(&fs_root)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    let relative = entry.path().strip_prefix(&fs_root).ok()?.to_path_buf();
+                    let stamp = FileStamp {
+                        size: metadata.len(),
+                        modified: metadata.modified().ok(),
+                    };
+                    Some((relative, stamp))
+                })
+                .collect::<BTreeMap<_, _>>()
+        })
+        .await?;
+        Ok(FsInventory { files })
+    }
+
     pub fn snapshot(&self) -> FsSnapshot {
-        let mount_root = self.root.join("fs");
-        let overlays = vec![
-            VirtualMount::new("C:\\\\Program Files", mount_root.join("ProgramFiles")),
-            VirtualMount::new("C:\\\\ProgramData", mount_root.join("ProgramData")),
-            VirtualMount::new(
-                "%LOCALAPPDATA%",
-                mount_root.join("Users/Default/AppData/Local"),
-            ),
-        ];
+        let mount_root = self.fs_root();
+        let overlays = self
+            .mapper
+            .mounts()
+            .into_iter()
+            .map(|mount| match mount.source {
+                Some(source) => VirtualMount {
+                    kind: MountKind::Bind,
+                    read_only: mount.read_only,
+                    ..VirtualMount::new(mount.virtual_path, source)
+                },
+                None => VirtualMount {
+                    read_only: mount.read_only,
+                    ..VirtualMount::new(mount.virtual_path, mount_root.join(mount.target))
+                },
+            })
+            .collect();
 
         FsSnapshot {
             mount_root: mount_root.display().to_string(),
@@ -52,16 +179,134 @@ impl FsLayer {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Foto de los archivos de `fs/`; comparando dos se obtiene lo que cambio una instalacion.
+#[derive(Debug, Clone, Default)]
+pub struct FsInventory {
+    files: BTreeMap<PathBuf, FileStamp>,
+}
+
+/// Diferencia entre dos inventarios.
+#[derive(Debug, Clone, Default)]
+pub struct FsChanges {
+    pub added: BTreeMap<PathBuf, u64>,
+    /// Archivos reescritos: tamano anterior y nuevo.
+    pub modified: BTreeMap<PathBuf, (u64, u64)>,
+    pub removed: BTreeMap<PathBuf, u64>,
+}
+
+impl FsInventory {
+    /// Ejecutables (`.exe`) del inventario.
+    pub fn executables(&self) -> BTreeSet<PathBuf> {
+        self.files
+            .keys()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn changes_since(&self, before: &FsInventory) -> FsChanges {
+        let mut changes = FsChanges::default();
+        for (path, stamp) in &self.files {
+            match before.files.get(path) {
+                None => {
+                    changes.added.insert(path.clone(), stamp.size);
+                }
+                Some(previous) if previous != stamp => {
+                    changes
+                        .modified
+                        .insert(path.clone(), (previous.size, stamp.size));
+                }
+                Some(_) => {}
+            }
+        }
+        for (path, stamp) in &before.files {
+            if !self.files.contains_key(path) {
+                changes.removed.insert(path.clone(), stamp.size);
+            }
+        }
+        changes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FsEntryKind {
+    File,
+    Directory,
+    Symlink,
+}
+
+/// Entrada de un directorio del contenedor.
+#[derive(Debug, Clone, Serialize)]
+pub struct FsEntry {
+    pub name: String,
+    /// Ruta vista desde el contenedor (`C:\Program Files\...`).
+    pub path: String,
+    pub kind: FsEntryKind,
+    pub size: u64,
+    pub modified: Option<String>,
+}
+
+impl FsEntry {
+    /// Describe `path` (ruta del contenedor) a partir de sus metadatos sin seguir enlaces.
+    pub fn new(path: String, metadata: &std::fs::Metadata) -> Self {
+        let kind = if metadata.is_symlink() {
+            FsEntryKind::Symlink
+        } else if metadata.is_dir() {
+            FsEntryKind::Directory
+        } else {
+            FsEntryKind::File
+        };
+        Self {
+            name: path.rsplit('\\').next().unwrap_or_default().to_string(),
+            size: if kind == FsEntryKind::File {
+                metadata.len()
+            } else {
+                0
+            },
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| OffsetDateTime::from(time).format(&Rfc3339).ok()),
+            path,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct FsSnapshot {
     pub mount_root: String,
     pub overlays: Vec<VirtualMount>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MountKind {
+    /// Directorio de `fs/`.
+    #[default]
+    Overlay,
+    /// Directorio del host montado tal cual.
+    Bind,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VirtualMount {
     pub virtual_path: String,
     pub physical_path: String,
+    #[serde(default)]
+    pub kind: MountKind,
+    #[serde(default)]
+    pub read_only: bool,
 }
 
 impl VirtualMount {
@@ -69,6 +314,24 @@ impl VirtualMount {
         Self {
             virtual_path: path.into(),
             physical_path: physical.as_ref().display().to_string(),
+            kind: MountKind::Overlay,
+            read_only: false,
         }
     }
 }
+
+/// Traduce una ruta relativa a `fs/` a la ruta que ve la aplicacion dentro del
+/// contenedor, con los montajes por defecto.
+pub fn virtual_path(relative: &Path) -> String {
+    DEFAULT_MAPPER
+        .to_virtual(relative)
+        .unwrap_or_else(|| "C:\\".to_string())
+}
+
+/// Inverso de [`virtual_path`]: traduce una ruta del contenedor (`C:\Program Files\...`,
+/// `%PROGRAMDATA%\...` o relativa a `C:\`) a la ruta fisica. Devuelve `None` si sale
+/// del contenedor o apunta a la raiz.
+pub fn resolve_virtual_path(fs_root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = DEFAULT_MAPPER.to_physical(path).ok()?.relative?;
+    (!relative.as_os_str().is_empty()).then(|| fs_root.join(relative))
+}
@@ -0,0 +1,246 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use super::app_files::AppFileManifest;
+use super::filesystem::REQUIRED_DIRS;
+use super::paths::PathMapper;
+use super::registry::HIVES;
+use super::snapshot::{MANIFEST_FILE, SnapshotLayer};
+use crate::security::sha256_file;
+
+/// Lo que se espera encontrar de una app instalada en el contenedor.
+#[derive(Debug, Clone)]
+pub struct AppExpectation {
+    pub manifest: AppFileManifest,
+    /// Directorio de instalacion fisico; los archivos que no esten en ningun
+    /// manifest se informan como inesperados.
+    pub install_dir: Option<PathBuf>,
+    /// Archivos imprescindibles (los ejecutables de sus entry points), con su ruta fisica.
+    pub critical: BTreeSet<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityFinding {
+    /// `sandbox`, `app:<id>` o `snapshot:<id>`.
+    pub scope: String,
+    pub path: String,
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_sha256: Option<String>,
+}
+
+impl IntegrityFinding {
+    fn new(scope: &str, path: String, critical: bool) -> Self {
+        Self {
+            scope: scope.to_string(),
+            path,
+            critical,
+            expected_sha256: None,
+            actual_sha256: None,
+        }
+    }
+}
+
+/// Resultado de comparar el sandbox con los manifests guardados.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityReport {
+    pub checked_files: u64,
+    pub missing: Vec<IntegrityFinding>,
+    pub modified: Vec<IntegrityFinding>,
+    pub unexpected: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty() && self.unexpected.is_empty()
+    }
+
+    /// Archivos criticos ausentes; con alguno el contenedor no puede usarse.
+    pub fn critical_missing(&self) -> impl Iterator<Item = &IntegrityFinding> {
+        self.missing.iter().filter(|finding| finding.critical)
+    }
+
+    /// Compara `physical` con el hash esperado y anota el hallazgo que corresponda.
+    fn check(&mut self, finding: IntegrityFinding, physical: &Path, expected: &str) {
+        if !physical.is_file() {
+            self.missing.push(finding);
+            return;
+        }
+        self.checked_files += 1;
+        match sha256_file(physical) {
+            Ok(actual) if actual == expected => {}
+            Ok(actual) => self.modified.push(IntegrityFinding {
+                expected_sha256: Some(expected.to_string()),
+                actual_sha256: Some(actual),
+                ..finding
+            }),
+            Err(err) => {
+                tracing::warn!(?err, path = %physical.display(), "No se pudo leer el archivo");
+                self.modified.push(IntegrityFinding {
+                    expected_sha256: Some(expected.to_string()),
+                    ..finding
+                });
+            }
+        }
+    }
+}
+
+/// Recorre el sandbox en `root`, recalcula los hashes y los compara con los
+/// manifests de las apps y de los snapshots indicados. `mapper` traduce las rutas
+/// fisicas a las del contenedor en los hallazgos.
+pub async fn verify(
+    root: &Path,
+    mapper: PathMapper,
+    apps: Vec<AppExpectation>,
+    snapshots: Vec<Uuid>,
+) -> Result<IntegrityReport> {
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut report = IntegrityReport::default();
+        verify_sandbox(&root, &mut report);
+        verify_apps(&root.join("fs"), &mapper, &apps, &mut report);
+        let layer = SnapshotLayer::new(&root);
+        for snapshot_id in snapshots {
+            verify_snapshot(&layer, snapshot_id, &mut report)?;
+        }
+        Ok(report)
+    })
+    .await?
+}
+
+fn verify_sandbox(root: &Path, report: &mut IntegrityReport) {
+    for dir in REQUIRED_DIRS {
+        if !root.join(dir).is_dir() {
+            report
+                .missing
+                .push(IntegrityFinding::new("sandbox", format!("{dir}/"), true));
+        }
+    }
+    let files = HIVES
+        .iter()
+        .map(|hive| format!("registry/{hive}"))
+        .chain(["runtime/manifest.json".to_string()]);
+    for file in files {
+        if root.join(&file).is_file() {
+            report.checked_files += 1;
+        } else {
+            report
+                .missing
+                .push(IntegrityFinding::new("sandbox", file, true));
+        }
+    }
+}
+
+fn verify_apps(
+    fs_root: &Path,
+    mapper: &PathMapper,
+    apps: &[AppExpectation],
+    report: &mut IntegrityReport,
+) {
+    let display = |physical: &Path| {
+        mapper
+            .to_virtual(physical)
+            .unwrap_or_else(|| physical.display().to_string())
+    };
+    let known = apps
+        .iter()
+        .flat_map(|app| app.manifest.files.iter())
+        .map(|file| fs_root.join(&file.relative_path))
+        .collect::<BTreeSet<_>>();
+
+    for app in apps {
+        let scope = format!("app:{}", app.manifest.app_id);
+        for file in &app.manifest.files {
+            let physical = fs_root.join(&file.relative_path);
+            let critical = app.critical.contains(&physical);
+            report.check(
+                IntegrityFinding::new(&scope, file.path.clone(), critical),
+                &physical,
+                &file.sha256,
+            );
+        }
+        // Entry points que apuntan fuera de lo que instalo la app: solo se exige que existan.
+        for physical in app.critical.difference(&known) {
+            if !physical.is_file() {
+                report
+                    .missing
+                    .push(IntegrityFinding::new(&scope, display(physical), true));
+            }
+        }
+
+        let Some(install_dir) = &app.install_dir else {
+            continue;
+        };
+        for entry in WalkDir::new(install_dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            if !known.contains(entry.path()) {
+                report
+                    .unexpected
+                    .push(IntegrityFinding::new(&scope, display(entry.path()), false));
+            }
+        }
+    }
+}
+
+fn verify_snapshot(
+    layer: &SnapshotLayer,
+    snapshot_id: Uuid,
+    report: &mut IntegrityReport,
+) -> Result<()> {
+    let scope = format!("snapshot:{snapshot_id}");
+    let dir = layer.snapshot_dir(snapshot_id);
+    let display = |relative: &Path| {
+        Path::new("snapshots")
+            .join(snapshot_id.to_string())
+            .join(relative)
+            .display()
+            .to_string()
+    };
+    if !dir.is_dir() {
+        report
+            .missing
+            .push(IntegrityFinding::new(&scope, display(Path::new("")), false));
+        return Ok(());
+    }
+    let Some(manifest) = layer.manifest(snapshot_id)? else {
+        tracing::debug!(%snapshot_id, "Snapshot sin manifest; no se verifica");
+        return Ok(());
+    };
+
+    let expected = manifest
+        .files
+        .iter()
+        .map(|file| (file.path.clone(), file))
+        .collect::<BTreeMap<_, _>>();
+    for (relative, file) in &expected {
+        report.check(
+            IntegrityFinding::new(&scope, display(relative), false),
+            &dir.join(relative),
+            &file.sha256,
+        );
+    }
+    for entry in WalkDir::new(&dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let Ok(relative) = entry.path().strip_prefix(&dir) else {
+            continue;
+        };
+        if relative != Path::new(MANIFEST_FILE) && !expected.contains_key(relative) {
+            report
+                .unexpected
+                .push(IntegrityFinding::new(&scope, display(relative), false));
+        }
+    }
+    Ok(())
+}
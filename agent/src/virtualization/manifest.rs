@@ -0,0 +1,224 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+use super::Platform;
+use super::app_files::{self, AppFileManifest};
+use super::filesystem::FsSnapshot;
+use super::paths::MountSpec;
+use super::registry::RegistrySnapshot;
+use super::runtime::RuntimeEnv;
+use crate::models::{ContainerLimits, ContainerSettings};
+
+/// Version del esquema que escribe este agente. Los manifests anteriores se
+/// actualizan al leerlos con los pasos de [`UPGRADES`].
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Pasos de actualizacion: `UPGRADES[n]` convierte un manifest de la version `n + 1`
+/// a la `n + 2`.
+const UPGRADES: &[fn(&mut Map<String, Value>)] = &[upgrade_v1, upgrade_v2];
+
+/// Serializa las escrituras de `runtime/manifest.json`: el contenedor y sus apps lo
+/// reescriben desde servicios distintos.
+static WRITE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Variables que anade el launcher; no forman parte de `env` del contenedor.
+const LAUNCHER_ENV: &[&str] = &["ORBIT_CONTAINER_ID", "ORBIT_RUNTIME_ROOT"];
+
+/// Contenido de `runtime/manifest.json`. Guarda lo necesario para reconstruir la
+/// fila del contenedor si la base de datos la pierde.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SandboxManifest {
+    pub schema_version: u32,
+    pub container_id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub platform: Platform,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub filesystem: FsSnapshot,
+    pub registry: RegistrySnapshot,
+    /// Entorno efectivo al lanzar: el del launcher mas `env`.
+    pub runtime: RuntimeEnv,
+    /// Variables propias del contenedor.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    pub compat_flags: Vec<String>,
+    pub limits: ContainerLimits,
+    #[serde(default)]
+    pub mounts: Vec<MountSpec>,
+    /// Archivos que escribio cada app al instalarse, por id de app.
+    #[serde(default)]
+    pub app_files: BTreeMap<Uuid, AppFileManifest>,
+}
+
+impl SandboxManifest {
+    pub fn settings(&self) -> ContainerSettings {
+        ContainerSettings {
+            env: self.env.clone(),
+            compat_flags: self.compat_flags.clone(),
+            limits: self.limits.clone(),
+            mounts: self.mounts.clone(),
+        }
+    }
+}
+
+/// Manifest leido del disco y version desde la que se actualizo, si no era la actual.
+#[derive(Debug)]
+pub struct LoadedManifest {
+    pub manifest: SandboxManifest,
+    pub upgraded_from: Option<u32>,
+}
+
+pub fn manifest_path(root: &Path) -> PathBuf {
+    root.join("runtime").join("manifest.json")
+}
+
+/// Lee el manifest del sandbox en `root` y lo actualiza a [`SCHEMA_VERSION`]; `None`
+/// si no existe. Falla si el JSON es invalido o viene de un agente mas nuevo.
+pub async fn load(root: &Path) -> Result<Option<LoadedManifest>> {
+    let path = manifest_path(root);
+    let raw = match fs::read(&path).await {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let value = serde_json::from_slice(&raw)
+        .with_context(|| format!("Manifest invalido: {}", path.display()))?;
+    let Value::Object(mut object) = value else {
+        bail!("Manifest invalido: {} no es un objeto", path.display());
+    };
+    let upgraded_from = upgrade(&mut object)
+        .with_context(|| format!("No se pudo actualizar {}", path.display()))?;
+    let manifest = serde_json::from_value(Value::Object(object))
+        .with_context(|| format!("Manifest invalido: {}", path.display()))?;
+    Ok(Some(LoadedManifest {
+        manifest,
+        upgraded_from,
+    }))
+}
+
+/// Reescribe el manifest con la configuracion de `manifest` conservando los archivos de
+/// las apps del que ya habia en disco. Incorpora los `runtime/apps/<app_id>.json` de
+/// agentes anteriores y los borra una vez guardados.
+pub async fn save(root: &Path, mut manifest: SandboxManifest) -> Result<()> {
+    let _guard = WRITE_LOCK.lock().await;
+    match load(root).await {
+        Ok(Some(current)) => manifest.app_files = current.manifest.app_files,
+        Ok(None) => {}
+        Err(err) => {
+            tracing::warn!(error = %err, "Manifest ilegible; se regenera sin archivos de apps")
+        }
+    }
+    let legacy = app_files::load_legacy(root).await?;
+    for (app_id, files) in &legacy {
+        manifest
+            .app_files
+            .entry(*app_id)
+            .or_insert_with(|| files.clone());
+    }
+    write(root, &manifest).await?;
+    if !legacy.is_empty() {
+        app_files::remove_legacy(root, legacy.keys()).await?;
+    }
+    Ok(())
+}
+
+/// Aplica `change` a los archivos de las apps del manifest en `root` y lo guarda.
+pub async fn update_app_files<T>(
+    root: &Path,
+    change: impl FnOnce(&mut BTreeMap<Uuid, AppFileManifest>) -> T,
+) -> Result<T> {
+    let _guard = WRITE_LOCK.lock().await;
+    let mut manifest = load(root)
+        .await?
+        .with_context(|| format!("Sin manifest en {}", manifest_path(root).display()))?
+        .manifest;
+    let outcome = change(&mut manifest.app_files);
+    write(root, &manifest).await?;
+    Ok(outcome)
+}
+
+async fn write(root: &Path, manifest: &SandboxManifest) -> Result<()> {
+    fs::write(manifest_path(root), serde_json::to_vec_pretty(manifest)?).await?;
+    Ok(())
+}
+
+/// Aplica los pasos pendientes y devuelve la version original si cambio algo. Los
+/// manifests sin `schema_version` son de la version 1.
+fn upgrade(object: &mut Map<String, Value>) -> Result<Option<u32>> {
+    let version = match object.get("schema_version") {
+        None => 1,
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .context("schema_version invalida")?,
+    };
+    if version > SCHEMA_VERSION {
+        bail!(
+            "schema_version {version} no soportada (maxima {})",
+            SCHEMA_VERSION
+        );
+    }
+    if version == SCHEMA_VERSION {
+        return Ok(None);
+    }
+    for step in &UPGRADES[(version - 1) as usize..] {
+        step(object);
+    }
+    object.insert("schema_version".into(), SCHEMA_VERSION.into());
+    Ok(Some(version))
+}
+
+/// v1 no guardaba descripcion, tags, variables ni montajes: las variables se recuperan
+/// de `runtime.env` quitando las del launcher y los binds de `filesystem.overlays`.
+fn upgrade_v1(object: &mut Map<String, Value>) {
+    let env = object
+        .get("runtime")
+        .and_then(|runtime| runtime.get("env"))
+        .and_then(Value::as_object)
+        .map(|env| {
+            env.iter()
+                .filter(|(key, _)| !LAUNCHER_ENV.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Map<_, _>>()
+        })
+        .unwrap_or_default();
+    let mounts = object
+        .get("filesystem")
+        .and_then(|filesystem| filesystem.get("overlays"))
+        .and_then(Value::as_array)
+        .map(|overlays| {
+            overlays
+                .iter()
+                .filter(|overlay| overlay.get("kind").and_then(Value::as_str) == Some("bind"))
+                .map(|overlay| {
+                    serde_json::json!({
+                        "virtual_path": overlay.get("virtual_path").cloned().unwrap_or_default(),
+                        "source": overlay.get("physical_path").cloned().unwrap_or_default(),
+                        "read_only": overlay.get("read_only").cloned().unwrap_or(Value::Bool(false)),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    object.entry("description").or_insert(Value::Null);
+    object.entry("tags").or_insert_with(|| Value::Array(vec![]));
+    object.entry("env").or_insert(Value::Object(env));
+    object.entry("mounts").or_insert(Value::Array(mounts));
+}
+
+/// v2 guardaba los archivos de cada app aparte, en `runtime/apps/`; `save` los
+/// incorpora al reescribir el manifest.
+fn upgrade_v2(object: &mut Map<String, Value>) {
+    object
+        .entry("app_files")
+        .or_insert_with(|| Value::Object(Map::new()));
+}
@@ -1,17 +1,37 @@
+pub mod app_files;
+pub mod archive;
+pub mod compat;
 mod filesystem;
+pub mod integrity;
+pub mod manifest;
+mod paths;
 mod registry;
 mod runtime;
+mod snapshot;
+mod usage;
+mod watcher;
 
-pub use filesystem::{FsLayer, FsSnapshot};
-pub use registry::RegistryLayer;
-pub use runtime::{ProcessLauncher, RuntimeEnv};
+pub use filesystem::{
+    FileStamp, FsChanges, FsEntry, FsEntryKind, FsInventory, FsLayer, FsSnapshot,
+    resolve_virtual_path, virtual_path,
+};
+pub use manifest::{LoadedManifest, SandboxManifest};
+pub use paths::{MappedPath, MountSpec, PathMapError, PathMapper, default_mounts};
+pub use registry::{RegistryLayer, RegistrySeed, RegistryValue};
+pub use runtime::{ProcessLauncher, ProcessOutcome, RuntimeEnv, split_args};
+pub use snapshot::SnapshotLayer;
+pub use usage::{DiskUsage, measure as measure_usage};
+pub use watcher::{ChangeJournal, FsChange, FsChangeKind, FsWatcher, RawChange, WatchSignal};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use crate::models::{ContainerModel, ContainerSettings};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum Platform {
@@ -20,6 +40,8 @@ pub enum Platform {
 }
 
 impl Platform {
+    pub const VALUES: &'static [&'static str] = &["windows-x64", "windows-arm64"];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Platform::WindowsX64 => "windows-x64",
@@ -39,8 +61,12 @@ impl Platform {
 pub struct SandboxDescriptor {
     pub container_id: Uuid,
     pub name: String,
+    pub description: Option<String>,
     pub platform: Platform,
+    pub tags: Vec<String>,
     pub root: PathBuf,
+    pub settings: ContainerSettings,
+    pub created_at: Option<String>,
 }
 
 impl SandboxDescriptor {
@@ -48,8 +74,26 @@ impl SandboxDescriptor {
         Self {
             container_id: Uuid::new_v4(),
             name: name.into(),
+            description: None,
             platform,
+            tags: vec![],
+            root: root.as_ref().to_path_buf(),
+            settings: ContainerSettings::default(),
+            created_at: None,
+        }
+    }
+
+    /// Describe el sandbox de un contenedor ya registrado en el store.
+    pub fn for_container(container: &ContainerModel, root: impl AsRef<Path>) -> Self {
+        Self {
+            container_id: container.id,
+            name: container.name.clone(),
+            description: container.description.clone(),
+            platform: container.platform.clone(),
+            tags: container.tags.clone(),
             root: root.as_ref().to_path_buf(),
+            settings: container.settings.clone(),
+            created_at: Some(container.created_at.clone()),
         }
     }
 }
@@ -65,8 +109,9 @@ impl SandboxRuntime {
     pub fn new(descriptor: SandboxDescriptor) -> Self {
         let root = descriptor.root.clone();
         Self {
+            fs: FsLayer::new(root.clone())
+                .with_settings(&descriptor.platform, &descriptor.settings),
             descriptor,
-            fs: FsLayer::new(root.clone()),
             registry: RegistryLayer::new(root.clone()),
             launcher: ProcessLauncher::new(root.join("runtime")),
         }
@@ -78,37 +123,45 @@ impl SandboxRuntime {
         Ok(())
     }
 
+    /// Crea los directorios de los overlays que falten tras cambiar la tabla de montajes.
+    pub async fn prepare_mounts(&self) -> Result<()> {
+        self.fs.prepare_mounts().await
+    }
+
     pub async fn persist_manifest(&self) -> Result<()> {
+        let settings = &self.descriptor.settings;
+        let mut runtime = self.launcher.runtime_env(self.descriptor.container_id);
+        runtime.env.extend(
+            settings
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
         let manifest = SandboxManifest {
+            schema_version: manifest::SCHEMA_VERSION,
             container_id: self.descriptor.container_id,
             name: self.descriptor.name.clone(),
+            description: self.descriptor.description.clone(),
             platform: self.descriptor.platform.clone(),
-            created_at: OffsetDateTime::now_utc()
-                .format(&time::format_description::well_known::Rfc3339)
-                .unwrap_or_default(),
+            tags: self.descriptor.tags.clone(),
+            created_at: self.descriptor.created_at.clone().unwrap_or_else(|| {
+                OffsetDateTime::now_utc()
+                    .format(&time::format_description::well_known::Rfc3339)
+                    .unwrap_or_default()
+            }),
             filesystem: self.fs.snapshot(),
             registry: self.registry.snapshot(),
-            runtime: self.launcher.runtime_env(self.descriptor.container_id),
+            runtime,
+            env: settings.env.clone(),
+            compat_flags: settings.compat_flags.clone(),
+            limits: settings.limits.clone(),
+            mounts: settings.mounts.clone(),
+            app_files: BTreeMap::new(),
         };
-
-        let manifest_path = self.descriptor.root.join("runtime").join("manifest.json");
-        let json = serde_json::to_vec_pretty(&manifest)?;
-        tokio::fs::write(&manifest_path, json).await?;
-        Ok(())
+        manifest::save(&self.descriptor.root, manifest).await
     }
 
     pub fn descriptor(&self) -> &SandboxDescriptor {
         &self.descriptor
     }
 }
-
-#[derive(Debug, Serialize)]
-struct SandboxManifest {
-    container_id: Uuid,
-    name: String,
-    platform: Platform,
-    created_at: String,
-    filesystem: FsSnapshot,
-    registry: registry::RegistrySnapshot,
-    runtime: RuntimeEnv,
-}
@@ -0,0 +1,442 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use super::Platform;
+
+/// Unica unidad que expone el contenedor.
+const DRIVE: &str = "C:";
+
+/// Limite de variables anidadas (`%TEMP%` -> `%LOCALAPPDATA%` -> `%USERPROFILE%`).
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// Variables de entorno de Windows que se pueden usar en rutas del contenedor. Las
+/// variables del contenedor con el mismo nombre tienen prioridad.
+const DEFAULT_VARIABLES: &[(&str, &str)] = &[
+    ("SYSTEMDRIVE", "C:"),
+    ("SYSTEMROOT", "C:\\Windows"),
+    ("WINDIR", "C:\\Windows"),
+    ("PROGRAMFILES", "C:\\Program Files"),
+    ("PROGRAMW6432", "C:\\Program Files"),
+    ("PROGRAMFILES(X86)", "C:\\Program Files (x86)"),
+    ("PROGRAMDATA", "C:\\ProgramData"),
+    ("ALLUSERSPROFILE", "C:\\ProgramData"),
+    ("PUBLIC", "C:\\Users\\Public"),
+    ("USERPROFILE", "C:\\Users\\Default"),
+    ("HOMEDRIVE", "C:"),
+    ("HOMEPATH", "\\Users\\Default"),
+    ("APPDATA", "%USERPROFILE%\\AppData\\Roaming"),
+    ("LOCALAPPDATA", "%USERPROFILE%\\AppData\\Local"),
+    ("TEMP", "%LOCALAPPDATA%\\Temp"),
+    ("TMP", "%TEMP%"),
+];
+
+/// Variables propias de Windows on Arm.
+const ARM64_VARIABLES: &[(&str, &str)] = &[("PROGRAMFILES(ARM)", "C:\\Program Files (Arm)")];
+
+/// Directorios redirigidos en todas las plataformas: ruta del contenedor (admite
+/// `%VARIABLES%`) y directorio de `fs/`. `C:\` garantiza que cualquier ruta se resuelva.
+const DEFAULT_OVERLAYS: &[(&str, &str)] = &[
+    ("C:\\", ""),
+    ("%PROGRAMFILES%", "ProgramFiles"),
+    ("%PROGRAMFILES(X86)%", "ProgramFiles (x86)"),
+    ("%PROGRAMDATA%", "ProgramData"),
+    ("%USERPROFILE%", "Users/Default"),
+    ("%USERPROFILE%\\Documents", "Users/Default/Documents"),
+    ("%APPDATA%", "Users/Default/AppData/Roaming"),
+    ("%LOCALAPPDATA%", "Users/Default/AppData/Local"),
+    ("%TEMP%", "Users/Default/AppData/Local/Temp"),
+];
+
+/// Directorios que solo existen en Windows on Arm (apps Arm32 nativas).
+const ARM64_OVERLAYS: &[(&str, &str)] = &[("%PROGRAMFILES(ARM)%", "ProgramFiles (Arm)")];
+
+/// Redireccion de una ruta del contenedor a un directorio de `fs/` (overlay) o a un
+/// directorio del host (bind).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MountSpec {
+    /// Ruta vista desde el contenedor (`C:\Program Files`).
+    pub virtual_path: String,
+    /// Directorio relativo a `fs/`; vacio para la raiz. Se ignora si hay `source`.
+    #[serde(default)]
+    pub target: String,
+    /// Directorio absoluto del host que se monta tal cual en lugar de uno de `fs/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl MountSpec {
+    pub fn new(virtual_path: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            virtual_path: virtual_path.into(),
+            target: target.into(),
+            source: None,
+            read_only: false,
+        }
+    }
+
+    /// Monta el directorio del host `source` en `virtual_path`.
+    pub fn bind(
+        virtual_path: impl Into<String>,
+        source: impl Into<String>,
+        read_only: bool,
+    ) -> Self {
+        Self {
+            virtual_path: virtual_path.into(),
+            target: String::new(),
+            source: Some(source.into()),
+            read_only,
+        }
+    }
+}
+
+/// Montajes por defecto de la plataforma, antes de expandir sus variables.
+pub fn default_mounts(platform: &Platform) -> Vec<MountSpec> {
+    let platform_overlays = match platform {
+        Platform::WindowsX64 => &[][..],
+        Platform::WindowsArm64 => ARM64_OVERLAYS,
+    };
+    DEFAULT_OVERLAYS
+        .iter()
+        .chain(platform_overlays)
+        .map(|(virtual_path, target)| MountSpec::new(*virtual_path, *target))
+        .collect()
+}
+
+/// Motivos por los que una ruta no se puede traducir.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PathMapError {
+    #[error("Variable de entorno desconocida: %{0}%")]
+    UnknownVariable(String),
+    #[error("Variables anidadas en exceso al expandir '{0}'")]
+    ExpansionTooDeep(String),
+    #[error("Unidad no soportada: {0}")]
+    UnsupportedDrive(String),
+    #[error("'{0}' sale del contenedor")]
+    OutsideContainer(String),
+}
+
+/// Resultado de traducir una ruta del contenedor.
+#[derive(Debug, Clone, Serialize)]
+pub struct MappedPath {
+    /// Ruta expandida y normalizada (`C:\Users\Default\AppData\Local\Temp`).
+    pub virtual_path: String,
+    /// Montaje que la resuelve.
+    pub mount: String,
+    /// Ruta relativa a `fs/`; `None` si la resuelve un bind del host.
+    pub relative: Option<PathBuf>,
+    pub physical: PathBuf,
+    pub read_only: bool,
+    /// Directorio del que no puede salir la ruta (`fs/` o el origen del bind).
+    #[serde(skip)]
+    pub base: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+enum MountLocation {
+    /// Directorio relativo a `fs/`.
+    Overlay(PathBuf),
+    /// Directorio absoluto del host.
+    Bind(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+struct Mount {
+    /// Componentes de la ruta virtual en minusculas, sin la unidad.
+    key: Vec<String>,
+    virtual_path: String,
+    location: MountLocation,
+    read_only: bool,
+}
+
+/// Traduce rutas entre el contenedor y el disco: tabla de montajes con el prefijo mas
+/// largo, sin distinguir mayusculas como Windows, y expansion de `%VARIABLES%`.
+#[derive(Clone, Debug)]
+pub struct PathMapper {
+    fs_root: PathBuf,
+    mounts: Vec<Mount>,
+    variables: BTreeMap<String, String>,
+}
+
+impl PathMapper {
+    /// Combina los montajes de la plataforma con `mounts` (que sustituyen a los que
+    /// tengan la misma ruta virtual) y las variables por defecto con `env`.
+    pub fn new(
+        fs_root: impl AsRef<Path>,
+        platform: &Platform,
+        mounts: &[MountSpec],
+        env: &BTreeMap<String, String>,
+    ) -> Self {
+        let platform_variables = match platform {
+            Platform::WindowsX64 => &[][..],
+            Platform::WindowsArm64 => ARM64_VARIABLES,
+        };
+        let mut variables = DEFAULT_VARIABLES
+            .iter()
+            .chain(platform_variables)
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        variables.extend(
+            env.iter()
+                .map(|(name, value)| (name.to_ascii_uppercase(), value.clone())),
+        );
+        let mut mapper = Self {
+            fs_root: fs_root.as_ref().to_path_buf(),
+            mounts: vec![],
+            variables,
+        };
+
+        let mut table = BTreeMap::new();
+        for spec in default_mounts(platform).iter().chain(mounts) {
+            let key = match mapper
+                .expand(&spec.virtual_path)
+                .and_then(|expanded| virtual_components(&expanded))
+            {
+                Ok(components) => components,
+                Err(err) => {
+                    tracing::warn!(virtual_path = %spec.virtual_path, %err, "Montaje ignorado");
+                    continue;
+                }
+            };
+            let location = match &spec.source {
+                Some(source) => MountLocation::Bind(PathBuf::from(source)),
+                None => MountLocation::Overlay(
+                    Path::new(&spec.target)
+                        .components()
+                        .filter_map(|component| match component {
+                            Component::Normal(part) => Some(part),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+            };
+            table.insert(
+                lowercase(&key),
+                Mount {
+                    virtual_path: join_virtual(&key),
+                    key: lowercase(&key),
+                    location,
+                    read_only: spec.read_only,
+                },
+            );
+        }
+        mapper.mounts = table.into_values().collect();
+        // Primero los prefijos mas largos.
+        mapper
+            .mounts
+            .sort_by_key(|mount| std::cmp::Reverse(mount.key.len()));
+        mapper
+    }
+
+    /// Montajes efectivos con sus variables expandidas, del mas especifico al mas general.
+    pub fn mounts(&self) -> Vec<MountSpec> {
+        self.mounts
+            .iter()
+            .map(|mount| match &mount.location {
+                MountLocation::Overlay(target) => MountSpec {
+                    read_only: mount.read_only,
+                    ..MountSpec::new(&mount.virtual_path, target.display().to_string())
+                },
+                MountLocation::Bind(source) => MountSpec::bind(
+                    &mount.virtual_path,
+                    source.display().to_string(),
+                    mount.read_only,
+                ),
+            })
+            .collect()
+    }
+
+    /// Directorios de los overlays, relativos a `fs/`.
+    pub fn overlay_dirs(&self) -> Vec<PathBuf> {
+        self.mounts
+            .iter()
+            .filter_map(|mount| match &mount.location {
+                MountLocation::Overlay(target) => Some(target.clone()),
+                MountLocation::Bind(_) => None,
+            })
+            .collect()
+    }
+
+    /// Sustituye las `%VARIABLES%` de `path`; `%%` queda como `%`.
+    pub fn expand(&self, path: &str) -> Result<String, PathMapError> {
+        self.expand_depth(path, 0)
+    }
+
+    fn expand_depth(&self, path: &str, depth: usize) -> Result<String, PathMapError> {
+        if depth > MAX_EXPANSION_DEPTH {
+            return Err(PathMapError::ExpansionTooDeep(path.to_string()));
+        }
+        let mut expanded = String::with_capacity(path.len());
+        let mut rest = path;
+        while let Some(start) = rest.find('%') {
+            expanded.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('%') else {
+                expanded.push_str(&rest[start..]);
+                return Ok(expanded);
+            };
+            let name = &after[..end];
+            if name.is_empty() {
+                expanded.push('%');
+            } else {
+                let value = self
+                    .variables
+                    .get(&name.to_ascii_uppercase())
+                    .ok_or_else(|| PathMapError::UnknownVariable(name.to_string()))?;
+                expanded.push_str(&self.expand_depth(value, depth + 1)?);
+            }
+            rest = &after[end + 1..];
+        }
+        expanded.push_str(rest);
+        Ok(expanded)
+    }
+
+    /// Traduce una ruta del contenedor (`%APPDATA%\App`, `C:\Program Files\App` o
+    /// relativa a `C:\`) a su ubicacion en disco. `..` se resuelve sin salir de `C:\`.
+    pub fn to_physical(&self, path: &str) -> Result<MappedPath, PathMapError> {
+        let expanded = self.expand(path)?;
+        let components = virtual_components(&expanded)?;
+        let lowered = lowercase(&components);
+        let mount = self
+            .mounts
+            .iter()
+            .find(|mount| lowered.starts_with(&mount.key))
+            .ok_or_else(|| PathMapError::OutsideContainer(path.to_string()))?;
+
+        let rest = &components[mount.key.len()..];
+        let (relative, physical, base) = match &mount.location {
+            MountLocation::Overlay(target) => {
+                let mut relative = target.clone();
+                relative.extend(rest);
+                let physical = self.fs_root.join(&relative);
+                (Some(relative), physical, self.fs_root.clone())
+            }
+            MountLocation::Bind(source) => {
+                let mut physical = source.clone();
+                physical.extend(rest);
+                (None, physical, source.clone())
+            }
+        };
+        Ok(MappedPath {
+            virtual_path: join_virtual(&components),
+            mount: mount.virtual_path.clone(),
+            relative,
+            physical,
+            read_only: mount.read_only,
+            base,
+        })
+    }
+
+    /// Inverso de [`PathMapper::to_physical`]: acepta una ruta relativa a `fs/`, absoluta
+    /// dentro de ella o dentro del origen de un bind. `None` si no la cubre ningun montaje.
+    pub fn to_virtual(&self, physical: &Path) -> Option<String> {
+        if physical.is_absolute() {
+            let bound = self
+                .mounts
+                .iter()
+                .filter_map(|mount| match &mount.location {
+                    MountLocation::Bind(source) => physical
+                        .strip_prefix(source)
+                        .ok()
+                        .map(|rest| (mount, source.components().count(), rest)),
+                    MountLocation::Overlay(_) => None,
+                })
+                .max_by_key(|(_, depth, _)| *depth);
+            if let Some((mount, _, rest)) = bound {
+                let mut components = virtual_components(&mount.virtual_path).ok()?;
+                components.extend(normal_components(rest)?);
+                return Some(join_virtual(&components));
+            }
+        }
+
+        let relative = if physical.is_absolute() {
+            physical.strip_prefix(&self.fs_root).ok()?
+        } else {
+            physical
+        };
+        let parts = normal_components(relative)?;
+        let lowered = lowercase(&parts);
+        self.mounts
+            .iter()
+            .filter_map(|mount| {
+                let MountLocation::Overlay(target) = &mount.location else {
+                    return None;
+                };
+                let target = target
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().to_lowercase())
+                    .collect::<Vec<_>>();
+                lowered
+                    .starts_with(&target)
+                    .then_some((mount, target.len()))
+            })
+            // Gana el directorio de destino mas especifico.
+            .max_by_key(|(_, depth)| *depth)
+            .map(|(mount, depth)| {
+                let mut components = virtual_components(&mount.virtual_path).unwrap_or_default();
+                components.extend(parts[depth..].iter().cloned());
+                join_virtual(&components)
+            })
+    }
+}
+
+/// Une `name` a una ruta del contenedor ya normalizada.
+pub fn join_child(parent: &str, name: &str) -> String {
+    if parent.ends_with('\\') {
+        format!("{parent}{name}")
+    } else {
+        format!("{parent}\\{name}")
+    }
+}
+
+/// Componentes de una ruta del contenedor sin la unidad. Las rutas sin unidad se
+/// consideran relativas a `C:\`; `..` nunca sube por encima de la raiz.
+fn virtual_components(path: &str) -> Result<Vec<String>, PathMapError> {
+    let trimmed = path.trim();
+    let rest = match trimmed.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => {
+            if !trimmed[..2].eq_ignore_ascii_case(DRIVE) {
+                return Err(PathMapError::UnsupportedDrive(trimmed[..2].to_string()));
+            }
+            &trimmed[2..]
+        }
+        _ => trimmed,
+    };
+
+    let mut components: Vec<String> = vec![];
+    for part in rest.split(['\\', '/']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                components
+                    .pop()
+                    .ok_or_else(|| PathMapError::OutsideContainer(path.to_string()))?;
+            }
+            part => components.push(part.to_string()),
+        }
+    }
+    Ok(components)
+}
+
+/// Componentes de una ruta fisica; `None` si contiene `..` o una raiz intermedia.
+fn normal_components(path: &Path) -> Option<Vec<String>> {
+    let mut parts = vec![];
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(parts)
+}
+
+fn lowercase(components: &[String]) -> Vec<String> {
+    components.iter().map(|part| part.to_lowercase()).collect()
+}
+
+fn join_virtual(components: &[String]) -> String {
+    format!("{DRIVE}\\{}", components.join("\\"))
+}
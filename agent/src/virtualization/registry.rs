@@ -1,13 +1,17 @@
-use anyhow::Result;
-use serde::Serialize;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use tokio::fs;
+use tokio::{fs, io::AsyncWriteExt};
 
 #[derive(Clone, Debug)]
 pub struct RegistryLayer {
     root: PathBuf,
 }
 
+/// Hives que se crean en `registry/` al preparar el sandbox.
+pub(super) const HIVES: &[&str] = &["SOFTWARE.reg", "SYSTEM.reg", "NTUSER.dat"];
+
 impl RegistryLayer {
     pub fn new(root: impl AsRef<Path>) -> Self {
         Self {
@@ -18,7 +22,7 @@ impl RegistryLayer {
     pub async fn prepare(&self) -> Result<()> {
         let hive_dir = self.root.join("registry");
         fs::create_dir_all(&hive_dir).await?;
-        for hive in ["SOFTWARE.reg", "SYSTEM.reg", "NTUSER.dat"] {
+        for hive in HIVES {
             let file_path = hive_dir.join(hive);
             if !file_path.exists() {
                 fs::write(&file_path, b"; orbit placeholder hive\n").await?;
@@ -27,6 +31,29 @@ impl RegistryLayer {
         Ok(())
     }
 
+    /// Anade los valores al final del hive que corresponde a cada clave, en formato `.reg`.
+    pub async fn seed(&self, seeds: &[RegistrySeed]) -> Result<()> {
+        let mut by_hive = BTreeMap::<&str, String>::new();
+        for seed in seeds {
+            let (hive, key) = seed
+                .hive()
+                .with_context(|| format!("Clave de registro fuera de los hives: {}", seed.key))?;
+            by_hive
+                .entry(hive)
+                .or_default()
+                .push_str(&seed.to_reg(&key));
+        }
+        for (hive, entries) in by_hive {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.root.join("registry").join(hive))
+                .await?;
+            file.write_all(entries.as_bytes()).await?;
+        }
+        Ok(())
+    }
+
     pub fn snapshot(&self) -> RegistrySnapshot {
         let base = self.root.join("registry");
         RegistrySnapshot {
@@ -37,9 +64,74 @@ impl RegistryLayer {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RegistrySnapshot {
     pub software: String,
     pub system: String,
     pub ntuser: String,
 }
+
+/// Prefijos aceptados en [`RegistrySeed::key`] y el hive de `registry/` donde se guardan.
+const HIVE_PREFIXES: &[(&str, &str, &str)] = &[
+    (
+        "HKLM\\SOFTWARE",
+        "HKEY_LOCAL_MACHINE\\SOFTWARE",
+        "SOFTWARE.reg",
+    ),
+    ("HKLM\\SYSTEM", "HKEY_LOCAL_MACHINE\\SYSTEM", "SYSTEM.reg"),
+    ("HKCU", "HKEY_CURRENT_USER", "NTUSER.dat"),
+];
+
+/// Valor que se escribe en el registro del contenedor al prepararlo desde una plantilla.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrySeed {
+    /// Clave completa (`HKLM\SOFTWARE\Vendor\App`); admite `HKLM`/`HKCU` abreviados.
+    pub key: String,
+    /// Nombre del valor; `None` es el valor por defecto de la clave.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub value: RegistryValue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RegistryValue {
+    Dword(u32),
+    String(String),
+}
+
+impl RegistrySeed {
+    /// Hive y clave con el prefijo largo; `None` si la clave no cae en ningun hive.
+    pub fn hive(&self) -> Option<(&'static str, String)> {
+        let key = self.key.trim().trim_end_matches('\\');
+        HIVE_PREFIXES.iter().find_map(|(short, long, hive)| {
+            [short, long].into_iter().find_map(|prefix| {
+                let rest = strip_prefix_ignore_case(key, prefix)?;
+                (rest.is_empty() || rest.starts_with('\\'))
+                    .then(|| (*hive, format!("{long}{rest}")))
+            })
+        })
+    }
+
+    fn to_reg(&self, key: &str) -> String {
+        let name = match &self.name {
+            Some(name) => format!("\"{}\"", escape_reg(name)),
+            None => "@".to_string(),
+        };
+        let value = match &self.value {
+            RegistryValue::Dword(value) => format!("dword:{value:08x}"),
+            RegistryValue::String(value) => format!("\"{}\"", escape_reg(value)),
+        };
+        format!("\n[{key}]\n{name}={value}\n")
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = value.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &value[prefix.len()..])
+}
+
+fn escape_reg(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
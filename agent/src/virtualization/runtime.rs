@@ -1,8 +1,15 @@
-use serde::Serialize;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
 use uuid::Uuid;
 
+/// Codigos de salida que los instaladores de Windows usan para "exito, requiere reinicio".
+const REBOOT_REQUIRED_CODES: &[i32] = &[1641, 3010];
+
 #[derive(Clone, Debug)]
 pub struct ProcessLauncher {
     workspace: PathBuf,
@@ -25,9 +32,93 @@ impl ProcessLauncher {
 
         RuntimeEnv { env }
     }
+
+    /// Ejecuta `program` con stdout/stderr redirigidos a `log_path`.
+    /// Si se supera `timeout` el proceso se termina y se informa sin codigo de salida.
+    pub async fn run(
+        &self,
+        program: &Path,
+        args: &[String],
+        env: &HashMap<String, String>,
+        log_path: &Path,
+        timeout: Duration,
+    ) -> Result<ProcessOutcome> {
+        let log = std::fs::File::create(log_path)
+            .with_context(|| format!("No se pudo crear el log {}", log_path.display()))?;
+        let stderr = log.try_clone()?;
+        let workdir = program.parent().unwrap_or(&self.workspace);
+
+        let mut child = Command::new(program)
+            .args(args)
+            .envs(env)
+            .current_dir(workdir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::from(log))
+            .stderr(Stdio::from(stderr))
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("No se pudo ejecutar {}", program.display()))?;
+
+        match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(status) => Ok(ProcessOutcome {
+                exit_code: status?.code(),
+                timed_out: false,
+            }),
+            Err(_) => {
+                child.kill().await.ok();
+                Ok(ProcessOutcome {
+                    exit_code: None,
+                    timed_out: true,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessOutcome {
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+}
+
+impl ProcessOutcome {
+    pub fn succeeded(&self) -> bool {
+        matches!(self.exit_code, Some(code) if code == 0 || REBOOT_REQUIRED_CODES.contains(&code))
+    }
+}
+
+/// Separa argumentos al estilo de la linea de comandos de Windows: espacios como
+/// separador y comillas dobles para agrupar. Las barras invertidas se conservan.
+pub fn split_args(raw: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut pending = false;
+    for c in raw.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                pending = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if pending {
+                    args.push(std::mem::take(&mut current));
+                    pending = false;
+                }
+            }
+            c => {
+                current.push(c);
+                pending = true;
+            }
+        }
+    }
+    if pending {
+        args.push(current);
+    }
+    args
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RuntimeEnv {
     pub env: HashMap<String, String>,
 }
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+use crate::security::sha256_file;
+
+/// Directorios del sandbox que forman parte de un snapshot.
+const CAPTURED_DIRS: &[&str] = &["fs", "registry"];
+
+pub(super) const MANIFEST_FILE: &str = "manifest.json";
+
+/// Archivo copiado en un snapshot, relativo a `snapshots/<id>/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Contenido de un snapshot, guardado en `snapshots/<id>/manifest.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub snapshot_id: Uuid,
+    pub files: Vec<SnapshotFile>,
+}
+
+/// Copias completas de `fs/` y `registry/` guardadas en `snapshots/<id>/`.
+#[derive(Clone, Debug)]
+pub struct SnapshotLayer {
+    root: PathBuf,
+}
+
+impl SnapshotLayer {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn snapshot_dir(&self, snapshot_id: Uuid) -> PathBuf {
+        self.root.join("snapshots").join(snapshot_id.to_string())
+    }
+
+    /// Copia el estado actual del sandbox y escribe su manifest con el SHA-256 de
+    /// cada archivo; devuelve los bytes capturados.
+    pub async fn capture(&self, snapshot_id: Uuid) -> Result<u64> {
+        let root = self.root.clone();
+        let target = self.snapshot_dir(snapshot_id);
+        tokio::task::spawn_blocking(move || {
+            let mut bytes = 0;
+            for dir in CAPTURED_DIRS {
+                let source = root.join(dir);
+                if source.is_dir() {
+                    bytes += copy_tree(&source, &target.join(dir))?;
+                }
+            }
+            fs::create_dir_all(&target)?;
+            let manifest = SnapshotManifest {
+                snapshot_id,
+                files: hash_tree(&target)?,
+            };
+            fs::write(
+                target.join(MANIFEST_FILE),
+                serde_json::to_vec_pretty(&manifest)?,
+            )?;
+            Ok(bytes)
+        })
+        .await?
+    }
+
+    /// Lee el manifest del snapshot; `None` si se capturo antes de que existiera.
+    pub fn manifest(&self, snapshot_id: Uuid) -> Result<Option<SnapshotManifest>> {
+        let path = self.snapshot_dir(snapshot_id).join(MANIFEST_FILE);
+        match fs::read(&path) {
+            Ok(raw) => Ok(Some(serde_json::from_slice(&raw).with_context(|| {
+                format!("Manifest de snapshot invalido: {}", path.display())
+            })?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Borra la copia del snapshot; no falla si ya no estaba en disco.
+    pub async fn remove(&self, snapshot_id: Uuid) -> Result<()> {
+        let dir = self.snapshot_dir(snapshot_id);
+        tokio::task::spawn_blocking(move || remove_if_exists(&dir)).await?
+    }
+
+    /// Sustituye `fs/` y `registry/` por la copia del snapshot. Cada directorio se
+    /// prepara aparte y se intercambia por renombrado para no dejarlo a medias.
+    pub async fn restore(&self, snapshot_id: Uuid) -> Result<()> {
+        let root = self.root.clone();
+        let source = self.snapshot_dir(snapshot_id);
+        tokio::task::spawn_blocking(move || {
+            anyhow::ensure!(
+                source.is_dir(),
+                "El snapshot {snapshot_id} no tiene datos en {}",
+                source.display()
+            );
+            for dir in CAPTURED_DIRS {
+                let live = root.join(dir);
+                let staged = root.join(format!("{dir}.restoring"));
+                let previous = root.join(format!("{dir}.previous"));
+                remove_if_exists(&staged)?;
+                remove_if_exists(&previous)?;
+
+                let captured = source.join(dir);
+                if captured.is_dir() {
+                    copy_tree(&captured, &staged)?;
+                } else {
+                    fs::create_dir_all(&staged)?;
+                }
+                if live.exists() {
+                    fs::rename(&live, &previous)?;
+                }
+                fs::rename(&staged, &live)?;
+                remove_if_exists(&previous)?;
+            }
+            Ok(())
+        })
+        .await?
+    }
+}
+
+fn hash_tree(dir: &Path) -> Result<Vec<SnapshotFile>> {
+    let mut files = vec![];
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path().strip_prefix(dir)?.to_path_buf();
+        if path == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        files.push(SnapshotFile {
+            size: entry.metadata()?.len(),
+            sha256: sha256_file(entry.path())
+                .with_context(|| format!("No se pudo leer {}", entry.path().display()))?,
+            path,
+        });
+    }
+    Ok(files)
+}
+
+fn copy_tree(source: &Path, target: &Path) -> Result<u64> {
+    let mut bytes = 0;
+    for entry in WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let destination = target.join(relative);
+        let kind = entry.file_type();
+        if kind.is_dir() {
+            fs::create_dir_all(&destination)?;
+        } else if kind.is_file() {
+            bytes += fs::copy(entry.path(), &destination)
+                .with_context(|| format!("No se pudo copiar {}", entry.path().display()))?;
+        }
+    }
+    Ok(bytes)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    if path.exists() {
+        fs::remove_dir_all(path)
+            .with_context(|| format!("No se pudo eliminar {}", path.display()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Espacio ocupado por un sandbox, desglosado por area. Se suman los tamanos de los
+/// archivos sin seguir enlaces, asi los binds del host no cuentan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DiskUsage {
+    pub fs: u64,
+    pub registry: u64,
+    pub snapshots: u64,
+    pub logs: u64,
+    /// `runtime/` y cualquier otro archivo del sandbox.
+    pub other: u64,
+    pub total: u64,
+}
+
+impl DiskUsage {
+    fn add(&mut self, area: &str, size: u64) {
+        match area {
+            "fs" => self.fs += size,
+            "registry" => self.registry += size,
+            "snapshots" => self.snapshots += size,
+            "logs" => self.logs += size,
+            _ => self.other += size,
+        }
+        self.total += size;
+    }
+}
+
+impl std::ops::AddAssign for DiskUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.fs += other.fs;
+        self.registry += other.registry;
+        self.snapshots += other.snapshots;
+        self.logs += other.logs;
+        self.other += other.other;
+        self.total += other.total;
+    }
+}
+
+/// Recorre el sandbox en `root` y suma lo que ocupa cada area. Un sandbox que aun no
+/// existe ocupa cero.
+pub async fn measure(root: &Path) -> Result<DiskUsage> {
+    let root = root.to_path_buf();
+    let usage = tokio::task::spawn_blocking(move || {
+        let mut usage = DiskUsage::default();
+        for entry in WalkDir::new(&root)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let area = entry
+                .path()
+                .strip_prefix(&root)
+                .ok()
+                .and_then(|relative| relative.components().next())
+                .and_then(|component| component.as_os_str().to_str())
+                .unwrap_or_default();
+            usage.add(area, metadata.len());
+        }
+        usage
+    })
+    .await?;
+    Ok(usage)
+}
@@ -0,0 +1,379 @@
+use anyhow::Result;
+use notify::event::{AccessKind, AccessMode, CreateKind, ModifyKind, RemoveKind, RenameMode};
+use notify::{
+    Config, Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher, WatcherKind,
+};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use walkdir::WalkDir;
+
+/// Intervalo del backend por sondeo, usado donde no hay avisos del sistema.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FsChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// Cambio detectado por el backend, relativo a la raiz vigilada.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawChange {
+    pub kind: FsChangeKind,
+    pub relative: PathBuf,
+    pub is_dir: bool,
+}
+
+/// Entrada del diario de cambios de un contenedor.
+#[derive(Debug, Clone, Serialize)]
+pub struct FsChange {
+    /// Secuencia creciente dentro del diario; sirve de cursor para `since`.
+    pub seq: u64,
+    pub kind: FsChangeKind,
+    /// Ruta vista desde el contenedor (`C:\ProgramData\...`).
+    pub path: String,
+    /// Ruta relativa a `fs/`.
+    pub relative: PathBuf,
+    pub is_dir: bool,
+    pub at: String,
+}
+
+/// Lo que entrega el watcher: un cambio o el aviso de que el backend perdio cambios.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchSignal {
+    Change(RawChange),
+    Overflow,
+}
+
+/// Diario acotado de cambios: al llenarse descarta los mas antiguos.
+#[derive(Debug)]
+pub struct ChangeJournal {
+    capacity: usize,
+    entries: VecDeque<FsChange>,
+    last_seq: u64,
+    /// Secuencia consumida por el ultimo desbordamiento del backend (0 si no hubo).
+    overflow_seq: u64,
+}
+
+impl ChangeJournal {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: VecDeque::new(),
+            last_seq: 0,
+            overflow_seq: 0,
+        }
+    }
+
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    pub fn record(&mut self, change: RawChange, path: String) -> FsChange {
+        self.last_seq += 1;
+        let entry = FsChange {
+            seq: self.last_seq,
+            kind: change.kind,
+            path,
+            relative: change.relative,
+            is_dir: change.is_dir,
+            at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+        };
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry.clone());
+        entry
+    }
+
+    /// Cambios posteriores a `since`, como mucho `limit`. El segundo valor indica que
+    /// se descartaron cambios posteriores a `since` por falta de espacio.
+    pub fn since(&self, since: u64, limit: usize) -> (Vec<FsChange>, bool) {
+        let changes = self
+            .entries
+            .iter()
+            .filter(|entry| entry.seq > since)
+            .take(limit)
+            .cloned()
+            .collect();
+        (changes, self.lost_after(since))
+    }
+
+    /// Anota que el backend perdio cambios; consume una secuencia para que las consultas
+    /// con un cursor anterior salgan truncadas.
+    pub fn mark_overflow(&mut self) {
+        self.last_seq += 1;
+        self.overflow_seq = self.last_seq;
+    }
+
+    fn lost_after(&self, since: u64) -> bool {
+        let oldest = self
+            .entries
+            .front()
+            .map(|entry| entry.seq)
+            .unwrap_or(self.last_seq + 1);
+        since < self.overflow_seq || (since < self.last_seq && oldest > since + 1)
+    }
+}
+
+/// Vigila un arbol con `notify` y entrega cada cambio a `on_event` desde un hilo propio.
+/// Soltar el watcher cierra el backend y espera a que termine el hilo.
+pub struct FsWatcher {
+    backend: &'static str,
+    watcher: Option<Box<dyn Watcher + Send>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FsWatcher {
+    /// Abre el backend y registra el arbol; puede tardar en arboles grandes, asi que
+    /// conviene llamarlo fuera del runtime asincrono.
+    pub fn spawn(
+        root: impl AsRef<Path>,
+        mut on_event: impl FnMut(WatchSignal) + Send + 'static,
+    ) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let (watcher, backend) = open_backend(&root, sender)?;
+        let mut translator = Translator::new(root.clone(), backend);
+        let thread = std::thread::Builder::new()
+            .name("orbit-fs-watch".into())
+            .spawn(move || {
+                // Termina cuando el backend se cierra y suelta su extremo del canal.
+                for event in receiver {
+                    match event {
+                        Ok(event) => translator
+                            .translate(event)
+                            .into_iter()
+                            .for_each(&mut on_event),
+                        Err(err) => {
+                            tracing::warn!(?err, root = %root.display(), "Error del watcher");
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            backend,
+            watcher: Some(watcher),
+            thread: Some(thread),
+        })
+    }
+
+    /// `inotify`, `fsevent`, `kqueue`, `windows` o `poll`.
+    pub fn backend(&self) -> &'static str {
+        self.backend
+    }
+}
+
+impl Drop for FsWatcher {
+    fn drop(&mut self) {
+        drop(self.watcher.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Backend nativo de la plataforma y, si no se puede usar, sondeo cada [`POLL_INTERVAL`].
+fn open_backend(
+    root: &Path,
+    sender: Sender<notify::Result<Event>>,
+) -> Result<(Box<dyn Watcher + Send>, &'static str)> {
+    let native =
+        RecommendedWatcher::new(sender.clone(), Config::default()).and_then(|mut watcher| {
+            watcher.watch(root, RecursiveMode::Recursive)?;
+            Ok(watcher)
+        });
+    match native {
+        Ok(watcher) => Ok((Box::new(watcher), backend_name(RecommendedWatcher::kind()))),
+        Err(err) => {
+            tracing::warn!(
+                ?err,
+                "Avisos del sistema no disponibles; se vigila por sondeo"
+            );
+            let mut watcher =
+                PollWatcher::new(sender, Config::default().with_poll_interval(POLL_INTERVAL))?;
+            watcher.watch(root, RecursiveMode::Recursive)?;
+            Ok((Box::new(watcher), backend_name(PollWatcher::kind())))
+        }
+    }
+}
+
+fn backend_name(kind: WatcherKind) -> &'static str {
+    match kind {
+        WatcherKind::Inotify => "inotify",
+        WatcherKind::Fsevent => "fsevent",
+        WatcherKind::Kqueue => "kqueue",
+        WatcherKind::ReadDirectoryChangesWatcher => "windows",
+        _ => "poll",
+    }
+}
+
+/// Convierte los eventos de `notify` en cambios relativos a la raiz con el mismo
+/// criterio en todos los backends.
+struct Translator {
+    root: PathBuf,
+    /// Con inotify un archivo se da por modificado al cerrarse tras escribir; los demas
+    /// backends solo avisan de la escritura.
+    close_write: bool,
+    /// El sondeo ya informa de todo lo que aparece; los avisos del sistema no ven lo que
+    /// se crea dentro de un directorio nuevo antes de que llegue a vigilarse.
+    polling: bool,
+    /// Archivos creados que aun no se han cerrado tras escribirlos.
+    fresh: HashSet<PathBuf>,
+    /// Directorios conocidos, para saber que se borro cuando el backend no lo dice.
+    dirs: HashSet<PathBuf>,
+    /// Directorios creados mientras se vigila; lo que aparece en ellos entre el recorrido
+    /// de `created_inside` y el alta del aviso solo se ve al modificarse.
+    born: HashSet<PathBuf>,
+    /// Archivos de `born` ya anotados como creados.
+    announced: HashSet<PathBuf>,
+}
+
+impl Translator {
+    fn new(root: PathBuf, backend: &'static str) -> Self {
+        let dirs = WalkDir::new(&root)
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_dir())
+            .filter_map(|entry| entry.path().strip_prefix(&root).ok().map(Path::to_path_buf))
+            .collect();
+        Self {
+            close_write: backend == "inotify",
+            polling: backend == "poll",
+            fresh: HashSet::new(),
+            dirs,
+            born: HashSet::new(),
+            announced: HashSet::new(),
+            root,
+        }
+    }
+
+    fn translate(&mut self, event: Event) -> Vec<WatchSignal> {
+        if event.need_rescan() {
+            tracing::warn!(root = %self.root.display(), "Cola de avisos desbordada; se han perdido cambios");
+            return vec![WatchSignal::Overflow];
+        }
+        let kind =
+            match event.kind {
+                EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                    FsChangeKind::Created
+                }
+                EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    FsChangeKind::Removed
+                }
+                EventKind::Access(AccessKind::Close(AccessMode::Write)) if self.close_write => {
+                    FsChangeKind::Modified
+                }
+                EventKind::Modify(
+                    ModifyKind::Data(_) | ModifyKind::Metadata(_) | ModifyKind::Any,
+                ) if !self.close_write => FsChangeKind::Modified,
+                _ => return vec![],
+            };
+
+        let mut signals = vec![];
+        for path in &event.paths {
+            let Some(relative) = path
+                .strip_prefix(&self.root)
+                .ok()
+                .filter(|relative| !relative.as_os_str().is_empty())
+                .map(Path::to_path_buf)
+            else {
+                continue;
+            };
+            let is_dir = match event.kind {
+                EventKind::Create(CreateKind::Folder) | EventKind::Remove(RemoveKind::Folder) => {
+                    true
+                }
+                EventKind::Create(CreateKind::File) | EventKind::Remove(RemoveKind::File) => false,
+                _ if kind == FsChangeKind::Removed => self.dirs.contains(&relative),
+                _ => path.is_dir(),
+            };
+            match kind {
+                // Los cambios de fecha de un directorio no interesan.
+                FsChangeKind::Modified if is_dir => continue,
+                // Un archivo recien creado tambien avisa al cerrarse.
+                FsChangeKind::Modified if self.fresh.remove(&relative) => continue,
+                FsChangeKind::Modified => {
+                    if self.unannounced(&relative) {
+                        signals.push(WatchSignal::Change(RawChange {
+                            kind: FsChangeKind::Created,
+                            relative: relative.clone(),
+                            is_dir: false,
+                        }));
+                    }
+                }
+                FsChangeKind::Created if is_dir => {
+                    self.dirs.insert(relative.clone());
+                    if !self.polling {
+                        self.born.insert(relative.clone());
+                    }
+                }
+                FsChangeKind::Created => {
+                    self.unannounced(&relative);
+                    if self.close_write && matches!(event.kind, EventKind::Create(_)) {
+                        self.fresh.insert(relative.clone());
+                    }
+                }
+                FsChangeKind::Removed => {
+                    self.fresh.remove(&relative);
+                    self.dirs.retain(|dir| !dir.starts_with(&relative));
+                    self.born.retain(|dir| !dir.starts_with(&relative));
+                    self.announced.retain(|file| !file.starts_with(&relative));
+                }
+            }
+            signals.push(WatchSignal::Change(RawChange {
+                kind,
+                relative: relative.clone(),
+                is_dir,
+            }));
+            if is_dir && kind == FsChangeKind::Created && !self.polling {
+                self.created_inside(&relative, &mut signals);
+            }
+        }
+        signals
+    }
+
+    /// Lo que ya habia dentro de un directorio nuevo se anota como creado.
+    fn created_inside(&mut self, relative: &Path, signals: &mut Vec<WatchSignal>) {
+        for entry in WalkDir::new(self.root.join(relative))
+            .min_depth(1)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+        {
+            let Ok(child) = entry.path().strip_prefix(&self.root) else {
+                continue;
+            };
+            let is_dir = entry.file_type().is_dir();
+            if is_dir {
+                self.dirs.insert(child.to_path_buf());
+                self.born.insert(child.to_path_buf());
+            } else {
+                self.unannounced(child);
+            }
+            signals.push(WatchSignal::Change(RawChange {
+                kind: FsChangeKind::Created,
+                relative: child.to_path_buf(),
+                is_dir,
+            }));
+        }
+    }
+
+    /// Anota un archivo de un directorio nuevo; devuelve `true` si aun no constaba como
+    /// creado.
+    fn unannounced(&mut self, relative: &Path) -> bool {
+        relative
+            .parent()
+            .is_some_and(|parent| self.born.contains(parent))
+            && self.announced.insert(relative.to_path_buf())
+    }
+}
@@ -1,12 +1,20 @@
 use std::{net::SocketAddr, time::Duration as StdDuration};
 
 use agent::{
-    config::{AgentConfig, SecurityConfig},
+    config::{
+        AgentConfig, MountsConfig, RetentionConfig, RetryPolicy, SecurityConfig, StorageConfig,
+        TasksConfig,
+    },
     events::EventHub,
+    models::{
+        ContainerModel, ContainerStatus, MissedRunPolicy, Schedule, ScheduleJob, TaskModel,
+        TaskStatus, TaskStep,
+    },
     security::AuthManager,
     server::{self, AppState},
     services::{AppService, ContainerService, SnapshotService, TokenService},
     store::SqliteStore,
+    virtualization::Platform,
 };
 use reqwest::{Client, StatusCode};
 use serde_json;
@@ -48,6 +56,9 @@ async fn containers_endpoint_creates_tasks() -> anyhow::Result<()> {
             admin_token: None,
             api_tokens: vec![],
         },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
     };
 
     let events = EventHub::new(32);
@@ -168,6 +179,9 @@ async fn auth_rejects_without_token() -> anyhow::Result<()> {
             admin_token: Some("secret-token".into()),
             api_tokens: vec![],
         },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
     };
 
     let events = EventHub::new(16);
@@ -232,6 +246,9 @@ async fn system_config_requires_admin() -> anyhow::Result<()> {
             admin_token: Some("root-token".into()),
             api_tokens: vec!["readonly".into()],
         },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
     };
 
     let events = EventHub::new(8);
@@ -305,6 +322,9 @@ async fn scoped_tokens_limit_permissions() -> anyhow::Result<()> {
             admin_token: Some("root-token".into()),
             api_tokens: vec![],
         },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
     };
 
     let events = EventHub::new(32);
@@ -410,6 +430,9 @@ async fn service_tokens_flow_and_reload() -> anyhow::Result<()> {
             admin_token: Some("root-admin".into()),
             api_tokens: vec![],
         },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
     };
 
     let events = EventHub::new(16);
@@ -555,3 +578,3579 @@ async fn service_tokens_flow_and_reload() -> anyhow::Result<()> {
     let _ = server_handle.await?;
     Ok(())
 }
+
+fn open_config(temp: &TempDir) -> AgentConfig {
+    AgentConfig {
+        containers_root: temp.path().join("containers"),
+        telemetry_level: "info".into(),
+        api_bind: SocketAddr::from(([127, 0, 0, 1], next_port())),
+        database_path: temp.path().join("agent.db"),
+        security: SecurityConfig {
+            auth_enabled: false,
+            admin_token: None,
+            api_tokens: vec![],
+        },
+        storage: StorageConfig::default(),
+        tasks: TasksConfig::default(),
+        mounts: MountsConfig::default(),
+    }
+}
+
+async fn spawn_agent(
+    config: &AgentConfig,
+) -> anyhow::Result<(
+    oneshot::Sender<()>,
+    tokio::task::JoinHandle<anyhow::Result<()>>,
+)> {
+    let events = EventHub::new(32);
+    let store = SqliteStore::new(&config.database_path).await?;
+    let containers = ContainerService::new(config.clone(), events.clone(), store.clone());
+    let apps = AppService::new(events.clone(), store.clone());
+    let snapshots = SnapshotService::new(events.clone(), store.clone());
+    let tokens = TokenService::new(store.clone());
+    let auth = AuthManager::new(config.security.clone(), store.clone());
+    let state = AppState::new(
+        config.clone(),
+        events,
+        store,
+        containers,
+        apps,
+        snapshots,
+        tokens,
+        auth,
+    );
+    state.containers.resume_watches().await?;
+    tokio::spawn(state.retries.clone().run_auto_retry());
+    tokio::spawn(state.schedules.clone().run_scheduler());
+    tokio::spawn(state.retention.clone().run_retention());
+
+    let (tx, rx) = oneshot::channel();
+    let handle = tokio::spawn(async move { server::serve(state, rx).await });
+    tokio::time::sleep(StdDuration::from_millis(200)).await;
+    Ok((tx, handle))
+}
+
+async fn create_container_id(client: &Client, base: &str, name: &str) -> anyhow::Result<String> {
+    let created = client
+        .post(format!("{base}/containers"))
+        .json(&serde_json::json!({ "name": name, "platform": "windows-x64" }))
+        .send()
+        .await?;
+    assert!(created.status().is_success());
+    let containers: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let id = containers
+        .iter()
+        .find(|item| item.get("name").and_then(|v| v.as_str()) == Some(name))
+        .and_then(|item| item.get("id"))
+        .and_then(|v| v.as_str())
+        .expect("container id");
+    Ok(id.to_string())
+}
+
+#[tokio::test]
+async fn container_settings_patch_updates_manifest() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let container_id = create_container_id(&client, &base, "settings-test").await?;
+
+    let patched = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({
+            "description": "QA legacy",
+            "tags": ["qa", "legacy", "qa"],
+            "env": { "JAVA_HOME": "C:\\Java" },
+            "compat_flags": ["force_32bit_path", "disable_hw_accel"],
+            "limits": { "cpu_percent": 50, "memory_mb": 2048 }
+        }))
+        .send()
+        .await?;
+    assert_eq!(patched.status(), StatusCode::OK);
+    let body: serde_json::Value = patched.json().await?;
+    assert_eq!(body["description"], "QA legacy");
+    assert_eq!(body["tags"], serde_json::json!(["legacy", "qa"]));
+    assert_eq!(body["settings"]["limits"]["memory_mb"], 2048);
+
+    let storage_path = body["storage_path"].as_str().expect("storage_path");
+    assert!(storage_path.ends_with(&container_id));
+    let manifest_path = std::path::Path::new(storage_path)
+        .join("runtime")
+        .join("manifest.json");
+    let manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(manifest_path)?)?;
+    assert_eq!(
+        manifest["compat_flags"],
+        serde_json::json!(["disable_hw_accel", "force_32bit_path"])
+    );
+    assert_eq!(manifest["runtime"]["env"]["JAVA_HOME"], "C:\\Java");
+    assert_eq!(manifest["limits"]["cpu_percent"], 50);
+
+    let unknown_flag = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({ "compat_flags": ["force_32bit_path", "turbo_mode"] }))
+        .send()
+        .await?;
+    assert_eq!(unknown_flag.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = unknown_flag.json().await?;
+    assert_eq!(body["code"], "unknown_compat_flag");
+    assert_eq!(body["details"]["fields"][0]["field"], "compat_flags[1]");
+    assert_eq!(body["details"]["fields"][0]["code"], "unknown_value");
+
+    let missing = client
+        .patch(format!("{base}/containers/{}", Uuid::new_v4()))
+        .json(&serde_json::json!({ "description": "x" }))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn container_rename_and_tags_keep_storage_path() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let container_id = create_container_id(&client, &base, "rename-me").await?;
+    let _ = create_container_id(&client, &base, "untagged").await?;
+
+    let renamed: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/rename"))
+        .json(&serde_json::json!({ "name": "renamed" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(renamed["name"], "renamed");
+    let storage_path = renamed["storage_path"].as_str().unwrap().to_string();
+    assert!(std::path::Path::new(&storage_path).join("fs").is_dir());
+    let manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(
+        std::path::Path::new(&storage_path).join("runtime/manifest.json"),
+    )?)?;
+    assert_eq!(manifest["name"], "renamed");
+
+    let tagged: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/tags"))
+        .json(&serde_json::json!({ "tags": ["qa", "nightly"] }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(tagged["tags"], serde_json::json!(["nightly", "qa"]));
+
+    let filtered: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers?tag=qa"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0]["id"], container_id.as_str());
+
+    let untagged: serde_json::Value = client
+        .delete(format!("{base}/containers/{container_id}/tags/qa"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(untagged["tags"], serde_json::json!(["nightly"]));
+    let filtered: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers?tag=qa"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(filtered.is_empty());
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn legacy_name_based_sandboxes_are_migrated() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let store = SqliteStore::new(&config.database_path).await?;
+    let containers = ContainerService::new(config.clone(), EventHub::new(8), store.clone());
+
+    let legacy = ContainerModel::new(
+        Uuid::new_v4(),
+        "legacy:app".into(),
+        None,
+        Platform::WindowsX64,
+    );
+    store.upsert_container(&legacy).await?;
+    let legacy_dir = config.containers_root.join("legacy_app");
+    std::fs::create_dir_all(legacy_dir.join("fs/ProgramFiles/Tool"))?;
+    std::fs::write(legacy_dir.join("fs/ProgramFiles/Tool/tool.exe"), b"MZ")?;
+
+    assert_eq!(containers.migrate_storage().await?, 1);
+    let migrated = store.get_container(legacy.id).await?.expect("container");
+    let target = std::path::PathBuf::from(&migrated.storage_path);
+    assert!(target.ends_with(legacy.id.to_string()));
+    assert!(target.join("fs/ProgramFiles/Tool/tool.exe").is_file());
+    assert!(target.join("runtime/manifest.json").is_file());
+    assert!(!legacy_dir.exists());
+
+    assert_eq!(containers.migrate_storage().await?, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn duplicate_container_names_return_conflict() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let first_id = create_container_id(&client, &base, "unique-name").await?;
+    let other_id = create_container_id(&client, &base, "other-name").await?;
+
+    let duplicate = client
+        .post(format!("{base}/containers"))
+        .json(&serde_json::json!({ "name": "Unique-Name", "platform": "windows-x64" }))
+        .send()
+        .await?;
+    assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = duplicate.json().await?;
+    assert_eq!(body["code"], "container_name_conflict");
+    assert_eq!(body["details"]["name"], "Unique-Name");
+    assert!(body["message"].as_str().is_some());
+
+    let rename_clash = client
+        .post(format!("{base}/containers/{other_id}/rename"))
+        .json(&serde_json::json!({ "name": "unique-name" }))
+        .send()
+        .await?;
+    assert_eq!(rename_clash.status(), StatusCode::CONFLICT);
+
+    let containers: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(containers.len(), 2);
+    assert!(containers.iter().any(|c| c["id"] == first_id.as_str()));
+
+    let missing = client
+        .get(format!("{base}/containers/{}", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = missing.json().await?;
+    assert_eq!(body["code"], "container_not_found");
+
+    let bad_id = client
+        .get(format!("{base}/containers/not-a-uuid"))
+        .send()
+        .await?;
+    assert_eq!(bad_id.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = bad_id.json().await?;
+    assert_eq!(body["code"], "invalid_path");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn api_errors_use_stable_codes_and_accept_language() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    config.security.auth_enabled = true;
+    config.security.admin_token = Some("secret-token".into());
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let unauthorized = client.get(format!("{base}/containers")).send().await?;
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        unauthorized
+            .headers()
+            .get("content-language")
+            .and_then(|v| v.to_str().ok()),
+        Some("es")
+    );
+    let body: serde_json::Value = unauthorized.json().await?;
+    assert_eq!(body["code"], "unauthorized");
+    assert_eq!(body["message"], "Token ausente o invalido");
+
+    let missing_id = Uuid::new_v4();
+    let missing = client
+        .get(format!("{base}/containers/{missing_id}"))
+        .header("Authorization", "Bearer secret-token")
+        .header("Accept-Language", "fr-FR, en-US;q=0.8, es;q=0.5")
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        missing
+            .headers()
+            .get("content-language")
+            .and_then(|v| v.to_str().ok()),
+        Some("en")
+    );
+    let body: serde_json::Value = missing.json().await?;
+    assert_eq!(body["code"], "container_not_found");
+    assert_eq!(body["message"], "Container not found");
+    assert_eq!(body["details"]["container_id"], missing_id.to_string());
+
+    let invalid = client
+        .post(format!("{base}/containers"))
+        .header("Authorization", "Bearer secret-token")
+        .header("Accept-Language", "en")
+        .header("Content-Type", "application/json")
+        .body("{\"name\": 42}")
+        .send()
+        .await?;
+    assert!(invalid.status().is_client_error());
+    let body: serde_json::Value = invalid.json().await?;
+    assert_eq!(body["code"], "invalid_body");
+    assert_eq!(body["message"], "The request body is not valid");
+    assert!(body["details"]["reason"].as_str().is_some());
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_payloads_return_field_errors() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let invalid_container = client
+        .post(format!("{base}/containers"))
+        .json(&serde_json::json!({ "name": "bad\u{7}name", "platform": "linux-x64" }))
+        .send()
+        .await?;
+    assert_eq!(invalid_container.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = invalid_container.json().await?;
+    assert_eq!(body["code"], "validation_failed");
+    let fields = body["details"]["fields"].as_array().expect("fields");
+    assert!(
+        fields
+            .iter()
+            .any(|f| f["field"] == "name" && f["code"] == "invalid_characters")
+    );
+    assert!(
+        fields
+            .iter()
+            .any(|f| f["field"] == "platform" && f["code"] == "unknown_value")
+    );
+
+    let empty_name = client
+        .post(format!("{base}/containers"))
+        .json(&serde_json::json!({ "name": "   ", "platform": "windows-x64" }))
+        .send()
+        .await?;
+    assert_eq!(empty_name.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = empty_name.json().await?;
+    assert_eq!(body["details"]["fields"][0]["code"], "required");
+
+    let container_id = create_container_id(&client, &base, "validated").await?;
+    let bad_patch = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({
+            "tags": ["ok", "no spaces"],
+            "limits": { "cpu_percent": 150 }
+        }))
+        .send()
+        .await?;
+    assert_eq!(bad_patch.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = bad_patch.json().await?;
+    let fields = body["details"]["fields"].as_array().expect("fields");
+    assert!(fields.iter().any(|f| f["field"] == "tags[1]"));
+    assert!(
+        fields
+            .iter()
+            .any(|f| f["field"] == "limits.cpu_percent" && f["code"] == "out_of_range")
+    );
+
+    let bad_snapshot = client
+        .post(format!("{base}/containers/{container_id}/snapshots"))
+        .json(&serde_json::json!({ "type": "incremental" }))
+        .send()
+        .await?;
+    assert_eq!(bad_snapshot.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let bad_token = client
+        .post(format!("{base}/security/tokens"))
+        .json(&serde_json::json!({ "name": "ci", "expires_at": "tomorrow" }))
+        .send()
+        .await?;
+    assert_eq!(bad_token.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = bad_token.json().await?;
+    assert_eq!(body["code"], "invalid_expiration");
+    assert_eq!(body["details"]["fields"][0]["field"], "expires_at");
+    assert_eq!(body["details"]["fields"][0]["code"], "invalid_datetime");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn write_installer(dir: &std::path::Path, name: &str, script: &str) -> anyhow::Result<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(path.display().to_string())
+}
+
+async fn wait_for_app(
+    client: &Client,
+    base: &str,
+    container_id: &str,
+    name: &str,
+) -> anyhow::Result<serde_json::Value> {
+    for _ in 0..50 {
+        let apps: Vec<serde_json::Value> = client
+            .get(format!("{base}/containers/{container_id}/apps"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        if let Some(app) = apps.into_iter().find(|app| app["name"] == name)
+            && app["status"] != "installing"
+        {
+            return Ok(app);
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    anyhow::bail!("la app {name} no termino de instalarse")
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn installer_runs_inside_container_and_discovers_entry_points() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "installs").await?;
+
+    let installer = write_installer(
+        temp.path(),
+        "demo-setup.exe",
+        r#"echo "args: $@"
+mkdir -p "$ORBIT_FS_ROOT/ProgramFiles/Demo"
+touch "$ORBIT_FS_ROOT/ProgramFiles/Demo/demo.exe" "$ORBIT_FS_ROOT/ProgramFiles/Demo/unins000.exe""#,
+    )?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({
+            "name": "Demo",
+            "installer_path": installer,
+            "silent_args": "/S \"/D=C:\\Program Files\\Demo\""
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["type"], "app.install");
+
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "ready");
+    assert_eq!(app["exit_code"], 0);
+    assert_eq!(app["install_source"], installer.as_str());
+    let expected_hash = {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(std::fs::read(&installer)?))
+    };
+    assert_eq!(app["installer_sha256"], expected_hash.as_str());
+    assert!(app["install_duration_ms"].is_u64());
+    assert_eq!(app["size_bytes"], 0);
+    let entry_points = app["entry_points"].as_array().expect("entry_points");
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(entry_points[0]["id"], "demo");
+    assert_eq!(
+        entry_points[0]["command"],
+        "C:\\Program Files\\Demo\\demo.exe"
+    );
+    let log = std::fs::read_to_string(app["log_path"].as_str().expect("log_path"))?;
+    assert!(log.contains("args: /S /D=C:\\Program Files\\Demo"));
+
+    let task_id = task["id"].as_str().expect("task id");
+    let finished: serde_json::Value = client
+        .get(format!("{base}/tasks/{task_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(finished["status"], "succeeded");
+
+    let broken = write_installer(temp.path(), "broken.exe", "echo boom >&2\nexit 3")?;
+    let failed_task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Broken", "installer_path": broken }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let failed = wait_for_app(&client, &base, &container_id, "Broken").await?;
+    assert_eq!(failed["status"], "failed");
+    assert_eq!(failed["exit_code"], 3);
+    let log = std::fs::read_to_string(failed["log_path"].as_str().expect("log_path"))?;
+    assert!(log.contains("boom"));
+    let failed_id = failed_task["id"].as_str().expect("task id");
+    let failed_task: serde_json::Value = client
+        .get(format!("{base}/tasks/{failed_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(failed_task["status"], "failed");
+
+    let missing = client
+        .post(format!("{base}/containers/{}/apps", Uuid::new_v4()))
+        .json(&serde_json::json!({ "name": "Ghost" }))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+fn write_zip(path: &std::path::Path, entries: &[(&str, &str)]) -> anyhow::Result<String> {
+    use std::io::Write;
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    for (name, content) in entries {
+        zip.start_file(*name, zip::write::SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(path.display().to_string())
+}
+
+fn write_tar_gz(path: &std::path::Path, entries: &[(&str, &str)]) -> anyhow::Result<String> {
+    let encoder =
+        flate2::write::GzEncoder::new(std::fs::File::create(path)?, flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+    for (name, content) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, name, content.as_bytes())?;
+    }
+    tar.into_inner()?.finish()?;
+    Ok(path.display().to_string())
+}
+
+#[tokio::test]
+async fn portable_archives_are_extracted_into_program_files() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "portables").await?;
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let program_files = std::path::Path::new(container["storage_path"].as_str().expect("path"))
+        .join("fs")
+        .join("ProgramFiles");
+
+    let zip_path = write_zip(
+        &temp.path().join("tool-1.0.zip"),
+        &[
+            ("Tool-1.0/tool.exe", "MZ"),
+            ("Tool-1.0/bin/helper.exe", "MZ"),
+            (
+                "Tool-1.0/orbit-app.json",
+                r#"{ "vendor": "Acme", "version": "1.0.0" }"#,
+            ),
+        ],
+    )?;
+    let response = client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Tool", "archive_path": zip_path }))
+        .send()
+        .await?;
+    assert!(response.status().is_success());
+    let tool = wait_for_app(&client, &base, &container_id, "Tool").await?;
+    assert_eq!(tool["status"], "ready");
+    assert_eq!(tool["vendor"], "Acme");
+    assert_eq!(tool["version"], "1.0.0");
+    assert_eq!(tool["install_source"], zip_path.as_str());
+    assert!(tool["installer_path"].is_null());
+    assert_eq!(tool["installer_sha256"].as_str().map(str::len), Some(64));
+    let footprint = ("MZ".len() * 2 + r#"{ "vendor": "Acme", "version": "1.0.0" }"#.len()) as u64;
+    assert_eq!(tool["size_bytes"], footprint);
+    assert!(program_files.join("Tool").join("tool.exe").is_file());
+    let commands: Vec<&str> = tool["entry_points"]
+        .as_array()
+        .expect("entry_points")
+        .iter()
+        .filter_map(|entry| entry["command"].as_str())
+        .collect();
+    assert!(commands.contains(&"C:\\Program Files\\Tool\\tool.exe"));
+    assert!(commands.contains(&"C:\\Program Files\\Tool\\bin\\helper.exe"));
+
+    let tar_path = write_tar_gz(
+        &temp.path().join("notes.tar.gz"),
+        &[
+            ("notes.exe", "MZ"),
+            (
+                "App/AppInfo/appinfo.ini",
+                "[Details]\nName=Notes Portable\nPublisher=Notes Team\n[Version]\nDisplayVersion=2.3\n",
+            ),
+        ],
+    )?;
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "archive_path": tar_path, "version": "2.3-custom" }))
+        .send()
+        .await?;
+    let notes = wait_for_app(&client, &base, &container_id, "Notes Portable").await?;
+    assert_eq!(notes["status"], "ready");
+    assert_eq!(notes["vendor"], "Notes Team");
+    assert_eq!(notes["version"], "2.3-custom");
+    assert!(program_files.join("notes").join("notes.exe").is_file());
+    assert_eq!(notes["install_dir"], "C:\\Program Files\\notes");
+
+    let notes_v3 = write_tar_gz(
+        &temp.path().join("notes-3.tar.gz"),
+        &[("notes.exe", "MZv3")],
+    )?;
+    let task: serde_json::Value = client
+        .post(format!(
+            "{base}/apps/{}/update",
+            notes["id"].as_str().expect("id")
+        ))
+        .json(&serde_json::json!({ "archive_path": notes_v3, "version": "3.0" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let task = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(task["status"], "succeeded");
+    assert_eq!(
+        std::fs::read_to_string(program_files.join("notes").join("notes.exe"))?,
+        "MZv3"
+    );
+    assert!(!program_files.join("Notes Portable").exists());
+
+    let evil_path = write_zip(
+        &temp.path().join("evil.zip"),
+        &[("ok.exe", "MZ"), ("../../../escaped.exe", "MZ")],
+    )?;
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Evil", "archive_path": evil_path }))
+        .send()
+        .await?;
+    let evil = wait_for_app(&client, &base, &container_id, "Evil").await?;
+    assert_eq!(evil["status"], "failed");
+    assert!(!program_files.join("Evil").exists());
+    assert!(!program_files.join("escaped.exe").exists());
+    assert!(
+        !std::path::Path::new(container["storage_path"].as_str().expect("path"))
+            .join("escaped.exe")
+            .exists()
+    );
+
+    let unsupported = client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "archive_path": "/tmp/tool.rar" }))
+        .send()
+        .await?;
+    assert_eq!(unsupported.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn entry_points_are_managed_and_resolved_inside_container() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "entry-points").await?;
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let app_dir = std::path::Path::new(container["storage_path"].as_str().expect("path"))
+        .join("fs")
+        .join("ProgramFiles")
+        .join("Demo");
+    std::fs::create_dir_all(app_dir.join("bin"))?;
+    std::fs::write(app_dir.join("demo.exe"), "MZ")?;
+    std::fs::write(app_dir.join("bin").join("tool.exe"), "MZ")?;
+
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Demo" }))
+        .send()
+        .await?;
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "disabled");
+    let app_id = app["id"].as_str().expect("id").to_string();
+    let entry_points_url = format!("{base}/apps/{app_id}/entry-points");
+
+    let missing = client
+        .post(&entry_points_url)
+        .json(
+            &serde_json::json!({ "label": "Demo", "command": "C:\\Program Files\\Demo\\nope.exe" }),
+        )
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = missing.json().await?;
+    assert_eq!(body["details"]["fields"][0]["field"], "command");
+    assert_eq!(body["details"]["fields"][0]["code"], "not_in_container");
+
+    let escape = client
+        .post(&entry_points_url)
+        .json(&serde_json::json!({ "label": "Shell", "command": "C:\\..\\..\\bin\\sh" }))
+        .send()
+        .await?;
+    assert_eq!(escape.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let placeholder = client
+        .post(&entry_points_url)
+        .json(&serde_json::json!({
+            "label": "Demo",
+            "command": "C:\\Program Files\\Demo\\demo.exe",
+            "args": "--user {user}",
+        }))
+        .send()
+        .await?;
+    assert_eq!(placeholder.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let created = client
+        .post(&entry_points_url)
+        .json(&serde_json::json!({
+            "label": "Demo App",
+            "command": "c:/program files/Demo/demo.exe",
+            "args": "--profile {container_id}",
+            "working_dir": "C:\\Program Files\\Demo",
+            "env": { "DEMO_MODE": "1" },
+        }))
+        .send()
+        .await?;
+    assert_eq!(created.status(), StatusCode::CREATED);
+    let entry: serde_json::Value = created.json().await?;
+    assert_eq!(entry["id"], "demo-app");
+    assert_eq!(entry["command"], "C:\\Program Files\\Demo\\demo.exe");
+    assert_eq!(entry["working_dir"], "C:\\Program Files\\Demo");
+    assert_eq!(entry["env"]["DEMO_MODE"], "1");
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "ready");
+
+    let duplicate = client
+        .post(&entry_points_url)
+        .json(&serde_json::json!({ "label": "Demo App", "command": "ProgramFiles/Demo/demo.exe" }))
+        .send()
+        .await?;
+    assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = duplicate.json().await?;
+    assert_eq!(body["code"], "entry_point_conflict");
+
+    let last = client
+        .delete(format!("{entry_points_url}/demo-app"))
+        .send()
+        .await?;
+    assert_eq!(last.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = last.json().await?;
+    assert_eq!(body["code"], "entry_point_required");
+
+    let created = client
+        .post(&entry_points_url)
+        .json(&serde_json::json!({
+            "id": "tool",
+            "label": "Tool",
+            "command": "C:\\Program Files\\Demo\\bin\\tool.exe",
+        }))
+        .send()
+        .await?;
+    assert_eq!(created.status(), StatusCode::CREATED);
+
+    let patched = client
+        .patch(format!("{entry_points_url}/tool"))
+        .json(&serde_json::json!({ "label": "Demo Tool", "working_dir": "C:\\Program Files\\Demo\\bin" }))
+        .send()
+        .await?;
+    assert_eq!(patched.status(), StatusCode::OK);
+    let entry: serde_json::Value = patched.json().await?;
+    assert_eq!(entry["label"], "Demo Tool");
+    assert_eq!(entry["working_dir"], "C:\\Program Files\\Demo\\bin");
+
+    let not_dir = client
+        .patch(format!("{entry_points_url}/tool"))
+        .json(&serde_json::json!({ "working_dir": "C:\\Program Files\\Demo\\demo.exe" }))
+        .send()
+        .await?;
+    assert_eq!(not_dir.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let removed = client
+        .delete(format!("{entry_points_url}/demo-app"))
+        .send()
+        .await?;
+    assert_eq!(removed.status(), StatusCode::NO_CONTENT);
+    let unknown = client
+        .delete(format!("{entry_points_url}/demo-app"))
+        .send()
+        .await?;
+    assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = unknown.json().await?;
+    assert_eq!(body["code"], "entry_point_not_found");
+
+    let entries: Vec<serde_json::Value> =
+        client.get(&entry_points_url).send().await?.json().await?;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["id"], "tool");
+
+    let missing_app = client
+        .get(format!("{base}/apps/{}/entry-points", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(missing_app.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+async fn wait_for_task(
+    client: &Client,
+    base: &str,
+    task: &serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let task_id = task["id"].as_str().expect("task id");
+    for _ in 0..50 {
+        let current: serde_json::Value = client
+            .get(format!("{base}/tasks/{task_id}"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        if current["status"] != "running" {
+            return Ok(current);
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    anyhow::bail!("la tarea {task_id} no termino")
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn apps_can_be_disabled_updated_with_rollback_and_uninstalled() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "lifecycle").await?;
+    let storage = container_storage(&client, &base, &container_id).await?;
+    let program_data = std::path::Path::new(&storage).join("fs/ProgramData");
+    std::fs::write(program_data.join("shared.ini"), "[shared]\n")?;
+
+    let installer = write_installer(
+        temp.path(),
+        "demo-setup.exe",
+        r#"dir="$ORBIT_FS_ROOT/ProgramFiles/Demo"
+mkdir -p "$dir" "$ORBIT_FS_ROOT/ProgramData/Demo/cache"
+echo "demo=1" >> "$ORBIT_FS_ROOT/ProgramData/shared.ini"
+echo config > "$ORBIT_FS_ROOT/ProgramData/Demo/cache/config.ini"
+echo v1 > "$dir/demo.exe"
+printf '#!/bin/sh\necho "uninstall: $@"\nrm -f "$(dirname "$0")/demo.exe"\n' > "$dir/unins000.exe"
+chmod +x "$dir/unins000.exe""#,
+    )?;
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Demo", "installer_path": installer, "version": "1.0" }))
+        .send()
+        .await?;
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "ready");
+    assert_eq!(app["install_dir"], "C:\\Program Files\\Demo");
+    assert_eq!(app["uninstaller"], "C:\\Program Files\\Demo\\unins000.exe");
+    assert_eq!(app["install_source"], installer.as_str());
+    let staged = std::path::Path::new(app["installer_path"].as_str().expect("installer_path"));
+    assert!(staged.ends_with(format!(
+        "runtime/installers/{}/demo-setup.exe",
+        app["id"].as_str().expect("id")
+    )));
+    assert!(staged.is_file());
+    let app_id = app["id"].as_str().expect("id").to_string();
+    let manifest: serde_json::Value = client
+        .get(format!("{base}/apps/{app_id}/files"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let files = manifest["files"].as_array().expect("files");
+    let file = |path: &str| {
+        files
+            .iter()
+            .find(|file| file["path"] == path)
+            .cloned()
+            .unwrap_or_else(|| panic!("{path} no capturado"))
+    };
+    let demo = file("C:\\Program Files\\Demo\\demo.exe");
+    assert_eq!(demo["change"], "created");
+    assert_eq!(demo["size"], 3);
+    let expected_hash = {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(b"v1\n"))
+    };
+    assert_eq!(demo["sha256"], expected_hash.as_str());
+    assert_eq!(file("C:\\ProgramData\\shared.ini")["change"], "modified");
+    assert_eq!(
+        file("C:\\ProgramData\\Demo\\cache\\config.ini")["change"],
+        "created"
+    );
+    let sandbox_manifest = std::path::Path::new(&storage).join("runtime/manifest.json");
+    let stored: serde_json::Value = serde_json::from_slice(&std::fs::read(&sandbox_manifest)?)?;
+    assert_eq!(stored["app_files"][&app_id]["files"], manifest["files"]);
+    assert_eq!(files.len(), 4);
+    assert_eq!(manifest["total_bytes"], app["size_bytes"]);
+    let app_dir = std::path::Path::new(&storage).join("fs/ProgramFiles/Demo");
+
+    let disabled: serde_json::Value = client
+        .post(format!("{base}/apps/{app_id}/disable"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(disabled["type"], "app.disable");
+    assert_eq!(disabled["status"], "succeeded");
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "disabled");
+    let enabled = client
+        .post(format!("{base}/apps/{app_id}/enable"))
+        .send()
+        .await?;
+    assert_eq!(enabled.status(), StatusCode::OK);
+
+    let upgrade = write_installer(
+        temp.path(),
+        "demo-2.exe",
+        r#"dir="$ORBIT_FS_ROOT/ProgramFiles/Demo"
+echo v2 > "$dir/demo.exe"
+echo tool > "$dir/tool.exe""#,
+    )?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/apps/{app_id}/update"))
+        .json(&serde_json::json!({ "installer_path": upgrade, "version": "2.0" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["type"], "app.update");
+    let task = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(task["status"], "succeeded");
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "ready");
+    assert_eq!(app["version"], "2.0");
+    let ids: Vec<&str> = app["entry_points"]
+        .as_array()
+        .expect("entry_points")
+        .iter()
+        .filter_map(|entry| entry["id"].as_str())
+        .collect();
+    assert_eq!(ids, vec!["demo", "tool"]);
+    let manifest: serde_json::Value = client
+        .get(format!("{base}/apps/{app_id}/files"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let changes: Vec<(&str, &str)> = manifest["files"]
+        .as_array()
+        .expect("files")
+        .iter()
+        .filter_map(|file| Some((file["path"].as_str()?, file["change"].as_str()?)))
+        .collect();
+    assert!(changes.contains(&("C:\\Program Files\\Demo\\demo.exe", "created")));
+    assert!(changes.contains(&("C:\\Program Files\\Demo\\tool.exe", "created")));
+    let snapshots: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/snapshots"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(
+        snapshots
+            .iter()
+            .any(|snapshot| snapshot["label"] == "pre-update Demo")
+    );
+
+    let broken = write_installer(
+        temp.path(),
+        "demo-3.exe",
+        r#"echo v3 > "$ORBIT_FS_ROOT/ProgramFiles/Demo/demo.exe"
+echo partial > "$ORBIT_FS_ROOT/ProgramFiles/Demo/partial.exe"
+exit 5"#,
+    )?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/apps/{app_id}/update"))
+        .json(&serde_json::json!({ "installer_path": broken, "version": "3.0" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let task = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(task["status"], "failed");
+    assert!(
+        task["message"]
+            .as_str()
+            .expect("message")
+            .contains("se restauro el snapshot")
+    );
+    let app = wait_for_app(&client, &base, &container_id, "Demo").await?;
+    assert_eq!(app["status"], "ready");
+    assert_eq!(app["version"], "2.0");
+    assert_eq!(app["exit_code"], 5);
+    assert_eq!(
+        std::fs::read_to_string(app_dir.join("demo.exe"))?.trim(),
+        "v2"
+    );
+    assert!(!app_dir.join("partial.exe").exists());
+
+    let missing_source = client
+        .post(format!("{base}/apps/{app_id}/update"))
+        .json(&serde_json::json!({ "version": "4.0" }))
+        .send()
+        .await?;
+    assert_eq!(missing_source.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    std::fs::write(app_dir.join("notes.txt"), "del usuario")?;
+
+    let task: serde_json::Value = client
+        .delete(format!("{base}/apps/{app_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["type"], "app.uninstall");
+    let task = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(task["status"], "succeeded");
+    assert!(!app_dir.join("demo.exe").exists());
+    assert!(!app_dir.join("unins000.exe").exists());
+    assert!(app_dir.join("notes.txt").is_file());
+    assert!(!program_data.join("Demo").exists());
+    assert!(program_data.join("shared.ini").is_file());
+    let stored: serde_json::Value = serde_json::from_slice(&std::fs::read(&sandbox_manifest)?)?;
+    assert!(stored["app_files"].get(&app_id).is_none());
+    let apps: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/apps"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(apps.is_empty());
+    let log = std::fs::read_to_string(
+        std::path::Path::new(&storage)
+            .join("logs")
+            .join(format!("uninstall-{app_id}.log")),
+    )?;
+    assert!(log.contains("uninstall: /VERYSILENT"));
+
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Manual" }))
+        .send()
+        .await?;
+    let manual = wait_for_app(&client, &base, &container_id, "Manual").await?;
+    let manual_id = manual["id"].as_str().expect("id");
+    let enable = client
+        .post(format!("{base}/apps/{manual_id}/enable"))
+        .send()
+        .await?;
+    assert_eq!(enable.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = enable.json().await?;
+    assert_eq!(body["code"], "entry_point_required");
+
+    let missing = client
+        .delete(format!("{base}/apps/{}", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+async fn container_storage(
+    client: &Client,
+    base: &str,
+    container_id: &str,
+) -> anyhow::Result<String> {
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    Ok(container["storage_path"]
+        .as_str()
+        .expect("storage_path")
+        .to_string())
+}
+
+#[tokio::test]
+async fn snapshots_capture_and_restore_container_files() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "snapshots").await?;
+    let data = std::path::Path::new(&container_storage(&client, &base, &container_id).await?)
+        .join("fs/ProgramData/settings.ini");
+    std::fs::write(&data, "original")?;
+
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/snapshots"))
+        .json(&serde_json::json!({ "label": "baseline" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["status"], "succeeded");
+    let snapshots: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/snapshots"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let snapshot = &snapshots[0];
+    assert!(snapshot["size_bytes"].as_u64().expect("size") >= "original".len() as u64);
+
+    std::fs::write(&data, "changed")?;
+    std::fs::write(data.with_file_name("extra.ini"), "new")?;
+    let snapshot_id = snapshot["id"].as_str().expect("id");
+    let task: serde_json::Value = client
+        .post(format!("{base}/snapshots/{snapshot_id}/restore"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["status"], "succeeded");
+    assert_eq!(std::fs::read_to_string(&data)?, "original");
+    assert!(!data.with_file_name("extra.ini").exists());
+
+    let missing = client
+        .post(format!("{base}/containers/{}/snapshots", Uuid::new_v4()))
+        .json(&serde_json::json!({}))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn verify_reports_drift_and_flags_missing_critical_files() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "verify").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    let tool_dir = storage.join("fs/ProgramFiles/Tool");
+
+    let zip_path = write_zip(
+        &temp.path().join("tool.zip"),
+        &[("tool.exe", "MZ"), ("tool.dll", "library")],
+    )?;
+    client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Tool", "archive_path": zip_path }))
+        .send()
+        .await?;
+    let tool = wait_for_app(&client, &base, &container_id, "Tool").await?;
+    assert_eq!(tool["status"], "ready");
+    let snapshot: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/snapshots"))
+        .json(&serde_json::json!({ "label": "baseline" }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(snapshot["status"], "succeeded");
+
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/verify"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(task["type"], "container.verify");
+    let task = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(task["status"], "succeeded");
+    let report = &task["result"];
+    assert!(report["checked_files"].as_u64().expect("checked") >= 2);
+    for list in ["missing", "modified", "unexpected"] {
+        assert_eq!(report[list], serde_json::json!([]), "{list}");
+    }
+
+    std::fs::write(tool_dir.join("tool.dll"), "tampered")?;
+    std::fs::write(tool_dir.join("payload.exe"), "MZ")?;
+    std::fs::remove_file(tool_dir.join("tool.exe"))?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/verify"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let task = wait_for_task(&client, &base, &task).await?;
+    let report = &task["result"];
+    let paths = |list: &str| -> Vec<(String, bool)> {
+        report[list]
+            .as_array()
+            .expect("findings")
+            .iter()
+            .map(|finding| {
+                (
+                    finding["path"].as_str().expect("path").to_string(),
+                    finding["critical"].as_bool().expect("critical"),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(
+        paths("missing"),
+        vec![("C:\\Program Files\\Tool\\tool.exe".to_string(), true)]
+    );
+    assert_eq!(
+        paths("modified"),
+        vec![("C:\\Program Files\\Tool\\tool.dll".to_string(), false)]
+    );
+    assert_eq!(
+        paths("unexpected"),
+        vec![("C:\\Program Files\\Tool\\payload.exe".to_string(), false)]
+    );
+    assert_eq!(
+        report["modified"][0]["scope"],
+        format!("app:{}", tool["id"].as_str().expect("id"))
+    );
+
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(container["status"], "error");
+    assert!(
+        container["status_reason"]
+            .as_str()
+            .expect("reason")
+            .contains("tool.exe")
+    );
+
+    // Restaurar el ejecutable devuelve el contenedor a `ready`; los cambios en los
+    // snapshots tambien se detectan.
+    std::fs::write(tool_dir.join("tool.exe"), "MZ")?;
+    let snapshot_dir = storage
+        .join("snapshots")
+        .read_dir()?
+        .next()
+        .expect("snapshot")?
+        .path();
+    std::fs::write(
+        snapshot_dir.join("fs/ProgramFiles/Tool/tool.exe"),
+        "corrupt",
+    )?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/verify"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let task = wait_for_task(&client, &base, &task).await?;
+    let report = &task["result"];
+    assert_eq!(report["missing"], serde_json::json!([]));
+    let modified_scopes: Vec<&str> = report["modified"]
+        .as_array()
+        .expect("modified")
+        .iter()
+        .filter_map(|finding| finding["scope"].as_str())
+        .collect();
+    assert!(
+        modified_scopes
+            .iter()
+            .any(|scope| scope.starts_with("snapshot:"))
+    );
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(container["status"], "ready");
+    assert!(container["status_reason"].is_null());
+
+    // Un error que no viene de la verificacion sigue ahi aunque no falte nada.
+    let store = SqliteStore::new(&config.database_path).await?;
+    let mut model = store
+        .get_container(container_id.parse()?)
+        .await?
+        .expect("container");
+    model.status = ContainerStatus::Error;
+    model.status_reason = Some("Fallo al montar".into());
+    store.upsert_container(&model).await?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/verify"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    wait_for_task(&client, &base, &task).await?;
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(container["status"], "error");
+    assert_eq!(container["status_reason"], "Fallo al montar");
+
+    // Las rutas de la app se resuelven con los montajes del contenedor.
+    let patched = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({
+            "mounts": [{ "virtual_path": "C:\\Tools", "target": "Extra" }]
+        }))
+        .send()
+        .await?;
+    assert_eq!(patched.status(), StatusCode::OK);
+    let relocated = storage.join("fs/Extra/Tool");
+    std::fs::create_dir_all(&relocated)?;
+    std::fs::write(relocated.join("tool.exe"), "MZ")?;
+    let mut app = store
+        .get_app(tool["id"].as_str().expect("id").parse()?)
+        .await?
+        .expect("app");
+    app.install_dir = Some("C:\\Tools\\Tool".into());
+    app.entry_points[0].command = "C:\\Tools\\Tool\\tool.exe".into();
+    store.insert_app(&app).await?;
+    let task: serde_json::Value = client
+        .post(format!("{base}/containers/{container_id}/verify"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let task = wait_for_task(&client, &base, &task).await?;
+    let report = &task["result"];
+    assert_eq!(report["missing"], serde_json::json!([]));
+    let unexpected: Vec<&str> = report["unexpected"]
+        .as_array()
+        .expect("unexpected")
+        .iter()
+        .filter_map(|finding| finding["path"].as_str())
+        .collect();
+    assert_eq!(unexpected, vec!["C:\\Tools\\Tool\\tool.exe"]);
+
+    let missing = client
+        .post(format!("{base}/containers/{}/verify", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn container_files_can_be_browsed_uploaded_and_downloaded() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "files").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    let files = format!("{base}/containers/{container_id}/files");
+
+    let root: Vec<serde_json::Value> = client.get(&files).send().await?.json().await?;
+    let program_files = root
+        .iter()
+        .find(|entry| entry["name"] == "Program Files")
+        .expect("Program Files");
+    assert_eq!(program_files["path"], "C:\\Program Files");
+    assert_eq!(program_files["kind"], "directory");
+
+    // Mas grande que el limite por defecto de los cuerpos JSON.
+    let payload = vec![b'x'; 3 * 1024 * 1024];
+    let response = client
+        .put(format!("{files}/content"))
+        .query(&[("path", "C:\\ProgramData\\Demo\\config.ini")])
+        .body(payload.clone())
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let entry: serde_json::Value = response.json().await?;
+    assert_eq!(entry["path"], "C:\\ProgramData\\Demo\\config.ini");
+    assert_eq!(entry["size"], payload.len() as u64);
+    assert_eq!(
+        std::fs::read(storage.join("fs/ProgramData/Demo/config.ini"))?,
+        payload
+    );
+
+    let listing: Vec<serde_json::Value> = client
+        .get(&files)
+        .query(&[("path", "C:\\ProgramData\\Demo")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(listing.len(), 1, "no quedan temporales: {listing:?}");
+    assert_eq!(listing[0]["name"], "config.ini");
+    assert_eq!(listing[0]["kind"], "file");
+    assert!(listing[0]["modified"].is_string());
+
+    let download = client
+        .get(format!("{files}/content"))
+        .query(&[("path", "C:/ProgramData/Demo/config.ini")])
+        .send()
+        .await?;
+    assert_eq!(download.status(), StatusCode::OK);
+    assert_eq!(
+        download.headers()[reqwest::header::CONTENT_DISPOSITION],
+        "attachment; filename=\"config.ini\""
+    );
+    assert_eq!(download.bytes().await?.as_ref(), payload.as_slice());
+
+    let field_code = |body: &serde_json::Value| body["details"]["fields"][0]["code"].clone();
+    let escape = client
+        .put(format!("{files}/content"))
+        .query(&[("path", "C:\\ProgramData\\..\\..\\escaped.txt")])
+        .body("nope")
+        .send()
+        .await?;
+    assert_eq!(escape.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(field_code(&escape.json().await?), "not_in_container");
+    assert!(!storage.join("escaped.txt").exists());
+
+    let missing = client
+        .get(format!("{files}/content"))
+        .query(&[("path", "C:\\ProgramData\\missing.log")])
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        missing.json::<serde_json::Value>().await?["code"],
+        "file_not_found"
+    );
+
+    let not_dir: serde_json::Value = client
+        .get(&files)
+        .query(&[("path", "C:\\ProgramData\\Demo\\config.ini")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(field_code(&not_dir), "not_a_directory");
+    let not_file: serde_json::Value = client
+        .get(format!("{files}/content"))
+        .query(&[("path", "C:\\ProgramData")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(field_code(&not_file), "not_a_file");
+    let required: serde_json::Value = client
+        .get(format!("{files}/content"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(field_code(&required), "required");
+
+    #[cfg(unix)]
+    {
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(&outside)?;
+        std::fs::write(outside.join("secret.txt"), "secret")?;
+        std::os::unix::fs::symlink(&outside, storage.join("fs/ProgramData/link"))?;
+        for path in ["C:\\ProgramData\\link", "C:\\ProgramData\\link\\secret.txt"] {
+            let response = client
+                .get(format!("{files}/content"))
+                .query(&[("path", path)])
+                .send()
+                .await?;
+            assert_eq!(
+                response.status(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "{path}"
+            );
+        }
+        let upload = client
+            .put(format!("{files}/content"))
+            .query(&[("path", "C:\\ProgramData\\link\\planted.txt")])
+            .body("planted")
+            .send()
+            .await?;
+        assert_eq!(upload.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(!outside.join("planted.txt").exists());
+    }
+
+    let unknown = client
+        .get(format!("{base}/containers/{}/files", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn mount_table_prepares_platform_dirs_and_binds_host_dirs() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    config.mounts.bind_roots = vec![temp.path().join("shared")];
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "mounts").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    for dir in [
+        "ProgramFiles (x86)",
+        "Users/Default/Documents",
+        "Users/Default/AppData/Roaming",
+        "Users/Default/AppData/Local/Temp",
+    ] {
+        assert!(storage.join("fs").join(dir).is_dir(), "{dir}");
+    }
+    assert!(!storage.join("fs/ProgramFiles (Arm)").exists());
+
+    let created = client
+        .post(format!("{base}/containers"))
+        .json(&serde_json::json!({ "name": "arm", "platform": "windows-arm64" }))
+        .send()
+        .await?;
+    assert!(created.status().is_success());
+    let containers: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let arm = containers
+        .iter()
+        .find(|item| item["name"] == "arm")
+        .expect("contenedor arm64");
+    let arm_storage = std::path::PathBuf::from(arm["storage_path"].as_str().expect("storage"));
+    assert!(arm_storage.join("fs/ProgramFiles (Arm)").is_dir());
+
+    let host = temp.path().join("shared/host-assets");
+    std::fs::create_dir_all(host.join("textures"))?;
+    std::fs::write(host.join("textures/grass.dds"), "dds")?;
+    let container = format!("{base}/containers/{container_id}");
+    let response = client
+        .patch(&container)
+        .json(&serde_json::json!({
+            "mounts": [
+                { "virtual_path": "C:\\Assets", "source": host.display().to_string(), "read_only": true },
+                { "virtual_path": "C:\\Saves", "target": "Storage/Saves" },
+            ],
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(storage.join("fs/Storage/Saves").is_dir());
+
+    let files = format!("{container}/files");
+    let listing: Vec<serde_json::Value> = client
+        .get(&files)
+        .query(&[("path", "C:\\Assets\\textures")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(listing.len(), 1);
+    assert_eq!(listing[0]["path"], "C:\\Assets\\textures\\grass.dds");
+    let download = client
+        .get(format!("{files}/content"))
+        .query(&[("path", "C:\\Assets\\textures\\grass.dds")])
+        .send()
+        .await?;
+    assert_eq!(download.status(), StatusCode::OK);
+    assert_eq!(download.text().await?, "dds");
+    let upload = client
+        .put(format!("{files}/content"))
+        .query(&[("path", "C:\\Assets\\textures\\planted.dds")])
+        .body("nope")
+        .send()
+        .await?;
+    assert_eq!(upload.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = upload.json().await?;
+    assert_eq!(body["details"]["fields"][0]["code"], "read_only");
+    assert!(!host.join("textures/planted.dds").exists());
+
+    let resolved: serde_json::Value = client
+        .get(format!("{container}/paths/resolve"))
+        .query(&[("physical", host.join("textures").display().to_string())])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(resolved["virtual_path"], "C:\\Assets\\textures");
+    assert_eq!(resolved["read_only"], true);
+    assert!(resolved["relative"].is_null());
+
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(storage.join("runtime/manifest.json"))?)?;
+    let overlays = manifest["filesystem"]["overlays"]
+        .as_array()
+        .expect("overlays");
+    let assets = overlays
+        .iter()
+        .find(|overlay| overlay["virtual_path"] == "C:\\Assets")
+        .expect("bind en el manifest");
+    assert_eq!(assets["kind"], "bind");
+    assert_eq!(assets["read_only"], true);
+    assert_eq!(assets["physical_path"], host.display().to_string());
+    assert!(overlays.iter().any(|overlay| {
+        overlay["virtual_path"] == "C:\\Program Files (x86)" && overlay["kind"] == "overlay"
+    }));
+
+    let invalid: serde_json::Value = client
+        .patch(&container)
+        .json(&serde_json::json!({
+            "mounts": [
+                { "virtual_path": "C:\\Relative", "source": "relative/dir" },
+                { "virtual_path": "C:\\Missing", "source": temp.path().join("missing").display().to_string() },
+                { "virtual_path": "C:\\Both", "target": "Both", "source": host.display().to_string() },
+            ],
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let codes: Vec<(&str, &str)> = invalid["details"]["fields"]
+        .as_array()
+        .expect("fields")
+        .iter()
+        .filter_map(|field| Some((field["field"].as_str()?, field["code"].as_str()?)))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            ("mounts[0].source", "invalid_value"),
+            ("mounts[1].source", "not_a_directory"),
+            ("mounts[2].target", "conflicts_with"),
+        ]
+    );
+
+    // Solo se montan directorios de `mounts.bind_roots` y, sin `writable_binds`, en
+    // solo lectura.
+    let private = temp.path().join("private");
+    std::fs::create_dir_all(&private)?;
+    for (mount, field) in [
+        (
+            serde_json::json!({ "virtual_path": "C:\\Private", "source": private.display().to_string(), "read_only": true }),
+            "mounts[0].source",
+        ),
+        (
+            serde_json::json!({ "virtual_path": "C:\\Escape", "source": host.join("../../private").display().to_string(), "read_only": true }),
+            "mounts[0].source",
+        ),
+        (
+            serde_json::json!({ "virtual_path": "C:\\Assets", "source": host.display().to_string() }),
+            "mounts[0].read_only",
+        ),
+    ] {
+        let rejected = client
+            .patch(&container)
+            .json(&serde_json::json!({ "mounts": [mount] }))
+            .send()
+            .await?;
+        assert_eq!(rejected.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = rejected.json().await?;
+        assert_eq!(body["details"]["fields"][0]["field"], field);
+        assert_eq!(body["details"]["fields"][0]["code"], "not_allowed");
+    }
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn disk_usage_is_reported_and_quotas_block_installs_and_snapshots() -> anyhow::Result<()> {
+    const MIB: u64 = 1024 * 1024;
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    config.storage.global_quota_mb = Some(3);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let first = create_container_id(&client, &base, "usage-a").await?;
+    let second = create_container_id(&client, &base, "usage-b").await?;
+    let upload = |container_id: &str, bytes: u64| {
+        client
+            .put(format!("{base}/containers/{container_id}/files/content"))
+            .query(&[("path", "C:\\ProgramData\\blob.bin")])
+            .body(vec![0u8; bytes as usize])
+            .send()
+    };
+    assert_eq!(upload(&first, 2 * MIB).await?.status(), StatusCode::OK);
+
+    let summary: serde_json::Value = client
+        .get(format!("{base}/system/storage"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(summary["global_quota_bytes"], 3 * MIB);
+    let entry = summary["containers"]
+        .as_array()
+        .expect("containers")
+        .iter()
+        .find(|entry| entry["container_id"] == first.as_str())
+        .expect("usage-a")
+        .clone();
+    let fs_bytes = entry["usage"]["fs"].as_u64().expect("fs");
+    let total = entry["usage"]["total"].as_u64().expect("total");
+    assert!(fs_bytes >= 2 * MIB, "{entry}");
+    assert!(entry["usage"]["registry"].as_u64().expect("registry") > 0);
+    assert_eq!(
+        total,
+        ["fs", "registry", "snapshots", "logs", "other"]
+            .iter()
+            .map(|area| entry["usage"][area].as_u64().expect(area))
+            .sum::<u64>()
+    );
+    assert!(entry["quota_bytes"].is_null());
+    assert_eq!(entry["over_quota"], false);
+    assert!(summary["usage"]["total"].as_u64().expect("total") >= total);
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{first}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(container["size_bytes"], total);
+
+    let response = client
+        .patch(format!("{base}/containers/{first}"))
+        .json(&serde_json::json!({ "limits": { "disk_mb": 1 } }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let snapshot = client
+        .post(format!("{base}/containers/{first}/snapshots"))
+        .json(&serde_json::json!({ "label": "before" }))
+        .send()
+        .await?;
+    assert_eq!(snapshot.status(), StatusCode::INSUFFICIENT_STORAGE);
+    let body: serde_json::Value = snapshot.json().await?;
+    assert_eq!(body["code"], "quota_exceeded");
+    assert_eq!(body["details"]["scope"], "container");
+    assert_eq!(body["details"]["quota_bytes"], MIB);
+    let install = client
+        .post(format!("{base}/containers/{first}/apps"))
+        .json(&serde_json::json!({ "name": "Blocked" }))
+        .send()
+        .await?;
+    assert_eq!(install.status(), StatusCode::INSUFFICIENT_STORAGE);
+
+    // El segundo contenedor no tiene cuota propia, pero entre los dos superan la global.
+    let snapshot = client
+        .post(format!("{base}/containers/{second}/snapshots"))
+        .json(&serde_json::json!({ "label": "first" }))
+        .send()
+        .await?;
+    assert_eq!(snapshot.status(), StatusCode::OK);
+    assert_eq!(upload(&second, 2 * MIB).await?.status(), StatusCode::OK);
+    let install = client
+        .post(format!("{base}/containers/{second}/apps"))
+        .json(&serde_json::json!({ "name": "Blocked" }))
+        .send()
+        .await?;
+    assert_eq!(install.status(), StatusCode::INSUFFICIENT_STORAGE);
+    let body: serde_json::Value = install.json().await?;
+    assert_eq!(body["details"]["scope"], "global");
+    assert!(body["details"]["used_bytes"].as_u64().expect("used") >= 4 * MIB);
+
+    let summary: serde_json::Value = client
+        .get(format!("{base}/system/storage"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let over_quota = summary["containers"]
+        .as_array()
+        .expect("containers")
+        .iter()
+        .find(|entry| entry["container_id"] == first.as_str())
+        .expect("usage-a");
+    assert_eq!(over_quota["quota_bytes"], MIB);
+    assert_eq!(over_quota["over_quota"], true);
+
+    let invalid: serde_json::Value = client
+        .patch(format!("{base}/containers/{first}"))
+        .json(&serde_json::json!({ "limits": { "disk_mb": 0 } }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(invalid["details"]["fields"][0]["field"], "limits.disk_mb");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn fs_watcher_journals_changes_while_running() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "watched").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    let container = format!("{base}/containers/{container_id}");
+
+    let idle: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(idle["watching"], false);
+    assert_eq!(idle["changes"].as_array().map(Vec::len), Some(0));
+
+    // Solo se vigilan los contenedores arrancados.
+    let started: serde_json::Value = client
+        .post(format!("{container}/start"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(started["status"], "running");
+    let status: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(status["watching"], true);
+    assert_eq!(status["backend"], "inotify");
+
+    let dir = storage.join("fs/ProgramData/Watch");
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("log.txt"), "one")?;
+    tokio::time::sleep(StdDuration::from_millis(100)).await;
+    std::fs::write(dir.join("log.txt"), "one two")?;
+    tokio::time::sleep(StdDuration::from_millis(100)).await;
+    std::fs::remove_file(dir.join("log.txt"))?;
+
+    let file = "C:\\ProgramData\\Watch\\log.txt";
+    let mut log = serde_json::Value::Null;
+    for _ in 0..50 {
+        log = client
+            .get(format!("{container}/changes"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let removed = log["changes"].as_array().is_some_and(|changes| {
+            changes
+                .iter()
+                .any(|change| change["path"] == file && change["kind"] == "removed")
+        });
+        if removed {
+            break;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    let changes = log["changes"].as_array().expect("changes");
+    let kinds = |path: &str| {
+        changes
+            .iter()
+            .filter(|change| change["path"] == path)
+            .map(|change| change["kind"].as_str().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+    };
+    let dir_created = changes
+        .iter()
+        .find(|change| change["path"] == "C:\\ProgramData\\Watch")
+        .expect("directorio creado");
+    assert_eq!(dir_created["kind"], "created");
+    assert_eq!(dir_created["is_dir"], true);
+    let file_kinds = kinds(file);
+    assert_eq!(file_kinds.first().map(String::as_str), Some("created"));
+    assert!(
+        file_kinds.iter().any(|kind| kind == "modified"),
+        "{file_kinds:?}"
+    );
+    assert_eq!(file_kinds.last().map(String::as_str), Some("removed"));
+    let seqs = changes
+        .iter()
+        .filter_map(|change| change["seq"].as_u64())
+        .collect::<Vec<_>>();
+    assert!(seqs.windows(2).all(|pair| pair[0] < pair[1]));
+    let last_seq = log["last_seq"].as_u64().expect("last_seq");
+    assert_eq!(seqs.last(), Some(&last_seq));
+
+    let page: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .query(&[("since", "1"), ("limit", "1")])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(page["changes"][0]["seq"], 2);
+    assert_eq!(page["changes"].as_array().map(Vec::len), Some(1));
+
+    let stopped: serde_json::Value = client
+        .post(format!("{container}/stop"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(stopped["status"], "ready");
+    std::fs::write(dir.join("late.txt"), "late")?;
+    tokio::time::sleep(StdDuration::from_millis(500)).await;
+    let after: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .query(&[("since", last_seq.to_string())])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(after["watching"], false);
+    assert_eq!(after["last_seq"], last_seq);
+    assert_eq!(after["changes"].as_array().map(Vec::len), Some(0));
+
+    let missing = client
+        .post(format!("{base}/containers/{}/start", Uuid::new_v4()))
+        .send()
+        .await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+    // Un contenedor que sigue `running` al reiniciar el agente vuelve a vigilarse.
+    let response = client.post(format!("{container}/start")).send().await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    let mut config = config;
+    config.api_bind = SocketAddr::from(([127, 0, 0, 1], next_port()));
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let base = format!("http://{}", config.api_bind);
+    let container = format!("{base}/containers/{container_id}");
+    let resumed: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(resumed["watching"], true);
+
+    // Sin sandbox queda en `error`, deja de vigilarse y ya no puede arrancarse.
+    std::fs::rename(&storage, temp.path().join("moved"))?;
+    let response = client
+        .post(format!("{base}/system/reconcile"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let broken: serde_json::Value = client
+        .get(format!("{container}/changes"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(broken["watching"], false);
+    let response = client.post(format!("{container}/start")).send().await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "container_not_ready");
+    assert_eq!(body["details"]["status"], "error");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn paths_resolve_through_the_container_mount_table() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let container_id = create_container_id(&client, &base, "paths").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    let resolve = format!("{base}/containers/{container_id}/paths/resolve");
+    let query = |param: &'static str, value: &'static str| {
+        let client = client.clone();
+        let resolve = resolve.clone();
+        async move {
+            let response = client.get(&resolve).query(&[(param, value)]).send().await?;
+            let status = response.status();
+            anyhow::Ok((status, response.json::<serde_json::Value>().await?))
+        }
+    };
+
+    let (status, temp_dir) = query("virtual", "%TEMP%\\setup.log").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        temp_dir["virtual_path"],
+        "C:\\Users\\Default\\AppData\\Local\\Temp\\setup.log"
+    );
+    assert_eq!(
+        temp_dir["mount"],
+        "C:\\Users\\Default\\AppData\\Local\\Temp"
+    );
+    assert_eq!(temp_dir["exists"], false);
+    let (_, program) = query("virtual", "c:/PROGRAM FILES/Tool/../Tool/tool.exe").await?;
+    assert_eq!(program["mount"], "C:\\Program Files");
+    assert_eq!(program["virtual_path"], "C:\\PROGRAM FILES\\Tool\\tool.exe");
+    assert_eq!(
+        std::path::Path::new(program["physical"].as_str().expect("physical")),
+        storage
+            .join("fs")
+            .join("ProgramFiles")
+            .join("Tool")
+            .join("tool.exe")
+    );
+
+    let response = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({
+            "env": { "USERPROFILE": "C:\\Users\\orbit" },
+            "mounts": [{ "virtual_path": "C:\\Games", "target": "Storage/Games" }],
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let (_, appdata) = query("virtual", "%AppData%\\App").await?;
+    assert_eq!(
+        appdata["virtual_path"],
+        "C:\\Users\\orbit\\AppData\\Roaming\\App"
+    );
+    assert_eq!(appdata["mount"], "C:\\Users\\orbit\\AppData\\Roaming");
+    assert_eq!(
+        std::path::Path::new(appdata["relative"].as_str().expect("relative")),
+        std::path::Path::new("Users/Default/AppData/Roaming/App")
+    );
+    let (_, game) = query("virtual", "C:\\games\\save.dat").await?;
+    assert_eq!(game["mount"], "C:\\Games");
+    assert_eq!(
+        std::path::Path::new(game["relative"].as_str().expect("relative")),
+        std::path::Path::new("Storage/Games/save.dat")
+    );
+    let (_, reverse) = query("physical", "Storage/Games/save.dat").await?;
+    assert_eq!(reverse["virtual_path"], "C:\\Games\\save.dat");
+    let absolute = storage.join("fs/ProgramData/Vendor");
+    let reverse: serde_json::Value = client
+        .get(&resolve)
+        .query(&[("physical", absolute.display().to_string())])
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(reverse["virtual_path"], "C:\\ProgramData\\Vendor");
+
+    // La API de archivos usa la misma tabla de montajes.
+    let upload = client
+        .put(format!("{base}/containers/{container_id}/files/content"))
+        .query(&[("path", "C:\\Games\\save.dat")])
+        .body("progress")
+        .send()
+        .await?;
+    assert_eq!(upload.status(), StatusCode::OK);
+    assert!(storage.join("fs/Storage/Games/save.dat").is_file());
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(storage.join("runtime/manifest.json"))?)?;
+    let overlays: Vec<&str> = manifest["filesystem"]["overlays"]
+        .as_array()
+        .expect("overlays")
+        .iter()
+        .filter_map(|overlay| overlay["virtual_path"].as_str())
+        .collect();
+    assert!(overlays.contains(&"C:\\Program Files"));
+    assert!(overlays.contains(&"C:\\Games"));
+
+    for (param, value, code) in [
+        ("virtual", "%NOPE%\\x", "unknown_variable"),
+        ("virtual", "D:\\data", "unsupported_drive"),
+        ("virtual", "C:\\..\\escape", "not_in_container"),
+        ("physical", "/etc/passwd", "not_in_container"),
+    ] {
+        let (status, body) = query(param, value).await?;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{value}");
+        assert_eq!(body["details"]["fields"][0]["field"], param);
+        assert_eq!(body["details"]["fields"][0]["code"], code, "{value}");
+    }
+    let both = client
+        .get(&resolve)
+        .query(&[("virtual", "C:\\"), ("physical", "Users")])
+        .send()
+        .await?;
+    assert_eq!(both.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let invalid: serde_json::Value = client
+        .patch(format!("{base}/containers/{container_id}"))
+        .json(&serde_json::json!({
+            "mounts": [
+                { "virtual_path": "C:\\", "target": "root" },
+                { "virtual_path": "C:\\Data", "target": "../outside" },
+            ],
+        }))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let codes: Vec<(&str, &str)> = invalid["details"]["fields"]
+        .as_array()
+        .expect("fields")
+        .iter()
+        .filter_map(|field| Some((field["field"].as_str()?, field["code"].as_str()?)))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            ("mounts[0].virtual_path", "reserved"),
+            ("mounts[1].target", "invalid_value"),
+        ]
+    );
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn reconcile_rebuilds_rows_from_manifests_and_flags_missing_sandboxes() -> anyhow::Result<()>
+{
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let lost = create_container_id(&client, &base, "reconcile-lost").await?;
+    let gone = create_container_id(&client, &base, "reconcile-gone").await?;
+    let stale = create_container_id(&client, &base, "reconcile-stale").await?;
+    let response = client
+        .patch(format!("{base}/containers/{lost}"))
+        .json(&serde_json::json!({
+            "description": "Perdido",
+            "tags": ["qa"],
+            "env": { "LANG": "es_ES" },
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let lost_root = std::path::PathBuf::from(container_storage(&client, &base, &lost).await?);
+    let manifest_path = lost_root.join("runtime").join("manifest.json");
+    let manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path)?)?;
+    assert_eq!(manifest["schema_version"], 3);
+    assert_eq!(manifest["description"], "Perdido");
+    assert_eq!(manifest["env"]["LANG"], "es_ES");
+    assert_eq!(
+        manifest["runtime"]["env"]["ORBIT_CONTAINER_ID"],
+        lost.as_str()
+    );
+
+    // La fila desaparece pero el sandbox sigue en disco con un manifest v1.
+    let parked = temp.path().join("parked");
+    std::fs::rename(&lost_root, &parked)?;
+    let response = client
+        .delete(format!("{base}/containers/{lost}"))
+        .send()
+        .await?;
+    assert!(response.status().is_success());
+    std::fs::rename(&parked, &lost_root)?;
+    let mut legacy = manifest.as_object().expect("manifest").clone();
+    for field in [
+        "schema_version",
+        "description",
+        "tags",
+        "env",
+        "mounts",
+        "app_files",
+    ] {
+        legacy.remove(field);
+    }
+    std::fs::write(&manifest_path, serde_json::to_vec(&legacy)?)?;
+    // Los agentes anteriores guardaban los archivos de cada app aparte.
+    let legacy_app = Uuid::new_v4();
+    std::fs::create_dir_all(lost_root.join("runtime/apps"))?;
+    std::fs::write(
+        lost_root.join(format!("runtime/apps/{legacy_app}.json")),
+        serde_json::to_vec(&serde_json::json!({
+            "app_id": legacy_app,
+            "generated_at": "2026-01-01T00:00:00Z",
+            "total_bytes": 2,
+            "files": [{
+                "path": "C:\\Program Files\\Old\\old.exe",
+                "relative_path": "ProgramFiles/Old/old.exe",
+                "size": 2,
+                "sha256": "00",
+                "change": "created",
+            }],
+        }))?,
+    )?;
+
+    let gone_root = container_storage(&client, &base, &gone).await?;
+    std::fs::remove_dir_all(&gone_root)?;
+    let stale_root = std::path::PathBuf::from(container_storage(&client, &base, &stale).await?);
+    std::fs::write(stale_root.join("runtime").join("manifest.json"), b"{ roto")?;
+    std::fs::create_dir_all(config.containers_root.join("junk"))?;
+
+    let report: serde_json::Value = client
+        .post(format!("{base}/system/reconcile"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(report["restored"], serde_json::json!([lost]));
+    assert_eq!(report["repaired"], serde_json::json!([stale]));
+    assert_eq!(report["missing_sandbox"], serde_json::json!([gone]));
+    let skipped = report["skipped"].as_array().expect("skipped");
+    assert_eq!(skipped.len(), 1, "{report}");
+    assert!(skipped[0]["path"].as_str().expect("path").ends_with("junk"));
+
+    let restored: serde_json::Value = client
+        .get(format!("{base}/containers/{lost}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(restored["name"], "reconcile-lost");
+    assert_eq!(restored["status"], "ready");
+    assert_eq!(restored["created_at"], manifest["created_at"]);
+    assert_eq!(restored["settings"]["env"]["LANG"], "es_ES");
+    assert!(restored["tags"].as_array().expect("tags").is_empty());
+    let upgraded: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path)?)?;
+    assert_eq!(upgraded["schema_version"], 3);
+    assert_eq!(upgraded["env"]["LANG"], "es_ES");
+    assert_eq!(
+        upgraded["app_files"][legacy_app.to_string()]["files"][0]["relative_path"],
+        "ProgramFiles/Old/old.exe"
+    );
+    assert!(!lost_root.join("runtime/apps").exists());
+    let repaired: serde_json::Value = serde_json::from_slice(&std::fs::read(
+        stale_root.join("runtime").join("manifest.json"),
+    )?)?;
+    assert_eq!(repaired["container_id"], stale.as_str());
+
+    let flagged: serde_json::Value = client
+        .get(format!("{base}/containers/{gone}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(flagged["status"], "error");
+    assert!(
+        flagged["status_reason"]
+            .as_str()
+            .expect("status_reason")
+            .starts_with("Sandbox ausente")
+    );
+
+    // Una segunda pasada no encuentra nada nuevo que reparar.
+    let report: serde_json::Value = client
+        .post(format!("{base}/system/reconcile"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(report["restored"], serde_json::json!([]));
+    assert_eq!(report["repaired"], serde_json::json!([]));
+    assert_eq!(report["missing_sandbox"], serde_json::json!([gone]));
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn orphan_sandboxes_are_listed_and_adopted() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    let shared = temp.path().join("shared");
+    std::fs::create_dir_all(&shared)?;
+    config.mounts.bind_roots = vec![shared.clone()];
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let original = create_container_id(&client, &base, "orphan-source").await?;
+    let source = std::path::PathBuf::from(container_storage(&client, &base, &original).await?);
+
+    // Ambas copias montan un directorio del host ademas de un overlay propio.
+    let mounts = serde_json::json!([
+        { "virtual_path": "C:\\Shared", "source": shared.display().to_string(), "read_only": true },
+        { "virtual_path": "C:\\Saves", "target": "Storage/Saves", "read_only": false },
+    ]);
+    let source_manifest = source.join("runtime").join("manifest.json");
+    let mut with_mounts: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&source_manifest)?)?;
+    with_mounts["mounts"] = mounts.clone();
+    std::fs::write(&source_manifest, serde_json::to_vec(&with_mounts)?)?;
+
+    // Una copia a mano conserva el id; otra se edita para que parezca de otra maquina.
+    let copy = source.with_file_name("copia");
+    copy_dir(&source, &copy)?;
+    let foreign = source.with_file_name("ajeno");
+    copy_dir(&source, &foreign)?;
+    let foreign_id = uuid::Uuid::new_v4();
+    let manifest_path = foreign.join("runtime").join("manifest.json");
+    let mut manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path)?)?;
+    manifest["container_id"] = serde_json::json!(foreign_id);
+    manifest["name"] = serde_json::json!("orphan-foreign");
+    std::fs::write(&manifest_path, serde_json::to_vec(&manifest)?)?;
+
+    let orphans: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/orphans"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(orphans.len(), 2, "{orphans:?}");
+    let by_path = |path: &std::path::Path| {
+        orphans
+            .iter()
+            .find(|orphan| orphan["path"] == path.display().to_string())
+            .cloned()
+            .expect("orphan")
+    };
+    assert_eq!(by_path(&copy)["container_id"], original.as_str());
+    assert_eq!(by_path(&copy)["conflict"], "id_in_use");
+    assert_eq!(by_path(&copy)["schema_version"], 3);
+    assert!(by_path(&foreign)["conflict"].is_null());
+
+    // Al arrancar se registran los huerfanos sin conflictos.
+    let store = SqliteStore::new(&config.database_path).await?;
+    let startup = ContainerService::new(config.clone(), EventHub::new(8), store);
+    assert_eq!(startup.adopt_orphans().await?, vec![foreign_id]);
+    let adopted: serde_json::Value = client
+        .get(format!("{base}/containers/{foreign_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(adopted["name"], "orphan-foreign");
+    assert_eq!(adopted["storage_path"], foreign.display().to_string());
+    // Al adoptarlo solo, el bind del host se descarta.
+    assert_eq!(
+        adopted["settings"]["mounts"],
+        serde_json::json!([mounts[1]])
+    );
+
+    let adopt = |body: serde_json::Value| {
+        client
+            .post(format!("{base}/containers/orphans/adopt"))
+            .json(&body)
+            .send()
+    };
+    let response = adopt(serde_json::json!({ "path": copy })).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "container_name_conflict");
+
+    let response = adopt(serde_json::json!({ "path": copy, "name": "orphan-copy" })).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let adopted: serde_json::Value = response.json().await?;
+    let adopted_id = adopted["id"].as_str().expect("id").to_string();
+    assert_ne!(adopted_id, original);
+    assert_eq!(adopted["name"], "orphan-copy");
+    assert_eq!(adopted["storage_path"], copy.display().to_string());
+    assert_eq!(
+        adopted["settings"]["mounts"][0]["source"],
+        mounts[0]["source"]
+    );
+    let manifest: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(copy.join("runtime").join("manifest.json"))?)?;
+    assert_eq!(manifest["container_id"], adopted_id.as_str());
+    assert_eq!(
+        manifest["runtime"]["env"]["ORBIT_CONTAINER_ID"],
+        adopted_id.as_str()
+    );
+
+    let response = adopt(serde_json::json!({ "path": copy })).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "orphan_not_found");
+    let orphans: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/orphans"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(orphans.is_empty(), "{orphans:?}");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn templates_are_stored_and_build_containers() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let installer = write_installer(
+        temp.path(),
+        "tool-setup.exe",
+        r#"mkdir -p "$ORBIT_FS_ROOT/ProgramFiles/Tool"
+touch "$ORBIT_FS_ROOT/ProgramFiles/Tool/tool.exe""#,
+    )?;
+
+    let blueprint = format!(
+        r#"description = "Base de QA"
+platform = "windows-x64"
+tags = ["qa"]
+
+[env]
+LANG = "es_ES"
+
+[limits]
+memory_mb = 512
+
+[[registry]]
+key = 'HKLM\SOFTWARE\Acme'
+name = "Version"
+value = "1.0"
+
+[[registry]]
+key = 'HKCU\Software\Acme'
+name = "Level"
+value = 3
+
+[[apps]]
+name = "Tool"
+installer_path = "{installer}"
+silent_args = "/S"
+"#
+    );
+    let response = client
+        .put(format!("{base}/templates/qa-base"))
+        .header("content-type", "application/toml")
+        .body(blueprint)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let stored: serde_json::Value = response.json().await?;
+    assert_eq!(stored["name"], "qa-base");
+    assert_eq!(stored["registry"][1]["value"], 3);
+    let file = std::fs::read_to_string(temp.path().join("templates").join("qa-base.toml"))?;
+    assert!(file.contains("Base de QA"), "{file}");
+
+    let mut replaced = stored.clone();
+    replaced["tags"] = serde_json::json!(["qa", "nightly"]);
+    let response = client
+        .put(format!("{base}/templates/qa-base"))
+        .json(&replaced)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = client
+        .put(format!("{base}/templates/broken"))
+        .json(&serde_json::json!({
+            "platform": "windows-x64",
+            "registry": [{ "key": "HKXX\\Acme", "value": "x" }],
+            "apps": [{ "installer_path": "a.exe", "archive_path": "a.zip" }],
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await?;
+    let fields = body["details"]["fields"].as_array().expect("fields");
+    assert!(
+        fields
+            .iter()
+            .any(|field| field["field"] == "registry[0].key" && field["code"] == "unknown_hive")
+    );
+    assert!(
+        fields
+            .iter()
+            .any(|field| field["field"] == "apps[0].archive_path"
+                && field["code"] == "conflicts_with")
+    );
+    let response = client
+        .put(format!("{base}/templates/broken"))
+        .header("content-type", "application/toml")
+        .body("platform = ")
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let templates: Vec<serde_json::Value> = client
+        .get(format!("{base}/templates"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0]["tags"], serde_json::json!(["qa", "nightly"]));
+
+    let response = client
+        .post(format!("{base}/containers/from-template"))
+        .json(&serde_json::json!({ "template": "qa-base", "name": "qa-1" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let task: serde_json::Value = response.json().await?;
+    assert_eq!(task["type"], "container.template");
+    let finished = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(finished["status"], "succeeded", "{finished}");
+    assert_eq!(finished["result"]["template"], "qa-base");
+    assert_eq!(
+        finished["result"]["app_tasks"]
+            .as_array()
+            .expect("app_tasks")
+            .len(),
+        1
+    );
+    let container_id = finished["result"]["container_id"]
+        .as_str()
+        .expect("container_id")
+        .to_string();
+
+    let container: serde_json::Value = client
+        .get(format!("{base}/containers/{container_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(container["name"], "qa-1");
+    assert_eq!(container["description"], "Base de QA");
+    assert_eq!(container["tags"], serde_json::json!(["nightly", "qa"]));
+    assert_eq!(container["settings"]["env"]["LANG"], "es_ES");
+    assert_eq!(container["settings"]["limits"]["memory_mb"], 512);
+    let registry =
+        std::path::Path::new(container["storage_path"].as_str().expect("storage")).join("registry");
+    let software = std::fs::read_to_string(registry.join("SOFTWARE.reg"))?;
+    assert!(software.contains("[HKEY_LOCAL_MACHINE\\SOFTWARE\\Acme]\n\"Version\"=\"1.0\""));
+    let ntuser = std::fs::read_to_string(registry.join("NTUSER.dat"))?;
+    assert!(ntuser.contains("[HKEY_CURRENT_USER\\Software\\Acme]\n\"Level\"=dword:00000003"));
+    let app = wait_for_app(&client, &base, &container_id, "Tool").await?;
+    assert_eq!(app["status"], "ready");
+
+    let response = client
+        .post(format!("{base}/containers/from-template"))
+        .json(&serde_json::json!({ "template": "qa-base", "name": "qa-1" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let response = client
+        .post(format!("{base}/containers/from-template"))
+        .json(&serde_json::json!({ "template": "missing", "name": "qa-2" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "template_not_found");
+
+    let response = client
+        .delete(format!("{base}/templates/qa-base"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = client
+        .get(format!("{base}/templates/qa-base"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = client
+        .get(format!("{base}/templates/..secret"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn desired_state_is_planned_and_applied() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let installer = write_installer(
+        temp.path(),
+        "tool-setup.exe",
+        r#"mkdir -p "$ORBIT_FS_ROOT/ProgramFiles/Tool"
+touch "$ORBIT_FS_ROOT/ProgramFiles/Tool/tool.exe""#,
+    )?;
+    let web_id = create_container_id(&client, &base, "web").await?;
+    create_container_id(&client, &base, "legacy").await?;
+    client
+        .post(format!("{base}/containers/{web_id}/tags"))
+        .json(&serde_json::json!({ "tags": ["old"] }))
+        .send()
+        .await?;
+
+    let document = format!(
+        r#"[[containers]]
+name = "web"
+platform = "windows-x64"
+tags = ["qa"]
+
+[containers.env]
+LANG = "es_ES"
+
+[[containers.apps]]
+name = "Tool"
+installer_path = "{installer}"
+
+[[containers]]
+name = "office"
+platform = "windows-x64"
+description = "Ofimatica"
+
+[[containers.apps]]
+name = "Notes"
+"#
+    );
+    let plan = |prune: bool| {
+        client
+            .post(format!("{base}/containers/plan?prune={prune}"))
+            .header("content-type", "application/toml")
+            .body(document.clone())
+            .send()
+    };
+    let response = plan(false).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(
+        body["items"],
+        serde_json::json!([
+            { "action": "update", "container": "web", "changes": ["tags", "env"] },
+            { "action": "create", "container": "web", "app": "Tool" },
+            { "action": "create", "container": "office" },
+            { "action": "create", "container": "office", "app": "Notes" },
+        ])
+    );
+    assert_eq!(body["unmanaged"], serde_json::json!(["legacy"]));
+
+    let response = client
+        .post(format!("{base}/containers/plan"))
+        .json(&serde_json::json!({
+            "containers": [
+                { "name": "web", "platform": "windows-arm64" },
+                { "name": "WEB", "platform": "windows-x64", "apps": [{ "version": "1" }] },
+            ]
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await?;
+    let fields = body["details"]["fields"].as_array().expect("fields");
+    assert!(
+        fields
+            .iter()
+            .any(|field| field["field"] == "containers[1].name" && field["code"] == "duplicate")
+    );
+    assert!(
+        fields
+            .iter()
+            .any(|field| field["field"] == "containers[1].apps[0].name"
+                && field["code"] == "required")
+    );
+    let response = client
+        .post(format!("{base}/containers/plan"))
+        .json(&serde_json::json!({
+            "containers": [{ "name": "web", "platform": "windows-arm64" }]
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(
+        body["details"]["fields"][0]["field"],
+        "containers[0].platform"
+    );
+    assert_eq!(body["details"]["fields"][0]["code"], "immutable");
+
+    // Un plan con borrados solo se aplica con la huella del plan que se mostro.
+    let apply = |fingerprint: Option<&str>| {
+        let mut url = format!("{base}/containers/apply?prune=true");
+        if let Some(fingerprint) = fingerprint {
+            url.push_str(&format!("&fingerprint={fingerprint}"));
+        }
+        client
+            .post(url)
+            .header("content-type", "application/toml")
+            .body(document.clone())
+            .send()
+    };
+    let response = apply(None).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "plan_not_confirmed");
+    assert_eq!(body["details"]["reason"], "missing");
+    assert_eq!(body["details"]["deletes"], 1);
+
+    let shown: serde_json::Value = plan(true).await?.json().await?;
+    let shown = shown["fingerprint"]
+        .as_str()
+        .expect("fingerprint")
+        .to_string();
+    create_container_id(&client, &base, "stray").await?;
+    let response = apply(Some(&shown)).await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["details"]["reason"], "changed");
+    assert_eq!(body["details"]["deletes"], 2);
+    assert_ne!(body["details"]["fingerprint"], shown.as_str());
+
+    let shown: serde_json::Value = plan(true).await?.json().await?;
+    let response = apply(shown["fingerprint"].as_str()).await?;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let task: serde_json::Value = response.json().await?;
+    assert_eq!(task["type"], "container.apply");
+    let finished = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(finished["status"], "succeeded", "{finished}");
+    let results = finished["result"]["items"].as_array().expect("items");
+    assert_eq!(results.len(), 6);
+    assert!(results.iter().all(|item| item["status"] == "succeeded"));
+    assert_eq!(results[4]["action"], "delete");
+    assert_eq!(results[4]["container"], "legacy");
+    assert_eq!(results[5]["container"], "stray");
+    assert!(results[1]["task_id"].is_string());
+
+    let containers: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let mut names = containers
+        .iter()
+        .map(|container| container["name"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["office", "web"]);
+    let web = containers
+        .iter()
+        .find(|container| container["name"] == "web")
+        .expect("web");
+    assert_eq!(web["tags"], serde_json::json!(["qa"]));
+    assert_eq!(web["settings"]["env"]["LANG"], "es_ES");
+    let tool = wait_for_app(&client, &base, &web_id, "Tool").await?;
+    assert_eq!(tool["status"], "ready");
+
+    let response = plan(true).await?;
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["items"], serde_json::json!([]));
+    assert_eq!(body["unmanaged"], serde_json::json!([]));
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn template_steps_are_child_tasks() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+    let installer = write_installer(
+        temp.path(),
+        "tool-setup.exe",
+        r#"mkdir -p "$ORBIT_FS_ROOT/ProgramFiles/Tool"
+touch "$ORBIT_FS_ROOT/ProgramFiles/Tool/tool.exe""#,
+    )?;
+    let broken = write_installer(temp.path(), "broken-setup.exe", "exit 1")?;
+
+    let response = client
+        .put(format!("{base}/templates/chain"))
+        .json(&serde_json::json!({
+            "platform": "windows-x64",
+            "apps": [
+                { "name": "Tool", "installer_path": installer },
+                { "name": "Broken", "installer_path": broken },
+            ],
+        }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = client
+        .post(format!("{base}/containers/from-template"))
+        .json(&serde_json::json!({ "template": "chain", "name": "chain-1" }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let task: serde_json::Value = response.json().await?;
+    assert_eq!(task["total_steps"], 3);
+    let finished = wait_for_task(&client, &base, &task).await?;
+    assert_eq!(finished["status"], "failed", "{finished}");
+    assert!(finished.get("children").is_none());
+
+    let task_id = task["id"].as_str().expect("task id");
+    let tree: serde_json::Value = client
+        .get(format!("{base}/tasks/{task_id}?include=children"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(tree["id"], task["id"]);
+    let children = tree["children"].as_array().expect("children");
+    let summary: Vec<_> = children
+        .iter()
+        .map(|child| {
+            assert_eq!(child["parent_task_id"], task["id"]);
+            (
+                child["step"].as_u64().expect("step"),
+                child["type"].as_str().expect("type").to_string(),
+                child["status"].as_str().expect("status").to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, "container.create".into(), "succeeded".into()),
+            (2, "app.install".into(), "succeeded".into()),
+            (3, "app.install".into(), "failed".into()),
+        ]
+    );
+
+    let child_id = children[0]["id"].as_str().expect("child id");
+    let child: serde_json::Value = client
+        .get(format!("{base}/tasks/{child_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(child["parent_task_id"], task["id"]);
+    assert_eq!(child["required"], true);
+
+    let response = client
+        .get(format!("{base}/tasks/{task_id}?include=parents"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "invalid_query");
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn failed_tasks_are_retried_and_keys_replayed() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    config.tasks.retry.insert(
+        "app.install".into(),
+        RetryPolicy {
+            max_attempts: 3,
+            backoff_ms: 50,
+            max_backoff_ms: 200,
+        },
+    );
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let create = |name: &str| {
+        client
+            .post(format!("{base}/containers"))
+            .header("Idempotency-Key", "create-lab")
+            .json(&serde_json::json!({ "name": name, "platform": "windows-x64" }))
+            .send()
+    };
+    let first = create("lab").await?;
+    assert_eq!(first.status(), StatusCode::OK);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    let first: serde_json::Value = first.json().await?;
+    let second = create("lab").await?;
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(second.headers()["idempotent-replayed"], "true");
+    let second: serde_json::Value = second.json().await?;
+    assert_eq!(second["id"], first["id"]);
+    let reused = create("lab-2").await?;
+    assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = reused.json().await?;
+    assert_eq!(body["code"], "idempotency_key_reused");
+    let containers: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(containers.len(), 1);
+    let container_id = containers[0]["id"].as_str().expect("id").to_string();
+
+    // Las subidas no se guardan en memoria para la clave: ni limite de tamano ni repeticion.
+    let upload = |bytes: usize| {
+        client
+            .put(format!("{base}/containers/{container_id}/files/content"))
+            .header("Idempotency-Key", "upload-blob")
+            .query(&[("path", "C:\\ProgramData\\blob.bin")])
+            .body(vec![0u8; bytes])
+            .send()
+    };
+    let large = upload(33 * 1024 * 1024).await?;
+    assert_eq!(large.status(), StatusCode::OK);
+    let small = upload(16).await?;
+    assert_eq!(small.status(), StatusCode::OK);
+    assert!(small.headers().get("idempotent-replayed").is_none());
+    let entry: serde_json::Value = small.json().await?;
+    assert_eq!(entry["size"], 16);
+
+    // El instalador falla hasta la cuarta ejecucion: la politica agota tres intentos.
+    let counter = temp.path().join("attempts");
+    let installer = write_installer(
+        temp.path(),
+        "flaky-setup.exe",
+        &format!(
+            r#"count=$(cat "{counter}" 2>/dev/null || echo 0)
+count=$((count + 1))
+echo "$count" > "{counter}"
+[ "$count" -ge 4 ] || exit 1
+mkdir -p "$ORBIT_FS_ROOT/ProgramFiles/Flaky"
+touch "$ORBIT_FS_ROOT/ProgramFiles/Flaky/flaky.exe""#,
+            counter = counter.display()
+        ),
+    )?;
+    let response = client
+        .post(format!("{base}/containers/{container_id}/apps"))
+        .json(&serde_json::json!({ "name": "Flaky", "installer_path": installer }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let task: serde_json::Value = response.json().await?;
+    assert_eq!(task["attempt"], 1);
+    assert_eq!(task["max_attempts"], 3);
+
+    let mut attempts = Vec::new();
+    for _ in 0..50 {
+        let tasks: Vec<serde_json::Value> = client
+            .get(format!("{base}/tasks"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        attempts = tasks
+            .into_iter()
+            .filter(|task| task["type"] == "app.install")
+            .collect();
+        attempts.sort_by_key(|task| task["attempt"].as_u64());
+        if attempts.len() == 3 && attempts[2]["status"] == "failed" {
+            break;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    assert_eq!(attempts.len(), 3, "{attempts:?}");
+    assert!(attempts.iter().all(|task| task["status"] == "failed"));
+    assert_eq!(attempts[1]["retry_of"], attempts[0]["id"]);
+    assert_eq!(attempts[2]["retry_of"], attempts[1]["id"]);
+    assert!(attempts[0]["next_retry_at"].is_string());
+    assert!(attempts[2]["next_retry_at"].is_null());
+
+    let last_id = attempts[2]["id"].as_str().expect("id");
+    let response = client
+        .post(format!("{base}/tasks/{last_id}/retry"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let retry: serde_json::Value = response.json().await?;
+    assert_eq!(retry["attempt"], 4);
+    assert_eq!(retry["retry_of"], attempts[2]["id"]);
+    let finished = wait_for_task(&client, &base, &retry).await?;
+    assert_eq!(finished["status"], "succeeded", "{finished}");
+    let apps: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/apps"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(apps.len(), 1);
+    assert_eq!(apps[0]["status"], "ready");
+
+    let response = client
+        .post(format!("{base}/tasks/{last_id}/retry"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "task_not_retryable");
+    assert_eq!(body["details"]["reason"], "already_retried");
+    assert_eq!(body["details"]["retry_task_id"], retry["id"]);
+    let first_id = first["id"].as_str().expect("id");
+    let response = client
+        .post(format!("{base}/tasks/{first_id}/retry"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["details"]["reason"], "not_failed");
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+
+    // Un reintento programado que no llego a lanzarse antes de parar el agente se
+    // vuelve a armar al arrancar.
+    let store = SqliteStore::new(&config.database_path).await?;
+    let first_attempt: uuid::Uuid = attempts[0]["id"].as_str().expect("id").parse()?;
+    let mut pending = store.get_task(first_attempt).await?.expect("task");
+    pending.id = Uuid::new_v4();
+    pending.next_retry_at =
+        Some((OffsetDateTime::now_utc() - TimeDuration::minutes(5)).format(&Rfc3339)?);
+    store.upsert_task(&pending).await?;
+    store
+        .delete_app(apps[0]["id"].as_str().expect("id").parse()?)
+        .await?;
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let mut rearmed = None;
+    for _ in 0..50 {
+        let tasks: Vec<serde_json::Value> = client
+            .get(format!("{base}/tasks"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        rearmed = tasks.into_iter().find(|task| {
+            task["retry_of"] == pending.id.to_string() && task["status"] == "succeeded"
+        });
+        if rearmed.is_some() {
+            break;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    let rearmed = rearmed.expect("reintento recuperado al arrancar");
+    assert_eq!(rearmed["attempt"], 2);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn schedules_run_jobs_as_tasks_and_catch_up_missed_runs() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let config = open_config(&temp);
+
+    // Vencimientos que cayeron con el agente parado y un token ya caducado.
+    let store = SqliteStore::new(&config.database_path).await?;
+    let past = OffsetDateTime::now_utc() - TimeDuration::hours(2);
+    store
+        .create_api_token(
+            "old".into(),
+            vec!["containers:read".into()],
+            "hash".into(),
+            "old-pref".into(),
+            Some(past.format(&Rfc3339)?),
+        )
+        .await?;
+    let mut missed = Vec::new();
+    for (name, policy) in [
+        ("catch-up", MissedRunPolicy::RunOnce),
+        ("skipped", MissedRunPolicy::Skip),
+    ] {
+        let mut schedule = Schedule::new(name.into(), "0 3 * * *".into(), ScheduleJob::TokenSweep);
+        schedule.missed_runs = policy;
+        schedule.next_run_at = Some(past.format(&Rfc3339)?);
+        store.upsert_schedule(&schedule).await?;
+        missed.push(schedule.id);
+    }
+
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let mut caught_up = serde_json::Value::Null;
+    for _ in 0..50 {
+        caught_up = client
+            .get(format!("{base}/schedules/{}", missed[0]))
+            .send()
+            .await?
+            .json()
+            .await?;
+        if caught_up["last_task_id"].is_string() {
+            break;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    assert!(
+        caught_up["next_run_at"]
+            .as_str()
+            .expect("next_run_at")
+            .ends_with("T03:00:00Z")
+    );
+    let tasks: Vec<serde_json::Value> = client
+        .get(format!("{base}/tasks?schedule_id={}", missed[0]))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], caught_up["last_task_id"]);
+    let sweep = wait_for_task(&client, &base, &tasks[0]).await?;
+    assert_eq!(sweep["type"], "token.sweep");
+    assert_eq!(sweep["status"], "succeeded", "{sweep}");
+    assert_eq!(sweep["result"]["removed"], 1);
+    let skipped: serde_json::Value = client
+        .get(format!("{base}/schedules/{}", missed[1]))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(skipped["last_task_id"].is_null());
+    assert!(skipped["next_run_at"].is_string());
+
+    let container_id = create_container_id(&client, &base, "nightly").await?;
+    let storage = std::path::PathBuf::from(container_storage(&client, &base, &container_id).await?);
+    std::fs::write(storage.join("fs/ProgramData/settings.ini"), "v1")?;
+
+    let create =
+        |body: serde_json::Value| client.post(format!("{base}/schedules")).json(&body).send();
+    let response = create(serde_json::json!({
+        "name": "nightly-snapshot",
+        "cron": "*/5 * * * *",
+        "job": { "type": "snapshot", "container_id": container_id, "label": "nightly" },
+    }))
+    .await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let snapshot_schedule: serde_json::Value = response.json().await?;
+    assert_eq!(snapshot_schedule["missed_runs"], "run-once");
+    let next = OffsetDateTime::parse(
+        snapshot_schedule["next_run_at"]
+            .as_str()
+            .expect("next_run_at"),
+        &Rfc3339,
+    )?;
+    assert_eq!(next.minute() % 5, 0);
+    assert!(next > OffsetDateTime::now_utc());
+
+    let response = create(serde_json::json!({
+        "name": "nightly-snapshot",
+        "cron": "@daily",
+        "job": { "type": "verify", "container_id": container_id },
+    }))
+    .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "schedule_name_conflict");
+    let response = create(serde_json::json!({
+        "name": "broken",
+        "cron": "61 * * * *",
+        "job": { "type": "prune", "container_id": container_id, "keep": 0 },
+    }))
+    .await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await?;
+    let fields: Vec<_> = body["details"]["fields"]
+        .as_array()
+        .expect("fields")
+        .iter()
+        .map(|field| (field["field"].clone(), field["code"].clone()))
+        .collect();
+    assert!(fields.contains(&("cron".into(), "invalid_cron".into())));
+    assert!(fields.contains(&("job.keep".into(), "out_of_range".into())));
+    let response = create(serde_json::json!({
+        "name": "never",
+        "cron": "0 0 30 2 *",
+        "job": { "type": "token-sweep" },
+    }))
+    .await?;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["details"]["fields"][0]["code"], "never_matches");
+    let response = create(serde_json::json!({
+        "name": "ghost",
+        "cron": "@hourly",
+        "job": { "type": "verify", "container_id": Uuid::new_v4() },
+    }))
+    .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "container_not_found");
+
+    // Dos ejecuciones inmediatas dejan dos snapshots; la poda conserva el mas reciente.
+    let snapshot_schedule_id = snapshot_schedule["id"].as_str().expect("id");
+    let run = |id: String| client.post(format!("{base}/schedules/{id}/run")).send();
+    for _ in 0..2 {
+        let response = run(snapshot_schedule_id.to_string()).await?;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let task: serde_json::Value = response.json().await?;
+        assert_eq!(task["schedule_id"], snapshot_schedule["id"]);
+        let task = wait_for_task(&client, &base, &task).await?;
+        assert_eq!(task["status"], "succeeded", "{task}");
+    }
+    let snapshots: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/snapshots"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(snapshots.len(), 2);
+    assert!(
+        snapshots
+            .iter()
+            .all(|snapshot| snapshot["label"] == "nightly")
+    );
+
+    let run_job = async |name: &str, job: serde_json::Value| -> anyhow::Result<serde_json::Value> {
+        let response =
+            create(serde_json::json!({ "name": name, "cron": "0 4 * * sun", "job": job })).await?;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let schedule: serde_json::Value = response.json().await?;
+        let task: serde_json::Value = run(schedule["id"].as_str().expect("id").to_string())
+            .await?
+            .json()
+            .await?;
+        let task = wait_for_task(&client, &base, &task).await?;
+        assert_eq!(task["status"], "succeeded", "{task}");
+        Ok(task)
+    };
+    let pruned = run_job(
+        "prune",
+        serde_json::json!({ "type": "prune", "container_id": container_id, "keep": 1 }),
+    )
+    .await?;
+    assert_eq!(pruned["type"], "snapshot.prune");
+    assert_eq!(pruned["result"]["kept"], 1);
+    assert_eq!(pruned["result"]["deleted"][0], snapshots[1]["id"]);
+    let remaining: Vec<serde_json::Value> = client
+        .get(format!("{base}/containers/{container_id}/snapshots"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0]["id"], snapshots[0]["id"]);
+    assert!(
+        !storage
+            .join("snapshots")
+            .join(snapshots[1]["id"].as_str().expect("id"))
+            .exists()
+    );
+
+    let verified = run_job(
+        "weekly-verify",
+        serde_json::json!({ "type": "verify", "container_id": container_id }),
+    )
+    .await?;
+    assert_eq!(verified["type"], "container.verify");
+
+    // El destino es relativo a `<data_dir>/exports`; no se puede salir de ahi.
+    for destination in [
+        temp.path().join("elsewhere").display().to_string(),
+        "../escape".into(),
+    ] {
+        let response = create(serde_json::json!({
+            "name": "escape",
+            "cron": "@daily",
+            "job": { "type": "export", "container_id": container_id, "destination": destination },
+        }))
+        .await?;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = response.json().await?;
+        assert_eq!(body["details"]["fields"][0]["field"], "job.destination");
+        assert_eq!(body["details"]["fields"][0]["code"], "not_allowed");
+    }
+    let exports = temp.path().join("exports").join("weekly");
+    std::fs::create_dir_all(&exports)?;
+    // Los archivos se distinguen por id: otro contenedor con el mismo nombre no se toca.
+    let old = format!("{container_id}-20200101T000000Z-nightly.tar.gz");
+    let other = format!("{}-20200101T000000Z-nightly.tar.gz", Uuid::new_v4());
+    std::fs::write(exports.join(&old), "old")?;
+    std::fs::write(exports.join(&other), "other")?;
+    let exported = run_job(
+        "export",
+        serde_json::json!({
+            "type": "export",
+            "container_id": container_id,
+            "destination": "weekly",
+            "keep": 1,
+        }),
+    )
+    .await?;
+    assert_eq!(exported["type"], "container.export");
+    let archive = std::path::PathBuf::from(exported["result"]["path"].as_str().expect("path"));
+    assert!(archive.starts_with(&exports));
+    assert!(
+        archive
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&format!("{container_id}-"))
+                && name.ends_with("-nightly.tar.gz"))
+    );
+    assert_eq!(exported["result"]["deleted"], serde_json::json!([old]));
+    assert!(!exports.join(&old).exists());
+    assert!(exports.join(&other).exists());
+    let mut names = Vec::new();
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(std::fs::File::open(&archive)?));
+    for entry in tar.entries()? {
+        names.push(entry?.path()?.display().to_string());
+    }
+    assert!(
+        names
+            .iter()
+            .any(|name| name == "fs/ProgramData/settings.ini")
+    );
+    assert!(!names.iter().any(|name| name.starts_with("snapshots")));
+
+    let response = client
+        .patch(format!("{base}/schedules/{snapshot_schedule_id}"))
+        .json(&serde_json::json!({ "enabled": false }))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let disabled: serde_json::Value = response.json().await?;
+    assert!(disabled["next_run_at"].is_null());
+    assert!(disabled["last_task_id"].is_string());
+    let response = client
+        .patch(format!("{base}/schedules/{snapshot_schedule_id}"))
+        .json(&serde_json::json!({ "enabled": true, "cron": "30 2 * * *", "missed_runs": "skip" }))
+        .send()
+        .await?;
+    let enabled: serde_json::Value = response.json().await?;
+    assert!(
+        enabled["next_run_at"]
+            .as_str()
+            .expect("next_run_at")
+            .ends_with("T02:30:00Z")
+    );
+    assert_eq!(enabled["missed_runs"], "skip");
+
+    let response = client
+        .delete(format!("{base}/schedules/{snapshot_schedule_id}"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = client
+        .get(format!("{base}/schedules/{snapshot_schedule_id}"))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = response.json().await?;
+    assert_eq!(body["code"], "schedule_not_found");
+    let tasks: Vec<serde_json::Value> = client
+        .get(format!("{base}/tasks?schedule_id={snapshot_schedule_id}"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(tasks.len(), 2);
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn task_retention_archives_and_prunes_finished_history() -> anyhow::Result<()> {
+    let temp = TempDir::new()?;
+    let mut config = open_config(&temp);
+    config.tasks.retention = RetentionConfig {
+        max_age_days: Some(30),
+        max_tasks: Some(2),
+        failed_max_age_days: Some(90),
+        archive: true,
+        ..RetentionConfig::default()
+    };
+
+    let store = SqliteStore::new(&config.database_path).await?;
+    let now = OffsetDateTime::now_utc();
+    let insert = async |status: TaskStatus, days: i64, parent: Option<Uuid>| {
+        let mut task = TaskModel::new("snapshot.create").with_status(status);
+        if let Some(parent) = parent {
+            task = task.with_step(TaskStep {
+                parent,
+                step: 1,
+                required: true,
+            });
+        }
+        let timestamp = (now - TimeDuration::days(days)).format(&Rfc3339)?;
+        task.created_at = timestamp.clone();
+        task.updated_at = timestamp;
+        store.upsert_task(&task).await?;
+        anyhow::Ok(task.id)
+    };
+    // Viejas: la correcta y su subtarea se borran; el fallo de 40 dias se conserva y el
+    // de 100 no. La que sigue en curso nunca se toca.
+    let old = insert(TaskStatus::Succeeded, 40, None).await?;
+    let old_step = insert(TaskStatus::Succeeded, 40, Some(old)).await?;
+    let recent_failure = insert(TaskStatus::Failed, 40, None).await?;
+    let ancient_failure = insert(TaskStatus::Failed, 100, None).await?;
+    let running = insert(TaskStatus::Running, 200, None).await?;
+    // De las tres recientes solo caben dos.
+    let oldest_recent = insert(TaskStatus::Succeeded, 3, None).await?;
+    let kept_recent = vec![
+        insert(TaskStatus::Cancelled, 2, None).await?,
+        insert(TaskStatus::Succeeded, 1, None).await?,
+    ];
+    // Un fallo con reintento automatico programado y ya lanzado: el reintento, aunque
+    // viejo, se conserva mientras exista su original.
+    let retried_at = (now - TimeDuration::days(40)).format(&Rfc3339)?;
+    let mut original = TaskModel::new("snapshot.create").with_status(TaskStatus::Failed);
+    original.payload = Some(serde_json::json!({
+        "op": "snapshot_create",
+        "container_id": Uuid::new_v4(),
+        "label": null,
+        "snapshot_type": "full",
+    }));
+    original.next_retry_at = Some(retried_at.clone());
+    original.created_at = retried_at.clone();
+    original.updated_at = retried_at.clone();
+    store.upsert_task(&original).await?;
+    let mut retry = original.next_attempt().with_status(TaskStatus::Succeeded);
+    retry.created_at = retried_at.clone();
+    retry.updated_at = retried_at;
+    store.upsert_task(&retry).await?;
+
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    let client = Client::new();
+    let base = format!("http://{}", config.api_bind);
+
+    let mut info = serde_json::Value::Null;
+    for _ in 0..50 {
+        info = client
+            .get(format!("{base}/system/info"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        if info["task_retention"]["last_sweep"].is_object() {
+            break;
+        }
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+    }
+    let retention = &info["task_retention"];
+    assert_eq!(retention["enabled"], true);
+    assert_eq!(retention["last_sweep"]["deleted"], 4, "{retention}");
+    assert_eq!(retention["last_sweep"]["archived"], 4);
+    assert_eq!(retention["deleted_total"], 4);
+    assert_eq!(retention["archive_files"], 1);
+    assert!(retention["archive_bytes"].as_u64().expect("bytes") > 0);
+
+    let tasks: Vec<serde_json::Value> = client
+        .get(format!("{base}/tasks"))
+        .send()
+        .await?
+        .json()
+        .await?;
+    let mut remaining: Vec<String> = tasks
+        .iter()
+        .map(|task| task["id"].as_str().expect("id").to_string())
+        .collect();
+    remaining.sort();
+    let mut expected: Vec<String> = [recent_failure, running, original.id, retry.id]
+        .into_iter()
+        .chain(kept_recent)
+        .map(|id| id.to_string())
+        .collect();
+    expected.sort();
+    assert_eq!(remaining, expected);
+
+    let file = retention["last_sweep"]["archive_files"][0]
+        .as_str()
+        .expect("archivo");
+    let archive = temp.path().join("archive/tasks").join(file);
+    let mut ndjson = String::new();
+    std::io::Read::read_to_string(
+        &mut flate2::read::GzDecoder::new(std::fs::File::open(archive)?),
+        &mut ndjson,
+    )?;
+    let archived: Vec<serde_json::Value> = ndjson
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    let archived_ids: Vec<_> = archived.iter().map(|task| task["id"].clone()).collect();
+    assert_eq!(archived.len(), 4);
+    for id in [old, old_step, ancient_failure, oldest_recent] {
+        assert!(archived_ids.contains(&serde_json::json!(id)));
+    }
+    let step = archived
+        .iter()
+        .find(|task| task["id"] == serde_json::json!(old_step))
+        .expect("subtarea archivada");
+    assert_eq!(step["parent_task_id"], serde_json::json!(old));
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+
+    // Al arrancar de nuevo, el original ya reintentado no se vuelve a lanzar.
+    let (tx, server_handle) = spawn_agent(&config).await?;
+    for _ in 0..10 {
+        tokio::time::sleep(StdDuration::from_millis(100)).await;
+        let tasks: Vec<serde_json::Value> = client
+            .get(format!("{base}/tasks"))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let retries: Vec<_> = tasks
+            .iter()
+            .filter(|task| task["retry_of"] == serde_json::json!(original.id))
+            .map(|task| task["id"].clone())
+            .collect();
+        assert_eq!(retries, vec![serde_json::json!(retry.id)]);
+    }
+
+    let _ = tx.send(());
+    let _ = server_handle.await?;
+    Ok(())
+}
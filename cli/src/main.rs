@@ -1,6 +1,18 @@
-use anyhow::{Context, Result};
+use std::{
+    io::{IsTerminal, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
 use clap::{Parser, Subcommand, ValueEnum};
-use orbit_cli_sdk::apis::{configuration::Configuration, system_api};
+use orbit_cli_sdk::{
+    apis::{Error, configuration::Configuration, containers_api, system_api, tasks_api},
+    models::{DesiredState, PlanItem, TasksTaskIdGet200Response, plan_item::Action, task::Status},
+};
+
+/// Intervalo entre consultas al seguir la tarea de `apply`.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Parser, Debug)]
 #[command(name = "orbit", version, about = "CLI para el agente Orbit")]
@@ -25,6 +37,22 @@ enum Commands {
         #[arg(long, default_value_t = OutputFormat::Pretty)]
         format: OutputFormat,
     },
+    /// Llevar los contenedores al estado declarado en un archivo TOML
+    /// (POST /containers/plan y POST /containers/apply)
+    Apply {
+        /// Archivo con el estado deseado (`[[containers]]`)
+        #[arg(short = 'f', long = "file")]
+        file: PathBuf,
+        /// Eliminar contenedores y apps que no esten en el archivo
+        #[arg(long)]
+        prune: bool,
+        /// Mostrar el plan sin aplicarlo
+        #[arg(long)]
+        dry_run: bool,
+        /// Aplicar sin pedir confirmacion aunque el plan elimine algo
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -49,15 +77,17 @@ async fn main() -> Result<()> {
         Commands::SystemConfig { format } => {
             system_config(cli.base_url, cli.admin_token, format).await?
         }
+        Commands::Apply {
+            file,
+            prune,
+            dry_run,
+            yes,
+        } => apply(cli.base_url, cli.admin_token, file, prune, dry_run, yes).await?,
     }
     Ok(())
 }
 
-async fn system_config(
-    base_url: String,
-    admin_token: Option<String>,
-    format: OutputFormat,
-) -> Result<()> {
+fn configuration(base_url: String, admin_token: Option<String>) -> Result<Configuration> {
     let token = admin_token
         .or_else(|| std::env::var("ORBIT_ADMIN_TOKEN").ok())
         .context("Debes proporcionar --admin-token o la variable ORBIT_ADMIN_TOKEN")?;
@@ -65,7 +95,15 @@ async fn system_config(
     let mut configuration = Configuration::new();
     configuration.base_path = base_url;
     configuration.bearer_access_token = Some(token);
+    Ok(configuration)
+}
 
+async fn system_config(
+    base_url: String,
+    admin_token: Option<String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let configuration = configuration(base_url, admin_token)?;
     let snapshot = system_api::system_config_get(&configuration)
         .await
         .context("No se pudo consultar /system/config")?;
@@ -88,3 +126,164 @@ async fn system_config(
 
     Ok(())
 }
+
+async fn apply(
+    base_url: String,
+    admin_token: Option<String>,
+    file: PathBuf,
+    prune: bool,
+    dry_run: bool,
+    yes: bool,
+) -> Result<()> {
+    let configuration = configuration(base_url, admin_token)?;
+    let document = std::fs::read_to_string(&file)
+        .with_context(|| format!("No se pudo leer {}", file.display()))?;
+    let desired: DesiredState = toml::from_str(&document)
+        .with_context(|| format!("{} no es un estado deseado valido", file.display()))?;
+
+    let plan = containers_api::containers_plan_post(&configuration, desired.clone(), Some(prune))
+        .await
+        .map_err(api_error)
+        .context("No se pudo calcular el plan")?;
+    for item in &plan.items {
+        println!("{}", describe_item(item));
+    }
+    let count = |action: Action| {
+        plan.items
+            .iter()
+            .filter(|item| item.action == action)
+            .count()
+    };
+    let deletes = count(Action::Delete);
+    println!(
+        "Plan: {} para crear, {} para actualizar, {deletes} para eliminar",
+        count(Action::Create),
+        count(Action::Update),
+    );
+    if !plan.unmanaged.is_empty() {
+        println!(
+            "Sin gestionar (usa --prune para eliminarlos): {}",
+            plan.unmanaged.join(", ")
+        );
+    }
+    if dry_run || plan.items.is_empty() {
+        return Ok(());
+    }
+    if deletes > 0 && !yes && !confirm_deletes(deletes)? {
+        bail!("Cancelado: no se aplico ningun cambio");
+    }
+
+    // El agente solo aplica los borrados si el plan recalculado es el que se mostro.
+    let task = containers_api::containers_apply_post(
+        &configuration,
+        desired,
+        None,
+        Some(prune),
+        Some(&plan.fingerprint),
+    )
+    .await
+    .map_err(|err| match err {
+        Error::ResponseError(response) if response.status.as_u16() == 409 => anyhow!(
+            "El plan ha cambiado desde que se mostro; vuelve a ejecutar apply para revisarlo"
+        ),
+        err => api_error(err),
+    })
+    .context("No se pudo aplicar el plan")?;
+    let task_id = task
+        .id
+        .context("La respuesta de /containers/apply no trae el id de la tarea")?
+        .to_string();
+    let task = loop {
+        let response = tasks_api::tasks_task_id_get(&configuration, &task_id, None)
+            .await
+            .map_err(api_error)
+            .with_context(|| format!("No se pudo consultar la tarea {task_id}"))?;
+        let TasksTaskIdGet200Response::Task(task) = response else {
+            bail!("La tarea {task_id} se devolvio como arbol sin pedir `include=children`");
+        };
+        if matches!(
+            task.status,
+            Some(Status::Succeeded | Status::Failed | Status::Cancelled)
+        ) {
+            break *task;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    };
+
+    let results = task
+        .result
+        .as_ref()
+        .and_then(|result| result["items"].as_array().cloned())
+        .unwrap_or_default();
+    for result in results {
+        let status = result["status"].as_str().unwrap_or("unknown").to_string();
+        let error = result["error"].as_str().map(str::to_string);
+        let item: PlanItem = serde_json::from_value(result)
+            .context("La tarea devolvio un cambio que no es un PlanItem")?;
+        match error {
+            Some(error) => println!("[{status}] {}: {error}", describe_item(&item)),
+            None => println!("[{status}] {}", describe_item(&item)),
+        }
+    }
+    if task.status != Some(Status::Succeeded) {
+        bail!("La tarea {task_id} no se completo");
+    }
+    Ok(())
+}
+
+/// Pide confirmacion antes de eliminar; sin terminal exige `--yes`.
+fn confirm_deletes(deletes: usize) -> Result<bool> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        bail!("El plan elimina {deletes} elemento(s); repite con --yes para confirmarlo");
+    }
+    print!("El plan elimina {deletes} elemento(s). Continuar? [s/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    stdin.read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_lowercase().as_str(),
+        "s" | "si" | "y" | "yes"
+    ))
+}
+
+/// Incluye el cuerpo `ApiError` en los errores de respuesta del agente.
+fn api_error<T: std::fmt::Debug + Send + Sync + 'static>(err: Error<T>) -> anyhow::Error {
+    match err {
+        Error::ResponseError(response) => {
+            anyhow!(
+                "el agente respondio {}: {}",
+                response.status,
+                response.content
+            )
+        }
+        err => err.into(),
+    }
+}
+
+/// Linea del plan: `+` crear, `~` actualizar, `-` eliminar.
+fn describe_item(item: &PlanItem) -> String {
+    let symbol = match item.action {
+        Action::Create => "+",
+        Action::Update => "~",
+        Action::Delete => "-",
+    };
+    let container = &item.container;
+    let target = match &item.app {
+        Some(app) => format!("app {container}/{app}"),
+        None => format!("contenedor {container}"),
+    };
+    let changes = item
+        .changes
+        .iter()
+        .flatten()
+        .filter_map(|change| serde_json::to_value(change).ok())
+        .filter_map(|change| change.as_str().map(str::to_string))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if changes.is_empty() {
+        format!("{symbol} {target}")
+    } else {
+        format!("{symbol} {target} ({changes})")
+    }
+}
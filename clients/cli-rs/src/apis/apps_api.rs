@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -13,10 +13,133 @@ use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
 
+/// struct for typed errors of method [`apps_app_id_delete`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdDeleteError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_disable_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdDisablePostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_enable_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdEnablePostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_entry_points_entry_point_id_delete`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdEntryPointsEntryPointIdDeleteError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_entry_points_entry_point_id_patch`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdEntryPointsEntryPointIdPatchError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_entry_points_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdEntryPointsGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_entry_points_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdEntryPointsPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_files_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdFilesGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`apps_app_id_launch_post`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum AppsAppIdLaunchPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`apps_app_id_update_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppsAppIdUpdatePostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -24,6 +147,11 @@ pub enum AppsAppIdLaunchPostError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdAppsGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -31,9 +159,442 @@ pub enum ContainersContainerIdAppsGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdAppsPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status507(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
+/// Ejecuta el desinstalador registrado en modo silencioso (`/VERYSILENT` para Inno Setup, `/S` en otro caso), elimina solo los archivos `created` de su manifest, poda las carpetas que quedan vacias y borra la app. Los archivos que no son de la app (por ejemplo datos del usuario dentro de `install_dir`) se conservan. Si el desinstalador falla la app vuelve a su estado anterior. `409 app_busy` si la app esta instalandose.
+pub async fn apps_app_id_delete(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<AppsAppIdDeleteError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!(
+        "{}/apps/{appId}",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::DELETE, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdDeleteError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn apps_app_id_disable_post(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+) -> Result<models::Task, Error<AppsAppIdDisablePostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/disable",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdDisablePostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Requiere al menos un entry point (`409 entry_point_required`).
+pub async fn apps_app_id_enable_post(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+) -> Result<models::Task, Error<AppsAppIdEnablePostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/enable",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdEnablePostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Una app `ready` no puede quedarse sin entry points (`409 entry_point_required`).
+pub async fn apps_app_id_entry_points_entry_point_id_delete(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+    entry_point_id: &str,
+) -> Result<(), Error<AppsAppIdEntryPointsEntryPointIdDeleteError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+    let p_path_entry_point_id = entry_point_id;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/entry-points/{entryPointId}",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id),
+        entryPointId = crate::apis::urlencode(p_path_entry_point_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::DELETE, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+
+    if !status.is_client_error() && !status.is_server_error() {
+        Ok(())
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdEntryPointsEntryPointIdDeleteError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Los campos omitidos conservan su valor.
+pub async fn apps_app_id_entry_points_entry_point_id_patch(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+    entry_point_id: &str,
+    entry_point_input: models::EntryPointInput,
+) -> Result<models::AppEntryPoint, Error<AppsAppIdEntryPointsEntryPointIdPatchError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+    let p_path_entry_point_id = entry_point_id;
+    let p_body_entry_point_input = entry_point_input;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/entry-points/{entryPointId}",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id),
+        entryPointId = crate::apis::urlencode(p_path_entry_point_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::PATCH, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_entry_point_input);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::AppEntryPoint`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::AppEntryPoint`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdEntryPointsEntryPointIdPatchError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn apps_app_id_entry_points_get(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+) -> Result<Vec<models::AppEntryPoint>, Error<AppsAppIdEntryPointsGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/entry-points",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::AppEntryPoint&gt;`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::AppEntryPoint&gt;`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdEntryPointsGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// `id` se deriva de `label` si no se indica. El primer entry point de una app `disabled` la deja en `ready`.
+pub async fn apps_app_id_entry_points_post(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+    apps_app_id_entry_points_post_request: models::AppsAppIdEntryPointsPostRequest,
+) -> Result<models::AppEntryPoint, Error<AppsAppIdEntryPointsPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+    let p_body_apps_app_id_entry_points_post_request = apps_app_id_entry_points_post_request;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/entry-points",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_apps_app_id_entry_points_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::AppEntryPoint`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::AppEntryPoint`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdEntryPointsPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Diferencia de `fs/` antes y despues de instalar o actualizar, guardada en `app_files` de `runtime/manifest.json` dentro del contenedor. Vacio para apps solo registradas.
+pub async fn apps_app_id_files_get(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+) -> Result<models::AppFileManifest, Error<AppsAppIdFilesGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/files",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::AppFileManifest`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::AppFileManifest`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdFilesGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
 pub async fn apps_app_id_launch_post(
     configuration: &configuration::Configuration,
     app_id: &str,
@@ -89,6 +650,67 @@ pub async fn apps_app_id_launch_post(
     }
 }
 
+/// Captura un snapshot `pre-update <app>` y ejecuta el instalador o sustituye la carpeta portable registrada en `install_dir`. Si algo falla se restaura el snapshot y el registro previo de la app, y la tarea termina en `failed`. Los entry points nuevos se agregan a los existentes.
+pub async fn apps_app_id_update_post(
+    configuration: &configuration::Configuration,
+    app_id: &str,
+    apps_app_id_update_post_request: models::AppsAppIdUpdatePostRequest,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<AppsAppIdUpdatePostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_app_id = app_id;
+    let p_body_apps_app_id_update_post_request = apps_app_id_update_post_request;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!(
+        "{}/apps/{appId}/update",
+        configuration.base_path,
+        appId = crate::apis::urlencode(p_path_app_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_apps_app_id_update_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<AppsAppIdUpdatePostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
 pub async fn containers_container_id_apps_get(
     configuration: &configuration::Configuration,
     container_id: &str,
@@ -139,15 +761,18 @@ pub async fn containers_container_id_apps_get(
     }
 }
 
+/// Con `installer_path` la app queda en `installing` mientras el instalador se ejecuta en segundo plano con `silent_args`; al terminar pasa a `ready` con los entry points detectados o a `failed` con `exit_code` y `log_path`. Con `archive_path` (zip, tar, tar.gz) el contenido se extrae en `fs/ProgramFiles/<app>`; `vendor`, `version` y `name` se toman de `orbit-app.json` o `App/AppInfo/appinfo.ini` si no se indican. Sin instalador ni archivo solo se registra en `disabled`; igual que una instalacion que no deja ejecutables nuevos, pasa a `ready` al agregar un entry point. Se rechaza con `507 quota_exceeded` si el contenedor o el total ya alcanzaron su cuota de disco.
 pub async fn containers_container_id_apps_post(
     configuration: &configuration::Configuration,
     container_id: &str,
     containers_container_id_apps_post_request: models::ContainersContainerIdAppsPostRequest,
+    idempotency_key: Option<&str>,
 ) -> Result<models::Task, Error<ContainersContainerIdAppsPostError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_container_id = container_id;
     let p_body_containers_container_id_apps_post_request =
         containers_container_id_apps_post_request;
+    let p_header_idempotency_key = idempotency_key;
 
     let uri_str = format!(
         "{}/containers/{containerId}/apps",
@@ -161,6 +786,9 @@ pub async fn containers_container_id_apps_post(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
     if let Some(ref token) = configuration.bearer_access_token {
         req_builder = req_builder.bearer_auth(token.to_owned());
     };
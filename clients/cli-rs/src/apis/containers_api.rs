@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -13,10 +13,76 @@ use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{de::Error as _, Deserialize, Serialize};
 
+/// struct for typed errors of method [`containers_apply_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersApplyPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_changes_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdChangesGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`containers_container_id_delete`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdDeleteError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_files_content_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdFilesContentGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_files_content_put`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdFilesContentPutError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_files_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdFilesGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -24,7 +90,122 @@ pub enum ContainersContainerIdDeleteError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdGetError {
-    Status404(),
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_patch`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdPatchError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_paths_resolve_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdPathsResolveGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_rename_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdRenamePostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_start_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdStartPostError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_stop_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdStopPostError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_tags_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdTagsPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_tags_tag_delete`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdTagsTagDeleteError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_container_id_verify_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersContainerIdVerifyPostError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_from_template_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersFromTemplatePostError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -32,6 +213,46 @@ pub enum ContainersContainerIdGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_orphans_adopt_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersOrphansAdoptPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_orphans_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersOrphansGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`containers_plan_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ContainersPlanPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -39,9 +260,141 @@ pub enum ContainersGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
+/// Recalcula el plan y lo ejecuta como tarea `container.apply`. Un cambio fallido no detiene los demás; las apps de un contenedor que no se pudo crear o actualizar quedan `skipped`. La tarea termina en `failed` si algún cambio no se aplicó. Si el plan recalculado elimina algo, solo se aplica con el `fingerprint` del plan que se confirmó en `POST /containers/plan`.
+pub async fn containers_apply_post(
+    configuration: &configuration::Configuration,
+    desired_state: models::DesiredState,
+    idempotency_key: Option<&str>,
+    prune: Option<bool>,
+    fingerprint: Option<&str>,
+) -> Result<models::Task, Error<ContainersApplyPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_desired_state = desired_state;
+    let p_header_idempotency_key = idempotency_key;
+    let p_query_prune = prune;
+    let p_query_fingerprint = fingerprint;
+
+    let uri_str = format!("{}/containers/apply", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref param_value) = p_query_prune {
+        req_builder = req_builder.query(&[("prune", &param_value.to_string())]);
+    }
+    if let Some(ref param_value) = p_query_fingerprint {
+        req_builder = req_builder.query(&[("fingerprint", &param_value.to_string())]);
+    }
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_desired_state);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersApplyPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_container_id_changes_get(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    since: Option<i32>,
+    limit: Option<i32>,
+) -> Result<models::ChangeLog, Error<ContainersContainerIdChangesGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_query_since = since;
+    let p_query_limit = limit;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/changes",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref param_value) = p_query_since {
+        req_builder = req_builder.query(&[("since", &param_value.to_string())]);
+    }
+    if let Some(ref param_value) = p_query_limit {
+        req_builder = req_builder.query(&[("limit", &param_value.to_string())]);
+    }
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::ChangeLog`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::ChangeLog`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdChangesGetError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
 pub async fn containers_container_id_delete(
     configuration: &configuration::Configuration,
     container_id: &str,
@@ -94,26 +447,77 @@ pub async fn containers_container_id_delete(
     }
 }
 
-pub async fn containers_container_id_get(
+/// Devuelve el contenido en streaming con `Content-Disposition: attachment`. Las rutas que salen del contenedor dan `422` (`not_in_container`); los directorios, `422` (`not_a_file`); los archivos inexistentes, `404 file_not_found`.
+pub async fn containers_container_id_files_content_get(
     configuration: &configuration::Configuration,
     container_id: &str,
-) -> Result<models::Container, Error<ContainersContainerIdGetError>> {
+    path: &str,
+) -> Result<reqwest::Response, Error<ContainersContainerIdFilesContentGetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_container_id = container_id;
+    let p_query_path = path;
 
     let uri_str = format!(
-        "{}/containers/{containerId}",
+        "{}/containers/{containerId}/files/content",
         configuration.base_path,
         containerId = crate::apis::urlencode(p_path_container_id)
     );
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
+    req_builder = req_builder.query(&[("path", &p_query_path.to_string())]);
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+
+    if !status.is_client_error() && !status.is_server_error() {
+        Ok(resp)
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdFilesContentGetError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Escribe el cuerpo en la ruta indicada creando los directorios que falten. Se escribe en un temporal y se renombra al terminar, así una subida cortada no deja el archivo a medias. Sobrescribe archivos existentes; no admite directorios como destino ni rutas de montajes de solo lectura (`422 read_only`). No admite `Idempotency-Key`: el cuerpo no se retiene, y repetir la subida deja el mismo archivo.
+pub async fn containers_container_id_files_content_put(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    path: &str,
+    body: Vec<u8>,
+) -> Result<models::FsEntry, Error<ContainersContainerIdFilesContentPutError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_query_path = path;
+    let p_body_body = body;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/files/content",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::PUT, &uri_str);
+
+    req_builder = req_builder.query(&[("path", &p_query_path.to_string())]);
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
     if let Some(ref token) = configuration.bearer_access_token {
         req_builder = req_builder.bearer_auth(token.to_owned());
     };
+    req_builder = req_builder.body(p_body_body);
 
     let req = req_builder.build()?;
     let resp = configuration.client.execute(req).await?;
@@ -130,12 +534,13 @@ pub async fn containers_container_id_get(
         let content = resp.text().await?;
         match content_type {
             ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
-            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
-            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::FsEntry`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::FsEntry`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<ContainersContainerIdGetError> = serde_json::from_str(&content).ok();
+        let entity: Option<ContainersContainerIdFilesContentPutError> =
+            serde_json::from_str(&content).ok();
         Err(Error::ResponseError(ResponseContent {
             status,
             content,
@@ -144,18 +549,25 @@ pub async fn containers_container_id_get(
     }
 }
 
-pub async fn containers_get(
+/// La ruta se resuelve dentro de `fs/` siguiendo enlaces; si sale del contenedor (`..`, enlaces simbólicos) se responde `422` con `not_in_container`. Si la ruta no es un directorio, `422` con `not_a_directory`.
+pub async fn containers_container_id_files_get(
     configuration: &configuration::Configuration,
-    status: Option<&str>,
-) -> Result<Vec<models::Container>, Error<ContainersGetError>> {
+    container_id: &str,
+    path: Option<&str>,
+) -> Result<Vec<models::FsEntry>, Error<ContainersContainerIdFilesGetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
-    let p_query_status = status;
+    let p_path_container_id = container_id;
+    let p_query_path = path;
 
-    let uri_str = format!("{}/containers", configuration.base_path);
+    let uri_str = format!(
+        "{}/containers/{containerId}/files",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
-    if let Some(ref param_value) = p_query_status {
-        req_builder = req_builder.query(&[("status", &param_value.to_string())]);
+    if let Some(ref param_value) = p_query_path {
+        req_builder = req_builder.query(&[("path", &param_value.to_string())]);
     }
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
@@ -179,12 +591,13 @@ pub async fn containers_get(
         let content = resp.text().await?;
         match content_type {
             ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
-            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::Container&gt;`"))),
-            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::Container&gt;`")))),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::FsEntry&gt;`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::FsEntry&gt;`")))),
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<ContainersGetError> = serde_json::from_str(&content).ok();
+        let entity: Option<ContainersContainerIdFilesGetError> =
+            serde_json::from_str(&content).ok();
         Err(Error::ResponseError(ResponseContent {
             status,
             content,
@@ -193,17 +606,19 @@ pub async fn containers_get(
     }
 }
 
-pub async fn containers_post(
+pub async fn containers_container_id_get(
     configuration: &configuration::Configuration,
-    containers_post_request: models::ContainersPostRequest,
-) -> Result<models::Task, Error<ContainersPostError>> {
+    container_id: &str,
+) -> Result<models::Container, Error<ContainersContainerIdGetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
-    let p_body_containers_post_request = containers_post_request;
+    let p_path_container_id = container_id;
 
-    let uri_str = format!("{}/containers", configuration.base_path);
-    let mut req_builder = configuration
-        .client
-        .request(reqwest::Method::POST, &uri_str);
+    let uri_str = format!(
+        "{}/containers/{containerId}",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
@@ -211,6 +626,766 @@ pub async fn containers_post(
     if let Some(ref token) = configuration.bearer_access_token {
         req_builder = req_builder.bearer_auth(token.to_owned());
     };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Los campos omitidos conservan su valor. Regenera `runtime/manifest.json`.
+pub async fn containers_container_id_patch(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    containers_container_id_patch_request: models::ContainersContainerIdPatchRequest,
+) -> Result<models::Container, Error<ContainersContainerIdPatchError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_body_containers_container_id_patch_request = containers_container_id_patch_request;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::PATCH, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_containers_container_id_patch_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdPatchError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Exige exactamente uno de `virtual` o `physical`. Usa la tabla de montajes del contenedor con el prefijo más largo. Errores `422` sobre el parámetro: `unknown_variable`, `expansion_too_deep`, `unsupported_drive` (solo existe `C:`) y `not_in_container`.
+pub async fn containers_container_id_paths_resolve_get(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    r#virtual: Option<&str>,
+    physical: Option<&str>,
+) -> Result<models::PathResolution, Error<ContainersContainerIdPathsResolveGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_query_virtual = r#virtual;
+    let p_query_physical = physical;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/paths/resolve",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref param_value) = p_query_virtual {
+        req_builder = req_builder.query(&[("virtual", &param_value.to_string())]);
+    }
+    if let Some(ref param_value) = p_query_physical {
+        req_builder = req_builder.query(&[("physical", &param_value.to_string())]);
+    }
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::PathResolution`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::PathResolution`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdPathsResolveGetError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_container_id_rename_post(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    containers_container_id_rename_post_request: models::ContainersContainerIdRenamePostRequest,
+) -> Result<models::Container, Error<ContainersContainerIdRenamePostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_body_containers_container_id_rename_post_request =
+        containers_container_id_rename_post_request;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/rename",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_containers_container_id_rename_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdRenamePostError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Pasa el contenedor de `ready` a `running` y empieza a vigilar su `fs/` (inotify en Linux, FSEvents en macOS, ReadDirectoryChangesW en Windows y sondeo si no hay avisos del sistema). Cada alta, modificación o borrado se anota en un diario acotado (10 000 entradas) y se publica como evento `container.fs-changed`. Si ya estaba en `running` no hace nada; en cualquier otro estado responde `409 container_not_ready`. Los contenedores que siguen en `running` vuelven a vigilarse al reiniciar el agente.
+pub async fn containers_container_id_start_post(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+) -> Result<models::Container, Error<ContainersContainerIdStartPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/start",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdStartPostError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Pasa el contenedor de `running` a `ready` y deja de vigilar su `fs/`; el diario se conserva hasta que se elimina el contenedor. En cualquier otro estado no hace nada.
+pub async fn containers_container_id_stop_post(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+) -> Result<models::Container, Error<ContainersContainerIdStopPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/stop",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdStopPostError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_container_id_tags_post(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    containers_container_id_tags_post_request: models::ContainersContainerIdTagsPostRequest,
+) -> Result<models::Container, Error<ContainersContainerIdTagsPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_body_containers_container_id_tags_post_request =
+        containers_container_id_tags_post_request;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/tags",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_containers_container_id_tags_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdTagsPostError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_container_id_tags_tag_delete(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+    tag: &str,
+) -> Result<models::Container, Error<ContainersContainerIdTagsTagDeleteError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+    let p_path_tag = tag;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/tags/{tag}",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id),
+        tag = crate::apis::urlencode(p_path_tag)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::DELETE, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdTagsTagDeleteError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Recorre el sandbox, recalcula los SHA-256 y los compara con los manifests de archivos de las apps y de los snapshots. El informe (`IntegrityReport`) queda en `result` de la tarea. Si faltan archivos críticos el contenedor pasa a `error` con `status_reason` (`Faltan archivos criticos: ...`); una verificación posterior sin ausencias críticas lo devuelve a `ready`. Los errores con otro motivo no se tocan.
+pub async fn containers_container_id_verify_post(
+    configuration: &configuration::Configuration,
+    container_id: &str,
+) -> Result<models::Task, Error<ContainersContainerIdVerifyPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_container_id = container_id;
+
+    let uri_str = format!(
+        "{}/containers/{containerId}/verify",
+        configuration.base_path,
+        containerId = crate::apis::urlencode(p_path_container_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersContainerIdVerifyPostError> =
+            serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Lanza una tarea `container.template` que crea el contenedor con los ajustes de la plantilla, escribe sus valores de registro e instala sus apps una tras otra (cada una con su tarea `app.install`, sujeta a las cuotas de disco). Si una instalación falla, la tarea termina en `failed` y el contenedor se conserva.
+pub async fn containers_from_template_post(
+    configuration: &configuration::Configuration,
+    containers_from_template_post_request: models::ContainersFromTemplatePostRequest,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<ContainersFromTemplatePostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_containers_from_template_post_request = containers_from_template_post_request;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!("{}/containers/from-template", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_containers_from_template_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersFromTemplatePostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_get(
+    configuration: &configuration::Configuration,
+    status: Option<&str>,
+    tag: Option<&str>,
+) -> Result<Vec<models::Container>, Error<ContainersGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_query_status = status;
+    let p_query_tag = tag;
+
+    let uri_str = format!("{}/containers", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref param_value) = p_query_status {
+        req_builder = req_builder.query(&[("status", &param_value.to_string())]);
+    }
+    if let Some(ref param_value) = p_query_tag {
+        req_builder = req_builder.query(&[("tag", &param_value.to_string())]);
+    }
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::Container&gt;`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::Container&gt;`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Registra el sandbox en su ubicación actual y reescribe su manifest. Si su id ya está en uso recibe uno nuevo; `name` sustituye al del manifest y es obligatorio en la práctica cuando hay conflicto de nombre. Conserva los binds del manifest, que deben cumplir `[mounts]` (si no, `422` en `mounts[n]`); los sandboxes que se registran solos al arrancar o en `POST /system/reconcile` los pierden.
+pub async fn containers_orphans_adopt_post(
+    configuration: &configuration::Configuration,
+    containers_orphans_adopt_post_request: models::ContainersOrphansAdoptPostRequest,
+) -> Result<models::Container, Error<ContainersOrphansAdoptPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_containers_orphans_adopt_post_request = containers_orphans_adopt_post_request;
+
+    let uri_str = format!("{}/containers/orphans/adopt", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_containers_orphans_adopt_post_request);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Container`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Container`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersOrphansAdoptPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Directorios de `containers_root` con un `runtime/manifest.json` válido que no están en la base de datos (p.e. copiados a mano). Al arrancar, el agente registra los que no tienen `conflict`; el resto espera a `POST /containers/orphans/adopt`.
+pub async fn containers_orphans_get(
+    configuration: &configuration::Configuration,
+) -> Result<Vec<models::OrphanSandbox>, Error<ContainersOrphansGetError>> {
+    let uri_str = format!("{}/containers/orphans", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::OrphanSandbox&gt;`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::OrphanSandbox&gt;`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersOrphansGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Compara el documento con los contenedores y apps actuales sin modificar nada. Crea los contenedores y apps que faltan, actualiza los que difieren y, con `prune=true`, desinstala las apps y elimina los contenedores no declarados.
+pub async fn containers_plan_post(
+    configuration: &configuration::Configuration,
+    desired_state: models::DesiredState,
+    prune: Option<bool>,
+) -> Result<models::ApplyPlan, Error<ContainersPlanPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_desired_state = desired_state;
+    let p_query_prune = prune;
+
+    let uri_str = format!("{}/containers/plan", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref param_value) = p_query_prune {
+        req_builder = req_builder.query(&[("prune", &param_value.to_string())]);
+    }
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_desired_state);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::ApplyPlan`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::ApplyPlan`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<ContainersPlanPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn containers_post(
+    configuration: &configuration::Configuration,
+    containers_post_request: models::ContainersPostRequest,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<ContainersPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_containers_post_request = containers_post_request;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!("{}/containers", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
     req_builder = req_builder.json(&p_body_containers_post_request);
 
     let req = req_builder.build()?;
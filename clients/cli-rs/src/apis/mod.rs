@@ -115,9 +115,11 @@ impl From<&str> for ContentType {
 
 pub mod apps_api;
 pub mod containers_api;
+pub mod schedules_api;
 pub mod security_api;
 pub mod snapshots_api;
 pub mod system_api;
 pub mod tasks_api;
+pub mod templates_api;
 
 pub mod configuration;
@@ -0,0 +1,395 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use super::{configuration, ContentType, Error};
+use crate::{apis::ResponseContent, models};
+use reqwest;
+use serde::{de::Error as _, Deserialize, Serialize};
+
+/// struct for typed errors of method [`schedules_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`schedules_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`schedules_schedule_id_delete`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesScheduleIdDeleteError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`schedules_schedule_id_get`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesScheduleIdGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`schedules_schedule_id_patch`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesScheduleIdPatchError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`schedules_schedule_id_run_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SchedulesScheduleIdRunPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+pub async fn schedules_get(
+    configuration: &configuration::Configuration,
+) -> Result<Vec<models::Schedule>, Error<SchedulesGetError>> {
+    let uri_str = format!("{}/schedules", configuration.base_path);
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `Vec&lt;models::Schedule&gt;`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `Vec&lt;models::Schedule&gt;`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Cada vencimiento lanza el trabajo como una tarea normal con `schedule_id`. Los trabajos `token-sweep` requieren el token de administrador.
+pub async fn schedules_post(
+    configuration: &configuration::Configuration,
+    schedule_input: models::ScheduleInput,
+    idempotency_key: Option<&str>,
+) -> Result<models::Schedule, Error<SchedulesPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_body_schedule_input = schedule_input;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!("{}/schedules", configuration.base_path);
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_schedule_input);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Schedule`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Schedule`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Las tareas ya lanzadas se conservan
+pub async fn schedules_schedule_id_delete(
+    configuration: &configuration::Configuration,
+    schedule_id: &str,
+) -> Result<(), Error<SchedulesScheduleIdDeleteError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_schedule_id = schedule_id;
+
+    let uri_str = format!(
+        "{}/schedules/{scheduleId}",
+        configuration.base_path,
+        scheduleId = crate::apis::urlencode(p_path_schedule_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::DELETE, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+
+    if !status.is_client_error() && !status.is_server_error() {
+        Ok(())
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesScheduleIdDeleteError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn schedules_schedule_id_get(
+    configuration: &configuration::Configuration,
+    schedule_id: &str,
+) -> Result<models::Schedule, Error<SchedulesScheduleIdGetError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_schedule_id = schedule_id;
+
+    let uri_str = format!(
+        "{}/schedules/{scheduleId}",
+        configuration.base_path,
+        scheduleId = crate::apis::urlencode(p_path_schedule_id)
+    );
+    let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Schedule`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Schedule`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesScheduleIdGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+pub async fn schedules_schedule_id_patch(
+    configuration: &configuration::Configuration,
+    schedule_id: &str,
+    schedule_patch: models::SchedulePatch,
+    idempotency_key: Option<&str>,
+) -> Result<models::Schedule, Error<SchedulesScheduleIdPatchError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_schedule_id = schedule_id;
+    let p_body_schedule_patch = schedule_patch;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!(
+        "{}/schedules/{scheduleId}",
+        configuration.base_path,
+        scheduleId = crate::apis::urlencode(p_path_schedule_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::PATCH, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+    req_builder = req_builder.json(&p_body_schedule_patch);
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Schedule`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Schedule`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesScheduleIdPatchError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Lanza el trabajo en el momento sin mover `next_run_at`
+pub async fn schedules_schedule_id_run_post(
+    configuration: &configuration::Configuration,
+    schedule_id: &str,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<SchedulesScheduleIdRunPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_schedule_id = schedule_id;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!(
+        "{}/schedules/{scheduleId}/run",
+        configuration.base_path,
+        scheduleId = crate::apis::urlencode(p_path_schedule_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::Task`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::Task`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<SchedulesScheduleIdRunPostError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -17,6 +17,9 @@ use serde::{de::Error as _, Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SecurityTokensGetError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -24,6 +27,11 @@ pub enum SecurityTokensGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SecurityTokensPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status422(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -31,7 +39,11 @@ pub enum SecurityTokensPostError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SecurityTokensTokenIdDeleteError {
-    Status404(),
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -39,6 +51,9 @@ pub enum SecurityTokensTokenIdDeleteError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SystemSecurityReloadPostError {
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
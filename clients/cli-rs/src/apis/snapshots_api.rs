@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -17,6 +17,11 @@ use serde::{de::Error as _, Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdSnapshotsGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -24,6 +29,13 @@ pub enum ContainersContainerIdSnapshotsGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContainersContainerIdSnapshotsPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status422(models::ApiError),
+    Status507(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -31,6 +43,11 @@ pub enum ContainersContainerIdSnapshotsPostError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SnapshotsSnapshotIdRestorePostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -85,15 +102,18 @@ pub async fn containers_container_id_snapshots_get(
     }
 }
 
+/// Copia `fs/` y `registry/` del contenedor en `snapshots/<snapshot_id>/`. Se rechaza con `507 quota_exceeded` si el contenedor o el total ya alcanzaron su cuota de disco.
 pub async fn containers_container_id_snapshots_post(
     configuration: &configuration::Configuration,
     container_id: &str,
+    idempotency_key: Option<&str>,
     containers_container_id_snapshots_post_request: Option<
         models::ContainersContainerIdSnapshotsPostRequest,
     >,
 ) -> Result<models::Task, Error<ContainersContainerIdSnapshotsPostError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_container_id = container_id;
+    let p_header_idempotency_key = idempotency_key;
     let p_body_containers_container_id_snapshots_post_request =
         containers_container_id_snapshots_post_request;
 
@@ -109,6 +129,9 @@ pub async fn containers_container_id_snapshots_post(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
     if let Some(ref token) = configuration.bearer_access_token {
         req_builder = req_builder.bearer_auth(token.to_owned());
     };
@@ -144,12 +167,15 @@ pub async fn containers_container_id_snapshots_post(
     }
 }
 
+/// Sustituye `fs/` y `registry/` del contenedor por la copia del snapshot.
 pub async fn snapshots_snapshot_id_restore_post(
     configuration: &configuration::Configuration,
     snapshot_id: &str,
+    idempotency_key: Option<&str>,
 ) -> Result<models::Task, Error<SnapshotsSnapshotIdRestorePostError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_snapshot_id = snapshot_id;
+    let p_header_idempotency_key = idempotency_key;
 
     let uri_str = format!(
         "{}/snapshots/{snapshotId}/restore",
@@ -163,6 +189,9 @@ pub async fn snapshots_snapshot_id_restore_post(
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
     if let Some(ref token) = configuration.bearer_access_token {
         req_builder = req_builder.bearer_auth(token.to_owned());
     };
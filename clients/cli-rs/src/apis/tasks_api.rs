@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -17,6 +17,10 @@ use serde::{de::Error as _, Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TasksGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
@@ -24,16 +28,36 @@ pub enum TasksGetError {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TasksTaskIdGetError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status500(models::ApiError),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`tasks_task_id_retry_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TasksTaskIdRetryPostError {
+    Status400(models::ApiError),
+    Status401(models::ApiError),
+    Status403(models::ApiError),
+    Status404(models::ApiError),
+    Status409(models::ApiError),
+    Status500(models::ApiError),
     UnknownValue(serde_json::Value),
 }
 
 pub async fn tasks_get(
     configuration: &configuration::Configuration,
     status: Option<&str>,
+    schedule_id: Option<&str>,
     limit: Option<i32>,
 ) -> Result<Vec<models::Task>, Error<TasksGetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_query_status = status;
+    let p_query_schedule_id = schedule_id;
     let p_query_limit = limit;
 
     let uri_str = format!("{}/tasks", configuration.base_path);
@@ -42,6 +66,9 @@ pub async fn tasks_get(
     if let Some(ref param_value) = p_query_status {
         req_builder = req_builder.query(&[("status", &param_value.to_string())]);
     }
+    if let Some(ref param_value) = p_query_schedule_id {
+        req_builder = req_builder.query(&[("schedule_id", &param_value.to_string())]);
+    }
     if let Some(ref param_value) = p_query_limit {
         req_builder = req_builder.query(&[("limit", &param_value.to_string())]);
     }
@@ -84,9 +111,11 @@ pub async fn tasks_get(
 pub async fn tasks_task_id_get(
     configuration: &configuration::Configuration,
     task_id: &str,
-) -> Result<models::Task, Error<TasksTaskIdGetError>> {
+    include: Option<&str>,
+) -> Result<models::TasksTaskIdGet200Response, Error<TasksTaskIdGetError>> {
     // add a prefix to parameters to efficiently prevent name collisions
     let p_path_task_id = task_id;
+    let p_query_include = include;
 
     let uri_str = format!(
         "{}/tasks/{taskId}",
@@ -95,6 +124,9 @@ pub async fn tasks_task_id_get(
     );
     let mut req_builder = configuration.client.request(reqwest::Method::GET, &uri_str);
 
+    if let Some(ref param_value) = p_query_include {
+        req_builder = req_builder.query(&[("include", &param_value.to_string())]);
+    }
     if let Some(ref user_agent) = configuration.user_agent {
         req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
     }
@@ -113,6 +145,64 @@ pub async fn tasks_task_id_get(
         .unwrap_or("application/octet-stream");
     let content_type = super::ContentType::from(content_type);
 
+    if !status.is_client_error() && !status.is_server_error() {
+        let content = resp.text().await?;
+        match content_type {
+            ContentType::Json => serde_json::from_str(&content).map_err(Error::from),
+            ContentType::Text => return Err(Error::from(serde_json::Error::custom("Received `text/plain` content type response that cannot be converted to `models::TasksTaskIdGet200Response`"))),
+            ContentType::Unsupported(unknown_type) => return Err(Error::from(serde_json::Error::custom(format!("Received `{unknown_type}` content type response that cannot be converted to `models::TasksTaskIdGet200Response`")))),
+        }
+    } else {
+        let content = resp.text().await?;
+        let entity: Option<TasksTaskIdGetError> = serde_json::from_str(&content).ok();
+        Err(Error::ResponseError(ResponseContent {
+            status,
+            content,
+            entity,
+        }))
+    }
+}
+
+/// Lanza un nuevo intento de una tarea fallida o cancelada de primer nivel, con la misma petición (`payload`). El intento es una tarea nueva con `attempt` + 1 y `retry_of`; en `app.install` sustituye a la app fallida. Cada tarea se reintenta una sola vez: los siguientes intentos se piden sobre el último.
+pub async fn tasks_task_id_retry_post(
+    configuration: &configuration::Configuration,
+    task_id: &str,
+    idempotency_key: Option<&str>,
+) -> Result<models::Task, Error<TasksTaskIdRetryPostError>> {
+    // add a prefix to parameters to efficiently prevent name collisions
+    let p_path_task_id = task_id;
+    let p_header_idempotency_key = idempotency_key;
+
+    let uri_str = format!(
+        "{}/tasks/{taskId}/retry",
+        configuration.base_path,
+        taskId = crate::apis::urlencode(p_path_task_id)
+    );
+    let mut req_builder = configuration
+        .client
+        .request(reqwest::Method::POST, &uri_str);
+
+    if let Some(ref user_agent) = configuration.user_agent {
+        req_builder = req_builder.header(reqwest::header::USER_AGENT, user_agent.clone());
+    }
+    if let Some(param_value) = p_header_idempotency_key {
+        req_builder = req_builder.header("Idempotency-Key", param_value.to_string());
+    }
+    if let Some(ref token) = configuration.bearer_access_token {
+        req_builder = req_builder.bearer_auth(token.to_owned());
+    };
+
+    let req = req_builder.build()?;
+    let resp = configuration.client.execute(req).await?;
+
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream");
+    let content_type = super::ContentType::from(content_type);
+
     if !status.is_client_error() && !status.is_server_error() {
         let content = resp.text().await?;
         match content_type {
@@ -122,7 +212,7 @@ pub async fn tasks_task_id_get(
         }
     } else {
         let content = resp.text().await?;
-        let entity: Option<TasksTaskIdGetError> = serde_json::from_str(&content).ok();
+        let entity: Option<TasksTaskIdRetryPostError> = serde_json::from_str(&content).ok();
         Err(Error::ResponseError(ResponseContent {
             status,
             content,
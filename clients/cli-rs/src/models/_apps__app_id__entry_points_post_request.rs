@@ -0,0 +1,46 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppsAppIdEntryPointsPostRequest {
+    #[serde(rename = "label")]
+    pub label: String,
+    /// Ruta virtual (`C:\\\\Program Files\\\\...`) o relativa a `fs/`; debe existir dentro del contenedor (si no, `422` con `not_in_container`). Se guarda en forma canonica.
+    #[serde(rename = "command")]
+    pub command: String,
+    #[serde(rename = "args", skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+    /// Debe ser un directorio dentro del contenedor
+    #[serde(rename = "working_dir", skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(rename = "icon", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+impl AppsAppIdEntryPointsPostRequest {
+    pub fn new(label: String, command: String) -> AppsAppIdEntryPointsPostRequest {
+        AppsAppIdEntryPointsPostRequest {
+            label,
+            command,
+            args: None,
+            working_dir: None,
+            icon: None,
+            env: None,
+            id: None,
+        }
+    }
+}
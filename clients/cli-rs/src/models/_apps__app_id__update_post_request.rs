@@ -0,0 +1,36 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppsAppIdUpdatePostRequest {
+    #[serde(rename = "installer_path", skip_serializing_if = "Option::is_none")]
+    pub installer_path: Option<String>,
+    /// Excluye `installer_path`; se exige uno de los dos
+    #[serde(rename = "archive_path", skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+    #[serde(rename = "version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(rename = "silent_args", skip_serializing_if = "Option::is_none")]
+    pub silent_args: Option<String>,
+}
+
+impl AppsAppIdUpdatePostRequest {
+    pub fn new() -> AppsAppIdUpdatePostRequest {
+        AppsAppIdUpdatePostRequest {
+            installer_path: None,
+            archive_path: None,
+            version: None,
+            silent_args: None,
+        }
+    }
+}
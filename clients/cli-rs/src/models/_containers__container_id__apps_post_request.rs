@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -13,20 +13,32 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ContainersContainerIdAppsPostRequest {
+    /// Ruta del instalador en el host del agente
     #[serde(rename = "installer_path", skip_serializing_if = "Option::is_none")]
     pub installer_path: Option<String>,
+    /// App portable (`.zip`, `.tar`, `.tar.gz`, `.tgz`); excluye `installer_path`
+    #[serde(rename = "archive_path", skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+    /// Argumentos separados por espacios; las comillas dobles agrupan
     #[serde(rename = "silent_args", skip_serializing_if = "Option::is_none")]
     pub silent_args: Option<String>,
     #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(rename = "version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(rename = "vendor", skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
 }
 
 impl ContainersContainerIdAppsPostRequest {
     pub fn new() -> ContainersContainerIdAppsPostRequest {
         ContainersContainerIdAppsPostRequest {
             installer_path: None,
+            archive_path: None,
             silent_args: None,
             name: None,
+            version: None,
+            vendor: None,
         }
     }
 }
@@ -0,0 +1,43 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainersContainerIdPatchRequest {
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "tags", skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+    /// Flags del catálogo; uno desconocido es un `422` con `compat_flags[i]` `unknown_value` y, si es el único error, código `unknown_compat_flag`
+    #[serde(rename = "compat_flags", skip_serializing_if = "Option::is_none")]
+    pub compat_flags: Option<Vec<String>>,
+    #[serde(rename = "limits", skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Box<models::ContainerLimits>>,
+    /// Sustituye la lista completa de montajes adicionales
+    #[serde(rename = "mounts", skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<Vec<models::MountSpec>>,
+}
+
+impl ContainersContainerIdPatchRequest {
+    pub fn new() -> ContainersContainerIdPatchRequest {
+        ContainersContainerIdPatchRequest {
+            description: None,
+            tags: None,
+            env: None,
+            compat_flags: None,
+            limits: None,
+            mounts: None,
+        }
+    }
+}
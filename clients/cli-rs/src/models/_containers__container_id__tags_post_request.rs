@@ -0,0 +1,24 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainersContainerIdTagsPostRequest {
+    #[serde(rename = "tags")]
+    pub tags: Vec<String>,
+}
+
+impl ContainersContainerIdTagsPostRequest {
+    pub fn new(tags: Vec<String>) -> ContainersContainerIdTagsPostRequest {
+        ContainersContainerIdTagsPostRequest { tags }
+    }
+}
@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -13,18 +13,19 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ContainersPostRequest {
+    /// Sin caracteres de control
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(rename = "platform")]
-    pub platform: String,
+    pub platform: Platform,
     #[serde(rename = "settings", skip_serializing_if = "Option::is_none")]
     pub settings: Option<serde_json::Value>,
 }
 
 impl ContainersPostRequest {
-    pub fn new(name: String, platform: String) -> ContainersPostRequest {
+    pub fn new(name: String, platform: Platform) -> ContainersPostRequest {
         ContainersPostRequest {
             name,
             description: None,
@@ -33,3 +34,17 @@ impl ContainersPostRequest {
         }
     }
 }
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Platform {
+    #[serde(rename = "windows-x64")]
+    WindowsX64,
+    #[serde(rename = "windows-arm64")]
+    WindowsArm64,
+}
+
+impl Default for Platform {
+    fn default() -> Platform {
+        Self::WindowsX64
+    }
+}
@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -21,6 +21,8 @@ pub struct SystemInfoGet200Response {
     pub uptime_seconds: Option<i32>,
     #[serde(rename = "driver_status", skip_serializing_if = "Option::is_none")]
     pub driver_status: Option<String>,
+    #[serde(rename = "task_retention", skip_serializing_if = "Option::is_none")]
+    pub task_retention: Option<Box<models::TaskRetentionStats>>,
 }
 
 impl SystemInfoGet200Response {
@@ -30,6 +32,7 @@ impl SystemInfoGet200Response {
             build: None,
             uptime_seconds: None,
             driver_status: None,
+            task_retention: None,
         }
     }
 }
@@ -0,0 +1,34 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    #[serde(rename = "code")]
+    pub code: models::ErrorCode,
+    /// Texto legible en el idioma indicado por `Content-Language`
+    #[serde(rename = "message")]
+    pub message: String,
+    /// Contexto adicional segun el codigo (p.e. `container_id`, `required_scope`, `fields`)
+    #[serde(rename = "details", skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(code: models::ErrorCode, message: String) -> ApiError {
+        ApiError {
+            code,
+            message,
+            details: None,
+        }
+    }
+}
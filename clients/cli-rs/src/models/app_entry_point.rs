@@ -0,0 +1,48 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppEntryPoint {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "label")]
+    pub label: String,
+    /// Ruta del ejecutable vista desde el contenedor (p.e. `C:\\\\Program Files\\\\App\\\\app.exe`)
+    #[serde(rename = "command")]
+    pub command: String,
+    /// Plantilla de argumentos; admite `{app_id}`, `{container_id}`, `{fs_root}` y `{args}`
+    #[serde(rename = "args", skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+    /// Directorio de trabajo vista desde el contenedor; por defecto el del ejecutable
+    #[serde(rename = "working_dir", skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(rename = "icon", skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Variables que se superponen al entorno del contenedor
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+}
+
+impl AppEntryPoint {
+    pub fn new(id: String, label: String, command: String) -> AppEntryPoint {
+        AppEntryPoint {
+            id,
+            label,
+            command,
+            args: None,
+            working_dir: None,
+            icon: None,
+            env: None,
+        }
+    }
+}
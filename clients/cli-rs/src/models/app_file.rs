@@ -0,0 +1,61 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppFile {
+    /// Ruta vista desde el contenedor
+    #[serde(rename = "path")]
+    pub path: String,
+    /// Ruta relativa a `fs/` dentro del almacenamiento del contenedor
+    #[serde(rename = "relative_path")]
+    pub relative_path: String,
+    #[serde(rename = "size")]
+    pub size: i32,
+    #[serde(rename = "sha256")]
+    pub sha256: String,
+    /// `created` si la app creo el archivo (se elimina al desinstalar); `modified` si ya existia y la instalacion lo reescribio.
+    #[serde(rename = "change")]
+    pub change: Change,
+}
+
+impl AppFile {
+    pub fn new(
+        path: String,
+        relative_path: String,
+        size: i32,
+        sha256: String,
+        change: Change,
+    ) -> AppFile {
+        AppFile {
+            path,
+            relative_path,
+            size,
+            sha256,
+            change,
+        }
+    }
+}
+/// `created` si la app creo el archivo (se elimina al desinstalar); `modified` si ya existia y la instalacion lo reescribio.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Change {
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "modified")]
+    Modified,
+}
+
+impl Default for Change {
+    fn default() -> Change {
+        Self::Created
+    }
+}
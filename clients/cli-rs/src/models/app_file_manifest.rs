@@ -0,0 +1,40 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AppFileManifest {
+    #[serde(rename = "app_id")]
+    pub app_id: uuid::Uuid,
+    #[serde(rename = "generated_at")]
+    pub generated_at: String,
+    #[serde(rename = "total_bytes")]
+    pub total_bytes: i32,
+    #[serde(rename = "files")]
+    pub files: Vec<models::AppFile>,
+}
+
+impl AppFileManifest {
+    pub fn new(
+        app_id: uuid::Uuid,
+        generated_at: String,
+        total_bytes: i32,
+        files: Vec<models::AppFile>,
+    ) -> AppFileManifest {
+        AppFileManifest {
+            app_id,
+            generated_at,
+            total_bytes,
+            files,
+        }
+    }
+}
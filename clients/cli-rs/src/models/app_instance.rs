@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -21,10 +21,45 @@ pub struct AppInstance {
     pub name: String,
     #[serde(rename = "version", skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(rename = "vendor", skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+    /// `ready` implica al menos un entry point; las apps sin entry points quedan en `disabled` hasta que se registre el primero.
     #[serde(rename = "status")]
     pub status: Status,
     #[serde(rename = "entry_points", skip_serializing_if = "Option::is_none")]
-    pub entry_points: Option<Vec<models::AppInstanceEntryPointsInner>>,
+    pub entry_points: Option<Vec<models::AppEntryPoint>>,
+    /// Instalador o archivo original con el que se instalo o actualizo la app
+    #[serde(rename = "install_source", skip_serializing_if = "Option::is_none")]
+    pub install_source: Option<String>,
+    /// SHA-256 (hex) de `install_source`, calculado antes de ejecutarlo
+    #[serde(rename = "installer_sha256", skip_serializing_if = "Option::is_none")]
+    pub installer_sha256: Option<String>,
+    /// Copia del instalador que se ejecutó dentro del contenedor (`runtime/installers/<app_id>/<archivo>`); ausente en las apps portables. El original queda en `install_source`.
+    #[serde(rename = "installer_path", skip_serializing_if = "Option::is_none")]
+    pub installer_path: Option<String>,
+    /// Duracion de la ultima instalacion o actualizacion
+    #[serde(
+        rename = "install_duration_ms",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub install_duration_ms: Option<i32>,
+    /// Suma de los archivos del manifest de la app (`GET /apps/{appId}/files`)
+    #[serde(rename = "size_bytes", skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i32>,
+    #[serde(rename = "silent_args", skip_serializing_if = "Option::is_none")]
+    pub silent_args: Option<String>,
+    /// Codigo de salida del instalador (0, 1641 y 3010 se consideran exito)
+    #[serde(rename = "exit_code", skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Salida del ultimo instalador o desinstalador (`logs/install-<app_id>.log`, `logs/uninstall-<app_id>.log`)
+    #[serde(rename = "log_path", skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<String>,
+    /// Carpeta de la app vista desde el contenedor; al desinstalar se poda si queda vacia
+    #[serde(rename = "install_dir", skip_serializing_if = "Option::is_none")]
+    pub install_dir: Option<String>,
+    /// Desinstalador detectado durante la instalacion (`unins*.exe`, `*uninstall*.exe`)
+    #[serde(rename = "uninstaller", skip_serializing_if = "Option::is_none")]
+    pub uninstaller: Option<String>,
     #[serde(rename = "created_at", skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
 }
@@ -41,13 +76,24 @@ impl AppInstance {
             container_id,
             name,
             version: None,
+            vendor: None,
             status,
             entry_points: None,
+            install_source: None,
+            installer_sha256: None,
+            installer_path: None,
+            install_duration_ms: None,
+            size_bytes: None,
+            silent_args: None,
+            exit_code: None,
+            log_path: None,
+            install_dir: None,
+            uninstaller: None,
             created_at: None,
         }
     }
 }
-///
+/// `ready` implica al menos un entry point; las apps sin entry points quedan en `disabled` hasta que se registre el primero.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum Status {
     #[serde(rename = "installing")]
@@ -58,6 +104,8 @@ pub enum Status {
     Failed,
     #[serde(rename = "disabled")]
     Disabled,
+    #[serde(rename = "uninstalling")]
+    Uninstalling,
 }
 
 impl Default for Status {
@@ -1,35 +0,0 @@
-/*
- * Orbit Container Agent API
- *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
- *
- * The version of the OpenAPI document: 0.1.0
- *
- * Generated by: https://openapi-generator.tech
- */
-
-use crate::models;
-use serde::{Deserialize, Serialize};
-
-#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
-pub struct AppInstanceEntryPointsInner {
-    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
-    #[serde(rename = "label", skip_serializing_if = "Option::is_none")]
-    pub label: Option<String>,
-    #[serde(rename = "command", skip_serializing_if = "Option::is_none")]
-    pub command: Option<String>,
-    #[serde(rename = "icon", skip_serializing_if = "Option::is_none")]
-    pub icon: Option<String>,
-}
-
-impl AppInstanceEntryPointsInner {
-    pub fn new() -> AppInstanceEntryPointsInner {
-        AppInstanceEntryPointsInner {
-            id: None,
-            label: None,
-            command: None,
-            icon: None,
-        }
-    }
-}
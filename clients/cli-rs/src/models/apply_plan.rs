@@ -0,0 +1,43 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplyPlan {
+    #[serde(rename = "prune")]
+    pub prune: bool,
+    /// Cambios en el orden en que se aplican
+    #[serde(rename = "items")]
+    pub items: Vec<models::PlanItem>,
+    /// Contenedores fuera del documento que se conservan porque no se pidió `prune`
+    #[serde(rename = "unmanaged")]
+    pub unmanaged: Vec<String>,
+    /// Huella SHA-256 de `prune` e `items`; `POST /containers/apply` la exige como `fingerprint` si el plan elimina algo
+    #[serde(rename = "fingerprint")]
+    pub fingerprint: String,
+}
+
+impl ApplyPlan {
+    pub fn new(
+        prune: bool,
+        items: Vec<models::PlanItem>,
+        unmanaged: Vec<String>,
+        fingerprint: String,
+    ) -> ApplyPlan {
+        ApplyPlan {
+            prune,
+            items,
+            unmanaged,
+            fingerprint,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChangeLog {
+    /// Solo se vigilan los contenedores en `running`
+    #[serde(rename = "watching")]
+    pub watching: bool,
+    #[serde(rename = "backend", deserialize_with = "Option::deserialize")]
+    pub backend: Option<Backend>,
+    /// Última secuencia anotada en el diario
+    #[serde(rename = "last_seq")]
+    pub last_seq: i32,
+    /// Se perdieron cambios posteriores a `since`, porque el diario estaba lleno o porque se desbordó la cola de avisos del sistema; hay que volver a leer el árbol
+    #[serde(rename = "truncated")]
+    pub truncated: bool,
+    #[serde(rename = "changes")]
+    pub changes: Vec<models::FsChange>,
+}
+
+impl ChangeLog {
+    pub fn new(
+        watching: bool,
+        backend: Option<Backend>,
+        last_seq: i32,
+        truncated: bool,
+        changes: Vec<models::FsChange>,
+    ) -> ChangeLog {
+        ChangeLog {
+            watching,
+            backend,
+            last_seq,
+            truncated,
+            changes,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Backend {
+    #[serde(rename = "inotify")]
+    Inotify,
+    #[serde(rename = "fsevent")]
+    Fsevent,
+    #[serde(rename = "kqueue")]
+    Kqueue,
+    #[serde(rename = "windows")]
+    Windows,
+    #[serde(rename = "poll")]
+    Poll,
+}
+
+impl Default for Backend {
+    fn default() -> Backend {
+        Self::Inotify
+    }
+}
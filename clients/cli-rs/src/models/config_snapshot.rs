@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -23,6 +23,12 @@ pub struct ConfigSnapshot {
     pub database_path: Option<String>,
     #[serde(rename = "security", skip_serializing_if = "Option::is_none")]
     pub security: Option<Box<models::ConfigSecurity>>,
+    #[serde(rename = "storage", skip_serializing_if = "Option::is_none")]
+    pub storage: Option<Box<models::ConfigStorage>>,
+    #[serde(rename = "tasks", skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<Box<models::ConfigTasks>>,
+    #[serde(rename = "mounts", skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<Box<models::ConfigMounts>>,
 }
 
 impl ConfigSnapshot {
@@ -33,6 +39,9 @@ impl ConfigSnapshot {
             api_bind: None,
             database_path: None,
             security: None,
+            storage: None,
+            tasks: None,
+            mounts: None,
         }
     }
 }
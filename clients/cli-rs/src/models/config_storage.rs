@@ -0,0 +1,33 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConfigStorage {
+    /// Intervalo del escaneo de uso de disco; `0` lo desactiva
+    #[serde(rename = "scan_interval_secs", skip_serializing_if = "Option::is_none")]
+    pub scan_interval_secs: Option<i32>,
+    #[serde(rename = "container_quota_mb", skip_serializing_if = "Option::is_none")]
+    pub container_quota_mb: Option<i32>,
+    #[serde(rename = "global_quota_mb", skip_serializing_if = "Option::is_none")]
+    pub global_quota_mb: Option<i32>,
+}
+
+impl ConfigStorage {
+    pub fn new() -> ConfigStorage {
+        ConfigStorage {
+            scan_interval_secs: None,
+            container_quota_mb: None,
+            global_quota_mb: None,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConfigTasks {
+    /// Política de reintentos automáticos por tipo de tarea
+    #[serde(rename = "retry", skip_serializing_if = "Option::is_none")]
+    pub retry: Option<std::collections::HashMap<String, models::RetryPolicy>>,
+    #[serde(rename = "retention", skip_serializing_if = "Option::is_none")]
+    pub retention: Option<Box<models::RetentionPolicy>>,
+}
+
+impl ConfigTasks {
+    pub fn new() -> ConfigTasks {
+        ConfigTasks {
+            retry: None,
+            retention: None,
+        }
+    }
+}
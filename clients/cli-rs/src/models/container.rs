@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -21,14 +21,21 @@ pub struct Container {
     pub description: Option<String>,
     #[serde(rename = "status")]
     pub status: Status,
+    /// Motivo del estado `error` (p. ej. archivos críticos ausentes tras una verificación)
+    #[serde(rename = "status_reason", skip_serializing_if = "Option::is_none")]
+    pub status_reason: Option<String>,
     #[serde(rename = "platform")]
     pub platform: Platform,
     #[serde(rename = "tags", skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// Espacio que ocupa el sandbox según el último escaneo de disco
     #[serde(rename = "size_bytes", skip_serializing_if = "Option::is_none")]
     pub size_bytes: Option<i32>,
+    /// Ruta absoluta del sandbox (`<containers_root>/<id>`)
+    #[serde(rename = "storage_path", skip_serializing_if = "Option::is_none")]
+    pub storage_path: Option<String>,
     #[serde(rename = "settings", skip_serializing_if = "Option::is_none")]
-    pub settings: Option<serde_json::Value>,
+    pub settings: Option<Box<models::ContainerSettings>>,
     #[serde(rename = "created_at")]
     pub created_at: String,
     #[serde(rename = "updated_at", skip_serializing_if = "Option::is_none")]
@@ -48,9 +55,11 @@ impl Container {
             name,
             description: None,
             status,
+            status_reason: None,
             platform,
             tags: None,
             size_bytes: None,
+            storage_path: None,
             settings: None,
             created_at,
             updated_at: None,
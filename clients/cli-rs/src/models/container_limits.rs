@@ -0,0 +1,36 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainerLimits {
+    #[serde(rename = "cpu_percent", skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<i32>,
+    #[serde(rename = "memory_mb", skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<i32>,
+    #[serde(rename = "max_processes", skip_serializing_if = "Option::is_none")]
+    pub max_processes: Option<i32>,
+    /// Cuota de disco del sandbox; sustituye a `storage.container_quota_mb`
+    #[serde(rename = "disk_mb", skip_serializing_if = "Option::is_none")]
+    pub disk_mb: Option<i32>,
+}
+
+impl ContainerLimits {
+    pub fn new() -> ContainerLimits {
+        ContainerLimits {
+            cpu_percent: None,
+            memory_mb: None,
+            max_processes: None,
+            disk_mb: None,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainerSettings {
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+    /// Flags del catalogo (`force_32bit_path`, `disable_hw_accel`, `high_dpi_unaware`, `run_as_invoker`, `windows7_mode`)
+    #[serde(rename = "compat_flags", skip_serializing_if = "Option::is_none")]
+    pub compat_flags: Option<Vec<String>>,
+    #[serde(rename = "limits", skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Box<models::ContainerLimits>>,
+    /// Montajes adicionales a los de la plataforma; sustituyen a los que tengan la misma ruta virtual. Por defecto se redirigen `C:\\\\` → `fs/`, `%PROGRAMFILES%` → `fs/ProgramFiles`, `%PROGRAMFILES(X86)%` → `fs/ProgramFiles (x86)`, `%PROGRAMDATA%` → `fs/ProgramData`, y `%USERPROFILE%`, `Documents`, `%APPDATA%`, `%LOCALAPPDATA%` y `%TEMP%` → `fs/Users/Default/...`; en `windows-arm64` además `%PROGRAMFILES(ARM)%` → `fs/ProgramFiles (Arm)`. Las variables se expanden con el `env` del contenedor.
+    #[serde(rename = "mounts", skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<Vec<models::MountSpec>>,
+}
+
+impl ContainerSettings {
+    pub fn new() -> ContainerSettings {
+        ContainerSettings {
+            env: None,
+            compat_flags: None,
+            limits: None,
+            mounts: None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainerStorage {
+    #[serde(rename = "container_id")]
+    pub container_id: uuid::Uuid,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "usage")]
+    pub usage: Box<models::DiskUsage>,
+    /// `limits.disk_mb` o, si no está, `storage.container_quota_mb`
+    #[serde(rename = "quota_bytes", deserialize_with = "Option::deserialize")]
+    pub quota_bytes: Option<i32>,
+    #[serde(rename = "over_quota")]
+    pub over_quota: bool,
+    #[serde(rename = "scanned_at")]
+    pub scanned_at: String,
+}
+
+impl ContainerStorage {
+    pub fn new(
+        container_id: uuid::Uuid,
+        name: String,
+        usage: models::DiskUsage,
+        quota_bytes: Option<i32>,
+        over_quota: bool,
+        scanned_at: String,
+    ) -> ContainerStorage {
+        ContainerStorage {
+            container_id,
+            name,
+            usage: Box::new(usage),
+            quota_bytes,
+            over_quota,
+            scanned_at,
+        }
+    }
+}
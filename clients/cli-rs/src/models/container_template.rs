@@ -0,0 +1,72 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ContainerTemplate : Plantilla de contenedor. Se guarda como `<data_dir>/templates/<name>.toml` (el directorio de la base de datos) y se acepta como JSON o como TOML con `Content-Type: application/toml`, con los mismos campos.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContainerTemplate {
+    /// Nombre de la plantilla; sale de la ruta
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "platform")]
+    pub platform: Platform,
+    #[serde(rename = "tags", skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+    #[serde(rename = "compat_flags", skip_serializing_if = "Option::is_none")]
+    pub compat_flags: Option<Vec<String>>,
+    #[serde(rename = "limits", skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Box<models::ContainerLimits>>,
+    #[serde(rename = "mounts", skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<Vec<models::MountSpec>>,
+    /// Valores que se añaden a los hives del contenedor en formato `.reg`.
+    #[serde(rename = "registry", skip_serializing_if = "Option::is_none")]
+    pub registry: Option<Vec<models::RegistrySeed>>,
+    /// Apps que se instalan en orden; mismos campos que `POST /containers/{containerId}/apps`.
+    #[serde(rename = "apps", skip_serializing_if = "Option::is_none")]
+    pub apps: Option<Vec<models::TemplateApp>>,
+}
+
+impl ContainerTemplate {
+    /// Plantilla de contenedor. Se guarda como `<data_dir>/templates/<name>.toml` (el directorio de la base de datos) y se acepta como JSON o como TOML con `Content-Type: application/toml`, con los mismos campos.
+    pub fn new(platform: Platform) -> ContainerTemplate {
+        ContainerTemplate {
+            name: None,
+            description: None,
+            platform,
+            tags: None,
+            env: None,
+            compat_flags: None,
+            limits: None,
+            mounts: None,
+            registry: None,
+            apps: None,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Platform {
+    #[serde(rename = "windows-x64")]
+    WindowsX64,
+    #[serde(rename = "windows-arm64")]
+    WindowsArm64,
+}
+
+impl Default for Platform {
+    fn default() -> Platform {
+        Self::WindowsX64
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DesiredContainer {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// No se puede cambiar en un contenedor existente (`immutable`)
+    #[serde(rename = "platform")]
+    pub platform: Platform,
+    #[serde(rename = "tags", skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(rename = "env", skip_serializing_if = "Option::is_none")]
+    pub env: Option<std::collections::HashMap<String, String>>,
+    #[serde(rename = "compat_flags", skip_serializing_if = "Option::is_none")]
+    pub compat_flags: Option<Vec<String>>,
+    #[serde(rename = "limits", skip_serializing_if = "Option::is_none")]
+    pub limits: Option<Box<models::ContainerLimits>>,
+    #[serde(rename = "mounts", skip_serializing_if = "Option::is_none")]
+    pub mounts: Option<Vec<models::MountSpec>>,
+    /// Apps que deben estar instaladas; `name` es obligatorio y se usa para compararlas.
+    #[serde(rename = "apps", skip_serializing_if = "Option::is_none")]
+    pub apps: Option<Vec<models::TemplateApp>>,
+}
+
+impl DesiredContainer {
+    pub fn new(name: String, platform: Platform) -> DesiredContainer {
+        DesiredContainer {
+            name,
+            description: None,
+            platform,
+            tags: None,
+            env: None,
+            compat_flags: None,
+            limits: None,
+            mounts: None,
+            apps: None,
+        }
+    }
+}
+/// No se puede cambiar en un contenedor existente (`immutable`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Platform {
+    #[serde(rename = "windows-x64")]
+    WindowsX64,
+    #[serde(rename = "windows-arm64")]
+    WindowsArm64,
+}
+
+impl Default for Platform {
+    fn default() -> Platform {
+        Self::WindowsX64
+    }
+}
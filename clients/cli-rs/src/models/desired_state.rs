@@ -0,0 +1,26 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// DesiredState : Estado deseado de `orbit apply`. Se acepta como JSON o como TOML con `Content-Type: application/toml`; los contenedores se identifican por nombre.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DesiredState {
+    #[serde(rename = "containers", skip_serializing_if = "Option::is_none")]
+    pub containers: Option<Vec<models::DesiredContainer>>,
+}
+
+impl DesiredState {
+    /// Estado deseado de `orbit apply`. Se acepta como JSON o como TOML con `Content-Type: application/toml`; los contenedores se identifican por nombre.
+    pub fn new() -> DesiredState {
+        DesiredState { containers: None }
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiskUsage {
+    #[serde(rename = "fs")]
+    pub fs: i32,
+    #[serde(rename = "registry")]
+    pub registry: i32,
+    #[serde(rename = "snapshots")]
+    pub snapshots: i32,
+    #[serde(rename = "logs")]
+    pub logs: i32,
+    /// `runtime/` y cualquier otro archivo del sandbox
+    #[serde(rename = "other")]
+    pub other: i32,
+    #[serde(rename = "total")]
+    pub total: i32,
+}
+
+impl DiskUsage {
+    pub fn new(
+        fs: i32,
+        registry: i32,
+        snapshots: i32,
+        logs: i32,
+        other: i32,
+        total: i32,
+    ) -> DiskUsage {
+        DiskUsage {
+            fs,
+            registry,
+            snapshots,
+            logs,
+            other,
+            total,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ErrorCode : Codigo estable para que los clientes ramifiquen; no cambia entre idiomas.
+/// Codigo estable para que los clientes ramifiquen; no cambia entre idiomas.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    #[serde(rename = "unauthorized")]
+    Unauthorized,
+    #[serde(rename = "admin_required")]
+    AdminRequired,
+    #[serde(rename = "scope_missing")]
+    ScopeMissing,
+    #[serde(rename = "invalid_body")]
+    InvalidBody,
+    #[serde(rename = "invalid_path")]
+    InvalidPath,
+    #[serde(rename = "invalid_query")]
+    InvalidQuery,
+    #[serde(rename = "validation_failed")]
+    ValidationFailed,
+    #[serde(rename = "unknown_compat_flag")]
+    UnknownCompatFlag,
+    #[serde(rename = "invalid_expiration")]
+    InvalidExpiration,
+    #[serde(rename = "container_not_found")]
+    ContainerNotFound,
+    #[serde(rename = "container_name_conflict")]
+    ContainerNameConflict,
+    #[serde(rename = "container_not_ready")]
+    ContainerNotReady,
+    #[serde(rename = "app_not_found")]
+    AppNotFound,
+    #[serde(rename = "app_busy")]
+    AppBusy,
+    #[serde(rename = "entry_point_not_found")]
+    EntryPointNotFound,
+    #[serde(rename = "entry_point_conflict")]
+    EntryPointConflict,
+    #[serde(rename = "entry_point_required")]
+    EntryPointRequired,
+    #[serde(rename = "file_not_found")]
+    FileNotFound,
+    #[serde(rename = "snapshot_not_found")]
+    SnapshotNotFound,
+    #[serde(rename = "task_not_found")]
+    TaskNotFound,
+    #[serde(rename = "token_not_found")]
+    TokenNotFound,
+    #[serde(rename = "orphan_not_found")]
+    OrphanNotFound,
+    #[serde(rename = "template_not_found")]
+    TemplateNotFound,
+    #[serde(rename = "schedule_not_found")]
+    ScheduleNotFound,
+    #[serde(rename = "schedule_name_conflict")]
+    ScheduleNameConflict,
+    #[serde(rename = "task_not_retryable")]
+    TaskNotRetryable,
+    #[serde(rename = "plan_not_confirmed")]
+    PlanNotConfirmed,
+    #[serde(rename = "idempotency_key_in_use")]
+    IdempotencyKeyInUse,
+    #[serde(rename = "idempotency_key_reused")]
+    IdempotencyKeyReused,
+    #[serde(rename = "quota_exceeded")]
+    QuotaExceeded,
+    #[serde(rename = "internal_error")]
+    InternalError,
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized => write!(f, "unauthorized"),
+            Self::AdminRequired => write!(f, "admin_required"),
+            Self::ScopeMissing => write!(f, "scope_missing"),
+            Self::InvalidBody => write!(f, "invalid_body"),
+            Self::InvalidPath => write!(f, "invalid_path"),
+            Self::InvalidQuery => write!(f, "invalid_query"),
+            Self::ValidationFailed => write!(f, "validation_failed"),
+            Self::UnknownCompatFlag => write!(f, "unknown_compat_flag"),
+            Self::InvalidExpiration => write!(f, "invalid_expiration"),
+            Self::ContainerNotFound => write!(f, "container_not_found"),
+            Self::ContainerNameConflict => write!(f, "container_name_conflict"),
+            Self::ContainerNotReady => write!(f, "container_not_ready"),
+            Self::AppNotFound => write!(f, "app_not_found"),
+            Self::AppBusy => write!(f, "app_busy"),
+            Self::EntryPointNotFound => write!(f, "entry_point_not_found"),
+            Self::EntryPointConflict => write!(f, "entry_point_conflict"),
+            Self::EntryPointRequired => write!(f, "entry_point_required"),
+            Self::FileNotFound => write!(f, "file_not_found"),
+            Self::SnapshotNotFound => write!(f, "snapshot_not_found"),
+            Self::TaskNotFound => write!(f, "task_not_found"),
+            Self::TokenNotFound => write!(f, "token_not_found"),
+            Self::OrphanNotFound => write!(f, "orphan_not_found"),
+            Self::TemplateNotFound => write!(f, "template_not_found"),
+            Self::ScheduleNotFound => write!(f, "schedule_not_found"),
+            Self::ScheduleNameConflict => write!(f, "schedule_name_conflict"),
+            Self::TaskNotRetryable => write!(f, "task_not_retryable"),
+            Self::PlanNotConfirmed => write!(f, "plan_not_confirmed"),
+            Self::IdempotencyKeyInUse => write!(f, "idempotency_key_in_use"),
+            Self::IdempotencyKeyReused => write!(f, "idempotency_key_reused"),
+            Self::QuotaExceeded => write!(f, "quota_exceeded"),
+            Self::InternalError => write!(f, "internal_error"),
+        }
+    }
+}
+
+impl Default for ErrorCode {
+    fn default() -> ErrorCode {
+        Self::Unauthorized
+    }
+}
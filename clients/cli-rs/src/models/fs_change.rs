@@ -0,0 +1,66 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FsChange {
+    #[serde(rename = "seq")]
+    pub seq: i32,
+    #[serde(rename = "kind")]
+    pub kind: Kind,
+    /// Ruta vista desde el contenedor
+    #[serde(rename = "path")]
+    pub path: String,
+    /// Ruta relativa a `fs/`
+    #[serde(rename = "relative")]
+    pub relative: String,
+    #[serde(rename = "is_dir")]
+    pub is_dir: bool,
+    #[serde(rename = "at")]
+    pub at: String,
+}
+
+impl FsChange {
+    pub fn new(
+        seq: i32,
+        kind: Kind,
+        path: String,
+        relative: String,
+        is_dir: bool,
+        at: String,
+    ) -> FsChange {
+        FsChange {
+            seq,
+            kind,
+            path,
+            relative,
+            is_dir,
+            at,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "modified")]
+    Modified,
+    #[serde(rename = "removed")]
+    Removed,
+}
+
+impl Default for Kind {
+    fn default() -> Kind {
+        Self::Created
+    }
+}
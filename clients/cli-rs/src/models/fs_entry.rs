@@ -0,0 +1,57 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FsEntry {
+    #[serde(rename = "name")]
+    pub name: String,
+    /// Ruta vista desde el contenedor (`C:\\\\Program Files\\\\...`)
+    #[serde(rename = "path")]
+    pub path: String,
+    /// Los enlaces se listan sin seguirlos
+    #[serde(rename = "kind")]
+    pub kind: Kind,
+    /// Bytes; `0` para directorios y enlaces
+    #[serde(rename = "size")]
+    pub size: i32,
+    #[serde(rename = "modified", skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+}
+
+impl FsEntry {
+    pub fn new(name: String, path: String, kind: Kind, size: i32) -> FsEntry {
+        FsEntry {
+            name,
+            path,
+            kind,
+            size,
+            modified: None,
+        }
+    }
+}
+/// Los enlaces se listan sin seguirlos
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Kind {
+    #[serde(rename = "file")]
+    File,
+    #[serde(rename = "directory")]
+    Directory,
+    #[serde(rename = "symlink")]
+    Symlink,
+}
+
+impl Default for Kind {
+    fn default() -> Kind {
+        Self::File
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityFinding {
+    /// `sandbox`, `app:<id>` o `snapshot:<id>`
+    #[serde(rename = "scope")]
+    pub scope: String,
+    /// Ruta virtual para archivos de apps; relativa al sandbox en el resto
+    #[serde(rename = "path")]
+    pub path: String,
+    /// Directorios del sandbox, hives, `runtime/manifest.json` y ejecutables de entry points de apps `ready`
+    #[serde(rename = "critical")]
+    pub critical: bool,
+    #[serde(rename = "expected_sha256", skip_serializing_if = "Option::is_none")]
+    pub expected_sha256: Option<String>,
+    #[serde(rename = "actual_sha256", skip_serializing_if = "Option::is_none")]
+    pub actual_sha256: Option<String>,
+}
+
+impl IntegrityFinding {
+    pub fn new(scope: String, path: String, critical: bool) -> IntegrityFinding {
+        IntegrityFinding {
+            scope,
+            path,
+            critical,
+            expected_sha256: None,
+            actual_sha256: None,
+        }
+    }
+}
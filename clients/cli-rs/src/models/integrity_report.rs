@@ -0,0 +1,41 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    #[serde(rename = "checked_files")]
+    pub checked_files: i32,
+    #[serde(rename = "missing")]
+    pub missing: Vec<models::IntegrityFinding>,
+    #[serde(rename = "modified")]
+    pub modified: Vec<models::IntegrityFinding>,
+    /// Archivos en el directorio de instalación de una app o en un snapshot que no figuran en ningún manifest
+    #[serde(rename = "unexpected")]
+    pub unexpected: Vec<models::IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    pub fn new(
+        checked_files: i32,
+        missing: Vec<models::IntegrityFinding>,
+        modified: Vec<models::IntegrityFinding>,
+        unexpected: Vec<models::IntegrityFinding>,
+    ) -> IntegrityReport {
+        IntegrityReport {
+            checked_files,
+            missing,
+            modified,
+            unexpected,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// MissedRunPolicy : Qué hacer con las ejecuciones que vencieron con el agente parado: `skip` las descarta y `run-once` lanza una sola al arrancar
+/// Qué hacer con las ejecuciones que vencieron con el agente parado: `skip` las descarta y `run-once` lanza una sola al arrancar
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum MissedRunPolicy {
+    #[serde(rename = "skip")]
+    Skip,
+    #[serde(rename = "run-once")]
+    RunOnce,
+}
+
+impl std::fmt::Display for MissedRunPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Skip => write!(f, "skip"),
+            Self::RunOnce => write!(f, "run-once"),
+        }
+    }
+}
+
+impl Default for MissedRunPolicy {
+    fn default() -> MissedRunPolicy {
+        Self::Skip
+    }
+}
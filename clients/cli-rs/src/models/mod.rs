@@ -1,13 +1,29 @@
+pub mod api_error;
+pub use self::api_error::ApiError;
 pub mod api_token;
 pub use self::api_token::ApiToken;
 pub mod api_token_created;
 pub use self::api_token_created::ApiTokenCreated;
+pub mod app_entry_point;
+pub use self::app_entry_point::AppEntryPoint;
+pub mod app_file;
+pub use self::app_file::AppFile;
+pub mod app_file_manifest;
+pub use self::app_file_manifest::AppFileManifest;
 pub mod app_instance;
 pub use self::app_instance::AppInstance;
-pub mod app_instance_entry_points_inner;
-pub use self::app_instance_entry_points_inner::AppInstanceEntryPointsInner;
+pub mod apply_plan;
+pub use self::apply_plan::ApplyPlan;
+pub mod _apps__app_id__entry_points_post_request;
+pub use self::_apps__app_id__entry_points_post_request::AppsAppIdEntryPointsPostRequest;
 pub mod _apps__app_id__launch_post_request;
 pub use self::_apps__app_id__launch_post_request::AppsAppIdLaunchPostRequest;
+pub mod _apps__app_id__update_post_request;
+pub use self::_apps__app_id__update_post_request::AppsAppIdUpdatePostRequest;
+pub mod change_log;
+pub use self::change_log::ChangeLog;
+pub mod config_mounts;
+pub use self::config_mounts::ConfigMounts;
 pub mod config_response;
 pub use self::config_response::ConfigResponse;
 pub mod config_security;
@@ -16,21 +32,115 @@ pub mod config_snapshot;
 pub use self::config_snapshot::ConfigSnapshot;
 pub mod config_sources;
 pub use self::config_sources::ConfigSources;
+pub mod config_storage;
+pub use self::config_storage::ConfigStorage;
+pub mod config_tasks;
+pub use self::config_tasks::ConfigTasks;
 pub mod container;
 pub use self::container::Container;
+pub mod container_limits;
+pub use self::container_limits::ContainerLimits;
 pub mod _containers__container_id__apps_post_request;
 pub use self::_containers__container_id__apps_post_request::ContainersContainerIdAppsPostRequest;
+pub mod _containers__container_id__patch_request;
+pub use self::_containers__container_id__patch_request::ContainersContainerIdPatchRequest;
+pub mod _containers__container_id__rename_post_request;
+pub use self::_containers__container_id__rename_post_request::ContainersContainerIdRenamePostRequest;
 pub mod _containers__container_id__snapshots_post_request;
 pub use self::_containers__container_id__snapshots_post_request::ContainersContainerIdSnapshotsPostRequest;
+pub mod _containers__container_id__tags_post_request;
+pub use self::_containers__container_id__tags_post_request::ContainersContainerIdTagsPostRequest;
+pub mod container_settings;
+pub use self::container_settings::ContainerSettings;
+pub mod _containers_from_template_post_request;
+pub use self::_containers_from_template_post_request::ContainersFromTemplatePostRequest;
+pub mod _containers_orphans_adopt_post_request;
+pub use self::_containers_orphans_adopt_post_request::ContainersOrphansAdoptPostRequest;
 pub mod _containers_post_request;
 pub use self::_containers_post_request::ContainersPostRequest;
+pub mod container_storage;
+pub use self::container_storage::ContainerStorage;
+pub mod container_template;
+pub use self::container_template::ContainerTemplate;
+pub mod desired_container;
+pub use self::desired_container::DesiredContainer;
+pub mod desired_state;
+pub use self::desired_state::DesiredState;
+pub mod disk_usage;
+pub use self::disk_usage::DiskUsage;
+pub mod entry_point_input;
+pub use self::entry_point_input::EntryPointInput;
+pub mod error_code;
+pub use self::error_code::ErrorCode;
+pub mod fs_change;
+pub use self::fs_change::FsChange;
+pub mod fs_entry;
+pub use self::fs_entry::FsEntry;
+pub mod integrity_finding;
+pub use self::integrity_finding::IntegrityFinding;
+pub mod integrity_report;
+pub use self::integrity_report::IntegrityReport;
+pub mod missed_run_policy;
+pub use self::missed_run_policy::MissedRunPolicy;
+pub mod mount_spec;
+pub use self::mount_spec::MountSpec;
+pub mod orphan_sandbox;
+pub use self::orphan_sandbox::OrphanSandbox;
+pub mod path_resolution;
+pub use self::path_resolution::PathResolution;
+pub mod plan_item;
+pub use self::plan_item::PlanItem;
+pub mod reconcile_report;
+pub use self::reconcile_report::ReconcileReport;
+pub mod reconcile_report_skipped_inner;
+pub use self::reconcile_report_skipped_inner::ReconcileReportSkippedInner;
+pub mod registry_seed;
+pub use self::registry_seed::RegistrySeed;
+pub mod registry_seed_value;
+pub use self::registry_seed_value::RegistrySeedValue;
+pub mod retention_policy;
+pub use self::retention_policy::RetentionPolicy;
+pub mod retry_policy;
+pub use self::retry_policy::RetryPolicy;
+pub mod schedule;
+pub use self::schedule::Schedule;
+pub mod schedule_input;
+pub use self::schedule_input::ScheduleInput;
+pub mod schedule_job;
+pub use self::schedule_job::ScheduleJob;
+pub mod schedule_job_one_of;
+pub use self::schedule_job_one_of::ScheduleJobOneOf;
+pub mod schedule_job_one_of_1;
+pub use self::schedule_job_one_of_1::ScheduleJobOneOf1;
+pub mod schedule_job_one_of_2;
+pub use self::schedule_job_one_of_2::ScheduleJobOneOf2;
+pub mod schedule_job_one_of_3;
+pub use self::schedule_job_one_of_3::ScheduleJobOneOf3;
+pub mod schedule_job_one_of_4;
+pub use self::schedule_job_one_of_4::ScheduleJobOneOf4;
+pub mod schedule_patch;
+pub use self::schedule_patch::SchedulePatch;
 pub mod security_status;
 pub use self::security_status::SecurityStatus;
 pub mod _security_tokens_post_request;
 pub use self::_security_tokens_post_request::SecurityTokensPostRequest;
 pub mod snapshot;
 pub use self::snapshot::Snapshot;
+pub mod storage_summary;
+pub use self::storage_summary::StorageSummary;
 pub mod _system_info_get_200_response;
 pub use self::_system_info_get_200_response::SystemInfoGet200Response;
 pub mod task;
 pub use self::task::Task;
+pub mod task_retention_stats;
+pub use self::task_retention_stats::TaskRetentionStats;
+pub mod task_retention_stats_last_sweep;
+pub use self::task_retention_stats_last_sweep::TaskRetentionStatsLastSweep;
+pub mod _tasks__task_id__get_200_response;
+pub use self::_tasks__task_id__get_200_response::TasksTaskIdGet200Response;
+pub mod task_tree;
+pub use self::task_tree::TaskTree;
+pub mod template_app;
+pub use self::template_app::TemplateApp;
+pub mod watch_status;
+pub use self::watch_status::WatchStatus;
@@ -0,0 +1,39 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MountSpec {
+    /// Ruta literal bajo `C:\\\\` (sin `%VARIABLES%` ni `..`); `C:\\\\` está reservada
+    #[serde(rename = "virtual_path")]
+    pub virtual_path: String,
+    /// Directorio relativo a `fs/` (sin `..` ni rutas absolutas); no se admite junto a `source`
+    #[serde(rename = "target", skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Directorio absoluto y existente del host que se monta tal cual (bind). Debe estar bajo `[mounts] bind_roots`; si no, `422` con `not_allowed` en `mounts[n].source`
+    #[serde(rename = "source", skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Rechaza las subidas de archivos bajo el montaje (`read_only`). Los binds deben ser de solo lectura salvo `[mounts] writable_binds`; si no, `422` con `not_allowed` en `mounts[n].read_only`
+    #[serde(rename = "read_only", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+}
+
+impl MountSpec {
+    pub fn new(virtual_path: String) -> MountSpec {
+        MountSpec {
+            virtual_path,
+            target: None,
+            source: None,
+            read_only: None,
+        }
+    }
+}
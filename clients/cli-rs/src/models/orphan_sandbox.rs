@@ -0,0 +1,81 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrphanSandbox {
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "container_id")]
+    pub container_id: uuid::Uuid,
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "platform")]
+    pub platform: Platform,
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+    /// Versión del manifest en disco; se actualiza al adoptarlo.
+    #[serde(rename = "schema_version")]
+    pub schema_version: i32,
+    #[serde(rename = "conflict", deserialize_with = "Option::deserialize")]
+    pub conflict: Option<Conflict>,
+}
+
+impl OrphanSandbox {
+    pub fn new(
+        path: String,
+        container_id: uuid::Uuid,
+        name: String,
+        platform: Platform,
+        created_at: String,
+        schema_version: i32,
+        conflict: Option<Conflict>,
+    ) -> OrphanSandbox {
+        OrphanSandbox {
+            path,
+            container_id,
+            name,
+            platform,
+            created_at,
+            schema_version,
+            conflict,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Platform {
+    #[serde(rename = "windows-x64")]
+    WindowsX64,
+    #[serde(rename = "windows-arm64")]
+    WindowsArm64,
+}
+
+impl Default for Platform {
+    fn default() -> Platform {
+        Self::WindowsX64
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Conflict {
+    #[serde(rename = "id_in_use")]
+    IdInUse,
+    #[serde(rename = "name_in_use")]
+    NameInUse,
+}
+
+impl Default for Conflict {
+    fn default() -> Conflict {
+        Self::IdInUse
+    }
+}
@@ -0,0 +1,52 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PathResolution {
+    /// Ruta expandida y normalizada (`C:\\\\Users\\\\Default\\\\AppData\\\\Local\\\\Temp`)
+    #[serde(rename = "virtual_path")]
+    pub virtual_path: String,
+    /// Montaje con el prefijo más largo que la resuelve
+    #[serde(rename = "mount")]
+    pub mount: String,
+    /// Ruta relativa a `fs/`; `null` si la resuelve un bind del host
+    #[serde(rename = "relative", deserialize_with = "Option::deserialize")]
+    pub relative: Option<String>,
+    /// Ruta absoluta en disco
+    #[serde(rename = "physical")]
+    pub physical: String,
+    #[serde(rename = "read_only")]
+    pub read_only: bool,
+    #[serde(rename = "exists")]
+    pub exists: bool,
+}
+
+impl PathResolution {
+    pub fn new(
+        virtual_path: String,
+        mount: String,
+        relative: Option<String>,
+        physical: String,
+        read_only: bool,
+        exists: bool,
+    ) -> PathResolution {
+        PathResolution {
+            virtual_path,
+            mount,
+            relative,
+            physical,
+            read_only,
+            exists,
+        }
+    }
+}
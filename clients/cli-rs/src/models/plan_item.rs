@@ -0,0 +1,77 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlanItem {
+    #[serde(rename = "action")]
+    pub action: Action,
+    #[serde(rename = "container")]
+    pub container: String,
+    /// Presente si el cambio es sobre una app del contenedor
+    #[serde(rename = "app", skip_serializing_if = "Option::is_none")]
+    pub app: Option<String>,
+    /// Campos que difieren en un `update`
+    #[serde(rename = "changes", skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Vec<Changes>>,
+}
+
+impl PlanItem {
+    pub fn new(action: Action, container: String) -> PlanItem {
+        PlanItem {
+            action,
+            container,
+            app: None,
+            changes: None,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Action {
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "update")]
+    Update,
+    #[serde(rename = "delete")]
+    Delete,
+}
+
+impl Default for Action {
+    fn default() -> Action {
+        Self::Create
+    }
+}
+/// Campos que difieren en un `update`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Changes {
+    #[serde(rename = "description")]
+    Description,
+    #[serde(rename = "tags")]
+    Tags,
+    #[serde(rename = "env")]
+    Env,
+    #[serde(rename = "compat_flags")]
+    CompatFlags,
+    #[serde(rename = "limits")]
+    Limits,
+    #[serde(rename = "mounts")]
+    Mounts,
+    #[serde(rename = "version")]
+    Version,
+}
+
+impl Default for Changes {
+    fn default() -> Changes {
+        Self::Description
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    /// Contenedores reconstruidos desde `runtime/manifest.json`.
+    #[serde(rename = "restored")]
+    pub restored: Vec<uuid::Uuid>,
+    /// Contenedores cuyo manifest (ausente, ilegible o de un esquema anterior) se reescribió desde la base de datos.
+    #[serde(rename = "repaired")]
+    pub repaired: Vec<uuid::Uuid>,
+    /// Contenedores sin directorio de sandbox; quedan en `error`.
+    #[serde(rename = "missing_sandbox")]
+    pub missing_sandbox: Vec<uuid::Uuid>,
+    #[serde(rename = "skipped")]
+    pub skipped: Vec<models::ReconcileReportSkippedInner>,
+}
+
+impl ReconcileReport {
+    pub fn new(
+        restored: Vec<uuid::Uuid>,
+        repaired: Vec<uuid::Uuid>,
+        missing_sandbox: Vec<uuid::Uuid>,
+        skipped: Vec<models::ReconcileReportSkippedInner>,
+    ) -> ReconcileReport {
+        ReconcileReport {
+            restored,
+            repaired,
+            missing_sandbox,
+            skipped,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegistrySeed {
+    /// Clave bajo `HKLM\\\\SOFTWARE`, `HKLM\\\\SYSTEM` o `HKCU` (también con los prefijos largos `HKEY_LOCAL_MACHINE` y `HKEY_CURRENT_USER`); otra raíz da `unknown_hive`.
+    #[serde(rename = "key")]
+    pub key: String,
+    /// Nombre del valor; sin él se escribe el valor por defecto (`@`)
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Texto (`REG_SZ`) o entero de 32 bits (`REG_DWORD`)
+    #[serde(rename = "value")]
+    pub value: Box<models::RegistrySeedValue>,
+}
+
+impl RegistrySeed {
+    pub fn new(key: String, value: models::RegistrySeedValue) -> RegistrySeed {
+        RegistrySeed {
+            key,
+            name: None,
+            value: Box::new(value),
+        }
+    }
+}
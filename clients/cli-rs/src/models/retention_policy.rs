@@ -0,0 +1,48 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// RetentionPolicy : Limpieza del historial de tareas terminadas (`[tasks.retention]`). Se evalúan las tareas de primer nivel y cada una se borra con sus subtareas; sin ningún límite no se borra nada. Un reintento se conserva mientras exista la tarea de su `retry_of`
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Días que se conservan las tareas terminadas desde su última actualización
+    #[serde(rename = "max_age_days", skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<i32>,
+    /// Tareas terminadas que se conservan como mucho, sin contar las fallidas
+    #[serde(rename = "max_tasks", skip_serializing_if = "Option::is_none")]
+    pub max_tasks: Option<i32>,
+    /// Días que se conservan las fallidas, nunca menos que `max_age_days`; sin valor no se borran
+    #[serde(
+        rename = "failed_max_age_days",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub failed_max_age_days: Option<i32>,
+    /// Guarda las tareas en `<data_dir>/archive/tasks/tasks-<fecha>-<lote>.ndjson.gz` (una por línea) antes de borrarlas
+    #[serde(rename = "archive", skip_serializing_if = "Option::is_none")]
+    pub archive: Option<bool>,
+    /// Cada cuánto se aplica, empezando al arrancar; `0` la desactiva
+    #[serde(rename = "interval_secs", skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<i32>,
+}
+
+impl RetentionPolicy {
+    /// Limpieza del historial de tareas terminadas (`[tasks.retention]`). Se evalúan las tareas de primer nivel y cada una se borra con sus subtareas; sin ningún límite no se borra nada. Un reintento se conserva mientras exista la tarea de su `retry_of`
+    pub fn new() -> RetentionPolicy {
+        RetentionPolicy {
+            max_age_days: None,
+            max_tasks: None,
+            failed_max_age_days: None,
+            archive: None,
+            interval_secs: None,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Intentos en total, contando el primero
+    #[serde(rename = "max_attempts")]
+    pub max_attempts: i32,
+    /// Espera antes del segundo intento; se duplica en cada uno
+    #[serde(rename = "backoff_ms", skip_serializing_if = "Option::is_none")]
+    pub backoff_ms: Option<i32>,
+    #[serde(rename = "max_backoff_ms", skip_serializing_if = "Option::is_none")]
+    pub max_backoff_ms: Option<i32>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: i32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            backoff_ms: None,
+            max_backoff_ms: None,
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(rename = "id")]
+    pub id: uuid::Uuid,
+    /// Único sin distinguir mayúsculas
+    #[serde(rename = "name")]
+    pub name: String,
+    /// Expresión de cinco campos (`minuto hora día mes día-semana`) evaluada en UTC; admite listas, rangos, pasos, nombres (`jan`, `mon`) y `@hourly`, `@daily`, `@weekly`, `@monthly` y `@yearly`
+    #[serde(rename = "cron")]
+    pub cron: String,
+    #[serde(rename = "job")]
+    pub job: Box<models::ScheduleJob>,
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+    #[serde(rename = "missed_runs")]
+    pub missed_runs: models::MissedRunPolicy,
+    /// Próxima ejecución; `null` si está desactivada
+    #[serde(rename = "next_run_at", skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<String>,
+    #[serde(rename = "last_run_at", skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<String>,
+    /// Última tarea lanzada; mientras no termine se saltan las siguientes ejecuciones
+    #[serde(rename = "last_task_id", skip_serializing_if = "Option::is_none")]
+    pub last_task_id: Option<uuid::Uuid>,
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+}
+
+impl Schedule {
+    pub fn new(
+        id: uuid::Uuid,
+        name: String,
+        cron: String,
+        job: models::ScheduleJob,
+        enabled: bool,
+        missed_runs: models::MissedRunPolicy,
+        created_at: String,
+        updated_at: String,
+    ) -> Schedule {
+        Schedule {
+            id,
+            name,
+            cron,
+            job: Box::new(job),
+            enabled,
+            missed_runs,
+            next_run_at: None,
+            last_run_at: None,
+            last_task_id: None,
+            created_at,
+            updated_at,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleInput {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "cron")]
+    pub cron: String,
+    #[serde(rename = "job")]
+    pub job: Box<models::ScheduleJob>,
+    #[serde(rename = "enabled", skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "missed_runs", skip_serializing_if = "Option::is_none")]
+    pub missed_runs: Option<models::MissedRunPolicy>,
+}
+
+impl ScheduleInput {
+    pub fn new(name: String, cron: String, job: models::ScheduleJob) -> ScheduleInput {
+        ScheduleInput {
+            name,
+            cron,
+            job: Box::new(job),
+            enabled: None,
+            missed_runs: None,
+        }
+    }
+}
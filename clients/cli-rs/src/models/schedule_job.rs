@@ -0,0 +1,34 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ScheduleJob : Trabajo que lanza una programación; `type` elige la variante
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ScheduleJob {
+    #[serde(rename = "snapshot")]
+    ScheduleJobOneOf(Box<models::ScheduleJobOneOf>),
+    #[serde(rename = "verify")]
+    ScheduleJobOneOf1(Box<models::ScheduleJobOneOf1>),
+    #[serde(rename = "prune")]
+    ScheduleJobOneOf2(Box<models::ScheduleJobOneOf2>),
+    #[serde(rename = "export")]
+    ScheduleJobOneOf3(Box<models::ScheduleJobOneOf3>),
+    #[serde(rename = "token-sweep")]
+    ScheduleJobOneOf4(Box<models::ScheduleJobOneOf4>),
+}
+
+impl Default for ScheduleJob {
+    fn default() -> Self {
+        Self::ScheduleJobOneOf(Default::default())
+    }
+}
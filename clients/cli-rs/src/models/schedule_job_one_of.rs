@@ -0,0 +1,46 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleJobOneOf {
+    #[serde(rename = "container_id")]
+    pub container_id: uuid::Uuid,
+    #[serde(rename = "label", skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(rename = "snapshot_type", skip_serializing_if = "Option::is_none")]
+    pub snapshot_type: Option<SnapshotType>,
+}
+
+impl ScheduleJobOneOf {
+    pub fn new(container_id: uuid::Uuid) -> ScheduleJobOneOf {
+        ScheduleJobOneOf {
+            container_id,
+            label: None,
+            snapshot_type: None,
+        }
+    }
+}
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum SnapshotType {
+    #[serde(rename = "full")]
+    Full,
+    #[serde(rename = "delta")]
+    Delta,
+}
+
+impl Default for SnapshotType {
+    fn default() -> SnapshotType {
+        Self::Full
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleJobOneOf2 {
+    #[serde(rename = "container_id")]
+    pub container_id: uuid::Uuid,
+    /// Snapshots más recientes que se conservan; también se conservan las bases de los deltas que quedan
+    #[serde(rename = "keep")]
+    pub keep: i32,
+}
+
+impl ScheduleJobOneOf2 {
+    pub fn new(container_id: uuid::Uuid, keep: i32) -> ScheduleJobOneOf2 {
+        ScheduleJobOneOf2 { container_id, keep }
+    }
+}
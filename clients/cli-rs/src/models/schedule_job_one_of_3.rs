@@ -0,0 +1,34 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleJobOneOf3 {
+    #[serde(rename = "container_id")]
+    pub container_id: uuid::Uuid,
+    /// Subdirectorio relativo de `<data_dir>/exports` donde se deja el `.tar.gz` del sandbox (sin snapshots), llamado `<container_id>-<fecha>-<nombre>.tar.gz`; por defecto el propio `<data_dir>/exports`. Las rutas absolutas o con `..` se rechazan con `not_allowed`
+    #[serde(rename = "destination", skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    /// Exportaciones más recientes del contenedor que se conservan en ese directorio; sin `keep` no se borra ninguna
+    #[serde(rename = "keep", skip_serializing_if = "Option::is_none")]
+    pub keep: Option<i32>,
+}
+
+impl ScheduleJobOneOf3 {
+    pub fn new(container_id: uuid::Uuid) -> ScheduleJobOneOf3 {
+        ScheduleJobOneOf3 {
+            container_id,
+            destination: None,
+            keep: None,
+        }
+    }
+}
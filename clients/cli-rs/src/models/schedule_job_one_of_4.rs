@@ -0,0 +1,23 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ScheduleJobOneOf4 : Borra los tokens gestionados caducados; solo el administrador
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleJobOneOf4 {}
+
+impl ScheduleJobOneOf4 {
+    /// Borra los tokens gestionados caducados; solo el administrador
+    pub fn new() -> ScheduleJobOneOf4 {
+        ScheduleJobOneOf4 {}
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// SchedulePatch : Solo cambian los campos presentes; `next_run_at` se recalcula desde ahora
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SchedulePatch {
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "cron", skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    #[serde(rename = "job", skip_serializing_if = "Option::is_none")]
+    pub job: Option<Box<models::ScheduleJob>>,
+    #[serde(rename = "enabled", skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "missed_runs", skip_serializing_if = "Option::is_none")]
+    pub missed_runs: Option<models::MissedRunPolicy>,
+}
+
+impl SchedulePatch {
+    /// Solo cambian los campos presentes; `next_run_at` se recalcula desde ahora
+    pub fn new() -> SchedulePatch {
+        SchedulePatch {
+            name: None,
+            cron: None,
+            job: None,
+            enabled: None,
+            missed_runs: None,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StorageSummary {
+    #[serde(rename = "usage")]
+    pub usage: Box<models::DiskUsage>,
+    #[serde(
+        rename = "global_quota_bytes",
+        deserialize_with = "Option::deserialize"
+    )]
+    pub global_quota_bytes: Option<i32>,
+    #[serde(rename = "containers")]
+    pub containers: Vec<models::ContainerStorage>,
+}
+
+impl StorageSummary {
+    pub fn new(
+        usage: models::DiskUsage,
+        global_quota_bytes: Option<i32>,
+        containers: Vec<models::ContainerStorage>,
+    ) -> StorageSummary {
+        StorageSummary {
+            usage: Box::new(usage),
+            global_quota_bytes,
+            containers,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 /*
  * Orbit Container Agent API
  *
- * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
  *
  * The version of the OpenAPI document: 0.1.0
  *
@@ -21,10 +21,39 @@ pub struct Task {
     pub status: Option<Status>,
     #[serde(rename = "progress", skip_serializing_if = "Option::is_none")]
     pub progress: Option<i32>,
+    /// Petición que originó la tarea (`op` más sus parámetros); solo la llevan los tipos que se pueden reintentar: `app.install`, `app.update`, `app.uninstall`, `snapshot.create`, `snapshot.restore` y `container.apply`
     #[serde(rename = "payload", skip_serializing_if = "Option::is_none")]
     pub payload: Option<serde_json::Value>,
+    /// Resultado estructurado; en `container.verify` es un `IntegrityReport` y en `container.template` `{template, container_id, app_tasks}` (también si falla a medias); en `container.apply` `{prune, items}`, con cada `PlanItem` más `status` (`succeeded`, `failed`, `skipped`), `task_id` y `error`; en `snapshot.prune` `{deleted, kept, freed_bytes}`, en `container.export` `{path, size_bytes, deleted}` y en `token.sweep` `{removed}`
     #[serde(rename = "result", skip_serializing_if = "Option::is_none")]
     pub result: Option<serde_json::Value>,
+    /// Programación que lanzó la tarea
+    #[serde(rename = "schedule_id", skip_serializing_if = "Option::is_none")]
+    pub schedule_id: Option<uuid::Uuid>,
+    /// Tarea de la que ésta es un paso; `null` en las de primer nivel
+    #[serde(rename = "parent_task_id", skip_serializing_if = "Option::is_none")]
+    pub parent_task_id: Option<uuid::Uuid>,
+    /// Orden del paso dentro de la tarea padre
+    #[serde(rename = "step", skip_serializing_if = "Option::is_none")]
+    pub step: Option<i32>,
+    /// Intento actual; cada reintento es una tarea nueva
+    #[serde(rename = "attempt", skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<i32>,
+    /// Intentos que admite la política de reintentos del tipo (`[tasks.retry]`)
+    #[serde(rename = "max_attempts", skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<i32>,
+    /// Tarea fallida de la que ésta es el reintento
+    #[serde(rename = "retry_of", skip_serializing_if = "Option::is_none")]
+    pub retry_of: Option<uuid::Uuid>,
+    /// Momento del reintento automático programado tras fallar; si el agente se reinicia antes, se lanza al arrancar
+    #[serde(rename = "next_retry_at", skip_serializing_if = "Option::is_none")]
+    pub next_retry_at: Option<String>,
+    /// Si un paso obligatorio falla o se cancela, la tarea padre falla con él
+    #[serde(rename = "required", skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Pasos previstos de una tarea agrupada (`container.template`, `container.apply`); su `progress` es la media del de sus subtareas
+    #[serde(rename = "total_steps", skip_serializing_if = "Option::is_none")]
+    pub total_steps: Option<i32>,
     #[serde(rename = "created_at", skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
     #[serde(rename = "updated_at", skip_serializing_if = "Option::is_none")]
@@ -40,6 +69,15 @@ impl Task {
             progress: None,
             payload: None,
             result: None,
+            schedule_id: None,
+            parent_task_id: None,
+            step: None,
+            attempt: None,
+            max_attempts: None,
+            retry_of: None,
+            next_retry_at: None,
+            required: None,
+            total_steps: None,
             created_at: None,
             updated_at: None,
         }
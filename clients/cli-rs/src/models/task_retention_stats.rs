@@ -0,0 +1,46 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TaskRetentionStats {
+    #[serde(rename = "enabled", skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "archive", skip_serializing_if = "Option::is_none")]
+    pub archive: Option<bool>,
+    /// Archivos `.ndjson.gz` en `archive/tasks`
+    #[serde(rename = "archive_files", skip_serializing_if = "Option::is_none")]
+    pub archive_files: Option<i32>,
+    #[serde(rename = "archive_bytes", skip_serializing_if = "Option::is_none")]
+    pub archive_bytes: Option<i32>,
+    /// Tareas borradas desde que arrancó el agente, subtareas incluidas
+    #[serde(rename = "deleted_total", skip_serializing_if = "Option::is_none")]
+    pub deleted_total: Option<i32>,
+    #[serde(rename = "archived_total", skip_serializing_if = "Option::is_none")]
+    pub archived_total: Option<i32>,
+    #[serde(rename = "last_sweep", skip_serializing_if = "Option::is_none")]
+    pub last_sweep: Option<Box<models::TaskRetentionStatsLastSweep>>,
+}
+
+impl TaskRetentionStats {
+    pub fn new() -> TaskRetentionStats {
+        TaskRetentionStats {
+            enabled: None,
+            archive: None,
+            archive_files: None,
+            archive_bytes: None,
+            deleted_total: None,
+            archived_total: None,
+            last_sweep: None,
+        }
+    }
+}
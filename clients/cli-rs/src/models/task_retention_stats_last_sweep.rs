@@ -0,0 +1,35 @@
+/*
+ * Orbit Container Agent API
+ *
+ * API REST del agente Orbit para gestionar contenedores portables, instalaciones de aplicaciones y tareas asíncronas.  Los errores devuelven siempre un cuerpo `ApiError` con un `code` estable. El campo `message` se localiza segun `Accept-Language` (`es` por defecto, `en`).  Los endpoints que modifican estado aceptan `Idempotency-Key`: una petición repetida con la misma clave y el mismo cuerpo recibe la respuesta original durante 24 horas.
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TaskRetentionStatsLastSweep {
+    #[serde(rename = "finished_at", skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(rename = "deleted", skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<i32>,
+    #[serde(rename = "archived", skip_serializing_if = "Option::is_none")]
+    pub archived: Option<i32>,
+    #[serde(rename = "archive_files", skip_serializing_if = "Option::is_none")]
+    pub archive_files: Option<Vec<String>>,
+}
+
+impl TaskRetentionStatsLastSweep {
+    pub fn new() -> TaskRetentionStatsLastSweep {
+        TaskRetentionStatsLastSweep {
+            finished_at: None,
+            deleted: None,
+            archived: None,
+            archive_files: None,
+        }
+    }
+}